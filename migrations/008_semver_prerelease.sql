@@ -0,0 +1,101 @@
+-- Migration: Semantic version pre-release and build metadata
+-- Description: Allow dotted pre-release identifiers (1.0.0-rc.1) and build
+-- metadata (1.0.0+build5) in rule_versions.version
+--
+-- This migration:
+-- 1. Replaces the version format CHECK constraint on rule_versions
+-- 2. Updates is_valid_semver() / compare_semver() to follow semver precedence
+
+-- =============================================================================
+-- 1. Version format constraint
+-- =============================================================================
+
+ALTER TABLE rule_versions DROP CONSTRAINT IF EXISTS version_format_valid;
+
+ALTER TABLE rule_versions ADD CONSTRAINT version_format_valid CHECK (
+    version ~ '^\d+\.\d+\.\d+(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$'
+);
+
+COMMENT ON COLUMN rule_versions.version IS 'Semantic version (e.g., 1.0.0, 2.1.0-rc.1, 1.0.0+build5)';
+
+-- =============================================================================
+-- 2. Utility Functions
+-- =============================================================================
+
+-- Check if a version string is valid semantic version
+CREATE OR REPLACE FUNCTION is_valid_semver(version_str TEXT)
+RETURNS BOOLEAN AS $$
+BEGIN
+    RETURN version_str ~ '^\d+\.\d+\.\d+(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$';
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- Compare semantic versions (returns -1, 0, or 1)
+-- Pre-release versions sort below the release; build metadata is ignored
+CREATE OR REPLACE FUNCTION compare_semver(v1 TEXT, v2 TEXT)
+RETURNS INTEGER AS $$
+DECLARE
+    v1_core TEXT;
+    v2_core TEXT;
+    v1_pre TEXT;
+    v2_pre TEXT;
+    v1_parts TEXT[];
+    v2_parts TEXT[];
+    v1_ids TEXT[];
+    v2_ids TEXT[];
+    a TEXT;
+    b TEXT;
+    i INTEGER;
+BEGIN
+    -- Strip build metadata, then split off the pre-release
+    v1_core := split_part(v1, '+', 1);
+    v2_core := split_part(v2, '+', 1);
+    v1_pre := NULLIF(substring(v1_core FROM position('-' IN v1_core) + 1), v1_core);
+    v2_pre := NULLIF(substring(v2_core FROM position('-' IN v2_core) + 1), v2_core);
+
+    v1_parts := regexp_split_to_array(split_part(v1_core, '-', 1), '\.');
+    v2_parts := regexp_split_to_array(split_part(v2_core, '-', 1), '\.');
+
+    FOR i IN 1..3 LOOP
+        IF v1_parts[i]::BIGINT != v2_parts[i]::BIGINT THEN
+            RETURN SIGN(v1_parts[i]::BIGINT - v2_parts[i]::BIGINT);
+        END IF;
+    END LOOP;
+
+    -- A release has higher precedence than any of its pre-releases
+    IF v1_pre IS NULL AND v2_pre IS NULL THEN
+        RETURN 0;
+    ELSIF v1_pre IS NULL THEN
+        RETURN 1;
+    ELSIF v2_pre IS NULL THEN
+        RETURN -1;
+    END IF;
+
+    -- Compare dot-separated pre-release identifiers left to right
+    v1_ids := string_to_array(v1_pre, '.');
+    v2_ids := string_to_array(v2_pre, '.');
+
+    FOR i IN 1..GREATEST(array_length(v1_ids, 1), array_length(v2_ids, 1)) LOOP
+        a := v1_ids[i];
+        b := v2_ids[i];
+
+        IF a IS NULL THEN
+            RETURN -1;
+        ELSIF b IS NULL THEN
+            RETURN 1;
+        ELSIF a ~ '^\d+$' AND b ~ '^\d+$' THEN
+            IF a::NUMERIC != b::NUMERIC THEN
+                RETURN SIGN(a::NUMERIC - b::NUMERIC);
+            END IF;
+        ELSIF a ~ '^\d+$' THEN
+            RETURN -1;
+        ELSIF b ~ '^\d+$' THEN
+            RETURN 1;
+        ELSIF a != b THEN
+            RETURN CASE WHEN a < b COLLATE "C" THEN -1 ELSE 1 END;
+        END IF;
+    END LOOP;
+
+    RETURN 0;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
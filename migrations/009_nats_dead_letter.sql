@@ -0,0 +1,72 @@
+-- Migration: NATS dead-letter queue
+-- Description: Keep webhook payloads whose NATS publish failed after all
+-- retries so they can be re-driven with rule_nats_replay_dead_letter()
+--
+-- This migration adds:
+-- 1. rule_nats_dead_letter table
+-- 2. Monitoring view for pending dead letters
+
+-- =============================================================================
+-- 1. Dead-Letter Table
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS rule_nats_dead_letter (
+    dead_letter_id BIGSERIAL PRIMARY KEY,
+    webhook_id INTEGER REFERENCES rule_webhooks(webhook_id) ON DELETE CASCADE,
+    config_name TEXT NOT NULL,
+
+    -- NATS Message
+    subject TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    message_id TEXT, -- Reused on replay so JetStream can deduplicate
+
+    -- Failure
+    error_message TEXT NOT NULL,
+    error_category TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+
+    -- Lifecycle
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    last_attempt_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    replayed_at TIMESTAMPTZ, -- Set once the message has been published
+    sequence_number BIGINT -- JetStream sequence of the successful replay
+);
+
+COMMENT ON TABLE rule_nats_dead_letter IS 'NATS webhook publishes that failed after all retries';
+COMMENT ON COLUMN rule_nats_dead_letter.attempts IS 'Total publish attempts, including replays';
+COMMENT ON COLUMN rule_nats_dead_letter.replayed_at IS 'When the message was successfully re-driven (NULL = pending)';
+
+CREATE INDEX idx_nats_dead_letter_pending
+    ON rule_nats_dead_letter(config_name, dead_letter_id)
+    WHERE replayed_at IS NULL;
+CREATE INDEX idx_nats_dead_letter_webhook ON rule_nats_dead_letter(webhook_id);
+
+-- =============================================================================
+-- 2. Monitoring View
+-- =============================================================================
+
+CREATE OR REPLACE VIEW nats_dead_letter_pending AS
+SELECT
+    config_name,
+    subject,
+    COUNT(*) AS pending,
+    MIN(created_at) AS oldest,
+    MAX(attempts) AS max_attempts
+FROM rule_nats_dead_letter
+WHERE replayed_at IS NULL
+GROUP BY config_name, subject;
+
+COMMENT ON VIEW nats_dead_letter_pending IS 'Dead-lettered NATS messages awaiting replay';
+
+GRANT SELECT ON nats_dead_letter_pending TO PUBLIC;
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'NATS dead-letter migration completed successfully';
+    RAISE NOTICE 'Tables created: rule_nats_dead_letter';
+    RAISE NOTICE 'Views created: nats_dead_letter_pending';
+END $$;
@@ -0,0 +1,57 @@
+-- Migration: NATS publish deduplication tracking
+-- Description: Record when JetStream acknowledged a publish as a duplicate
+-- (same Nats-Msg-Id inside the stream's duplicate window)
+--
+-- This migration:
+-- 1. Adds rule_nats_publish_history.duplicate
+-- 2. Adds a deduplicated count to nats_publish_summary
+
+-- =============================================================================
+-- 1. Publish History Column
+-- =============================================================================
+
+ALTER TABLE rule_nats_publish_history
+    ADD COLUMN IF NOT EXISTS duplicate BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN rule_nats_publish_history.duplicate IS
+    'True if JetStream deduplicated the message; sequence_number is then the original message''s sequence';
+
+CREATE INDEX IF NOT EXISTS idx_nats_publish_duplicate
+    ON rule_nats_publish_history(webhook_id)
+    WHERE duplicate = true;
+
+-- =============================================================================
+-- 2. Summary View
+-- =============================================================================
+
+CREATE OR REPLACE VIEW nats_publish_summary AS
+SELECT
+    w.webhook_id,
+    w.webhook_name,
+    w.nats_subject as subject,
+    w.publish_mode,
+    COUNT(h.publish_id) as total_published,
+    COUNT(*) FILTER (WHERE h.success = true) as successful,
+    COUNT(*) FILTER (WHERE h.success = false) as failed,
+    ROUND(100.0 * COUNT(*) FILTER (WHERE h.success = true) / NULLIF(COUNT(*), 0), 2) as success_rate_pct,
+    ROUND(AVG(h.latency_ms), 2) as avg_latency_ms,
+    MIN(h.latency_ms) as min_latency_ms,
+    MAX(h.latency_ms) as max_latency_ms,
+    MAX(h.published_at) as last_published_at,
+    COUNT(*) FILTER (WHERE h.duplicate = true) as deduplicated
+FROM rule_webhooks w
+LEFT JOIN rule_nats_publish_history h ON w.webhook_id = h.webhook_id
+WHERE w.nats_enabled = true
+GROUP BY w.webhook_id, w.webhook_name, w.nats_subject, w.publish_mode;
+
+COMMENT ON VIEW nats_publish_summary IS 'Summary statistics for NATS-enabled webhooks';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'NATS publish deduplication migration completed successfully';
+    RAISE NOTICE 'Column added: rule_nats_publish_history.duplicate';
+END $$;
@@ -0,0 +1,53 @@
+-- Migration: Conditional GET for data source caching
+-- Description: Store ETag / Last-Modified with cached responses so expired
+-- entries can be revalidated with If-None-Match / If-Modified-Since. A
+-- 304 Not Modified reply serves the cached body and extends its expiry.
+--
+-- This migration:
+-- 1. Adds rule_datasource_cache.etag and rule_datasource_cache.last_modified
+-- 2. Keeps revalidatable entries in rule_datasource_cache_cleanup()
+
+-- =============================================================================
+-- 1. Cache Validator Columns
+-- =============================================================================
+
+ALTER TABLE rule_datasource_cache
+    ADD COLUMN IF NOT EXISTS etag TEXT,
+    ADD COLUMN IF NOT EXISTS last_modified TEXT;
+
+COMMENT ON COLUMN rule_datasource_cache.etag IS
+    'ETag response header, sent as If-None-Match once the entry expires';
+COMMENT ON COLUMN rule_datasource_cache.last_modified IS
+    'Last-Modified response header, sent as If-Modified-Since once the entry expires';
+
+-- =============================================================================
+-- 2. Cache Cleanup
+-- =============================================================================
+
+-- Expired entries with validators are still useful: a 304 revalidates them
+-- without downloading the body again. Only drop those after a day.
+CREATE OR REPLACE FUNCTION rule_datasource_cache_cleanup()
+RETURNS BIGINT AS $$
+DECLARE
+    v_deleted_count BIGINT;
+BEGIN
+    DELETE FROM rule_datasource_cache
+    WHERE expires_at <= CURRENT_TIMESTAMP
+      AND (
+          (etag IS NULL AND last_modified IS NULL)
+          OR expires_at <= CURRENT_TIMESTAMP - INTERVAL '1 day'
+      );
+    GET DIAGNOSTICS v_deleted_count = ROW_COUNT;
+    RETURN v_deleted_count;
+END;
+$$ LANGUAGE plpgsql;
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Data source conditional cache migration completed successfully';
+    RAISE NOTICE 'Columns added: rule_datasource_cache.etag, rule_datasource_cache.last_modified';
+END $$;
@@ -0,0 +1,28 @@
+-- Migration: GraphQL data sources
+-- Description: Let a data source speak GraphQL. rule_datasource_fetch then
+-- POSTs {query, variables} built from its params, returns the response's
+-- "data" and reports GraphQL "errors" as the request error.
+--
+-- This migration:
+-- 1. Adds rule_datasources.source_type ('rest' or 'graphql')
+
+-- =============================================================================
+-- 1. Source Type Column
+-- =============================================================================
+
+ALTER TABLE rule_datasources
+    ADD COLUMN IF NOT EXISTS source_type TEXT NOT NULL DEFAULT 'rest'
+    CHECK (source_type IN ('rest', 'graphql'));
+
+COMMENT ON COLUMN rule_datasources.source_type IS
+    'rest = plain JSON over HTTP; graphql = POST {query, variables} and unwrap data/errors';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Data source GraphQL migration completed successfully';
+    RAISE NOTICE 'Column added: rule_datasources.source_type';
+END $$;
@@ -0,0 +1,34 @@
+-- Migration: Paginated data sources
+-- Description: Let rule_datasource_fetch follow "next" links or cursors and
+-- return one merged array of items, bounded by max_pages.
+--
+-- This migration:
+-- 1. Adds rule_datasources.pagination
+
+-- =============================================================================
+-- 1. Pagination Config Column
+-- =============================================================================
+
+ALTER TABLE rule_datasources
+    ADD COLUMN IF NOT EXISTS pagination JSONB
+    CHECK (
+        pagination IS NULL
+        OR (
+            jsonb_typeof(pagination) = 'object'
+            AND pagination ? 'items_path'
+            AND pagination ? 'next_path'
+        )
+    );
+
+COMMENT ON COLUMN rule_datasources.pagination IS
+    'NULL = single request. Otherwise {"items_path", "next_path", "max_pages" (default 10, max 100), "cursor_param" (default "cursor")}';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Data source pagination migration completed successfully';
+    RAISE NOTICE 'Column added: rule_datasources.pagination';
+END $$;
@@ -0,0 +1,91 @@
+-- Migration: Rule execution audit
+-- Description: Compliance trail of who executed which stored rule. Rows are
+-- written by rule_execute_by_name / rule_execute_by_name_jsonb while
+-- rule_engine.audit_enabled is on:
+--
+--   ALTER SYSTEM SET rule_engine.audit_enabled = on;   -- or per database/role
+--   SELECT pg_reload_conf();
+--
+-- Facts are never stored, only a SHA-256 hash of their canonical JSON.
+-- (rule_audit_log, from 001_rule_repository.sql, tracks changes to rules;
+-- this table tracks executions.)
+--
+-- This migration adds:
+-- 1. rule_execution_audit table
+-- 2. rule_audit_query() function
+
+-- =============================================================================
+-- 1. Audit Log Table
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS rule_execution_audit (
+    audit_id BIGSERIAL PRIMARY KEY,
+    rule_name VARCHAR(255) NOT NULL,
+    rule_version VARCHAR(50),
+    executed_by VARCHAR(100) NOT NULL DEFAULT CURRENT_USER,
+    facts_hash CHAR(64) NOT NULL,
+    rules_fired INTEGER NOT NULL DEFAULT 0,
+    fired BOOLEAN NOT NULL,
+    success BOOLEAN NOT NULL,
+    executed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE rule_execution_audit IS 'Audit trail of stored-rule executions (rule_engine.audit_enabled)';
+COMMENT ON COLUMN rule_execution_audit.rule_version IS 'Version executed; the default version at the time if none was requested';
+COMMENT ON COLUMN rule_execution_audit.facts_hash IS 'Hex SHA-256 of the input facts as canonical JSON (sorted keys, no whitespace)';
+COMMENT ON COLUMN rule_execution_audit.fired IS 'True if at least one rule fired';
+COMMENT ON COLUMN rule_execution_audit.success IS 'False if execution returned an error';
+
+CREATE INDEX idx_rule_execution_audit_rule_time ON rule_execution_audit(rule_name, executed_at DESC);
+CREATE INDEX idx_rule_execution_audit_time ON rule_execution_audit(executed_at DESC);
+
+-- =============================================================================
+-- 2. Query Function
+-- =============================================================================
+
+CREATE OR REPLACE FUNCTION rule_audit_query(
+    p_rule_name TEXT DEFAULT NULL,
+    p_since TIMESTAMPTZ DEFAULT NULL
+)
+RETURNS TABLE (
+    audit_id BIGINT,
+    rule_name VARCHAR(255),
+    rule_version VARCHAR(50),
+    executed_by VARCHAR(100),
+    facts_hash CHAR(64),
+    rules_fired INTEGER,
+    fired BOOLEAN,
+    success BOOLEAN,
+    executed_at TIMESTAMPTZ
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        a.audit_id,
+        a.rule_name,
+        a.rule_version,
+        a.executed_by,
+        a.facts_hash,
+        a.rules_fired,
+        a.fired,
+        a.success,
+        a.executed_at
+    FROM rule_execution_audit a
+    WHERE (p_rule_name IS NULL OR a.rule_name = p_rule_name)
+      AND (p_since IS NULL OR a.executed_at >= p_since)
+    ORDER BY a.executed_at DESC, a.audit_id DESC;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+COMMENT ON FUNCTION rule_audit_query IS 'Audit entries for a rule (NULL = all) since a time (NULL = all), newest first';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Rule audit log migration completed successfully';
+    RAISE NOTICE 'Tables created: rule_execution_audit';
+    RAISE NOTICE 'Functions created: rule_audit_query';
+END $$;
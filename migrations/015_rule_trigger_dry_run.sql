@@ -0,0 +1,256 @@
+-- Migration: Rule trigger dry-run mode
+-- Description: Let a trigger evaluate its rule and record what it would have
+-- changed without modifying the row, for shadow-testing in production
+--
+-- This migration:
+-- 1. Adds rule_triggers.dry_run
+-- 2. Adds rule_trigger_history.dry_run and rule_trigger_history.changes
+-- 3. Updates execute_rule_trigger() to honour dry_run
+-- 4. Adds rule_trigger_set_dry_run() and surfaces dry-run data in
+--    rule_trigger_history() and rule_trigger_stats
+
+-- =============================================================================
+-- 1. Trigger Column
+-- =============================================================================
+
+ALTER TABLE rule_triggers
+    ADD COLUMN IF NOT EXISTS dry_run BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN rule_triggers.dry_run IS
+    'If true, the rule runs and its outcome is recorded but the row is not modified';
+
+-- =============================================================================
+-- 2. History Columns
+-- =============================================================================
+
+ALTER TABLE rule_trigger_history
+    ADD COLUMN IF NOT EXISTS dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS changes JSONB;
+
+COMMENT ON COLUMN rule_trigger_history.dry_run IS 'True if the execution ran in dry-run mode (row not modified)';
+COMMENT ON COLUMN rule_trigger_history.changes IS
+    'Columns changed (or, in dry-run mode, that would have changed) as {column: {old, new}}';
+
+-- =============================================================================
+-- 3. Trigger Function
+-- =============================================================================
+
+CREATE OR REPLACE FUNCTION execute_rule_trigger()
+RETURNS TRIGGER AS $$
+DECLARE
+    trigger_config RECORD;
+    facts_json TEXT;
+    result_json TEXT;
+    merged_row JSONB;
+    row_changes JSONB;
+    start_time TIMESTAMP;
+    end_time TIMESTAMP;
+    execution_ms NUMERIC;
+    error_occurred BOOLEAN := FALSE;
+BEGIN
+    -- Loop through all enabled triggers for this table and event
+    FOR trigger_config IN
+        SELECT rt.id, rt.rule_name, rt.dry_run
+        FROM rule_triggers rt
+        WHERE rt.table_name = TG_TABLE_NAME
+          AND rt.event_type = TG_OP
+          AND rt.enabled = TRUE
+    LOOP
+        BEGIN
+            start_time := clock_timestamp();
+            row_changes := NULL;
+
+            -- Build facts JSON based on event type
+            IF TG_OP = 'DELETE' THEN
+                facts_json := row_to_json(OLD)::TEXT;
+            ELSE
+                facts_json := row_to_json(NEW)::TEXT;
+            END IF;
+
+            -- Execute rule by name (uses default version)
+            result_json := rule_execute_by_name(
+                trigger_config.rule_name,
+                facts_json,
+                NULL
+            );
+
+            -- Work out which columns the result changes (INSERT/UPDATE only)
+            IF TG_OP IN ('INSERT', 'UPDATE') THEN
+                merged_row := to_jsonb(jsonb_populate_record(NEW, result_json::JSONB));
+
+                SELECT jsonb_object_agg(n.key, jsonb_build_object('old', o.value, 'new', n.value))
+                INTO row_changes
+                FROM jsonb_each(merged_row) n
+                JOIN jsonb_each(to_jsonb(NEW)) o ON o.key = n.key
+                WHERE n.value IS DISTINCT FROM o.value;
+            END IF;
+
+            end_time := clock_timestamp();
+            execution_ms := EXTRACT(EPOCH FROM (end_time - start_time)) * 1000;
+
+            -- Log success
+            INSERT INTO rule_trigger_history (
+                trigger_id, event_type, old_data, new_data,
+                result_data, success, execution_time_ms, dry_run, changes
+            ) VALUES (
+                trigger_config.id,
+                TG_OP,
+                CASE WHEN TG_OP IN ('UPDATE', 'DELETE') THEN row_to_json(OLD) ELSE NULL END,
+                CASE WHEN TG_OP IN ('INSERT', 'UPDATE') THEN row_to_json(NEW) ELSE NULL END,
+                result_json::JSONB,
+                TRUE,
+                execution_ms,
+                trigger_config.dry_run,
+                row_changes
+            );
+
+            -- Update NEW with modified data, unless this trigger is a dry run
+            IF TG_OP IN ('INSERT', 'UPDATE') AND NOT trigger_config.dry_run THEN
+                NEW := jsonb_populate_record(NEW, result_json::JSONB);
+            END IF;
+
+        EXCEPTION WHEN OTHERS THEN
+            error_occurred := TRUE;
+
+            -- Log error
+            INSERT INTO rule_trigger_history (
+                trigger_id, event_type, old_data, new_data,
+                success, error_message, execution_time_ms, dry_run
+            ) VALUES (
+                trigger_config.id,
+                TG_OP,
+                CASE WHEN TG_OP IN ('UPDATE', 'DELETE') THEN row_to_json(OLD) ELSE NULL END,
+                CASE WHEN TG_OP IN ('INSERT', 'UPDATE') THEN row_to_json(NEW) ELSE NULL END,
+                FALSE,
+                SQLERRM,
+                EXTRACT(EPOCH FROM (clock_timestamp() - start_time)) * 1000,
+                trigger_config.dry_run
+            );
+
+            -- Log warning but don't fail the transaction
+            RAISE WARNING 'Rule trigger % failed: %', trigger_config.rule_name, SQLERRM;
+        END;
+    END LOOP;
+
+    -- Return appropriate value based on operation
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    ELSE
+        RETURN NEW;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION execute_rule_trigger() IS 'Generic trigger function that executes rules on table changes (honours dry_run)';
+
+-- =============================================================================
+-- 4. API Functions
+-- =============================================================================
+
+-- Turn dry-run mode on or off for a trigger
+CREATE OR REPLACE FUNCTION rule_trigger_set_dry_run(
+    p_trigger_id INTEGER,
+    p_dry_run BOOLEAN DEFAULT TRUE
+) RETURNS BOOLEAN AS $$
+BEGIN
+    UPDATE rule_triggers
+    SET dry_run = p_dry_run,
+        updated_at = NOW(),
+        updated_by = CURRENT_USER
+    WHERE id = p_trigger_id;
+
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'ERR_RT004: Trigger not found: %', p_trigger_id;
+    END IF;
+
+    RAISE NOTICE 'Trigger % dry-run %', p_trigger_id, CASE WHEN p_dry_run THEN 'on' ELSE 'off' END;
+
+    RETURN TRUE;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION rule_trigger_set_dry_run IS 'Enable or disable dry-run mode for a rule trigger';
+
+GRANT EXECUTE ON FUNCTION rule_trigger_set_dry_run TO PUBLIC;
+
+-- Return type changes, so the function has to be recreated
+DROP FUNCTION IF EXISTS rule_trigger_history(INTEGER, TIMESTAMP, TIMESTAMP);
+
+CREATE FUNCTION rule_trigger_history(
+    p_trigger_id INTEGER,
+    p_start_time TIMESTAMP DEFAULT NOW() - INTERVAL '1 day',
+    p_end_time TIMESTAMP DEFAULT NOW()
+) RETURNS TABLE (
+    id BIGINT,
+    executed_at TIMESTAMP,
+    event_type TEXT,
+    success BOOLEAN,
+    execution_time_ms NUMERIC,
+    error_message TEXT,
+    result_summary TEXT,
+    dry_run BOOLEAN,
+    changes JSONB
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        rth.id,
+        rth.executed_at,
+        rth.event_type::TEXT,
+        rth.success,
+        rth.execution_time_ms,
+        rth.error_message,
+        CASE
+            WHEN rth.result_data IS NOT NULL THEN
+                substring(rth.result_data::TEXT, 1, 100) || '...'
+            ELSE NULL
+        END AS result_summary,
+        rth.dry_run,
+        rth.changes
+    FROM rule_trigger_history rth
+    WHERE rth.trigger_id = p_trigger_id
+      AND rth.executed_at BETWEEN p_start_time AND p_end_time
+    ORDER BY rth.executed_at DESC
+    LIMIT 1000;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION rule_trigger_history IS 'Get execution history for a rule trigger';
+
+GRANT EXECUTE ON FUNCTION rule_trigger_history TO PUBLIC;
+
+-- =============================================================================
+-- 5. Stats View
+-- =============================================================================
+
+CREATE OR REPLACE VIEW rule_trigger_stats AS
+SELECT
+    rt.id AS trigger_id,
+    rt.name AS trigger_name,
+    rt.table_name,
+    rt.rule_name,
+    rt.event_type,
+    rt.enabled,
+    COUNT(rth.id) AS total_executions,
+    COUNT(CASE WHEN rth.success THEN 1 END) AS successful_executions,
+    COUNT(CASE WHEN NOT rth.success THEN 1 END) AS failed_executions,
+    ROUND(AVG(rth.execution_time_ms)::NUMERIC, 2) AS avg_execution_time_ms,
+    MAX(rth.executed_at) AS last_executed_at,
+    rt.dry_run,
+    COUNT(CASE WHEN rth.dry_run THEN 1 END) AS dry_run_executions
+FROM rule_triggers rt
+LEFT JOIN rule_trigger_history rth ON rt.id = rth.trigger_id
+GROUP BY rt.id, rt.name, rt.table_name, rt.rule_name, rt.event_type, rt.enabled, rt.dry_run;
+
+COMMENT ON VIEW rule_trigger_stats IS 'Statistics for rule trigger executions';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Rule trigger dry-run migration completed successfully';
+    RAISE NOTICE 'Columns added: rule_triggers.dry_run, rule_trigger_history.dry_run, rule_trigger_history.changes';
+    RAISE NOTICE 'Function added: rule_trigger_set_dry_run';
+END $$;
@@ -0,0 +1,39 @@
+-- Migration: rule_save idempotency keys
+-- Description: Makes rule deployment safely retryable. When rule_save is
+-- called with an idempotency_key that was already used for the same rule, it
+-- returns the earlier rule ID instead of creating another version:
+--
+--   SELECT rule_save('discount_rule', '...', NULL, NULL, NULL, NULL, 'deploy-4812');
+--
+-- This migration adds:
+-- 1. rule_save_idempotency table
+
+-- =============================================================================
+-- 1. Idempotency Key Table
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS rule_save_idempotency (
+    rule_name VARCHAR(255) NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    rule_id INTEGER NOT NULL REFERENCES rule_definitions(id) ON DELETE CASCADE,
+    version VARCHAR(50) NOT NULL,
+    created_by VARCHAR(100) NOT NULL DEFAULT CURRENT_USER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (rule_name, idempotency_key)
+);
+
+COMMENT ON TABLE rule_save_idempotency IS 'Idempotency keys used by rule_save, one row per (rule, key)';
+COMMENT ON COLUMN rule_save_idempotency.rule_id IS 'Rule ID returned by the original rule_save call';
+COMMENT ON COLUMN rule_save_idempotency.version IS 'Version created by the original rule_save call';
+
+CREATE INDEX idx_rule_save_idempotency_rule_id ON rule_save_idempotency(rule_id);
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'rule_save idempotency migration completed successfully';
+    RAISE NOTICE 'Table added: rule_save_idempotency';
+END $$;
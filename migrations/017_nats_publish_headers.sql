@@ -0,0 +1,26 @@
+-- Migration: NATS publish headers
+-- Description: rule_webhook_publish_nats accepts custom headers (e.g.
+-- X-Tenant) for consumers to route or filter on. Failed publishes keep their
+-- headers so rule_nats_replay_dead_letter() re-sends them unchanged.
+--
+-- This migration adds:
+-- 1. rule_nats_dead_letter.headers
+
+-- =============================================================================
+-- 1. Dead-Letter Headers
+-- =============================================================================
+
+ALTER TABLE rule_nats_dead_letter
+    ADD COLUMN IF NOT EXISTS headers JSONB;
+
+COMMENT ON COLUMN rule_nats_dead_letter.headers IS 'Custom headers of the original publish ({name: value}), re-sent on replay';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'NATS publish headers migration completed successfully';
+    RAISE NOTICE 'Column added: rule_nats_dead_letter.headers';
+END $$;
@@ -0,0 +1,37 @@
+-- Migration: Debug persistence indexes
+-- Description: Loading a persisted debug session reads its events ordered by
+-- step, and listing/cleaning up sessions orders or filters by started_at.
+-- Databases whose debug tables were created without these indexes scan both
+-- tables on every call.
+--
+-- This migration adds (if missing):
+-- 1. idx_events_session_step on rule_execution_events(session_id, step)
+-- 2. idx_sessions_started on rule_execution_sessions(started_at)
+
+-- =============================================================================
+-- 1. Event Lookup by Session
+-- =============================================================================
+
+CREATE INDEX IF NOT EXISTS idx_events_session_step
+    ON rule_execution_events(session_id, step);
+
+COMMENT ON INDEX idx_events_session_step IS 'Events of a debug session in step order (load_events_from_db)';
+
+-- =============================================================================
+-- 2. Session Start Time
+-- =============================================================================
+
+CREATE INDEX IF NOT EXISTS idx_sessions_started
+    ON rule_execution_sessions(started_at);
+
+COMMENT ON INDEX idx_sessions_started IS 'Recent-session listing and debug_cleanup_old_sessions() cutoff';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Debug persistence indexes migration completed successfully';
+    RAISE NOTICE 'Indexes ensured: idx_events_session_step, idx_sessions_started';
+END $$;
@@ -0,0 +1,76 @@
+-- Migration: Structured rule_validate issues
+-- Description: rule_validate() reports every structural problem in the GRL at
+-- once (unbalanced braces, missing when/then, invalid or duplicate rule
+-- names), each with a code and a line/column, so editors can underline them
+-- instead of fixing one error per round trip.
+--
+-- This migration changes:
+-- 1. rule_validate() adds an "issues" array from rule_validate_detailed()
+
+-- =============================================================================
+-- 1. rule_validate
+-- =============================================================================
+
+CREATE OR REPLACE FUNCTION rule_validate(p_grl TEXT)
+RETURNS JSON AS $$
+DECLARE
+    v_errors TEXT[] := '{}';
+    v_warnings TEXT[] := '{}';
+    v_test_result TEXT;
+    v_detailed JSONB;
+    v_issue JSONB;
+BEGIN
+    -- Try to compile the rule
+    BEGIN
+        SELECT run_rule_engine('{}'::JSONB, p_grl) INTO v_test_result;
+    EXCEPTION WHEN OTHERS THEN
+        v_errors := array_append(v_errors, 'Syntax error: ' || SQLERRM);
+    END;
+
+    -- Structural checks, all reported at once
+    v_detailed := rule_validate_detailed(p_grl);
+    FOR v_issue IN SELECT * FROM jsonb_array_elements(v_detailed->'issues') LOOP
+        v_errors := array_append(
+            v_errors,
+            CASE
+                WHEN v_issue->'location' = 'null'::JSONB THEN v_issue->>'message'
+                ELSE format('Line %s, column %s: %s',
+                    v_issue->'location'->>'line',
+                    v_issue->'location'->>'column',
+                    v_issue->>'message')
+            END
+        );
+    END LOOP;
+
+    -- Warning: Check for complex conditions
+    IF (length(p_grl) - length(replace(p_grl, '&&', ''))) / 2 > 5 THEN
+        v_warnings := array_append(v_warnings, 'Complex condition: More than 5 AND operators may impact performance');
+    END IF;
+
+    -- Warning: Check for deep nesting
+    IF (length(p_grl) - length(replace(p_grl, '{', ''))) > 10 THEN
+        v_warnings := array_append(v_warnings, 'Deep nesting detected: Consider refactoring');
+    END IF;
+
+    RETURN json_build_object(
+        'valid', array_length(v_errors, 1) IS NULL,
+        'errors', v_errors,
+        'warnings', v_warnings,
+        'error_count', COALESCE(array_length(v_errors, 1), 0),
+        'warning_count', COALESCE(array_length(v_warnings, 1), 0),
+        'issues', v_detailed->'issues'
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION rule_validate(TEXT) IS 'Validate GRL; "issues" lists every structural problem with code and line/column';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Structured rule_validate issues migration completed successfully';
+    RAISE NOTICE 'Function updated: rule_validate (now includes issues)';
+END $$;
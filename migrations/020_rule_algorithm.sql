@@ -0,0 +1,36 @@
+-- Migration: Per-rule execution algorithm
+-- Description: Stored rules run with RETE by default. Some rule sets rely on
+-- forward chaining's in-order firing, so each rule can now record which
+-- algorithm rule_execute_by_name() uses, set with rule_set_algorithm().
+--
+-- This migration adds:
+-- 1. rule_definitions.algorithm ('rete' or 'fc', default 'rete')
+
+-- =============================================================================
+-- 1. Algorithm Column
+-- =============================================================================
+
+ALTER TABLE rule_definitions
+    ADD COLUMN IF NOT EXISTS algorithm TEXT NOT NULL DEFAULT 'rete';
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint WHERE conname = 'rule_algorithm_valid'
+    ) THEN
+        ALTER TABLE rule_definitions
+            ADD CONSTRAINT rule_algorithm_valid CHECK (algorithm IN ('rete', 'fc'));
+    END IF;
+END $$;
+
+COMMENT ON COLUMN rule_definitions.algorithm IS 'Execution algorithm used by rule_execute_by_name: rete (default) or fc (forward chaining)';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Per-rule execution algorithm migration completed successfully';
+    RAISE NOTICE 'Column added: rule_definitions.algorithm';
+END $$;
@@ -0,0 +1,40 @@
+-- Migration: Encryption keys for Encrypt/Decrypt
+-- Description: The Encrypt() and Decrypt() built-in functions use AES-256-GCM
+-- with 32-byte keys looked up by name, so rules can store sensitive derived
+-- values encrypted without the key appearing in GRL or facts.
+--
+-- This migration adds:
+-- 1. rule_crypto_keys table (readable only by its owner and explicit grants)
+--
+-- Add a key (gen_random_bytes needs pgcrypto):
+--   INSERT INTO rule_crypto_keys (key_name, key_material)
+--   VALUES ('pii', gen_random_bytes(32));
+
+-- =============================================================================
+-- 1. Key Table
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS rule_crypto_keys (
+    key_name TEXT PRIMARY KEY,
+    key_material BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_by TEXT DEFAULT current_user,
+
+    CONSTRAINT crypto_key_length CHECK (length(key_material) = 32)
+);
+
+-- Rule executions read keys with the caller's privileges
+REVOKE ALL ON rule_crypto_keys FROM PUBLIC;
+
+COMMENT ON TABLE rule_crypto_keys IS 'AES-256 keys used by the Encrypt/Decrypt built-in functions';
+COMMENT ON COLUMN rule_crypto_keys.key_material IS 'Raw 32-byte key; rotating it makes existing ciphertext undecryptable';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Encryption keys migration completed successfully';
+    RAISE NOTICE 'Table created: rule_crypto_keys';
+END $$;
@@ -0,0 +1,27 @@
+-- Migration: Data source response size limit
+-- Description: Cap how many bytes rule_datasource_fetch reads from an API
+-- response, so an oversized or endless body cannot exhaust backend memory.
+--
+-- This migration:
+-- 1. Adds rule_datasources.max_response_bytes
+
+-- =============================================================================
+-- 1. Response Size Limit Column
+-- =============================================================================
+
+ALTER TABLE rule_datasources
+    ADD COLUMN IF NOT EXISTS max_response_bytes BIGINT DEFAULT 10485760
+    CHECK (max_response_bytes IS NULL OR max_response_bytes > 0);
+
+COMMENT ON COLUMN rule_datasources.max_response_bytes IS
+    'Largest response body accepted, in bytes (default 10 MiB). Larger responses fail with "Response too large". NULL = unlimited';
+
+-- =============================================================================
+-- Migration Complete
+-- =============================================================================
+
+DO $$
+BEGIN
+    RAISE NOTICE 'Data source response limit migration completed successfully';
+    RAISE NOTICE 'Column added: rule_datasources.max_response_bytes';
+END $$;
@@ -32,10 +32,11 @@ RETURNS TABLE(session_id TEXT, total_steps BIGINT, total_events BIGINT, result J
 AS 'MODULE_PATHNAME', 'run_rule_engine_debug_wrapper'
 LANGUAGE C STRICT;
 
-CREATE OR REPLACE FUNCTION debug_get_events(session_id TEXT)
+-- Not STRICT: the filters accept NULL (no filter)
+CREATE OR REPLACE FUNCTION debug_get_events(session_id TEXT, event_type TEXT DEFAULT NULL, from_step BIGINT DEFAULT NULL, to_step BIGINT DEFAULT NULL)
 RETURNS TABLE(step BIGINT, event_type TEXT, description TEXT, event_data JSONB)
 AS 'MODULE_PATHNAME', 'debug_get_events_wrapper'
-LANGUAGE C STRICT;
+LANGUAGE C;
 
 CREATE OR REPLACE FUNCTION debug_get_session(session_id TEXT)
 RETURNS TABLE(session_id TEXT, started_at BIGINT, completed_at BIGINT, duration_ms BIGINT, status TEXT, total_steps BIGINT, total_events BIGINT, rules_grl TEXT)
@@ -82,8 +83,39 @@ RETURNS JSONB
 AS 'MODULE_PATHNAME', 'debug_status_wrapper'
 LANGUAGE C STRICT;
 
+CREATE OR REPLACE FUNCTION debug_export_session(session_id TEXT)
+RETURNS JSONB
+AS 'MODULE_PATHNAME', 'debug_export_session_wrapper'
+LANGUAGE C STRICT;
+
+CREATE OR REPLACE FUNCTION debug_import_session(bundle JSONB)
+RETURNS TEXT
+AS 'MODULE_PATHNAME', 'debug_import_session_wrapper'
+LANGUAGE C STRICT;
+
+CREATE OR REPLACE FUNCTION debug_why_not_fired(session_id TEXT, rule_name TEXT)
+RETURNS JSONB
+AS 'MODULE_PATHNAME', 'debug_why_not_fired_wrapper'
+LANGUAGE C STRICT;
+
+CREATE OR REPLACE FUNCTION debug_cleanup_old_sessions(older_than_days INTEGER)
+RETURNS BIGINT
+AS 'MODULE_PATHNAME', 'debug_cleanup_old_sessions_wrapper'
+LANGUAGE C STRICT;
+
+CREATE OR REPLACE FUNCTION debug_enable_for_rule(rule_name TEXT)
+RETURNS BOOLEAN
+AS 'MODULE_PATHNAME', 'debug_enable_for_rule_wrapper'
+LANGUAGE C STRICT;
+
+-- Not STRICT: NULL keeps the current value of a limit
+CREATE OR REPLACE FUNCTION debug_set_limits(max_sessions INTEGER DEFAULT NULL, max_events_per_session INTEGER DEFAULT NULL, on_overflow TEXT DEFAULT NULL)
+RETURNS JSONB
+AS 'MODULE_PATHNAME', 'debug_set_limits_wrapper'
+LANGUAGE C;
+
 -- Backward chaining API
-CREATE OR REPLACE FUNCTION query_backward_chaining(facts_json TEXT, rules_grl TEXT, goal TEXT)
+CREATE OR REPLACE FUNCTION query_backward_chaining(facts_json TEXT, rules_grl TEXT, goal TEXT, assume_closed_world BOOLEAN DEFAULT false)
 RETURNS TEXT
 AS 'MODULE_PATHNAME', 'query_backward_chaining_wrapper'
 LANGUAGE C STRICT;
@@ -104,7 +136,7 @@ LANGUAGE C STRICT;
 -- parameters (mapped to Option<T> in Rust). Do NOT change to LANGUAGE C STRICT
 -- for those functions that accept NULL.
 
-CREATE OR REPLACE FUNCTION rule_save(name TEXT, grl_content TEXT, version TEXT, description TEXT, change_notes TEXT)
+CREATE OR REPLACE FUNCTION rule_save(name TEXT, grl_content TEXT, version TEXT, description TEXT, change_notes TEXT, bump TEXT DEFAULT NULL, idempotency_key TEXT DEFAULT NULL)
 RETURNS INT
 AS 'MODULE_PATHNAME', 'rule_save_wrapper'
 LANGUAGE C;
@@ -134,7 +166,12 @@ RETURNS BOOLEAN
 AS 'MODULE_PATHNAME', 'rule_tag_remove_wrapper'
 LANGUAGE C STRICT;
 
-CREATE OR REPLACE FUNCTION rule_execute_by_name(name TEXT, facts_json TEXT, version TEXT)
+CREATE OR REPLACE FUNCTION rule_set_algorithm(name TEXT, algorithm TEXT)
+RETURNS BOOLEAN
+AS 'MODULE_PATHNAME', 'rule_set_algorithm_wrapper'
+LANGUAGE C STRICT;
+
+CREATE OR REPLACE FUNCTION rule_execute_by_name(name TEXT, facts_json TEXT, version TEXT DEFAULT NULL, expected_version TEXT DEFAULT NULL)
 RETURNS TEXT
 AS 'MODULE_PATHNAME', 'rule_execute_by_name_wrapper'
 LANGUAGE C;
@@ -168,8 +205,10 @@ CREATE TABLE IF NOT EXISTS rule_definitions (
     updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
     updated_by TEXT,
     is_active BOOLEAN NOT NULL DEFAULT true,
+    algorithm TEXT NOT NULL DEFAULT 'rete',
     
-    CONSTRAINT rule_name_valid CHECK (name ~ '^[a-zA-Z][a-zA-Z0-9_-]*$')
+    CONSTRAINT rule_name_valid CHECK (name ~ '^[a-zA-Z][a-zA-Z0-9_-]*$'),
+    CONSTRAINT rule_algorithm_valid CHECK (algorithm IN ('rete', 'fc'))
 );
 
 CREATE INDEX IF NOT EXISTS idx_rule_definitions_name ON rule_definitions(name);
@@ -178,6 +217,7 @@ CREATE INDEX IF NOT EXISTS idx_rule_definitions_active ON rule_definitions(is_ac
 COMMENT ON TABLE rule_definitions IS 'Main rule metadata and lifecycle management';
 COMMENT ON COLUMN rule_definitions.name IS 'Unique rule identifier (alphanumeric, underscore, hyphen)';
 COMMENT ON COLUMN rule_definitions.is_active IS 'Whether rule is active and can be executed';
+COMMENT ON COLUMN rule_definitions.algorithm IS 'Execution algorithm used by rule_execute_by_name: rete (default) or fc (forward chaining)';
 
 -- Rule versions with semantic versioning
 CREATE TABLE IF NOT EXISTS rule_versions (
@@ -191,7 +231,7 @@ CREATE TABLE IF NOT EXISTS rule_versions (
     is_default BOOLEAN NOT NULL DEFAULT false,
     
     CONSTRAINT rule_version_unique UNIQUE (rule_id, version),
-    CONSTRAINT version_format_valid CHECK (version ~ '^\d+\.\d+\.\d+(-[a-zA-Z0-9]+)?$'),
+    CONSTRAINT version_format_valid CHECK (version ~ '^\d+\.\d+\.\d+(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$'),
     CONSTRAINT grl_not_empty CHECK (length(grl_content) > 0),
     CONSTRAINT grl_size_limit CHECK (length(grl_content) <= 1048576) -- 1MB
 );
@@ -201,7 +241,7 @@ CREATE INDEX IF NOT EXISTS idx_rule_versions_default ON rule_versions(rule_id, i
 CREATE INDEX IF NOT EXISTS idx_rule_versions_created ON rule_versions(created_at DESC);
 
 COMMENT ON TABLE rule_versions IS 'Version history for each rule with GRL content';
-COMMENT ON COLUMN rule_versions.version IS 'Semantic version (e.g., 1.0.0, 2.1.0-beta)';
+COMMENT ON COLUMN rule_versions.version IS 'Semantic version (e.g., 1.0.0, 2.1.0-rc.1, 1.0.0+build5)';
 COMMENT ON COLUMN rule_versions.is_default IS 'The active/default version used when version not specified';
 
 -- Tags for categorization
@@ -244,6 +284,46 @@ CREATE INDEX IF NOT EXISTS idx_rule_audit_action ON rule_audit_log(action);
 
 COMMENT ON TABLE rule_audit_log IS 'Complete audit trail of all rule changes';
 
+-- Idempotency keys for retryable rule_save calls
+CREATE TABLE IF NOT EXISTS rule_save_idempotency (
+    rule_name VARCHAR(255) NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    rule_id INTEGER NOT NULL REFERENCES rule_definitions(id) ON DELETE CASCADE,
+    version VARCHAR(50) NOT NULL,
+    created_by VARCHAR(100) NOT NULL DEFAULT CURRENT_USER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (rule_name, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rule_save_idempotency_rule_id ON rule_save_idempotency(rule_id);
+
+COMMENT ON TABLE rule_save_idempotency IS 'Idempotency keys used by rule_save, one row per (rule, key)';
+COMMENT ON COLUMN rule_save_idempotency.rule_id IS 'Rule ID returned by the original rule_save call';
+COMMENT ON COLUMN rule_save_idempotency.version IS 'Version created by the original rule_save call';
+
+-- Executions of stored rules, written while rule_engine.audit_enabled is on.
+-- Facts are never stored, only a SHA-256 hash of their canonical JSON.
+CREATE TABLE IF NOT EXISTS rule_execution_audit (
+    audit_id BIGSERIAL PRIMARY KEY,
+    rule_name VARCHAR(255) NOT NULL,
+    rule_version VARCHAR(50),
+    executed_by VARCHAR(100) NOT NULL DEFAULT CURRENT_USER,
+    facts_hash CHAR(64) NOT NULL,
+    rules_fired INTEGER NOT NULL DEFAULT 0,
+    fired BOOLEAN NOT NULL,
+    success BOOLEAN NOT NULL,
+    executed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE rule_execution_audit IS 'Audit trail of stored-rule executions (rule_engine.audit_enabled)';
+COMMENT ON COLUMN rule_execution_audit.rule_version IS 'Version executed; the default version at the time if none was requested';
+COMMENT ON COLUMN rule_execution_audit.facts_hash IS 'Hex SHA-256 of the input facts as canonical JSON (sorted keys, no whitespace)';
+COMMENT ON COLUMN rule_execution_audit.fired IS 'True if at least one rule fired';
+COMMENT ON COLUMN rule_execution_audit.success IS 'False if execution returned an error';
+
+CREATE INDEX IF NOT EXISTS idx_rule_execution_audit_rule_time ON rule_execution_audit(rule_name, executed_at DESC);
+CREATE INDEX IF NOT EXISTS idx_rule_execution_audit_time ON rule_execution_audit(executed_at DESC);
+
 -- =============================================================================
 -- Views
 -- =============================================================================
@@ -387,47 +467,117 @@ CREATE TRIGGER trigger_version_audit
 CREATE OR REPLACE FUNCTION is_valid_semver(version_str TEXT)
 RETURNS BOOLEAN AS $$
 BEGIN
-    RETURN version_str ~ '^\d+\.\d+\.\d+(-[a-zA-Z0-9]+)?$';
+    RETURN version_str ~ '^\d+\.\d+\.\d+(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$';
 END;
 $$ LANGUAGE plpgsql IMMUTABLE;
 
 -- Compare semantic versions (returns -1, 0, or 1)
+-- Pre-release versions sort below the release; build metadata is ignored
 CREATE OR REPLACE FUNCTION compare_semver(v1 TEXT, v2 TEXT)
 RETURNS INTEGER AS $$
 DECLARE
+    v1_core TEXT;
+    v2_core TEXT;
+    v1_pre TEXT;
+    v2_pre TEXT;
     v1_parts TEXT[];
     v2_parts TEXT[];
-    v1_major INTEGER;
-    v1_minor INTEGER;
-    v1_patch INTEGER;
-    v2_major INTEGER;
-    v2_minor INTEGER;
-    v2_patch INTEGER;
+    v1_ids TEXT[];
+    v2_ids TEXT[];
+    a TEXT;
+    b TEXT;
+    i INTEGER;
 BEGIN
-    -- Extract major.minor.patch (ignore pre-release)
-    v1_parts := regexp_split_to_array(split_part(v1, '-', 1), '\.');
-    v2_parts := regexp_split_to_array(split_part(v2, '-', 1), '\.');
-    
-    v1_major := v1_parts[1]::INTEGER;
-    v1_minor := v1_parts[2]::INTEGER;
-    v1_patch := v1_parts[3]::INTEGER;
-    
-    v2_major := v2_parts[1]::INTEGER;
-    v2_minor := v2_parts[2]::INTEGER;
-    v2_patch := v2_parts[3]::INTEGER;
-    
-    IF v1_major != v2_major THEN
-        RETURN SIGN(v1_major - v2_major);
-    ELSIF v1_minor != v2_minor THEN
-        RETURN SIGN(v1_minor - v2_minor);
-    ELSIF v1_patch != v2_patch THEN
-        RETURN SIGN(v1_patch - v2_patch);
-    ELSE
+    -- Strip build metadata, then split off the pre-release
+    v1_core := split_part(v1, '+', 1);
+    v2_core := split_part(v2, '+', 1);
+    v1_pre := NULLIF(substring(v1_core FROM position('-' IN v1_core) + 1), v1_core);
+    v2_pre := NULLIF(substring(v2_core FROM position('-' IN v2_core) + 1), v2_core);
+
+    v1_parts := regexp_split_to_array(split_part(v1_core, '-', 1), '\.');
+    v2_parts := regexp_split_to_array(split_part(v2_core, '-', 1), '\.');
+
+    FOR i IN 1..3 LOOP
+        IF v1_parts[i]::BIGINT != v2_parts[i]::BIGINT THEN
+            RETURN SIGN(v1_parts[i]::BIGINT - v2_parts[i]::BIGINT);
+        END IF;
+    END LOOP;
+
+    -- A release has higher precedence than any of its pre-releases
+    IF v1_pre IS NULL AND v2_pre IS NULL THEN
         RETURN 0;
+    ELSIF v1_pre IS NULL THEN
+        RETURN 1;
+    ELSIF v2_pre IS NULL THEN
+        RETURN -1;
     END IF;
+
+    -- Compare dot-separated pre-release identifiers left to right
+    v1_ids := string_to_array(v1_pre, '.');
+    v2_ids := string_to_array(v2_pre, '.');
+
+    FOR i IN 1..GREATEST(array_length(v1_ids, 1), array_length(v2_ids, 1)) LOOP
+        a := v1_ids[i];
+        b := v2_ids[i];
+
+        IF a IS NULL THEN
+            RETURN -1;
+        ELSIF b IS NULL THEN
+            RETURN 1;
+        ELSIF a ~ '^\d+$' AND b ~ '^\d+$' THEN
+            IF a::NUMERIC != b::NUMERIC THEN
+                RETURN SIGN(a::NUMERIC - b::NUMERIC);
+            END IF;
+        ELSIF a ~ '^\d+$' THEN
+            RETURN -1;
+        ELSIF b ~ '^\d+$' THEN
+            RETURN 1;
+        ELSIF a != b THEN
+            RETURN CASE WHEN a < b COLLATE "C" THEN -1 ELSE 1 END;
+        END IF;
+    END LOOP;
+
+    RETURN 0;
 END;
 $$ LANGUAGE plpgsql IMMUTABLE;
 
+-- Execution audit entries for a rule, newest first
+CREATE OR REPLACE FUNCTION rule_audit_query(
+    p_rule_name TEXT DEFAULT NULL,
+    p_since TIMESTAMPTZ DEFAULT NULL
+)
+RETURNS TABLE (
+    audit_id BIGINT,
+    rule_name VARCHAR(255),
+    rule_version VARCHAR(50),
+    executed_by VARCHAR(100),
+    facts_hash CHAR(64),
+    rules_fired INTEGER,
+    fired BOOLEAN,
+    success BOOLEAN,
+    executed_at TIMESTAMPTZ
+) AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        a.audit_id,
+        a.rule_name,
+        a.rule_version,
+        a.executed_by,
+        a.facts_hash,
+        a.rules_fired,
+        a.fired,
+        a.success,
+        a.executed_at
+    FROM rule_execution_audit a
+    WHERE (p_rule_name IS NULL OR a.rule_name = p_rule_name)
+      AND (p_since IS NULL OR a.executed_at >= p_since)
+    ORDER BY a.executed_at DESC, a.audit_id DESC;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+COMMENT ON FUNCTION rule_audit_query IS 'Audit entries for a rule (NULL = all) since a time (NULL = all), newest first';
+
 -- =============================================================================
 -- Permissions (Optional - uncomment if needed)
 -- =============================================================================
@@ -495,6 +645,7 @@ CREATE TABLE IF NOT EXISTS rule_triggers (
     rule_name VARCHAR(255) NOT NULL,
     event_type VARCHAR(10) NOT NULL CHECK (event_type IN ('INSERT', 'UPDATE', 'DELETE')),
     enabled BOOLEAN DEFAULT TRUE,
+    dry_run BOOLEAN NOT NULL DEFAULT FALSE,
     created_at TIMESTAMP DEFAULT NOW(),
     created_by VARCHAR(255) DEFAULT CURRENT_USER,
     updated_at TIMESTAMP DEFAULT NOW(),
@@ -509,6 +660,8 @@ COMMENT ON COLUMN rule_triggers.table_name IS 'Target table name to monitor';
 COMMENT ON COLUMN rule_triggers.rule_name IS 'Rule to execute (from rule_definitions)';
 COMMENT ON COLUMN rule_triggers.event_type IS 'Event type: INSERT, UPDATE, or DELETE';
 COMMENT ON COLUMN rule_triggers.enabled IS 'Whether trigger is active';
+COMMENT ON COLUMN rule_triggers.dry_run IS
+    'If true, the rule runs and its outcome is recorded but the row is not modified';
 
 -- ============================================================================
 -- 2. TRIGGER HISTORY TABLE
@@ -525,6 +678,8 @@ CREATE TABLE IF NOT EXISTS rule_trigger_history (
     success BOOLEAN NOT NULL,
     error_message TEXT,
     execution_time_ms NUMERIC(10, 2),
+    dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+    changes JSONB,
     CONSTRAINT chk_event_type CHECK (event_type IN ('INSERT', 'UPDATE', 'DELETE'))
 );
 
@@ -533,6 +688,9 @@ COMMENT ON COLUMN rule_trigger_history.old_data IS 'Row data before change (UPDA
 COMMENT ON COLUMN rule_trigger_history.new_data IS 'Row data after change (INSERT/UPDATE)';
 COMMENT ON COLUMN rule_trigger_history.result_data IS 'Rule execution result';
 COMMENT ON COLUMN rule_trigger_history.execution_time_ms IS 'Execution time in milliseconds';
+COMMENT ON COLUMN rule_trigger_history.dry_run IS 'True if the execution ran in dry-run mode (row not modified)';
+COMMENT ON COLUMN rule_trigger_history.changes IS
+    'Columns changed (or, in dry-run mode, that would have changed) as {column: {old, new}}';
 
 -- Indexes for performance
 CREATE INDEX idx_trigger_history_trigger_id ON rule_trigger_history(trigger_id);
@@ -551,6 +709,8 @@ DECLARE
     trigger_config RECORD;
     facts_json TEXT;
     result_json TEXT;
+    merged_row JSONB;
+    row_changes JSONB;
     start_time TIMESTAMP;
     end_time TIMESTAMP;
     execution_ms NUMERIC;
@@ -558,7 +718,7 @@ DECLARE
 BEGIN
     -- Loop through all enabled triggers for this table and event
     FOR trigger_config IN
-        SELECT rt.id, rt.rule_name
+        SELECT rt.id, rt.rule_name, rt.dry_run
         FROM rule_triggers rt
         WHERE rt.table_name = TG_TABLE_NAME
           AND rt.event_type = TG_OP
@@ -566,28 +726,40 @@ BEGIN
     LOOP
         BEGIN
             start_time := clock_timestamp();
-            
+            row_changes := NULL;
+
             -- Build facts JSON based on event type
             IF TG_OP = 'DELETE' THEN
                 facts_json := row_to_json(OLD)::TEXT;
             ELSE
                 facts_json := row_to_json(NEW)::TEXT;
             END IF;
-            
+
             -- Execute rule by name (uses default version)
             result_json := rule_execute_by_name(
                 trigger_config.rule_name,
                 facts_json,
                 NULL
             );
-            
+
+            -- Work out which columns the result changes (INSERT/UPDATE only)
+            IF TG_OP IN ('INSERT', 'UPDATE') THEN
+                merged_row := to_jsonb(jsonb_populate_record(NEW, result_json::JSONB));
+
+                SELECT jsonb_object_agg(n.key, jsonb_build_object('old', o.value, 'new', n.value))
+                INTO row_changes
+                FROM jsonb_each(merged_row) n
+                JOIN jsonb_each(to_jsonb(NEW)) o ON o.key = n.key
+                WHERE n.value IS DISTINCT FROM o.value;
+            END IF;
+
             end_time := clock_timestamp();
             execution_ms := EXTRACT(EPOCH FROM (end_time - start_time)) * 1000;
-            
+
             -- Log success
             INSERT INTO rule_trigger_history (
-                trigger_id, event_type, old_data, new_data, 
-                result_data, success, execution_time_ms
+                trigger_id, event_type, old_data, new_data,
+                result_data, success, execution_time_ms, dry_run, changes
             ) VALUES (
                 trigger_config.id,
                 TG_OP,
@@ -595,22 +767,23 @@ BEGIN
                 CASE WHEN TG_OP IN ('INSERT', 'UPDATE') THEN row_to_json(NEW) ELSE NULL END,
                 result_json::JSONB,
                 TRUE,
-                execution_ms
+                execution_ms,
+                trigger_config.dry_run,
+                row_changes
             );
-            
-            -- Update NEW with modified data (for INSERT/UPDATE only)
-            IF TG_OP IN ('INSERT', 'UPDATE') THEN
-                -- Merge result back into NEW row
+
+            -- Update NEW with modified data, unless this trigger is a dry run
+            IF TG_OP IN ('INSERT', 'UPDATE') AND NOT trigger_config.dry_run THEN
                 NEW := jsonb_populate_record(NEW, result_json::JSONB);
             END IF;
-            
+
         EXCEPTION WHEN OTHERS THEN
             error_occurred := TRUE;
-            
+
             -- Log error
             INSERT INTO rule_trigger_history (
                 trigger_id, event_type, old_data, new_data,
-                success, error_message, execution_time_ms
+                success, error_message, execution_time_ms, dry_run
             ) VALUES (
                 trigger_config.id,
                 TG_OP,
@@ -618,14 +791,15 @@ BEGIN
                 CASE WHEN TG_OP IN ('INSERT', 'UPDATE') THEN row_to_json(NEW) ELSE NULL END,
                 FALSE,
                 SQLERRM,
-                EXTRACT(EPOCH FROM (clock_timestamp() - start_time)) * 1000
+                EXTRACT(EPOCH FROM (clock_timestamp() - start_time)) * 1000,
+                trigger_config.dry_run
             );
-            
+
             -- Log warning but don't fail the transaction
             RAISE WARNING 'Rule trigger % failed: %', trigger_config.rule_name, SQLERRM;
         END;
     END LOOP;
-    
+
     -- Return appropriate value based on operation
     IF TG_OP = 'DELETE' THEN
         RETURN OLD;
@@ -635,7 +809,7 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
-COMMENT ON FUNCTION execute_rule_trigger() IS 'Generic trigger function that executes rules on table changes';
+COMMENT ON FUNCTION execute_rule_trigger() IS 'Generic trigger function that executes rules on table changes (honours dry_run)';
 
 -- ============================================================================
 -- 4. API FUNCTIONS
@@ -728,6 +902,30 @@ $$ LANGUAGE plpgsql;
 
 COMMENT ON FUNCTION rule_trigger_enable IS 'Enable or disable a rule trigger';
 
+-- Turn dry-run mode on or off for a trigger
+CREATE OR REPLACE FUNCTION rule_trigger_set_dry_run(
+    p_trigger_id INTEGER,
+    p_dry_run BOOLEAN DEFAULT TRUE
+) RETURNS BOOLEAN AS $$
+BEGIN
+    UPDATE rule_triggers
+    SET dry_run = p_dry_run,
+        updated_at = NOW(),
+        updated_by = CURRENT_USER
+    WHERE id = p_trigger_id;
+
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'ERR_RT004: Trigger not found: %', p_trigger_id;
+    END IF;
+
+    RAISE NOTICE 'Trigger % dry-run %', p_trigger_id, CASE WHEN p_dry_run THEN 'on' ELSE 'off' END;
+
+    RETURN TRUE;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON FUNCTION rule_trigger_set_dry_run IS 'Enable or disable dry-run mode for a rule trigger';
+
 -- Get trigger execution history
 CREATE OR REPLACE FUNCTION rule_trigger_history(
     p_trigger_id INTEGER,
@@ -740,22 +938,26 @@ CREATE OR REPLACE FUNCTION rule_trigger_history(
     success BOOLEAN,
     execution_time_ms NUMERIC,
     error_message TEXT,
-    result_summary TEXT
+    result_summary TEXT,
+    dry_run BOOLEAN,
+    changes JSONB
 ) AS $$
 BEGIN
     RETURN QUERY
-    SELECT 
+    SELECT
         rth.id,
         rth.executed_at,
-        rth.event_type,
+        rth.event_type::TEXT,
         rth.success,
         rth.execution_time_ms,
         rth.error_message,
-        CASE 
-            WHEN rth.result_data IS NOT NULL THEN 
+        CASE
+            WHEN rth.result_data IS NOT NULL THEN
                 substring(rth.result_data::TEXT, 1, 100) || '...'
             ELSE NULL
-        END AS result_summary
+        END AS result_summary,
+        rth.dry_run,
+        rth.changes
     FROM rule_trigger_history rth
     WHERE rth.trigger_id = p_trigger_id
       AND rth.executed_at BETWEEN p_start_time AND p_end_time
@@ -820,7 +1022,7 @@ COMMENT ON FUNCTION rule_trigger_delete IS 'Delete a rule trigger';
 -- ============================================================================
 
 CREATE OR REPLACE VIEW rule_trigger_stats AS
-SELECT 
+SELECT
     rt.id AS trigger_id,
     rt.name AS trigger_name,
     rt.table_name,
@@ -831,10 +1033,12 @@ SELECT
     COUNT(CASE WHEN rth.success THEN 1 END) AS successful_executions,
     COUNT(CASE WHEN NOT rth.success THEN 1 END) AS failed_executions,
     ROUND(AVG(rth.execution_time_ms)::NUMERIC, 2) AS avg_execution_time_ms,
-    MAX(rth.executed_at) AS last_executed_at
+    MAX(rth.executed_at) AS last_executed_at,
+    rt.dry_run,
+    COUNT(CASE WHEN rth.dry_run THEN 1 END) AS dry_run_executions
 FROM rule_triggers rt
 LEFT JOIN rule_trigger_history rth ON rt.id = rth.trigger_id
-GROUP BY rt.id, rt.name, rt.table_name, rt.rule_name, rt.event_type, rt.enabled;
+GROUP BY rt.id, rt.name, rt.table_name, rt.rule_name, rt.event_type, rt.enabled, rt.dry_run;
 
 COMMENT ON VIEW rule_trigger_stats IS 'Statistics for rule trigger executions';
 
@@ -851,6 +1055,7 @@ GRANT USAGE, SELECT ON SEQUENCE rule_trigger_history_id_seq TO PUBLIC;
 -- Grant execute on functions
 GRANT EXECUTE ON FUNCTION rule_trigger_create TO PUBLIC;
 GRANT EXECUTE ON FUNCTION rule_trigger_enable TO PUBLIC;
+GRANT EXECUTE ON FUNCTION rule_trigger_set_dry_run TO PUBLIC;
 GRANT EXECUTE ON FUNCTION rule_trigger_history TO PUBLIC;
 GRANT EXECUTE ON FUNCTION rule_trigger_delete TO PUBLIC;
 
@@ -1683,31 +1888,49 @@ $$ LANGUAGE plpgsql;
 -- 2.2 RULE VALIDATION & LINTING
 -- ============================================================================
 
+-- Structural GRL checks (implemented in Rust), all issues with line/column
+CREATE OR REPLACE FUNCTION rule_validate_detailed(grl TEXT)
+RETURNS JSONB
+AS 'MODULE_PATHNAME', 'rule_validate_detailed_wrapper'
+LANGUAGE C IMMUTABLE STRICT PARALLEL SAFE;
+
 CREATE OR REPLACE FUNCTION rule_validate(p_grl TEXT)
 RETURNS JSON AS $$
 DECLARE
     v_errors TEXT[] := '{}';
     v_warnings TEXT[] := '{}';
     v_test_result TEXT;
+    v_detailed JSONB;
+    v_issue JSONB;
 BEGIN
+    -- Try to compile the rule
     BEGIN
         SELECT run_rule_engine('{}'::JSONB, p_grl) INTO v_test_result;
     EXCEPTION WHEN OTHERS THEN
         v_errors := array_append(v_errors, 'Syntax error: ' || SQLERRM);
     END;
 
-    IF trim(p_grl) = '' THEN
-        v_errors := array_append(v_errors, 'Rule cannot be empty');
-    END IF;
-
-    IF p_grl !~ 'rule\s+\w+' THEN
-        v_errors := array_append(v_errors, 'Missing rule declaration');
-    END IF;
+    -- Structural checks, all reported at once
+    v_detailed := rule_validate_detailed(p_grl);
+    FOR v_issue IN SELECT * FROM jsonb_array_elements(v_detailed->'issues') LOOP
+        v_errors := array_append(
+            v_errors,
+            CASE
+                WHEN v_issue->'location' = 'null'::JSONB THEN v_issue->>'message'
+                ELSE format('Line %s, column %s: %s',
+                    v_issue->'location'->>'line',
+                    v_issue->'location'->>'column',
+                    v_issue->>'message')
+            END
+        );
+    END LOOP;
 
+    -- Warning: Check for complex conditions
     IF (length(p_grl) - length(replace(p_grl, '&&', ''))) / 2 > 5 THEN
         v_warnings := array_append(v_warnings, 'Complex condition: More than 5 AND operators may impact performance');
     END IF;
 
+    -- Warning: Check for deep nesting
     IF (length(p_grl) - length(replace(p_grl, '{', ''))) > 10 THEN
         v_warnings := array_append(v_warnings, 'Deep nesting detected: Consider refactoring');
     END IF;
@@ -1717,11 +1940,14 @@ BEGIN
         'errors', v_errors,
         'warnings', v_warnings,
         'error_count', COALESCE(array_length(v_errors, 1), 0),
-        'warning_count', COALESCE(array_length(v_warnings, 1), 0)
+        'warning_count', COALESCE(array_length(v_warnings, 1), 0),
+        'issues', v_detailed->'issues'
     );
 END;
 $$ LANGUAGE plpgsql;
 
+COMMENT ON FUNCTION rule_validate(TEXT) IS 'Validate GRL; "issues" lists every structural problem with code and line/column';
+
 CREATE OR REPLACE FUNCTION rule_lint(
     p_grl TEXT,
     p_strict_mode BOOLEAN DEFAULT false
@@ -2877,9 +3103,26 @@ CREATE TABLE IF NOT EXISTS rule_datasources (
     created_by TEXT DEFAULT CURRENT_USER,
     updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
     tags TEXT[] DEFAULT '{}',
+    source_type TEXT NOT NULL DEFAULT 'rest' CHECK (source_type IN ('rest', 'graphql')),
+    pagination JSONB CHECK (
+        pagination IS NULL
+        OR (
+            jsonb_typeof(pagination) = 'object'
+            AND pagination ? 'items_path'
+            AND pagination ? 'next_path'
+        )
+    ),
+    max_response_bytes BIGINT DEFAULT 10485760 CHECK (max_response_bytes IS NULL OR max_response_bytes > 0),
     CONSTRAINT valid_url CHECK (base_url ~* '^https?://')
 );
 
+COMMENT ON COLUMN rule_datasources.source_type IS
+    'rest = plain JSON over HTTP; graphql = POST {query, variables} and unwrap data/errors';
+COMMENT ON COLUMN rule_datasources.pagination IS
+    'NULL = single request. Otherwise {"items_path", "next_path", "max_pages" (default 10, max 100), "cursor_param" (default "cursor")}';
+COMMENT ON COLUMN rule_datasources.max_response_bytes IS
+    'Largest response body accepted, in bytes (default 10 MiB). Larger responses fail with "Response too large". NULL = unlimited';
+
 -- Table: rule_datasource_auth
 -- Stores authentication credentials for datasources
 CREATE TABLE IF NOT EXISTS rule_datasource_auth (
@@ -2904,9 +3147,16 @@ CREATE TABLE IF NOT EXISTS rule_datasource_cache (
     expires_at TIMESTAMPTZ NOT NULL,
     hit_count INTEGER DEFAULT 0,
     last_hit_at TIMESTAMPTZ,
+    etag TEXT,
+    last_modified TEXT,
     UNIQUE(datasource_id, cache_key)
 );
 
+COMMENT ON COLUMN rule_datasource_cache.etag IS
+    'ETag response header, sent as If-None-Match once the entry expires';
+COMMENT ON COLUMN rule_datasource_cache.last_modified IS
+    'Last-Modified response header, sent as If-Modified-Since once the entry expires';
+
 -- Table: rule_datasource_requests
 -- History and monitoring of data source requests
 CREATE TABLE IF NOT EXISTS rule_datasource_requests (
@@ -3226,13 +3476,19 @@ END;
 $$ LANGUAGE plpgsql;
 
 -- Function: rule_datasource_cache_cleanup
--- Removes expired cache entries
+-- Removes expired cache entries. Expired entries with an ETag/Last-Modified
+-- can still be revalidated with a 304, so those are kept for a day.
 CREATE OR REPLACE FUNCTION rule_datasource_cache_cleanup()
 RETURNS BIGINT AS $$
 DECLARE
     v_deleted_count BIGINT;
 BEGIN
-    DELETE FROM rule_datasource_cache WHERE expires_at <= CURRENT_TIMESTAMP;
+    DELETE FROM rule_datasource_cache
+    WHERE expires_at <= CURRENT_TIMESTAMP
+      AND (
+          (etag IS NULL AND last_modified IS NULL)
+          OR expires_at <= CURRENT_TIMESTAMP - INTERVAL '1 day'
+      );
     GET DIAGNOSTICS v_deleted_count = ROW_COUNT;
     RETURN v_deleted_count;
 END;
@@ -3573,6 +3829,26 @@ AS 'MODULE_PATHNAME', 'rule_function_list_wrapper';
 COMMENT ON FUNCTION rule_function_list IS 
 'List all 24 built-in functions available in GRL rules.
 Functions work in when conditions via preprocessing.';
+
+-- AES-256-GCM keys for the Encrypt/Decrypt built-in functions, looked up by
+-- name so keys never appear in GRL or facts. Add one with pgcrypto:
+--   INSERT INTO rule_crypto_keys (key_name, key_material)
+--   VALUES ('pii', gen_random_bytes(32));
+CREATE TABLE IF NOT EXISTS rule_crypto_keys (
+    key_name TEXT PRIMARY KEY,
+    key_material BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_by TEXT DEFAULT current_user,
+
+    CONSTRAINT crypto_key_length CHECK (length(key_material) = 32)
+);
+
+-- Rule executions read keys with the caller's privileges
+REVOKE ALL ON rule_crypto_keys FROM PUBLIC;
+
+COMMENT ON TABLE rule_crypto_keys IS 'AES-256 keys used by the Encrypt/Decrypt built-in functions';
+COMMENT ON COLUMN rule_crypto_keys.key_material IS 'Raw 32-byte key; rotating it makes existing ciphertext undecryptable';
+
 -- NATS Integration (v1.8.0)
 -- Migration: NATS Integration (RFC-0007)
 -- Version: 1.6.0
@@ -3743,6 +4019,7 @@ CREATE TABLE IF NOT EXISTS rule_nats_publish_history (
     success BOOLEAN NOT NULL,
     error_message TEXT,
     latency_ms NUMERIC(10,2),
+    duplicate BOOLEAN NOT NULL DEFAULT false, -- JetStream deduplicated the message
 
     -- Context
     triggered_by TEXT,
@@ -3755,12 +4032,52 @@ CREATE TABLE IF NOT EXISTS rule_nats_publish_history (
 COMMENT ON TABLE rule_nats_publish_history IS 'Audit log of all NATS publish operations';
 COMMENT ON COLUMN rule_nats_publish_history.sequence_number IS 'JetStream sequence number (if using JetStream)';
 COMMENT ON COLUMN rule_nats_publish_history.latency_ms IS 'Time taken to publish message in milliseconds';
+COMMENT ON COLUMN rule_nats_publish_history.duplicate IS
+    'True if JetStream deduplicated the message; sequence_number is then the original message''s sequence';
 
 CREATE INDEX idx_nats_publish_webhook ON rule_nats_publish_history(webhook_id);
 CREATE INDEX idx_nats_publish_time ON rule_nats_publish_history(published_at DESC);
 CREATE INDEX idx_nats_publish_subject ON rule_nats_publish_history(subject);
 CREATE INDEX idx_nats_publish_success ON rule_nats_publish_history(success);
 CREATE INDEX idx_nats_publish_expires ON rule_nats_publish_history(expires_at);
+CREATE INDEX idx_nats_publish_duplicate
+    ON rule_nats_publish_history(webhook_id)
+    WHERE duplicate = true;
+
+-- Webhook payloads whose NATS publish failed after all retries, kept so they
+-- can be re-driven with rule_nats_replay_dead_letter()
+CREATE TABLE IF NOT EXISTS rule_nats_dead_letter (
+    dead_letter_id BIGSERIAL PRIMARY KEY,
+    webhook_id INTEGER REFERENCES rule_webhooks(webhook_id) ON DELETE CASCADE,
+    config_name TEXT NOT NULL,
+
+    -- NATS Message
+    subject TEXT NOT NULL,
+    payload JSONB NOT NULL,
+    message_id TEXT, -- Reused on replay so JetStream can deduplicate
+    headers JSONB, -- Custom headers, re-sent on replay
+
+    -- Failure
+    error_message TEXT NOT NULL,
+    error_category TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+
+    -- Lifecycle
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    last_attempt_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    replayed_at TIMESTAMPTZ, -- Set once the message has been published
+    sequence_number BIGINT -- JetStream sequence of the successful replay
+);
+
+COMMENT ON TABLE rule_nats_dead_letter IS 'NATS webhook publishes that failed after all retries';
+COMMENT ON COLUMN rule_nats_dead_letter.headers IS 'Custom headers of the original publish ({name: value}), re-sent on replay';
+COMMENT ON COLUMN rule_nats_dead_letter.attempts IS 'Total publish attempts, including replays';
+COMMENT ON COLUMN rule_nats_dead_letter.replayed_at IS 'When the message was successfully re-driven (NULL = pending)';
+
+CREATE INDEX idx_nats_dead_letter_pending
+    ON rule_nats_dead_letter(config_name, dead_letter_id)
+    WHERE replayed_at IS NULL;
+CREATE INDEX idx_nats_dead_letter_webhook ON rule_nats_dead_letter(webhook_id);
 
 -- =============================================================================
 -- 5. Consumer Statistics
@@ -3820,7 +4137,8 @@ SELECT
     ROUND(AVG(h.latency_ms), 2) as avg_latency_ms,
     MIN(h.latency_ms) as min_latency_ms,
     MAX(h.latency_ms) as max_latency_ms,
-    MAX(h.published_at) as last_published_at
+    MAX(h.published_at) as last_published_at,
+    COUNT(*) FILTER (WHERE h.duplicate = true) as deduplicated
 FROM rule_webhooks w
 LEFT JOIN rule_nats_publish_history h ON w.webhook_id = h.webhook_id
 WHERE w.nats_enabled = true
@@ -3847,6 +4165,20 @@ LIMIT 100;
 
 COMMENT ON VIEW nats_recent_failures IS 'Recent NATS publish failures for debugging (last 24 hours)';
 
+-- View: NATS Dead Letters Awaiting Replay
+CREATE OR REPLACE VIEW nats_dead_letter_pending AS
+SELECT
+    config_name,
+    subject,
+    COUNT(*) AS pending,
+    MIN(created_at) AS oldest,
+    MAX(attempts) AS max_attempts
+FROM rule_nats_dead_letter
+WHERE replayed_at IS NULL
+GROUP BY config_name, subject;
+
+COMMENT ON VIEW nats_dead_letter_pending IS 'Dead-lettered NATS messages awaiting replay';
+
 -- View: NATS Performance Stats (with percentiles)
 CREATE OR REPLACE VIEW nats_performance_stats AS
 SELECT
@@ -3899,6 +4231,7 @@ COMMENT ON FUNCTION rule_nats_cleanup_old_history IS 'Delete old NATS publish hi
 GRANT SELECT ON nats_publish_summary TO PUBLIC;
 GRANT SELECT ON nats_recent_failures TO PUBLIC;
 GRANT SELECT ON nats_performance_stats TO PUBLIC;
+GRANT SELECT ON nats_dead_letter_pending TO PUBLIC;
 
 -- =============================================================================
 -- Migration Complete
@@ -1,11 +1,22 @@
-use crate::core::{json_to_facts, parse_and_validate_rules, query_goal, query_multiple_goals};
+use crate::core::{
+    explain_goal, json_to_facts, parse_and_validate_rules, query_goal, query_multiple_goals,
+};
 use crate::error::{codes, create_custom_error};
 use crate::validation::{validate_facts_input, validate_rules_input};
 
 /// Query a goal using backward chaining
 /// Returns JSON with provability status, proof trace, and metrics
+///
+/// With `assume_closed_world = true`, facts that cannot be proven are treated
+/// as false (negation as failure): `User.Blocked == false` is provable when
+/// `User.Blocked == true` is not. The default is open world.
 #[pgrx::pg_extern]
-pub fn query_backward_chaining(facts_json: &str, rules_grl: &str, goal: &str) -> String {
+pub fn query_backward_chaining(
+    facts_json: &str,
+    rules_grl: &str,
+    goal: &str,
+    assume_closed_world: pgrx::default!(bool, false),
+) -> String {
     // Validate inputs
     if let Err(e) = validate_facts_input(facts_json) {
         return create_custom_error(&codes::EMPTY_FACTS, e);
@@ -38,7 +49,7 @@ pub fn query_backward_chaining(facts_json: &str, rules_grl: &str, goal: &str) ->
     };
 
     // Execute backward chaining query
-    match query_goal(&facts, rules, goal) {
+    match query_goal(&facts, rules, goal, assume_closed_world) {
         Ok(result) => match result.to_json() {
             Ok(json) => json,
             Err(e) => create_custom_error(&codes::SERIALIZATION_FAILED, e),
@@ -113,6 +124,48 @@ pub fn query_backward_chaining_multi(
     }
 }
 
+/// Explain whether a goal can be proven
+/// Returns JSON with the goal, provability, the ordered rules/subgoals used,
+/// and the facts that were missing when the goal is unprovable
+#[pgrx::pg_extern]
+pub fn can_prove_goal_explain(facts_json: &str, rules_grl: &str, goal: &str) -> String {
+    // Validate inputs
+    if let Err(e) = validate_facts_input(facts_json) {
+        return create_custom_error(&codes::EMPTY_FACTS, e);
+    }
+    if let Err(e) = validate_rules_input(rules_grl) {
+        return create_custom_error(&codes::EMPTY_RULES, e);
+    }
+    if goal.is_empty() {
+        return create_custom_error(
+            &codes::INVALID_JSON,
+            "Goal query cannot be empty".to_string(),
+        );
+    }
+
+    // Parse facts
+    let facts = match json_to_facts(facts_json) {
+        Ok(f) => f,
+        Err(e) => return create_custom_error(&codes::INVALID_JSON, e),
+    };
+
+    // Parse rules
+    let rules = match parse_and_validate_rules(rules_grl) {
+        Ok(r) => r,
+        Err(e) => {
+            if e.contains("No valid rules") {
+                return create_custom_error(&codes::NO_RULES_FOUND, e);
+            }
+            return create_custom_error(&codes::INVALID_GRL, e);
+        }
+    };
+
+    match explain_goal(&facts, rules, goal) {
+        Ok(explanation) => explanation.to_json_value().to_string(),
+        Err(e) => create_custom_error(&codes::EXECUTION_FAILED, e),
+    }
+}
+
 /// Simple boolean query - just returns true/false (production mode)
 #[pgrx::pg_extern]
 pub fn can_prove_goal(facts_json: &str, rules_grl: &str, goal: &str) -> bool {
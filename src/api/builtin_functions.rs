@@ -31,6 +31,30 @@ fn rule_function_call(
     Ok(pgrx::JsonB(result))
 }
 
+/// Show how built-in function calls in GRL are rewritten, without running rules
+///
+/// # Example
+/// ```sql
+/// SELECT rule_preprocess_preview(
+///     '{"Order": {"createdAt": "2024-01-01"}}',
+///     'rule "Old" { when DaysSince(Order.createdAt) > 90 then Order.stale = true; }'
+/// );
+/// -- Returns: {"transformed_grl": "... when Order.__func_0_dayssince > 90 ...",
+/// --           "injected_fields": {"Order.__func_0_dayssince": 412}, "function_calls": [...]}
+/// ```
+#[pg_extern]
+fn rule_preprocess_preview(
+    facts_json: &str,
+    rules_grl: &str,
+) -> Result<pgrx::JsonB, Box<dyn std::error::Error + Send + Sync>> {
+    let facts: Value = serde_json::from_str(facts_json)?;
+
+    let preview = functions::preprocessing::preprocess_preview(rules_grl, &facts)
+        .map_err(|e| format!("Function preprocessing error: {}", e))?;
+
+    Ok(pgrx::JsonB(preview))
+}
+
 /// List all available built-in functions
 ///
 /// # Example
@@ -82,7 +106,17 @@ fn rule_function_list() -> TableIterator<
         (
             "Contains".to_string(),
             "string".to_string(),
-            "Check if string contains substring".to_string(),
+            "Check if string contains substring, or array contains element".to_string(),
+        ),
+        (
+            "IndexOf".to_string(),
+            "string".to_string(),
+            "Char index of the first occurrence of a substring (-1 if absent)".to_string(),
+        ),
+        (
+            "LastIndexOf".to_string(),
+            "string".to_string(),
+            "Char index of the last occurrence of a substring (-1 if absent)".to_string(),
         ),
         (
             "RegexMatch".to_string(),
@@ -104,21 +138,66 @@ fn rule_function_list() -> TableIterator<
             "string".to_string(),
             "Trim whitespace from both ends".to_string(),
         ),
+        (
+            "TrimStart".to_string(),
+            "string".to_string(),
+            "Trim whitespace from the start".to_string(),
+        ),
+        (
+            "TrimEnd".to_string(),
+            "string".to_string(),
+            "Trim whitespace from the end".to_string(),
+        ),
+        (
+            "PadLeft".to_string(),
+            "string".to_string(),
+            "Pad the start to a width with a character".to_string(),
+        ),
+        (
+            "PadRight".to_string(),
+            "string".to_string(),
+            "Pad the end to a width with a character".to_string(),
+        ),
         (
             "Length".to_string(),
             "string".to_string(),
-            "Get string length".to_string(),
+            "Get string length in characters".to_string(),
+        ),
+        (
+            "ByteLength".to_string(),
+            "string".to_string(),
+            "Get string length in UTF-8 bytes".to_string(),
         ),
         (
             "Substring".to_string(),
             "string".to_string(),
             "Get substring".to_string(),
         ),
+        (
+            "Levenshtein".to_string(),
+            "string".to_string(),
+            "Edit distance between two strings".to_string(),
+        ),
+        (
+            "Similarity".to_string(),
+            "string".to_string(),
+            "Similarity ratio between two strings (0.0 - 1.0)".to_string(),
+        ),
+        (
+            "EqualsIgnoreCase".to_string(),
+            "string".to_string(),
+            "Compare two strings ignoring case".to_string(),
+        ),
+        (
+            "ContainsIgnoreCase".to_string(),
+            "string".to_string(),
+            "Check if string contains substring ignoring case".to_string(),
+        ),
         // Math functions
         (
             "Round".to_string(),
             "math".to_string(),
-            "Round a number to specified decimal places".to_string(),
+            "Round a number to specified decimal places (mode: half_up or half_even)".to_string(),
         ),
         (
             "Abs".to_string(),
@@ -150,6 +229,16 @@ fn rule_function_list() -> TableIterator<
             "math".to_string(),
             "Square root".to_string(),
         ),
+        (
+            "Between".to_string(),
+            "math".to_string(),
+            "Check if a number is within an inclusive range".to_string(),
+        ),
+        (
+            "RoundToNearest".to_string(),
+            "math".to_string(),
+            "Round to the nearest multiple of a step (e.g. 0.05)".to_string(),
+        ),
         // JSON functions
         (
             "JsonParse".to_string(),
@@ -159,7 +248,7 @@ fn rule_function_list() -> TableIterator<
         (
             "JsonStringify".to_string(),
             "json".to_string(),
-            "Convert object to JSON string".to_string(),
+            "Convert object to JSON string (pass true as 2nd arg to pretty-print)".to_string(),
         ),
         (
             "JsonGet".to_string(),
@@ -171,6 +260,87 @@ fn rule_function_list() -> TableIterator<
             "json".to_string(),
             "Set value in JSON object by path".to_string(),
         ),
+        (
+            "Default".to_string(),
+            "json".to_string(),
+            "Return fallback when value is null, otherwise value".to_string(),
+        ),
+        (
+            "JsonMerge".to_string(),
+            "json".to_string(),
+            "Merge a JSON patch into an object (RFC 7386)".to_string(),
+        ),
+        (
+            "JsonPath".to_string(),
+            "json".to_string(),
+            "Get value by path with array indices (e.g. $.a.b[0].c)".to_string(),
+        ),
+        (
+            "TypeOf".to_string(),
+            "json".to_string(),
+            "Name a value's JSON type (string, number, boolean, array, object, null)".to_string(),
+        ),
+        // Conversion functions
+        (
+            "ToNumber".to_string(),
+            "convert".to_string(),
+            "Parse a string or boolean as a number".to_string(),
+        ),
+        (
+            "ToString".to_string(),
+            "convert".to_string(),
+            "Convert a scalar to its string form".to_string(),
+        ),
+        (
+            "ToBool".to_string(),
+            "convert".to_string(),
+            "Parse \"true\"/\"false\" (any case), \"1\"/\"0\" or 1/0 as a boolean".to_string(),
+        ),
+        // Crypto functions
+        (
+            "Encrypt".to_string(),
+            "crypto".to_string(),
+            "AES-256-GCM encrypt a string with a key from rule_crypto_keys (base64)".to_string(),
+        ),
+        (
+            "Decrypt".to_string(),
+            "crypto".to_string(),
+            "Decrypt an Encrypt result; fails if it was modified".to_string(),
+        ),
+        // Logic functions
+        (
+            "InList".to_string(),
+            "logic".to_string(),
+            "Check whether a value equals any element of an array".to_string(),
+        ),
+        (
+            "NotInList".to_string(),
+            "logic".to_string(),
+            "Check whether a value equals no element of an array".to_string(),
+        ),
+        // Array functions
+        (
+            "Sort".to_string(),
+            "array".to_string(),
+            "Sort an array of numbers or strings (\"asc\" or \"desc\")".to_string(),
+        ),
+        (
+            "Distinct".to_string(),
+            "array".to_string(),
+            "Remove duplicate array elements, keeping first-seen order".to_string(),
+        ),
+        // Template functions
+        (
+            "FormatTemplate".to_string(),
+            "template".to_string(),
+            "Replace {key} placeholders with object fields".to_string(),
+        ),
+        // Fact functions
+        (
+            "FactExists".to_string(),
+            "facts".to_string(),
+            "Check whether a field is present in facts (even if null)".to_string(),
+        ),
     ];
 
     TableIterator::new(functions)
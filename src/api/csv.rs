@@ -0,0 +1,105 @@
+//! CSV serialization for batch results
+
+use serde_json::Value;
+
+/// Render rows as CSV with a header row of `columns`
+///
+/// Each column is a dotted field path (`Order.total`); a literal key
+/// containing dots is preferred if present. Missing fields and nulls become
+/// empty cells, objects and arrays are written as JSON, and cells containing
+/// commas, quotes or newlines are quoted.
+pub fn write_csv(columns: &[String], rows: &[Value]) -> String {
+    let mut out = String::new();
+    write_record(&mut out, columns.iter().map(String::as_str));
+
+    for row in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| lookup(row, column).map(render).unwrap_or_default())
+            .collect();
+        write_record(&mut out, cells.iter().map(String::as_str));
+    }
+
+    out
+}
+
+fn write_record<'a>(out: &mut String, cells: impl Iterator<Item = &'a str>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(cell);
+        }
+    }
+    out.push('\n');
+}
+
+fn lookup<'a>(row: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = row.get(path) {
+        return Some(value);
+    }
+
+    path.split('.')
+        .try_fold(row, |current, part| current.get(part))
+}
+
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let rows = vec![
+            json!({"Order": {"id": 1, "total": 1500.5, "vip": true}}),
+            json!({"Order": {"id": 2, "total": 50}}),
+        ];
+
+        assert_eq!(
+            write_csv(&columns(&["Order.id", "Order.total", "Order.vip"]), &rows),
+            "Order.id,Order.total,Order.vip\n1,1500.5,true\n2,50,\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_quoting() {
+        let rows = vec![json!({
+            "Customer": {"name": "Doe, Jane", "note": "said \"hi\"\nthen left", "tags": ["a", "b"]}
+        })];
+
+        assert_eq!(
+            write_csv(
+                &columns(&["Customer.name", "Customer.note", "Customer.tags"]),
+                &rows
+            ),
+            "Customer.name,Customer.note,Customer.tags\n\"Doe, Jane\",\"said \"\"hi\"\"\nthen left\",\"[\"\"a\"\",\"\"b\"\"]\"\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_missing_and_null() {
+        let rows = vec![json!({"Order": {"id": null}}), json!({"error": "boom"})];
+
+        assert_eq!(
+            write_csv(&columns(&["Order.id", "error"]), &rows),
+            "Order.id,error\n,\n,boom\n"
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use crate::datasources::client::{DataSourceClient, HttpMethod};
-use crate::datasources::models::{DataSource, DataSourceAuth};
+use crate::datasources::client::{CacheValidators, DataSourceClient, HttpMethod};
+use crate::datasources::models::{
+    DataSource, DataSourceAuth, DataSourceResponse, PaginationConfig, SourceType,
+};
 use pgrx::prelude::*;
 use pgrx::JsonB;
 use serde_json::Value as JsonValue;
@@ -13,12 +15,318 @@ fn rule_datasource_fetch(
     endpoint: String,
     params: JsonB,
 ) -> Result<JsonB, String> {
-    // Get datasource configuration from database using parameterized query
+    let datasource = load_datasource(datasource_id)?;
+    let response = fetch_with_cache(&datasource, &endpoint, &params.0)?;
+
+    if response.cache_hit {
+        let _ = record_request(datasource_id, &endpoint, "GET", &params.0, true, None, None);
+
+        return Ok(JsonB(serde_json::json!({
+            "success": true,
+            "cache_hit": true,
+            "revalidated": response.response_status == Some(304),
+            "data": response.response_body,
+            "datasource_name": datasource.datasource_name
+        })));
+    }
+
+    let request_id = record_request(
+        datasource_id,
+        &endpoint,
+        "GET",
+        &params.0,
+        false,
+        response.error_message.as_deref(),
+        response.response_headers.as_ref(),
+    )?;
+
+    let result = serde_json::json!({
+        "success": response.status == "success",
+        "request_id": request_id,
+        "cache_hit": false,
+        "status": response.response_status,
+        "data": response.response_body,
+        "headers": response.response_headers,
+        "error": response.error_message,
+        "execution_time_ms": response.execution_time_ms,
+        "datasource_name": datasource.datasource_name
+    });
+
+    Ok(JsonB(result))
+}
+
+#[pg_extern]
+fn rule_datasource_fetch_with_method(
+    datasource_id: i32,
+    endpoint: String,
+    _method: String,
+    params: JsonB,
+) -> Result<JsonB, String> {
+    rule_datasource_fetch(datasource_id, endpoint, params)
+}
+
+/// Upper bound on the timeout used by `rule_datasource_test`
+const PROBE_TIMEOUT_MS: i32 = 3000;
+
+/// Probe a data source with a single request to check reachability and auth
+///
+/// Sends one GET with the data source's headers and credentials, a timeout of
+/// at most 3 seconds, no retries and no pagination. The response cache is
+/// neither read nor written and the request is not logged. `reachable` is
+/// true when the server answered at all; `error` explains any non-success.
+#[pg_extern]
+fn rule_datasource_test(datasource_id: i32, endpoint: String) -> Result<JsonB, String> {
+    let mut datasource = load_datasource(datasource_id)?;
+    datasource.timeout_ms = datasource.timeout_ms.clamp(1, PROBE_TIMEOUT_MS);
+    datasource.retry_enabled = false;
+    datasource.pagination = None;
+
+    let auth = load_auth_credentials(datasource_id)?;
+    let client =
+        DataSourceClient::new().map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let response = client.fetch(
+        &datasource,
+        &auth,
+        &endpoint,
+        HttpMethod::Get,
+        &serde_json::json!({}),
+    )?;
+
+    let error = match (&response.error_message, response.response_status) {
+        (Some(message), _) => Some(message.clone()),
+        (None, Some(status)) if response.status != "success" => Some(format!("HTTP {}", status)),
+        _ => None,
+    };
+
+    Ok(JsonB(serde_json::json!({
+        "reachable": response.response_status.is_some(),
+        "status": response.response_status,
+        "latency_ms": response.execution_time_ms,
+        "error": error
+    })))
+}
+
+/// Minutes a queued request may stay pending before it expires unexecuted
+///
+/// A pending row keeps its params unredacted because the fetch still needs
+/// them, so this caps how long secrets sit in rule_datasource_requests.
+const QUEUED_REQUEST_TTL_MINUTES: i32 = 60;
+
+/// Queue a fetch and return its request_id without calling the API
+///
+/// The request is stored as a 'pending' row in rule_datasource_requests and
+/// executed later by rule_datasource_poll() or rule_datasource_process_pending().
+/// Its params are stored as given until it runs or expires after
+/// `QUEUED_REQUEST_TTL_MINUTES`, then redacted.
+#[pg_extern]
+fn rule_datasource_fetch_async(
+    datasource_id: i32,
+    endpoint: String,
+    params: JsonB,
+) -> Result<i32, String> {
+    // Fail fast on unknown or disabled data sources
+    load_datasource(datasource_id)?;
+
+    Spi::get_one_with_args::<i32>(
+        "INSERT INTO rule_datasource_requests
+         (datasource_id, endpoint, method, params, status)
+         VALUES ($1, $2, 'GET', $3, 'pending')
+         RETURNING request_id",
+        &[datasource_id.into(), endpoint.into(), params.into()],
+    )
+    .map_err(|e| format!("Failed to queue request: {}", e))?
+    .ok_or_else(|| "Failed to queue request: no request_id returned".to_string())
+}
+
+/// Get the result of a queued fetch, executing it first if still pending
+#[pg_extern]
+fn rule_datasource_poll(request_id: i32) -> Result<JsonB, String> {
+    let request =
+        lock_request(request_id)?.ok_or_else(|| format!("Request {} not found", request_id))?;
+
+    let response = if request.response.status == "pending" {
+        run_queued_request(&request)?
+    } else {
+        request.response
+    };
+
+    Ok(JsonB(queued_result_json(&response)))
+}
+
+/// Execute up to `limit` pending requests, oldest first
+///
+/// Intended for a background job (e.g. pg_cron). Rows locked by a concurrent
+/// poll or worker are skipped; expired rows are marked failed. Returns the
+/// number of requests processed.
+#[pg_extern]
+fn rule_datasource_process_pending(limit: default!(i32, 10)) -> Result<i32, String> {
+    let request_ids = Spi::connect_mut(|client| -> Result<Vec<i32>, spi::Error> {
+        let result = client.update(
+            "SELECT request_id FROM rule_datasource_requests
+             WHERE status = 'pending'
+             ORDER BY request_id
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED",
+            None,
+            &[limit.into()],
+        )?;
+
+        let mut ids = Vec::new();
+        for row in result {
+            if let Some(id) = row.get::<i32>(1)? {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    })
+    .map_err(|e| format!("Failed to load pending requests: {}", e))?;
+
+    let mut processed = 0;
+    for request_id in request_ids {
+        if let Some(request) = lock_request(request_id)? {
+            if request.response.status == "pending" {
+                run_queued_request(&request)?;
+                processed += 1;
+            }
+        }
+    }
+
+    Ok(processed)
+}
+
+/// A row of rule_datasource_requests, as needed to run or report a queued fetch
+struct QueuedRequest {
+    datasource_id: i32,
+    endpoint: String,
+    params: JsonValue,
+    /// Pending for longer than `QUEUED_REQUEST_TTL_MINUTES`
+    expired: bool,
+    response: DataSourceResponse,
+}
+
+/// Load a request row, locking it so concurrent pollers don't fetch twice
+fn lock_request(request_id: i32) -> Result<Option<QueuedRequest>, String> {
+    Spi::connect_mut(|client| -> Result<Option<QueuedRequest>, spi::Error> {
+        let result = client.update(
+            "SELECT datasource_id, endpoint, params, status, cache_hit,
+                    response_status, response_body, error_message,
+                    execution_time_ms::FLOAT8, response_headers,
+                    created_at < CURRENT_TIMESTAMP - make_interval(mins => $2)
+             FROM rule_datasource_requests
+             WHERE request_id = $1
+             FOR UPDATE",
+            None,
+            &[request_id.into(), QUEUED_REQUEST_TTL_MINUTES.into()],
+        )?;
+
+        if result.is_empty() {
+            return Ok(None);
+        }
+
+        let row = result.first();
+        Ok(Some(QueuedRequest {
+            datasource_id: row.get::<i32>(1)?.unwrap_or_default(),
+            endpoint: row.get::<String>(2)?.unwrap_or_default(),
+            params: row
+                .get::<JsonB>(3)?
+                .map(|p| p.0)
+                .unwrap_or(serde_json::json!({})),
+            expired: row.get::<bool>(11)?.unwrap_or(false),
+            response: DataSourceResponse {
+                request_id,
+                status: row.get::<String>(4)?.unwrap_or("pending".to_string()),
+                cache_hit: row.get::<bool>(5)?.unwrap_or(false),
+                response_status: row.get::<i32>(6)?,
+                response_body: row.get::<JsonB>(7)?.map(|b| b.0),
+                response_headers: row.get::<JsonB>(10)?.map(|h| h.0),
+                error_message: row.get::<String>(8)?,
+                execution_time_ms: row.get::<f64>(9)?,
+            },
+        }))
+    })
+    .map_err(|e: spi::Error| format!("Failed to load request {}: {}", request_id, e))
+}
+
+/// Execute a pending request and store the outcome on its row
+///
+/// Errors from the fetch itself (disabled data source, missing credentials)
+/// mark the request failed rather than aborting, so it is not retried forever.
+/// An expired request is marked failed without calling the API.
+fn run_queued_request(request: &QueuedRequest) -> Result<DataSourceResponse, String> {
+    let outcome = if request.expired {
+        Err(format!(
+            "Request expired after {} minutes in the queue",
+            QUEUED_REQUEST_TTL_MINUTES
+        ))
+    } else {
+        load_datasource(request.datasource_id).and_then(|datasource| {
+            fetch_with_cache(&datasource, &request.endpoint, &request.params)
+        })
+    };
+    let mut response = outcome.unwrap_or_else(|e| DataSourceResponse {
+        request_id: 0,
+        status: "failed".to_string(),
+        cache_hit: false,
+        response_status: None,
+        response_body: None,
+        response_headers: None,
+        error_message: Some(e),
+        execution_time_ms: None,
+    });
+    response.request_id = request.response.request_id;
+
+    // Params were kept intact while pending; mask secrets now they are no longer needed
+    let sensitive_keys = sensitive_keys();
+    Spi::run_with_args(
+        "UPDATE rule_datasource_requests
+         SET status = $2, cache_hit = $3, response_status = $4, response_body = $5,
+             error_message = $6, execution_time_ms = $7, response_headers = $8,
+             params = $9, completed_at = CURRENT_TIMESTAMP
+         WHERE request_id = $1",
+        &[
+            response.request_id.into(),
+            response.status.clone().into(),
+            response.cache_hit.into(),
+            response.response_status.into(),
+            response.response_body.clone().map(JsonB).into(),
+            response.error_message.clone().into(),
+            response.execution_time_ms.into(),
+            response
+                .response_headers
+                .as_ref()
+                .map(|h| JsonB(redact(h, &sensitive_keys)))
+                .into(),
+            JsonB(redact(&request.params, &sensitive_keys)).into(),
+        ],
+    )
+    .map_err(|e| format!("Failed to update request {}: {}", response.request_id, e))?;
+
+    Ok(response)
+}
+
+fn queued_result_json(response: &DataSourceResponse) -> JsonValue {
+    serde_json::json!({
+        "request_id": response.request_id,
+        "state": response.status,
+        "complete": response.status != "pending",
+        "success": response.status == "success" || response.status == "cached",
+        "cache_hit": response.cache_hit,
+        "status": response.response_status,
+        "data": response.response_body,
+        "headers": response.response_headers,
+        "error": response.error_message,
+        "execution_time_ms": response.execution_time_ms
+    })
+}
+
+fn load_datasource(datasource_id: i32) -> Result<DataSource, String> {
     let datasource_result = Spi::connect(|client| -> Result<DataSource, spi::Error> {
         let result = client.select(
             "SELECT datasource_id, datasource_name, base_url, auth_type,
                     default_headers, timeout_ms, retry_enabled, max_retries,
-                    cache_enabled, cache_ttl_seconds, enabled
+                    cache_enabled, cache_ttl_seconds, enabled, source_type, pagination,
+                    max_response_bytes
              FROM rule_datasources
              WHERE datasource_id = $1",
             None,
@@ -40,6 +348,9 @@ fn rule_datasource_fetch(
         let cache_enabled = row.get::<bool>(9)?.unwrap_or(true);
         let cache_ttl_seconds = row.get::<i32>(10)?.unwrap_or(300);
         let enabled = row.get::<bool>(11)?.unwrap_or(true);
+        let source_type_str = row.get::<String>(12)?.unwrap_or("rest".to_string());
+        let pagination_json = row.get::<JsonB>(13)?;
+        let max_response_bytes = row.get::<i64>(14)?.and_then(|n| u64::try_from(n).ok());
 
         if !enabled {
             return Err(spi::Error::InvalidPosition);
@@ -57,11 +368,18 @@ fn rule_datasource_fetch(
 
         let auth_type = crate::datasources::models::AuthType::from_str(&auth_type_str)
             .map_err(|_| spi::Error::InvalidPosition)?;
+        let source_type =
+            SourceType::from_str(&source_type_str).map_err(|_| spi::Error::InvalidPosition)?;
+        let pagination = pagination_json
+            .map(|p| PaginationConfig::from_json(&p.0))
+            .transpose()
+            .map_err(|_| spi::Error::InvalidPosition)?;
 
         Ok(DataSource {
             datasource_id,
             datasource_name,
             base_url,
+            source_type,
             auth_type,
             default_headers,
             timeout_ms,
@@ -70,79 +388,99 @@ fn rule_datasource_fetch(
             cache_enabled,
             cache_ttl_seconds,
             enabled,
+            pagination,
+            max_response_bytes,
         })
     });
 
-    let datasource = datasource_result.map_err(|e| format!("Failed to load datasource: {}", e))?;
+    datasource_result.map_err(|e| format!("Failed to load datasource: {}", e))
+}
+
+/// GET an endpoint, serving from and populating the response cache when enabled
+///
+/// A cache hit is returned with `cache_hit` set and status "cached".
+fn fetch_with_cache(
+    datasource: &DataSource,
+    endpoint: &str,
+    params: &JsonValue,
+) -> Result<DataSourceResponse, String> {
+    let datasource_id = datasource.datasource_id;
 
     // Generate cache key
-    let cache_key = generate_cache_key(&endpoint, &params.0);
+    let cache_key = generate_cache_key(endpoint, params);
 
     // Check cache if enabled
     if datasource.cache_enabled {
         let cache_result = check_cache(datasource_id, &cache_key);
         if let Ok(Some(cached_value)) = cache_result {
-            let _ = record_request(datasource_id, &endpoint, "GET", &params.0, true, None);
-
-            return Ok(JsonB(serde_json::json!({
-                "success": true,
-                "cache_hit": true,
-                "data": cached_value,
-                "datasource_name": datasource.datasource_name
-            })));
+            return Ok(DataSourceResponse {
+                request_id: 0,
+                status: "cached".to_string(),
+                cache_hit: true,
+                response_status: None,
+                response_body: Some(cached_value),
+                response_headers: None,
+                error_message: None,
+                execution_time_ms: None,
+            });
         }
     }
 
+    // An expired entry with an ETag/Last-Modified can be revalidated cheaply
+    let stale = if datasource.cache_enabled {
+        find_revalidatable_cache(datasource_id, &cache_key).unwrap_or(None)
+    } else {
+        None
+    };
+
     let auth = load_auth_credentials(datasource_id)?;
     let client =
         DataSourceClient::new().map_err(|e| format!("Failed to create HTTP client: {}", e))?;
 
     let method = HttpMethod::Get;
-    let response = client.fetch(&datasource, &auth, &endpoint, method, &params.0)?;
+    let response = match stale {
+        Some((_, ref validators)) => client.fetch_conditional(
+            datasource,
+            &auth,
+            endpoint,
+            method,
+            params,
+            Some(validators),
+        )?,
+        None => client.fetch(datasource, &auth, endpoint, method, params)?,
+    };
+
+    if response.response_status == Some(304) {
+        if let Some((cached_value, _)) = stale {
+            if let Err(e) = refresh_cache(datasource_id, &cache_key, datasource.cache_ttl_seconds) {
+                pgrx::warning!("datasource {}: {}", datasource_id, e);
+            }
+
+            return Ok(DataSourceResponse {
+                status: "cached".to_string(),
+                cache_hit: true,
+                response_body: Some(cached_value),
+                ..response
+            });
+        }
+    }
 
     if datasource.cache_enabled && response.status == "success" {
         if let Some(ref body) = response.response_body {
-            let _ = store_cache(
+            if let Err(e) = store_cache(
                 datasource_id,
                 &cache_key,
                 body,
                 response.response_status.unwrap_or(200),
                 datasource.cache_ttl_seconds,
-            );
+                &CacheValidators::from_headers(response.response_headers.as_ref()),
+            ) {
+                pgrx::warning!("datasource {}: {}", datasource_id, e);
+            }
         }
     }
 
-    let request_id = record_request(
-        datasource_id,
-        &endpoint,
-        "GET",
-        &params.0,
-        false,
-        response.error_message.as_deref(),
-    )?;
-
-    let result = serde_json::json!({
-        "success": response.status == "success",
-        "request_id": request_id,
-        "cache_hit": false,
-        "status": response.response_status,
-        "data": response.response_body,
-        "error": response.error_message,
-        "execution_time_ms": response.execution_time_ms,
-        "datasource_name": datasource.datasource_name
-    });
-
-    Ok(JsonB(result))
-}
-
-#[pg_extern]
-fn rule_datasource_fetch_with_method(
-    datasource_id: i32,
-    endpoint: String,
-    _method: String,
-    params: JsonB,
-) -> Result<JsonB, String> {
-    rule_datasource_fetch(datasource_id, endpoint, params)
+    Ok(response)
 }
 
 fn generate_cache_key(endpoint: &str, params: &JsonValue) -> String {
@@ -184,23 +522,75 @@ fn check_cache(datasource_id: i32, cache_key: &str) -> Result<Option<JsonValue>,
     .map_err(|e: spi::Error| format!("Cache check failed: {}", e))
 }
 
+/// Find a cache entry (fresh or expired) that carries revalidation validators
+fn find_revalidatable_cache(
+    datasource_id: i32,
+    cache_key: &str,
+) -> Result<Option<(JsonValue, CacheValidators)>, String> {
+    Spi::connect(
+        |client| -> Result<Option<(JsonValue, CacheValidators)>, spi::Error> {
+            let result = client.select(
+                "SELECT cache_value, etag, last_modified FROM rule_datasource_cache
+             WHERE datasource_id = $1 AND cache_key = $2
+               AND (etag IS NOT NULL OR last_modified IS NOT NULL)",
+                None,
+                &[datasource_id.into(), cache_key.to_string().into()],
+            )?;
+
+            if result.is_empty() {
+                return Ok(None);
+            }
+
+            let row = result.first();
+            let validators = CacheValidators {
+                etag: row.get::<String>(2)?,
+                last_modified: row.get::<String>(3)?,
+            };
+
+            Ok(row.get::<JsonB>(1)?.map(|value| (value.0, validators)))
+        },
+    )
+    .map_err(|e: spi::Error| format!("Cache check failed: {}", e))
+}
+
+/// Extend a revalidated (304) cache entry by another TTL and count the hit
+fn refresh_cache(datasource_id: i32, cache_key: &str, ttl_seconds: i32) -> Result<(), String> {
+    Spi::run_with_args(
+        "UPDATE rule_datasource_cache
+         SET expires_at = CURRENT_TIMESTAMP + ($3 || ' seconds')::INTERVAL,
+             hit_count = hit_count + 1,
+             last_hit_at = CURRENT_TIMESTAMP
+         WHERE datasource_id = $1 AND cache_key = $2",
+        &[
+            datasource_id.into(),
+            cache_key.to_string().into(),
+            ttl_seconds.into(),
+        ],
+    )
+    .map_err(|e| format!("Failed to refresh cache: {}", e))
+}
+
 fn store_cache(
     datasource_id: i32,
     cache_key: &str,
     cache_value: &JsonValue,
     response_status: i32,
     ttl_seconds: i32,
+    validators: &CacheValidators,
 ) -> Result<(), String> {
     let cache_value_json = JsonB(cache_value.clone());
 
     Spi::connect(|client| -> Result<(), spi::Error> {
         client.select(
             "INSERT INTO rule_datasource_cache
-             (datasource_id, cache_key, cache_value, response_status, expires_at)
-             VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP + ($5 || ' seconds')::INTERVAL)
+             (datasource_id, cache_key, cache_value, response_status, expires_at,
+              etag, last_modified)
+             VALUES ($1, $2, $3, $4, CURRENT_TIMESTAMP + ($5 || ' seconds')::INTERVAL, $6, $7)
              ON CONFLICT (datasource_id, cache_key) DO UPDATE
              SET cache_value = EXCLUDED.cache_value,
                  response_status = EXCLUDED.response_status,
+                 etag = EXCLUDED.etag,
+                 last_modified = EXCLUDED.last_modified,
                  created_at = CURRENT_TIMESTAMP,
                  expires_at = CURRENT_TIMESTAMP + ($5 || ' seconds')::INTERVAL,
                  hit_count = 0,
@@ -212,6 +602,8 @@ fn store_cache(
                 cache_value_json.into(),
                 response_status.into(),
                 ttl_seconds.into(),
+                validators.etag.clone().into(),
+                validators.last_modified.clone().into(),
             ],
         )?;
         Ok(())
@@ -245,6 +637,7 @@ fn record_request(
     params: &JsonValue,
     cache_hit: bool,
     error_message: Option<&str>,
+    response_headers: Option<&JsonValue>,
 ) -> Result<i32, String> {
     let status = if error_message.is_some() {
         "failed"
@@ -254,14 +647,17 @@ fn record_request(
         "success"
     };
 
-    let params_json = JsonB(params.clone());
+    let sensitive_keys = sensitive_keys();
+    let params_json = JsonB(redact(params, &sensitive_keys));
+    let headers_json = response_headers.map(|h| JsonB(redact(h, &sensitive_keys)));
 
     Spi::connect(|client| -> Result<i32, spi::Error> {
         // Simplified version - just required fields for now
         let result = client.select(
             "INSERT INTO rule_datasource_requests
-             (datasource_id, endpoint, method, params, status, cache_hit, completed_at)
-             VALUES ($1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP)
+             (datasource_id, endpoint, method, params, status, cache_hit,
+              response_headers, completed_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, CURRENT_TIMESTAMP)
              RETURNING request_id",
             None,
             &[
@@ -271,6 +667,7 @@ fn record_request(
                 params_json.into(),
                 status.to_string().into(),
                 cache_hit.into(),
+                headers_json.into(),
             ],
         )?;
 
@@ -282,3 +679,89 @@ fn record_request(
     })
     .map_err(|e: spi::Error| format!("Failed to record request: {}", e))
 }
+
+/// Entries of `rule_engine.datasource_redact_keys`, normalized for matching
+fn sensitive_keys() -> Vec<String> {
+    crate::guc::DATASOURCE_REDACT_KEYS
+        .get()
+        .map(|keys| parse_key_list(&keys.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+fn parse_key_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|key| key.trim().to_lowercase().replace('-', "_"))
+        .filter(|key| !key.is_empty())
+        .collect()
+}
+
+/// Replace the value of every object key containing a sensitive key with "***"
+///
+/// `sensitive_keys` must come from `parse_key_list`. Matching is
+/// case-insensitive and treats `-` like `_`, so `X-Api-Key` matches `api_key`. Nested objects and arrays are redacted recursively.
+fn redact(value: &JsonValue, sensitive_keys: &[String]) -> JsonValue {
+    match value {
+        JsonValue::Object(map) => JsonValue::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let normalized = key.to_lowercase().replace('-', "_");
+                    let value = if sensitive_keys
+                        .iter()
+                        .any(|sensitive| normalized.contains(sensitive.as_str()))
+                    {
+                        JsonValue::String("***".to_string())
+                    } else {
+                        redact(value, sensitive_keys)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        JsonValue::Array(items) => JsonValue::Array(
+            items
+                .iter()
+                .map(|item| redact(item, sensitive_keys))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_list() {
+        assert_eq!(
+            parse_key_list(" Authorization, api_key,,TOKEN "),
+            vec!["authorization", "api_key", "token"]
+        );
+        assert!(parse_key_list("").is_empty());
+    }
+
+    #[test]
+    fn test_redact() {
+        let keys = parse_key_list("authorization,api_key,password,token");
+        let params = serde_json::json!({
+            "city": "London",
+            "X-Api-Key": "k1",
+            "access_token": "t1",
+            "nested": { "Password": "p1", "items": [{ "authorization": "Bearer x" }] },
+            "limit": 10
+        });
+
+        assert_eq!(
+            redact(&params, &keys),
+            serde_json::json!({
+                "city": "London",
+                "X-Api-Key": "***",
+                "access_token": "***",
+                "nested": { "Password": "***", "items": [{ "authorization": "***" }] },
+                "limit": 10
+            })
+        );
+
+        assert_eq!(redact(&params, &[]), params);
+    }
+}
@@ -1,6 +1,7 @@
 //! Debug API - SQL functions for time-travel debugging
 
 use crate::core::{execute_rules_debug, json_to_facts, parse_and_validate_rules};
+use crate::debug::event_store::ExecutionSession;
 use crate::debug::GLOBAL_EVENT_STORE;
 use crate::error::{codes, create_custom_error};
 use pgrx::prelude::*;
@@ -53,29 +54,33 @@ fn run_rule_engine_debug(
     let mut facts_value: serde_json::Value = serde_json::from_str(facts_json)
         .map_err(|e| create_custom_error(&codes::INVALID_JSON, e.to_string()))?;
 
-    let transformed_grl = match crate::functions::preprocessing::preprocess_grl_with_functions(
-        rules_grl,
-        &mut facts_value,
-    ) {
-        Ok(grl) => grl,
-        Err(e) => {
-            return Err(Box::new(DebugError(create_custom_error(
-                &codes::INVALID_GRL,
-                format!("Function preprocessing error: {}", e),
-            ))) as Box<dyn std::error::Error + Send + Sync>)
-        }
-    };
+    let (transformed_grl, function_calls) =
+        match crate::functions::preprocessing::preprocess_grl_with_function_calls(
+            rules_grl,
+            &mut facts_value,
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                return Err(Box::new(DebugError(create_custom_error(
+                    &codes::INVALID_GRL,
+                    format!("Function preprocessing error: {}", e),
+                )))
+                    as Box<dyn std::error::Error + Send + Sync>)
+            }
+        };
 
     // Update facts with preprocessed values
     facts = json_to_facts(&facts_value.to_string())
         .map_err(|e| create_custom_error(&codes::INVALID_JSON, e.to_string()))?;
 
     // Execute with debugging
-    let (final_facts, session_id) = execute_rules_debug(&facts, rules, session_id, transformed_grl)
-        .map_err(|e| {
-            Box::new(DebugError(create_custom_error(&codes::EXECUTION_FAILED, e)))
-                as Box<dyn std::error::Error + Send + Sync>
-        })?;
+    let (final_facts, session_id) =
+        execute_rules_debug(&facts, rules, session_id, transformed_grl, &function_calls).map_err(
+            |e| {
+                Box::new(DebugError(create_custom_error(&codes::EXECUTION_FAILED, e)))
+                    as Box<dyn std::error::Error + Send + Sync>
+            },
+        )?;
 
     // Get session info
     let session = GLOBAL_EVENT_STORE.get_session(&session_id).map_err(|e| {
@@ -105,11 +110,17 @@ fn run_rule_engine_debug(
     )))
 }
 
-/// Get all events for a debug session
+/// Get events for a debug session
+///
+/// All events by default; `event_type` (e.g. 'RuleFired') and the inclusive
+/// `from_step` / `to_step` window narrow the result.
 #[allow(clippy::type_complexity)]
 #[pg_extern]
 fn debug_get_events(
     session_id: &str,
+    event_type: default!(Option<String>, "NULL"),
+    from_step: default!(Option<i64>, "NULL"),
+    to_step: default!(Option<i64>, "NULL"),
 ) -> Result<
     TableIterator<
         'static,
@@ -127,9 +138,23 @@ fn debug_get_events(
             as Box<dyn std::error::Error + Send + Sync>
     })?;
 
+    let step_bound = |step: Option<i64>, name: &str| {
+        step.map(|s| {
+            u64::try_from(s).map_err(|_| {
+                Box::new(DebugError(create_custom_error(
+                    &codes::INVALID_INPUT,
+                    format!("{} must be >= 0, got {}", name, s),
+                ))) as Box<dyn std::error::Error + Send + Sync>
+            })
+        })
+        .transpose()
+    };
+    let from_step = step_bound(from_step, "from_step")?;
+    let to_step = step_bound(to_step, "to_step")?;
+
     let mut results = Vec::new();
 
-    for event in &session.events {
+    for event in session.filter_events(event_type.as_deref(), from_step, to_step) {
         let event_json = serde_json::to_value(event).map_err(|e| {
             Box::new(DebugError(create_custom_error(
                 &codes::SERIALIZATION_FAILED,
@@ -186,6 +211,23 @@ fn debug_get_session(
     )))
 }
 
+/// Explain why a rule didn't fire in a debug session
+///
+/// Reports whether and how often the rule fired, plus the failed conditions
+/// from any `RuleEvaluated` events recorded for it.
+#[pg_extern]
+fn debug_why_not_fired(
+    session_id: &str,
+    rule_name: &str,
+) -> Result<pgrx::JsonB, Box<dyn std::error::Error + Send + Sync>> {
+    let session = GLOBAL_EVENT_STORE.get_session(session_id).map_err(|e| {
+        Box::new(DebugError(create_custom_error(&codes::EXECUTION_FAILED, e)))
+            as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    Ok(pgrx::JsonB(session.why_not_fired(rule_name)))
+}
+
 /// List all debug sessions
 #[pg_extern]
 #[allow(clippy::type_complexity)]
@@ -218,6 +260,50 @@ fn debug_list_sessions() -> Result<
     Ok(TableIterator::new(results))
 }
 
+/// Export a debug session as a portable JSON bundle
+///
+/// The bundle holds the session metadata, rules, initial facts and every
+/// event, so it can be shared and loaded elsewhere with `debug_import_session`.
+#[pg_extern]
+fn debug_export_session(
+    session_id: &str,
+) -> Result<pgrx::JsonB, Box<dyn std::error::Error + Send + Sync>> {
+    let session = GLOBAL_EVENT_STORE.get_session(session_id).map_err(|e| {
+        Box::new(DebugError(create_custom_error(&codes::EXECUTION_FAILED, e)))
+            as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    let bundle = session.to_bundle().map_err(|e| {
+        Box::new(DebugError(create_custom_error(
+            &codes::SERIALIZATION_FAILED,
+            e,
+        ))) as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    Ok(pgrx::JsonB(bundle))
+}
+
+/// Load a session exported with `debug_export_session`
+///
+/// Returns the session ID; fails if a session with that ID already exists.
+#[pg_extern]
+fn debug_import_session(
+    bundle: pgrx::JsonB,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let session = ExecutionSession::from_bundle(&bundle.0).map_err(|e| {
+        Box::new(DebugError(create_custom_error(&codes::INVALID_INPUT, e)))
+            as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    crate::shutdown::ensure_registered();
+    let session_id = GLOBAL_EVENT_STORE.import_session(session).map_err(|e| {
+        Box::new(DebugError(create_custom_error(&codes::INVALID_INPUT, e)))
+            as Box<dyn std::error::Error + Send + Sync>
+    })?;
+
+    Ok(session_id)
+}
+
 /// Delete a debug session
 #[pg_extern]
 fn debug_delete_session(
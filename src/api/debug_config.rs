@@ -10,6 +10,15 @@ fn debug_enable() -> bool {
     true
 }
 
+/// Enable debug mode only for executions whose GRL defines `rule_name`
+/// (quoted or bare rule header); other executions run without capture
+/// Returns true if successful
+#[pg_extern]
+fn debug_enable_for_rule(rule_name: &str) -> bool {
+    crate::debug::debug_enable_for_rule(rule_name);
+    true
+}
+
 /// Disable debug mode globally
 /// Returns true if successful
 #[pg_extern]
@@ -34,15 +43,65 @@ fn debug_disable_persistence() -> bool {
     true
 }
 
+/// Set the in-memory event store limits; NULL keeps the current value
+/// `on_overflow` is 'stop' (ignore new events) or 'drop_oldest'
+/// Returns the limits now in effect
+#[pg_extern]
+fn debug_set_limits(
+    max_sessions: default!(Option<i32>, "NULL"),
+    max_events_per_session: default!(Option<i32>, "NULL"),
+    on_overflow: default!(Option<&str>, "NULL"),
+) -> pgrx::JsonB {
+    let current = crate::debug::store_limits();
+
+    let count = |value: Option<i32>, name: &str, current: usize| match value {
+        None => current,
+        Some(n) if n >= 1 => n as usize,
+        Some(n) => pgrx::error!("{} must be at least 1, got {}", name, n),
+    };
+    let overflow = match on_overflow {
+        None => current.overflow,
+        Some(value) => {
+            crate::debug::EventOverflow::parse(value).unwrap_or_else(|e| pgrx::error!("{}", e))
+        }
+    };
+
+    let limits = crate::debug::StoreLimits {
+        max_sessions: count(max_sessions, "max_sessions", current.max_sessions),
+        max_events_per_session: count(
+            max_events_per_session,
+            "max_events_per_session",
+            current.max_events_per_session,
+        ),
+        overflow,
+    };
+    if let Err(e) = crate::debug::set_store_limits(limits) {
+        pgrx::error!("{}", e);
+    }
+
+    limits_json(&limits)
+}
+
+fn limits_json(limits: &crate::debug::StoreLimits) -> pgrx::JsonB {
+    pgrx::JsonB(serde_json::json!({
+        "max_sessions": limits.max_sessions,
+        "max_events_per_session": limits.max_events_per_session,
+        "on_overflow": limits.overflow.as_str(),
+    }))
+}
+
 /// Get current debug configuration status
-/// Returns JSONB with debug_enabled and persistence_enabled flags
+/// Returns JSONB with debug_enabled and persistence_enabled flags, the rule
+/// debug mode is scoped to (null when global) and the event store limits
 #[pg_extern]
 fn debug_status() -> pgrx::JsonB {
     let (debug_enabled, persistence_enabled) = crate::debug::get_debug_config();
 
     let status = serde_json::json!({
         "debug_enabled": debug_enabled,
-        "persistence_enabled": persistence_enabled
+        "persistence_enabled": persistence_enabled,
+        "rule_scope": crate::debug::debug_rule_scope(),
+        "limits": limits_json(&crate::debug::store_limits()).0,
     });
 
     pgrx::JsonB(status)
@@ -1,6 +1,7 @@
-use crate::core::execute_rules_rete;
+use crate::core::{execute_rules_rete, execute_rules_rete_grouped};
 use crate::error::{codes, create_custom_error};
-use crate::validation::{validate_facts_input, validate_rules_input};
+use crate::validation::{validate_facts_input, validate_facts_schema, validate_rules_input};
+use pgrx::prelude::*;
 
 /// Execute rules using traditional forward chaining algorithm
 /// Useful for simple rules or when predictable execution order is needed
@@ -104,68 +105,209 @@ pub fn run_rule_engine_rete(facts_json: &str, rules_grl: &str) -> String {
     result_value.to_string()
 }
 
-/// Main function to execute GRL rules on JSON facts
-/// Default uses RETE algorithm for optimal performance
-/// Automatically enables debug mode if debug_enable() was called
+/// Execute the same rules against each element of a JSON array of facts
+/// Returns one row per element (`index` is the 0-based array position), produced
+/// lazily so large batches stream instead of building one big JSON result.
+/// A failing element yields its error JSON as `result` without aborting the batch.
+///
+/// # Example
+/// ```sql
+/// SELECT index, result->'Order'->>'discount'
+/// FROM run_rule_engine_batch_set(
+///     '[{"Order": {"total": 1500}}, {"Order": {"total": 50}}]',
+///     'rule "Discount" { when Order.total > 1000 then Order.discount = 100; }'
+/// );
+/// ```
 #[pgrx::pg_extern]
-pub fn run_rule_engine(facts_json: &str, rules_grl: &str) -> String {
-    // Check if debug mode is enabled
-    if crate::debug::is_debug_enabled() {
-        // Debug mode enabled - capture events and return detailed info
-        // Note: This returns JSON string with session info, not just facts
-        pgrx::log!("Debug mode enabled - executing with event capture");
+pub fn run_rule_engine_batch_set(
+    facts_array_json: &str,
+    rules_grl: &str,
+) -> Result<TableIterator<'static, (name!(index, i64), name!(result, pgrx::JsonB))>, String> {
+    validate_rules_input(rules_grl).map_err(|e| create_custom_error(&codes::EMPTY_RULES, e))?;
 
-        use crate::core::execute_rules_debug;
-        use crate::core::facts::json_to_facts;
-        use crate::core::rules::parse_and_validate_rules;
-        use uuid::Uuid;
+    let batch: Vec<serde_json::Value> = match serde_json::from_str(facts_array_json) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(_) => {
+            return Err(create_custom_error(
+                &codes::INVALID_JSON,
+                "Facts batch must be a JSON array".to_string(),
+            ))
+        }
+        Err(e) => return Err(create_custom_error(&codes::INVALID_JSON, e.to_string())),
+    };
 
-        // Validate inputs
-        if let Err(e) = validate_facts_input(facts_json) {
-            return create_custom_error(&codes::EMPTY_FACTS, e);
+    let rules_grl = rules_grl.to_string();
+    let rows = batch
+        .into_iter()
+        .enumerate()
+        .map(move |(idx, facts_value)| {
+            let result = execute_batch_item(facts_value, &rules_grl);
+            (idx as i64, pgrx::JsonB(result))
+        });
+
+    Ok(TableIterator::new(rows))
+}
+
+/// Execute rules for each element of a facts array and return selected columns as CSV
+///
+/// `columns` are dotted field paths into each result (e.g. `Order.total`),
+/// written in order under a header row. Missing fields are empty cells; an
+/// element that fails keeps its row, and its message is available through an
+/// `error` column.
+///
+/// # Example
+/// ```sql
+/// SELECT run_rule_engine_batch_csv(
+///     '[{"Order": {"id": 1, "total": 1500}}, {"Order": {"id": 2, "total": 50}}]',
+///     'rule "Discount" { when Order.total > 1000 then Order.discount = 100; }',
+///     ARRAY['Order.id', 'Order.discount']
+/// );
+/// -- Order.id,Order.discount
+/// -- 1,100
+/// -- 2,
+/// ```
+#[pgrx::pg_extern]
+pub fn run_rule_engine_batch_csv(
+    facts_array_json: &str,
+    rules_grl: &str,
+    columns: Vec<String>,
+) -> Result<String, String> {
+    validate_rules_input(rules_grl).map_err(|e| create_custom_error(&codes::EMPTY_RULES, e))?;
+
+    if columns.is_empty() {
+        return Err(create_custom_error(
+            &codes::INVALID_INPUT,
+            "At least one output column is required".to_string(),
+        ));
+    }
+
+    let batch: Vec<serde_json::Value> = match serde_json::from_str(facts_array_json) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(_) => {
+            return Err(create_custom_error(
+                &codes::INVALID_JSON,
+                "Facts batch must be a JSON array".to_string(),
+            ))
         }
-        if let Err(e) = validate_rules_input(rules_grl) {
-            return create_custom_error(&codes::EMPTY_RULES, e);
+        Err(e) => return Err(create_custom_error(&codes::INVALID_JSON, e.to_string())),
+    };
+
+    let results: Vec<serde_json::Value> = batch
+        .into_iter()
+        .map(|facts_value| execute_batch_item(facts_value, rules_grl))
+        .collect();
+
+    Ok(crate::api::csv::write_csv(&columns, &results))
+}
+
+/// Preprocess and execute one batch element with RETE, returning facts or error JSON
+fn execute_batch_item(mut facts_value: serde_json::Value, rules_grl: &str) -> serde_json::Value {
+    if !facts_value.is_object() {
+        return error_value(
+            &codes::INVALID_JSON,
+            "Facts must be a JSON object".to_string(),
+        );
+    }
+
+    let transformed_grl = match crate::functions::preprocessing::preprocess_grl_with_functions(
+        rules_grl,
+        &mut facts_value,
+    ) {
+        Ok(grl) => grl,
+        Err(e) => {
+            return error_value(
+                &codes::INVALID_GRL,
+                format!("Function preprocessing error: {}", e),
+            )
         }
+    };
 
-        // Parse facts from JSON
-        let mut facts_value: serde_json::Value = match serde_json::from_str(facts_json) {
-            Ok(v) => v,
-            Err(e) => return create_custom_error(&codes::INVALID_JSON, e.to_string()),
-        };
+    match execute_rules_rete(&facts_value, &transformed_grl) {
+        Ok(v) => v,
+        Err(e) => error_value(&codes::EXECUTION_FAILED, e),
+    }
+}
 
-        // Preprocess GRL
-        let transformed_grl = match crate::functions::preprocessing::preprocess_grl_with_functions(
+/// Build an error response as a JSON value (same shape as the TEXT error responses)
+fn error_value(code: &codes::ErrorCode, message: String) -> serde_json::Value {
+    serde_json::from_str(&create_custom_error(code, message)).unwrap_or_default()
+}
+
+/// Run rules with event capture in a new debug session
+///
+/// Returns the final facts and the session ID, or a coded error JSON.
+pub(crate) fn run_debug_session(
+    facts_json: &str,
+    rules_grl: &str,
+) -> Result<(rust_rule_engine::Facts, String), String> {
+    use crate::core::execute_rules_debug;
+    use crate::core::facts::json_to_facts;
+    use crate::core::rules::parse_and_validate_rules;
+    use uuid::Uuid;
+
+    // Validate inputs
+    if let Err(e) = validate_facts_input(facts_json) {
+        return Err(create_custom_error(&codes::EMPTY_FACTS, e));
+    }
+    if let Err(e) = validate_rules_input(rules_grl) {
+        return Err(create_custom_error(&codes::EMPTY_RULES, e));
+    }
+
+    // Parse facts from JSON
+    let mut facts_value: serde_json::Value = match serde_json::from_str(facts_json) {
+        Ok(v) => v,
+        Err(e) => return Err(create_custom_error(&codes::INVALID_JSON, e.to_string())),
+    };
+
+    // Preprocess GRL, keeping the evaluated calls for the debug session
+    let (transformed_grl, function_calls) =
+        match crate::functions::preprocessing::preprocess_grl_with_function_calls(
             rules_grl,
             &mut facts_value,
         ) {
-            Ok(grl) => grl,
+            Ok(result) => result,
             Err(e) => {
-                return create_custom_error(
+                return Err(create_custom_error(
                     &codes::INVALID_GRL,
                     format!("Function preprocessing error: {}", e),
-                )
+                ))
             }
         };
 
-        // Convert to Facts
-        let facts = match json_to_facts(&facts_value.to_string()) {
-            Ok(f) => f,
-            Err(e) => return create_custom_error(&codes::INVALID_JSON, e),
-        };
+    // Convert to Facts
+    let facts = match json_to_facts(&facts_value.to_string()) {
+        Ok(f) => f,
+        Err(e) => return Err(create_custom_error(&codes::INVALID_JSON, e)),
+    };
 
-        // Parse rules
-        let rules = match parse_and_validate_rules(&transformed_grl) {
-            Ok(r) => r,
-            Err(e) => return create_custom_error(&codes::INVALID_GRL, e),
-        };
+    // Parse rules
+    let rules = match parse_and_validate_rules(&transformed_grl) {
+        Ok(r) => r,
+        Err(e) => return Err(create_custom_error(&codes::INVALID_GRL, e)),
+    };
+
+    // Generate session ID
+    let session_id = format!("session_{}", Uuid::new_v4());
 
-        // Generate session ID
-        let session_id = format!("session_{}", Uuid::new_v4());
+    // Execute with debugging
+    execute_rules_debug(&facts, rules, session_id, transformed_grl, &function_calls)
+        .map_err(|e| create_custom_error(&codes::EXECUTION_FAILED, e))
+}
+
+/// Main function to execute GRL rules on JSON facts
+/// Default uses RETE algorithm for optimal performance
+/// Automatically enables debug mode if debug_enable() was called, or if
+/// debug_enable_for_rule() was called and the GRL defines that rule
+#[pgrx::pg_extern]
+pub fn run_rule_engine(facts_json: &str, rules_grl: &str) -> String {
+    // Check if debug mode is enabled for these rules
+    if crate::debug::should_debug(rules_grl) {
+        // Debug mode enabled - capture events and return detailed info
+        // Note: This returns JSON string with session info, not just facts
+        pgrx::log!("Debug mode enabled - executing with event capture");
 
-        // Execute with debugging
-        match execute_rules_debug(&facts, rules, session_id.clone(), transformed_grl) {
-            Ok((final_facts, _)) => {
+        match run_debug_session(facts_json, rules_grl) {
+            Ok((final_facts, session_id)) => {
                 // Return just the facts (same format as non-debug mode)
                 use crate::core::facts::facts_to_json;
                 match facts_to_json(&final_facts) {
@@ -179,7 +321,7 @@ pub fn run_rule_engine(facts_json: &str, rules_grl: &str) -> String {
                     Err(e) => create_custom_error(&codes::EXECUTION_FAILED, e),
                 }
             }
-            Err(e) => create_custom_error(&codes::EXECUTION_FAILED, e),
+            Err(e) => e,
         }
     } else {
         // Normal mode - no debug overhead
@@ -221,3 +363,122 @@ pub fn run_rule_engine(facts_json: &str, rules_grl: &str) -> String {
         result_value.to_string()
     }
 }
+
+/// Execute rules in agenda-group stages
+///
+/// Rules declare their group with `agenda-group "name"`; rules without one
+/// belong to `MAIN`. Groups fire in `group_order`, each to completion before
+/// the next starts, so later groups' changes never re-trigger earlier ones.
+/// `MAIN` runs last unless listed; rules in unlisted groups don't fire.
+#[pgrx::pg_extern]
+pub fn run_rule_engine_grouped(
+    facts_json: &str,
+    rules_grl: &str,
+    group_order: Vec<String>,
+) -> String {
+    if let Err(e) = validate_facts_input(facts_json) {
+        return create_custom_error(&codes::EMPTY_FACTS, e);
+    }
+    if let Err(e) = validate_rules_input(rules_grl) {
+        return create_custom_error(&codes::EMPTY_RULES, e);
+    }
+
+    let mut facts_value: serde_json::Value = match serde_json::from_str(facts_json) {
+        Ok(v) => v,
+        Err(e) => return create_custom_error(&codes::INVALID_JSON, e.to_string()),
+    };
+
+    let transformed_grl = match crate::functions::preprocessing::preprocess_grl_with_functions(
+        rules_grl,
+        &mut facts_value,
+    ) {
+        Ok(grl) => grl,
+        Err(e) => {
+            return create_custom_error(
+                &codes::INVALID_GRL,
+                format!("Function preprocessing error: {}", e),
+            )
+        }
+    };
+
+    match execute_rules_rete_grouped(&facts_value, &transformed_grl, &group_order) {
+        Ok(v) => v.to_string(),
+        Err(e) => create_custom_error(&codes::EXECUTION_FAILED, e),
+    }
+}
+
+/// Execute rules after checking facts against a declared schema
+///
+/// The schema maps field paths to types, e.g.
+/// `{"Customer.age": "integer", "Customer.email": "string?"}`. If any field is
+/// missing or has the wrong type, no rules run and an ERR016 error is returned
+/// with a `violations` array. Otherwise behaves like `run_rule_engine`.
+#[pgrx::pg_extern]
+pub fn run_rule_engine_typed(facts_json: &str, rules_grl: &str, schema_json: &str) -> String {
+    if let Err(e) = validate_facts_input(facts_json) {
+        return create_custom_error(&codes::EMPTY_FACTS, e);
+    }
+
+    let facts_value: serde_json::Value = match serde_json::from_str(facts_json) {
+        Ok(v) => v,
+        Err(e) => return create_custom_error(&codes::INVALID_JSON, e.to_string()),
+    };
+
+    let schema: serde_json::Value = match serde_json::from_str(schema_json) {
+        Ok(v) => v,
+        Err(e) => {
+            return create_custom_error(
+                &codes::INVALID_INPUT,
+                format!("Invalid schema JSON: {}", e),
+            )
+        }
+    };
+
+    let violations = match validate_facts_schema(&facts_value, &schema) {
+        Ok(v) => v,
+        Err(e) => return create_custom_error(&codes::INVALID_INPUT, e),
+    };
+
+    if !violations.is_empty() {
+        let mut error = error_value(
+            &codes::SCHEMA_MISMATCH,
+            format!(
+                "Facts do not match schema ({} violation(s))",
+                violations.len()
+            ),
+        );
+        error["violations"] = serde_json::json!(violations);
+        return error.to_string();
+    }
+
+    run_rule_engine(facts_json, rules_grl)
+}
+
+/// Execute rules with per-execution options
+///
+/// Supported options (unknown keys are rejected with ERR014):
+/// - `salience_overrides`: `{"RuleName": 50}` replaces the salience of the
+///   named rules for this execution only; every name must exist in the GRL
+///
+/// Otherwise behaves like `run_rule_engine`.
+#[pgrx::pg_extern]
+pub fn run_rule_engine_with_options(
+    facts_json: &str,
+    rules_grl: &str,
+    options_json: &str,
+) -> String {
+    use crate::core::options::ExecutionOptions;
+
+    if let Err(e) = validate_rules_input(rules_grl) {
+        return create_custom_error(&codes::EMPTY_RULES, e);
+    }
+
+    let rules_grl = match ExecutionOptions::from_json(options_json)
+        .and_then(|options| options.apply(rules_grl))
+    {
+        Ok(grl) => grl,
+        Err(e) => return create_custom_error(&codes::INVALID_INPUT, e),
+    };
+
+    run_rule_engine(facts_json, &rules_grl)
+}
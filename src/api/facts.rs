@@ -0,0 +1,40 @@
+use crate::core::facts::merge_facts;
+use crate::error::{codes, create_custom_error};
+
+/// Merge two fact objects, `b` winning on conflicts
+///
+/// With `deep` false, top-level keys of `b` replace those of `a`. With `deep`
+/// true, nested objects are merged key by key, so `b` only replaces the
+/// fields it sets. Arrays are replaced, not concatenated.
+///
+/// # Example
+/// ```sql
+/// SELECT facts_merge('{"Order": {"total": 150, "currency": "EUR"}}',
+///                    '{"Order": {"total": 200}}', true);
+/// -- {"Order": {"currency": "EUR", "total": 200}}
+/// ```
+#[pgrx::pg_extern(immutable, parallel_safe)]
+pub fn facts_merge(a_json: &str, b_json: &str, deep: bool) -> String {
+    let mut objects = Vec::with_capacity(2);
+    for (label, json) in [("a_json", a_json), ("b_json", b_json)] {
+        match serde_json::from_str::<serde_json::Value>(json) {
+            Ok(serde_json::Value::Object(map)) => objects.push(map),
+            Ok(_) => {
+                return create_custom_error(
+                    &codes::NON_OBJECT_JSON,
+                    format!("{} must be a JSON object", label),
+                )
+            }
+            Err(e) => {
+                return create_custom_error(&codes::INVALID_JSON, format!("{}: {}", label, e))
+            }
+        }
+    }
+    let b = objects.pop().unwrap();
+    let a = objects.pop().unwrap();
+
+    match merge_facts(a, b, deep) {
+        Ok(merged) => merged.to_string(),
+        Err(e) => create_custom_error(&codes::INVALID_INPUT, e),
+    }
+}
@@ -0,0 +1,27 @@
+use crate::core::format::format_grl;
+
+/// Re-emit GRL with canonical indentation and spacing
+///
+/// Meant for normalising stored rules, like `gofmt` for GRL. If the input
+/// doesn't parse it is returned unchanged and a WARNING explains why.
+///
+/// # Example
+/// ```sql
+/// SELECT rule_format('rule "A" salience 10 {when Order.total>100 then Order.discount=10;}');
+/// -- rule "A" salience 10 {
+/// --     when
+/// --         Order.total > 100
+/// --     then
+/// --         Order.discount = 10;
+/// -- }
+/// ```
+#[pgrx::pg_extern(immutable, parallel_safe)]
+pub fn rule_format(grl_content: &str) -> String {
+    match format_grl(grl_content) {
+        Ok(formatted) => formatted,
+        Err(e) => {
+            pgrx::warning!("rule_format: returning input unchanged: {}", e);
+            grl_content.to_string()
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::core::jsonlogic::jsonlogic_to_grl;
+use crate::error::{codes, create_custom_error};
+
+/// Execute rules written in JSON-logic instead of GRL
+///
+/// The document is a JSON-logic `if` whose branches are action objects
+/// (`{"Fact.field": value}`), or an array of them. It is translated to GRL
+/// and run like `run_rule_engine`, returning the modified facts.
+///
+/// # Example
+/// ```sql
+/// SELECT run_rule_engine_jsonlogic(
+///     '{"Order": {"amount": 150}}',
+///     '{"if": [{">": [{"var": "Order.amount"}, 100]}, {"Order.discount": 10},
+///              {"Order.discount": 0}]}'
+/// );
+/// -- {"Order": {"amount": 150, "discount": 10}}
+/// ```
+#[pgrx::pg_extern]
+pub fn run_rule_engine_jsonlogic(facts_json: &str, jsonlogic_json: &str) -> String {
+    let document: serde_json::Value = match serde_json::from_str(jsonlogic_json) {
+        Ok(v) => v,
+        Err(e) => {
+            return create_custom_error(
+                &codes::INVALID_JSON,
+                format!("Invalid JSON-logic document: {}", e),
+            )
+        }
+    };
+
+    let grl = match jsonlogic_to_grl(&document) {
+        Ok(grl) => grl,
+        Err(e) => return create_custom_error(&codes::INVALID_INPUT, e),
+    };
+
+    crate::api::engine::run_rule_engine(facts_json, &grl)
+}
@@ -0,0 +1,17 @@
+use crate::validation::lint_grl;
+
+/// Report style and quality warnings for GRL that parses
+///
+/// # Returns
+/// JSON array of `{severity, code, message, line}`; empty when nothing is
+/// found. GRL that doesn't parse yields one `SYNTAX_ERROR` entry.
+///
+/// # Example
+/// ```sql
+/// SELECT rule_lint('rule "A" { when true == true then Order.flag = true; }');
+/// -- [{"severity": "warning", "code": "CONSTANT_CONDITION", "message": "Rule 'A': 'true == true' is always true", "line": 1}]
+/// ```
+#[pgrx::pg_extern(immutable, parallel_safe)]
+pub fn rule_lint(grl_content: &str) -> pgrx::JsonB {
+    pgrx::JsonB(serde_json::json!(lint_grl(grl_content)))
+}
@@ -1,10 +1,15 @@
 pub mod backward;
 pub mod builtin_functions;
+pub mod csv;
 pub mod datasources;
 pub mod debug;
 pub mod debug_config;
 pub mod engine;
+pub mod facts;
+pub mod format;
 pub mod health;
+pub mod jsonlogic;
+pub mod lint;
 pub mod nats;
 pub mod rulesets;
 pub mod stats;
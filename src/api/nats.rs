@@ -7,7 +7,7 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Mutex;
 
-use crate::nats::{AuthType, NatsConfig, NatsPublisher};
+use crate::nats::{headers_from_json, NatsConfig, NatsPublisher, DEFAULT_PUBLISH_RETRIES};
 
 // Global registry of NATS publishers
 lazy_static::lazy_static! {
@@ -33,65 +33,38 @@ lazy_static::lazy_static! {
 /// ```
 #[pg_extern]
 fn rule_nats_init(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>> {
-    // Load configuration fields individually (pgrx doesn't support large tuples)
-    let query = format!(
-        "SELECT nats_url FROM rule_nats_config WHERE config_name = '{}' AND enabled = true",
-        config_name
-    );
-    let nats_url =
-        Spi::get_one::<String>(&query)?.ok_or("NATS configuration not found or disabled")?;
+    // Load the whole configuration row in a single parameterized query
+    let row = Spi::connect(|client| -> Result<_, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT row_to_json(c)::jsonb FROM rule_nats_config c \
+             WHERE config_name = $1 AND enabled = true",
+            None,
+            &[config_name.into()],
+        )?;
+
+        if result.is_empty() {
+            return Ok(None);
+        }
 
-    let jetstream_enabled = Spi::get_one::<bool>(&format!(
-        "SELECT jetstream_enabled FROM rule_nats_config WHERE config_name = '{}'",
-        config_name
-    ))?
-    .unwrap_or(true);
+        result.first().get_one::<JsonB>()
+    })?;
 
-    let stream_name = Spi::get_one::<String>(&format!(
-        "SELECT stream_name FROM rule_nats_config WHERE config_name = '{}'",
-        config_name
-    ))?
-    .unwrap_or("WEBHOOKS".to_string());
+    let JsonB(row) = row.ok_or("NATS configuration not found or disabled")?;
 
-    let subject_prefix = Spi::get_one::<String>(&format!(
-        "SELECT subject_prefix FROM rule_nats_config WHERE config_name = '{}'",
-        config_name
-    ))?
-    .unwrap_or("webhooks".to_string());
+    let config = NatsConfig::from_json(&row)?;
+    config.validate()?;
 
-    let max_connections = Spi::get_one::<i32>(&format!(
-        "SELECT max_connections FROM rule_nats_config WHERE config_name = '{}'",
-        config_name
-    ))?
-    .unwrap_or(10) as usize;
-
-    let connection_timeout_ms = Spi::get_one::<i32>(&format!(
-        "SELECT connection_timeout_ms FROM rule_nats_config WHERE config_name = '{}'",
-        config_name
-    ))?
-    .unwrap_or(5000) as u64;
-
-    // Build NATS configuration
-    let config = NatsConfig {
-        nats_url: nats_url.clone(),
-        cluster_urls: None,
-        auth_type: AuthType::None, // Simplified for initial version
-        connection_timeout_ms,
-        max_connections,
-        jetstream_enabled,
-        stream_name: stream_name.clone(),
-        subject_prefix: subject_prefix.clone(),
-        reconnect_delay_ms: 2000,
-        max_reconnect_attempts: -1,
-        tls_enabled: false,
-        tls_cert_file: None,
-        tls_key_file: None,
-        tls_ca_file: None,
-    };
+    let nats_url = config.nats_url.clone();
+    let jetstream_enabled = config.jetstream_enabled;
+    let stream_name = config.stream_name.clone();
+    let auth_type = config.auth_type.name();
 
     // Create publisher with tokio runtime
     let publisher = tokio::runtime::Runtime::new()?.block_on(NatsPublisher::new(config))?;
 
+    // Flush this backend's publishers when it exits
+    crate::shutdown::ensure_registered();
+
     // Store in global registry
     NATS_PUBLISHERS
         .lock()
@@ -104,16 +77,49 @@ fn rule_nats_init(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>
         "message": format!("NATS connection initialized for config '{}'", config_name),
         "nats_url": nats_url,
         "jetstream_enabled": jetstream_enabled,
-        "stream_name": stream_name
+        "stream_name": stream_name,
+        "auth_type": auth_type
     })))
 }
 
+/// Validate a NATS configuration without connecting
+///
+/// Accepts the same shape as a `rule_nats_config` row, so it can be used
+/// to check a configuration before inserting it.
+///
+/// # Arguments
+/// * `config` - Configuration as JSON (table column names)
+///
+/// # Returns
+/// JSON with `valid` and, when invalid, the error message and category
+///
+/// # Example
+/// ```sql
+/// SELECT rule_nats_validate_config('{"nats_url": "nats://localhost:4222", "auth_type": "token"}');
+/// -- Returns: {"valid": false, "error": "Configuration error: auth_type 'token' requires auth_token", ...}
+/// ```
+#[pg_extern]
+fn rule_nats_validate_config(config: JsonB) -> JsonB {
+    let result = NatsConfig::from_json(&config.0).and_then(|config| config.validate());
+
+    match result {
+        Ok(()) => JsonB(json!({ "valid": true })),
+        Err(e) => JsonB(json!({
+            "valid": false,
+            "error": e.to_string(),
+            "category": e.category()
+        })),
+    }
+}
+
 /// Publish a webhook event to NATS
 ///
 /// # Arguments
 /// * `webhook_id` - Webhook ID
 /// * `payload` - JSON payload to publish
 /// * `message_id` - Optional message ID for deduplication
+/// * `headers` - Optional JSON object of string headers (e.g. `{"X-Tenant": "acme"}`)
+///   for consumers to route or filter on
 ///
 /// # Returns
 /// JSON with publish acknowledgment
@@ -121,35 +127,25 @@ fn rule_nats_init(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>
 /// # Example
 /// ```sql
 /// SELECT rule_webhook_publish_nats(1, '{"test": true}'::jsonb, 'msg-123');
+/// SELECT rule_webhook_publish_nats(1, '{"test": true}'::jsonb, NULL, '{"X-Tenant": "acme"}');
 /// ```
 #[pg_extern]
 fn rule_webhook_publish_nats(
     webhook_id: i32,
     payload: JsonB,
     message_id: Option<String>,
+    headers: default!(Option<JsonB>, "NULL"),
 ) -> Result<JsonB, Box<dyn std::error::Error>> {
     let start = std::time::Instant::now();
 
-    // Get webhook configuration - load fields individually
-    let webhook_name = Spi::get_one::<String>(&format!(
-        "SELECT webhook_name FROM rule_webhooks WHERE webhook_id = {} AND nats_enabled = true",
-        webhook_id
-    ))?
-    .ok_or("Webhook not found or NATS not enabled")?;
-
-    let subject = Spi::get_one::<String>(&format!(
-        "SELECT nats_subject FROM rule_webhooks WHERE webhook_id = {}",
-        webhook_id
-    ))?
-    .ok_or("NATS subject not configured")?;
-
-    let config_name = Spi::get_one::<String>(&format!(
-        "SELECT c.config_name FROM rule_webhooks w \
-         JOIN rule_nats_config c ON w.nats_config_id = c.config_id \
-         WHERE w.webhook_id = {}",
-        webhook_id
-    ))?
-    .unwrap_or("default".to_string());
+    // Reject bad headers before touching the webhook or the connection
+    let header_map = headers
+        .as_ref()
+        .map(|h| headers_from_json(&h.0))
+        .transpose()?;
+
+    // Get webhook configuration
+    let (webhook_name, subject, config_name) = load_webhook_nats_target(webhook_id)?;
 
     // Get publisher from registry
     let publishers = NATS_PUBLISHERS
@@ -164,43 +160,223 @@ fn rule_webhook_publish_nats(
     // Serialize payload
     let payload_bytes = serde_json::to_vec(&payload.0)?;
 
-    // Publish to NATS JetStream
-    let ack = tokio::runtime::Runtime::new()?.block_on(async {
-        if let Some(msg_id) = message_id.as_ref() {
-            publisher
-                .publish_jetstream_with_id(&subject, msg_id, &payload_bytes)
-                .await
-        } else {
-            publisher.publish_jetstream(&subject, &payload_bytes).await
-        }
-    })?;
+    // Publish to NATS JetStream, retrying transient failures
+    let (result, attempts) =
+        tokio::runtime::Runtime::new()?.block_on(publisher.publish_jetstream_with_retry(
+            &subject,
+            message_id.as_deref(),
+            header_map.as_ref(),
+            &payload_bytes,
+            DEFAULT_PUBLISH_RETRIES,
+        ));
 
     let latency = start.elapsed().as_secs_f64() * 1000.0;
 
-    // Log to history
-    Spi::run(&format!(
-        "INSERT INTO rule_nats_publish_history \
-         (webhook_id, subject, payload, published_at, message_id, sequence_number, success, latency_ms) \
-         VALUES ({}, '{}', '{}'::jsonb, NOW(), {}, {}, true, {})",
-        webhook_id,
-        subject,
-        serde_json::to_string(&payload.0)?,
-        message_id
-            .as_ref()
-            .map(|s| format!("'{}'", s))
-            .unwrap_or("NULL".to_string()),
-        ack.sequence,
-        latency
-    ))?;
+    let ack = match result {
+        Ok(ack) => ack,
+        Err(e) => {
+            // Record the failure and keep the payload for replay. Returning an
+            // error here would roll both inserts back, so report it as JSON.
+            Spi::run_with_args(
+                "INSERT INTO rule_nats_publish_history \
+                 (webhook_id, subject, payload, published_at, message_id, success, error_message, latency_ms) \
+                 VALUES ($1, $2, $3, NOW(), $4, false, $5, $6)",
+                &[
+                    webhook_id.into(),
+                    subject.clone().into(),
+                    JsonB(payload.0.clone()).into(),
+                    message_id.clone().into(),
+                    e.to_string().into(),
+                    latency.into(),
+                ],
+            )?;
+
+            let dead_letter_id = Spi::get_one_with_args::<i64>(
+                "INSERT INTO rule_nats_dead_letter \
+                 (webhook_id, config_name, subject, payload, message_id, error_message, error_category, attempts, headers) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+                 RETURNING dead_letter_id",
+                &[
+                    webhook_id.into(),
+                    config_name.clone().into(),
+                    subject.clone().into(),
+                    JsonB(payload.0.clone()).into(),
+                    message_id.clone().into(),
+                    e.to_string().into(),
+                    e.category().into(),
+                    (attempts as i32).into(),
+                    headers.into(),
+                ],
+            )?;
+
+            return Ok(JsonB(json!({
+                "success": false,
+                "webhook_name": webhook_name,
+                "subject": subject,
+                "error": e.to_string(),
+                "attempts": attempts,
+                "dead_letter_id": dead_letter_id,
+                "latency_ms": latency
+            })));
+        }
+    };
 
-    Ok(JsonB(json!({
+    // Log to history (duplicates are flagged so a repeated sequence is explainable)
+    Spi::run_with_args(
+        "INSERT INTO rule_nats_publish_history \
+         (webhook_id, subject, payload, published_at, message_id, sequence_number, success, duplicate, latency_ms) \
+         VALUES ($1, $2, $3, NOW(), $4, $5, true, $6, $7)",
+        &[
+            webhook_id.into(),
+            subject.clone().into(),
+            JsonB(payload.0.clone()).into(),
+            message_id.clone().into(),
+            (ack.sequence as i64).into(),
+            ack.duplicate.into(),
+            latency.into(),
+        ],
+    )?;
+
+    let mut result = json!({
         "success": true,
         "webhook_name": webhook_name,
         "subject": subject,
-        "stream": ack.stream,
-        "sequence": ack.sequence,
-        "duplicate": ack.duplicate,
+        "attempts": attempts,
         "latency_ms": latency
+    });
+    if let (Some(result), serde_json::Value::Object(outcome)) =
+        (result.as_object_mut(), ack.outcome_json())
+    {
+        result.extend(outcome);
+    }
+
+    Ok(JsonB(result))
+}
+
+/// Re-publish dead-lettered messages for a NATS configuration
+///
+/// Pending rows in `rule_nats_dead_letter` are published in insertion order
+/// with the same retry policy as `rule_webhook_publish_nats`. Successful rows
+/// are marked `replayed_at`; failed rows keep their place with the new error
+/// and attempt count.
+///
+/// # Arguments
+/// * `config_name` - Configuration name
+///
+/// # Returns
+/// JSON with replayed / failed counts
+///
+/// # Example
+/// ```sql
+/// SELECT rule_nats_replay_dead_letter('default');
+/// -- Returns: {"success": true, "config": "default", "replayed": 3, "failed": 0}
+/// ```
+#[pg_extern]
+fn rule_nats_replay_dead_letter(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>> {
+    let publisher = NATS_PUBLISHERS
+        .lock()
+        .map_err(|e| format!("Failed to lock publisher registry: {}", e))?
+        .get(config_name)
+        .cloned()
+        .ok_or(format!(
+            "NATS publisher not initialized for config '{}'. Call rule_nats_init() first",
+            config_name
+        ))?;
+
+    let pending = Spi::connect(|client| -> Result<Vec<_>, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT dead_letter_id, webhook_id, subject, payload, message_id, headers \
+             FROM rule_nats_dead_letter \
+             WHERE config_name = $1 AND replayed_at IS NULL \
+             ORDER BY dead_letter_id",
+            None,
+            &[config_name.into()],
+        )?;
+
+        let mut rows = Vec::new();
+        for row in result {
+            rows.push((
+                row.get::<i64>(1)?.unwrap_or_default(),
+                row.get::<i32>(2)?,
+                row.get::<String>(3)?.unwrap_or_default(),
+                row.get::<JsonB>(4)?.map(|p| p.0).unwrap_or_default(),
+                row.get::<String>(5)?,
+                row.get::<JsonB>(6)?,
+            ));
+        }
+        Ok(rows)
+    })?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut replayed = 0;
+    let mut failed = 0;
+
+    for (dead_letter_id, webhook_id, subject, payload, message_id, headers) in pending {
+        let start = std::time::Instant::now();
+        let payload_bytes = serde_json::to_vec(&payload)?;
+        let header_map = headers.map(|h| headers_from_json(&h.0)).transpose()?;
+
+        let (result, attempts) = runtime.block_on(publisher.publish_jetstream_with_retry(
+            &subject,
+            message_id.as_deref(),
+            header_map.as_ref(),
+            &payload_bytes,
+            DEFAULT_PUBLISH_RETRIES,
+        ));
+
+        match result {
+            Ok(ack) => {
+                Spi::run_with_args(
+                    "INSERT INTO rule_nats_publish_history \
+                     (webhook_id, subject, payload, published_at, message_id, sequence_number, success, duplicate, latency_ms, triggered_by) \
+                     VALUES ($1, $2, $3, NOW(), $4, $5, true, $6, $7, 'dead_letter_replay')",
+                    &[
+                        webhook_id.into(),
+                        subject.into(),
+                        JsonB(payload).into(),
+                        message_id.into(),
+                        (ack.sequence as i64).into(),
+                        ack.duplicate.into(),
+                        (start.elapsed().as_secs_f64() * 1000.0).into(),
+                    ],
+                )?;
+
+                Spi::run_with_args(
+                    "UPDATE rule_nats_dead_letter \
+                     SET replayed_at = NOW(), last_attempt_at = NOW(), \
+                         attempts = attempts + $2, sequence_number = $3 \
+                     WHERE dead_letter_id = $1",
+                    &[
+                        dead_letter_id.into(),
+                        (attempts as i32).into(),
+                        (ack.sequence as i64).into(),
+                    ],
+                )?;
+                replayed += 1;
+            }
+            Err(e) => {
+                Spi::run_with_args(
+                    "UPDATE rule_nats_dead_letter \
+                     SET error_message = $2, error_category = $3, \
+                         attempts = attempts + $4, last_attempt_at = NOW() \
+                     WHERE dead_letter_id = $1",
+                    &[
+                        dead_letter_id.into(),
+                        e.to_string().into(),
+                        e.category().into(),
+                        (attempts as i32).into(),
+                    ],
+                )?;
+                failed += 1;
+            }
+        }
+    }
+
+    Ok(JsonB(json!({
+        "success": failed == 0,
+        "config": config_name,
+        "replayed": replayed,
+        "failed": failed
     })))
 }
 
@@ -225,10 +401,16 @@ fn rule_webhook_call_unified(
     payload: JsonB,
 ) -> Result<JsonB, Box<dyn std::error::Error>> {
     // Get webhook configuration
-    let publish_mode = Spi::get_one::<String>(&format!(
-        "SELECT publish_mode FROM rule_webhooks WHERE webhook_id = {}",
-        webhook_id
-    ))?;
+    let publish_mode = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT publish_mode FROM rule_webhooks WHERE webhook_id = $1",
+                None,
+                &[webhook_id.into()],
+            )?
+            .first()
+            .get_one::<String>()
+    })?;
 
     let publish_mode = publish_mode.ok_or("Webhook not found")?;
 
@@ -238,32 +420,18 @@ fn rule_webhook_call_unified(
     match publish_mode.as_str() {
         "queue" => {
             // Use existing PostgreSQL queue
-            let result = Spi::get_one::<JsonB>(&format!(
-                "SELECT rule_webhook_enqueue({}, '{}'::jsonb)",
-                webhook_id,
-                serde_json::to_string(&payload.0)?
-            ))?;
-            if let Some(r) = result {
-                results["queue"] = r.0;
-            }
+            results["queue"] = enqueue_webhook(webhook_id, &payload)?;
         }
         "nats" => {
             // Publish to NATS only
-            let result = rule_webhook_publish_nats(webhook_id, payload, None)?;
+            let result = rule_webhook_publish_nats(webhook_id, payload, None, None)?;
             results["nats"] = result.0;
         }
         "both" => {
             // Both queue and NATS
-            let queue_result = Spi::get_one::<JsonB>(&format!(
-                "SELECT rule_webhook_enqueue({}, '{}'::jsonb)",
-                webhook_id,
-                serde_json::to_string(&payload.0)?
-            ))?;
-            if let Some(r) = queue_result {
-                results["queue"] = r.0;
-            }
+            results["queue"] = enqueue_webhook(webhook_id, &payload)?;
 
-            let nats_result = rule_webhook_publish_nats(webhook_id, payload, None)?;
+            let nats_result = rule_webhook_publish_nats(webhook_id, payload, None, None)?;
             results["nats"] = nats_result.0;
         }
         _ => return Err(format!("Invalid publish_mode: {}", publish_mode).into()),
@@ -272,6 +440,84 @@ fn rule_webhook_call_unified(
     Ok(JsonB(results))
 }
 
+/// Flush every initialized publisher, giving each at most `timeout`
+///
+/// Called from the backend exit hook; returns the names of publishers that
+/// failed or timed out. Skips everything if the registry is busy or poisoned
+/// rather than blocking the exit.
+pub(crate) fn flush_all_publishers(timeout: std::time::Duration) -> Vec<String> {
+    let Ok(publishers) = NATS_PUBLISHERS.try_lock() else {
+        return vec!["<registry locked>".to_string()];
+    };
+    if publishers.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(runtime) = tokio::runtime::Runtime::new() else {
+        return publishers.keys().cloned().collect();
+    };
+
+    publishers
+        .iter()
+        .filter(|(_, publisher)| {
+            !matches!(
+                runtime.block_on(tokio::time::timeout(timeout, publisher.flush())),
+                Ok(Ok(()))
+            )
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Load webhook name, NATS subject and config name for a NATS-enabled webhook
+fn load_webhook_nats_target(
+    webhook_id: i32,
+) -> Result<(String, String, String), Box<dyn std::error::Error>> {
+    let row = Spi::connect(|client| -> Result<_, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT w.webhook_name, w.nats_subject, c.config_name \
+             FROM rule_webhooks w \
+             LEFT JOIN rule_nats_config c ON w.nats_config_id = c.config_id \
+             WHERE w.webhook_id = $1 AND w.nats_enabled = true",
+            None,
+            &[webhook_id.into()],
+        )?;
+
+        if result.is_empty() {
+            return Ok(None);
+        }
+
+        let row = result.first();
+        Ok(Some((
+            row.get::<String>(1)?,
+            row.get::<String>(2)?,
+            row.get::<String>(3)?,
+        )))
+    })?;
+
+    let (webhook_name, subject, config_name) =
+        row.ok_or("Webhook not found or NATS not enabled")?;
+
+    Ok((
+        webhook_name.ok_or("Webhook not found or NATS not enabled")?,
+        subject.ok_or("NATS subject not configured")?,
+        config_name.unwrap_or("default".to_string()),
+    ))
+}
+
+/// Enqueue a webhook call on the PostgreSQL queue, returning `{"call_id": ...}`
+fn enqueue_webhook(
+    webhook_id: i32,
+    payload: &JsonB,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let call_id = Spi::get_one_with_args::<i32>(
+        "SELECT rule_webhook_enqueue($1, $2)",
+        &[webhook_id.into(), JsonB(payload.0.clone()).into()],
+    )?;
+
+    Ok(json!({ "call_id": call_id }))
+}
+
 /// Health check for NATS connection
 ///
 /// # Arguments
@@ -315,6 +561,87 @@ fn rule_nats_health_check(config_name: &str) -> Result<JsonB, Box<dyn std::error
     }
 }
 
+/// Per-subject publish metrics for an initialized NATS publisher
+///
+/// Complements `rule_nats_health_check` (pool health) with publish counts and
+/// latencies per subject. Every attempt counts, including retries. Metrics
+/// live in the backend process and restart from zero on `rule_nats_init`.
+///
+/// # Arguments
+/// * `config_name` - Configuration name
+///
+/// # Returns
+/// JSON with a `subjects` object of `{published, failed, avg_latency_ms, max_latency_ms}`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_nats_metrics('default')->'subjects'->'webhooks.orders';
+/// -- Returns: {"published": 120, "failed": 2, "avg_latency_ms": 1.8, "max_latency_ms": 12.4}
+/// ```
+#[pg_extern]
+fn rule_nats_metrics(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>> {
+    let publishers = NATS_PUBLISHERS
+        .lock()
+        .map_err(|e| format!("Failed to lock publisher registry: {}", e))?;
+
+    let publisher = publishers.get(config_name).ok_or_else(|| {
+        format!(
+            "NATS publisher '{}' not initialized. Call rule_nats_init() first",
+            config_name
+        )
+    })?;
+
+    Ok(JsonB(json!({
+        "success": true,
+        "config": config_name,
+        "subjects": publisher.metrics().snapshot()
+    })))
+}
+
+/// Reconnect dead connections in an initialized NATS pool
+///
+/// Use this when `rule_nats_health_check` reports unhealthy connections; it
+/// recovers the pool without a full `rule_nats_init`.
+///
+/// # Arguments
+/// * `config_name` - Name of the configuration
+///
+/// # Returns
+/// JSON with the number of reconnected clients and the pool stats after healing
+///
+/// # Example
+/// ```sql
+/// SELECT rule_nats_heal('default');
+/// -- Returns: {"success": true, "config": "default", "reconnected": 2, "pool_stats": {...}}
+/// ```
+#[pg_extern]
+fn rule_nats_heal(config_name: &str) -> Result<JsonB, Box<dyn std::error::Error>> {
+    let mut publishers = NATS_PUBLISHERS
+        .lock()
+        .map_err(|e| format!("Failed to lock publisher registry: {}", e))?;
+
+    let publisher = publishers.get_mut(config_name).ok_or_else(|| {
+        format!(
+            "NATS publisher '{}' not initialized. Call rule_nats_init() first",
+            config_name
+        )
+    })?;
+
+    let reconnected = tokio::runtime::Runtime::new()?.block_on(publisher.heal())?;
+    let pool_stats = publisher.pool().pool_stats();
+
+    Ok(JsonB(json!({
+        "success": true,
+        "config": config_name,
+        "reconnected": reconnected,
+        "pool_stats": {
+            "total_connections": pool_stats.total_connections,
+            "healthy_connections": pool_stats.healthy_connections,
+            "health_percentage": pool_stats.health_percentage()
+        }
+    })))
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
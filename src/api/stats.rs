@@ -103,3 +103,22 @@ fn rule_clear_stats(
 
     Ok(result.unwrap_or(0))
 }
+
+/// In-memory execution counters for this backend (executions, failures,
+/// total_rules_fired, avg_latency_ms). Cheap to read; no debug store involved.
+///
+/// # Example
+/// ```sql
+/// SELECT rule_engine_metrics();
+/// ```
+#[pg_extern]
+fn rule_engine_metrics() -> pgrx::JsonB {
+    pgrx::JsonB(crate::core::metrics::METRICS.snapshot())
+}
+
+/// Reset the in-memory execution counters for this backend
+#[pg_extern]
+fn rule_engine_metrics_reset() -> bool {
+    crate::core::metrics::METRICS.reset();
+    true
+}
@@ -90,6 +90,49 @@ fn rule_trigger_enable(
     result.ok_or_else(|| "Failed to enable/disable trigger".into())
 }
 
+/// Turn dry-run mode on or off for a rule trigger
+///
+/// In dry-run mode the trigger still runs its rule and records the result and
+/// the columns it would have changed in `rule_trigger_history`, but the row
+/// is written unmodified.
+///
+/// # Arguments
+/// * `trigger_id` - ID of the trigger
+/// * `dry_run` - TRUE to only record outcomes, FALSE to apply them (default: TRUE)
+///
+/// # Returns
+/// TRUE if successful
+///
+/// # Errors
+/// - ERR_RT004: Trigger not found
+///
+/// # Example
+/// ```sql
+/// -- Shadow-test a trigger
+/// SELECT rule_trigger_set_dry_run(1, TRUE);
+///
+/// -- Start applying changes
+/// SELECT rule_trigger_set_dry_run(1, FALSE);
+/// ```
+#[pg_extern]
+fn rule_trigger_set_dry_run(
+    trigger_id: i32,
+    dry_run: default!(bool, true),
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let result: Option<bool> = Spi::connect_mut(|client| {
+        client
+            .update(
+                "SELECT rule_trigger_set_dry_run($1, $2)",
+                None,
+                &[trigger_id.into(), dry_run.into()],
+            )?
+            .first()
+            .get_one::<bool>()
+    })?;
+
+    result.ok_or_else(|| "Failed to set trigger dry-run mode".into())
+}
+
 /// Get execution history for a rule trigger
 ///
 /// # Arguments
@@ -118,22 +161,17 @@ fn rule_trigger_history(
     start_time: default!(Option<TimestampWithTimeZone>, "NULL"),
     end_time: default!(Option<TimestampWithTimeZone>, "NULL"),
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
-    let start_clause = match start_time {
-        Some(ts) => format!("'{}'::timestamptz", ts),
-        None => "NOW() - INTERVAL '1 day'".to_string(),
-    };
-
-    let end_clause = match end_time {
-        Some(ts) => format!("'{}'::timestamptz", ts),
-        None => "NOW()".to_string(),
-    };
-
+    // Timestamps are bound as parameters; NULL falls back to the default window
     let result: Option<String> = Spi::connect(|client| {
         client
             .select(
-                "SELECT json_agg(row_to_json(t)) FROM rule_trigger_history($1, $2, $3) t",
+                "SELECT json_agg(row_to_json(t)) FROM rule_trigger_history(
+                    $1,
+                    COALESCE($2::timestamp, NOW() - INTERVAL '1 day'),
+                    COALESCE($3::timestamp, NOW())
+                ) t",
                 None,
-                &[trigger_id.into(), start_clause.into(), end_clause.into()],
+                &[trigger_id.into(), start_time.into(), end_time.into()],
             )?
             .first()
             .get_one::<String>()
@@ -142,6 +180,71 @@ fn rule_trigger_history(
     Ok(result.unwrap_or_else(|| "[]".to_string()))
 }
 
+/// List configured rule triggers
+///
+/// # Arguments
+/// * `enabled_only` - Only return enabled triggers (default: FALSE)
+///
+/// # Returns
+/// One row per trigger, ordered by ID
+///
+/// # Example
+/// ```sql
+/// -- All triggers
+/// SELECT * FROM rule_trigger_list();
+///
+/// -- Only enabled triggers
+/// SELECT * FROM rule_trigger_list(TRUE);
+/// ```
+#[allow(clippy::type_complexity)]
+#[pg_extern]
+fn rule_trigger_list(
+    enabled_only: default!(bool, false),
+) -> Result<
+    TableIterator<
+        'static,
+        (
+            name!(trigger_id, i32),
+            name!(name, String),
+            name!(table_name, String),
+            name!(rule_name, String),
+            name!(event_type, String),
+            name!(enabled, bool),
+            name!(dry_run, bool),
+            name!(created_at, Option<Timestamp>),
+        ),
+    >,
+    Box<dyn std::error::Error + Send + Sync + 'static>,
+> {
+    let rows = Spi::connect(|client| -> Result<Vec<_>, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT id, name, table_name, rule_name, event_type, COALESCE(enabled, FALSE), dry_run, created_at
+             FROM rule_triggers
+             WHERE NOT $1 OR enabled
+             ORDER BY id",
+            None,
+            &[enabled_only.into()],
+        )?;
+
+        let mut rows = Vec::new();
+        for row in result {
+            rows.push((
+                row.get::<i32>(1)?.unwrap_or_default(),
+                row.get::<String>(2)?.unwrap_or_default(),
+                row.get::<String>(3)?.unwrap_or_default(),
+                row.get::<String>(4)?.unwrap_or_default(),
+                row.get::<String>(5)?.unwrap_or_default(),
+                row.get::<bool>(6)?.unwrap_or(false),
+                row.get::<bool>(7)?.unwrap_or(false),
+                row.get::<Timestamp>(8)?,
+            ));
+        }
+        Ok(rows)
+    })?;
+
+    Ok(TableIterator::new(rows))
+}
+
 /// Delete a rule trigger
 ///
 /// # Arguments
@@ -170,3 +273,141 @@ fn rule_trigger_delete(
 
     result.ok_or_else(|| "Failed to delete trigger".into())
 }
+
+/// Preview what a rule would do to existing table rows
+///
+/// Runs the rule's default version on up to `limit` rows, with each row's
+/// `row_to_json` as facts (the same input a trigger would pass), and reports
+/// which columns would change. Nothing is written: rows are not updated and
+/// no execution audit entries are recorded.
+///
+/// # Arguments
+/// * `rule_name` - Rule to simulate (must exist in rule_definitions)
+/// * `table_name` - Table whose rows to read
+/// * `limit` - Maximum number of rows to scan (default: 100)
+///
+/// # Returns
+/// JSON summary: `rows_scanned`, `rows_changed`, `errors`, and a `rows` array
+/// with `ctid` and per-column `{old, new}` changes for each affected row
+///
+/// # Example
+/// ```sql
+/// SELECT rule_simulate('order_discount_rule', 'orders', 50);
+/// ```
+#[pg_extern]
+fn rule_simulate(
+    rule_name: &str,
+    table_name: &str,
+    limit: default!(i32, 100),
+) -> Result<pgrx::JsonB, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    if limit <= 0 {
+        return Err("limit must be greater than 0".into());
+    }
+
+    let grl = crate::repository::queries::rule_get(rule_name.to_string(), None)
+        .map_err(|e| e.to_string())?;
+
+    let rows: Vec<(String, String)> = Spi::connect(|client| {
+        let relation = client
+            .select("SELECT to_regclass($1)::text", None, &[table_name.into()])?
+            .first()
+            .get_one::<String>()?
+            .ok_or_else(|| format!("Table not found: {}", table_name))?;
+
+        let result = client.select(
+            &format!(
+                "SELECT t.ctid::text, row_to_json(t)::text FROM {} t LIMIT $1",
+                relation
+            ),
+            None,
+            &[limit.into()],
+        )?;
+
+        let mut rows = Vec::new();
+        for row in result {
+            rows.push((
+                row.get::<String>(1)?.unwrap_or_default(),
+                row.get::<String>(2)?.unwrap_or_default(),
+            ));
+        }
+        Ok::<_, Box<dyn std::error::Error + Send + Sync + 'static>>(rows)
+    })?;
+
+    let mut changed = Vec::new();
+    let mut errors = Vec::new();
+
+    for (ctid, facts_json) in &rows {
+        let result = crate::api::engine::run_rule_engine(facts_json, &grl);
+
+        let before: serde_json::Value = serde_json::from_str(facts_json)?;
+        let after: serde_json::Value = serde_json::from_str(&result)?;
+
+        if let Some(error) = after.get("error") {
+            errors.push(serde_json::json!({ "ctid": ctid, "error": error }));
+            continue;
+        }
+
+        let changes = row_changes(&before, &after);
+        if !changes.is_empty() {
+            changed.push(serde_json::json!({ "ctid": ctid, "changes": changes }));
+        }
+    }
+
+    Ok(pgrx::JsonB(serde_json::json!({
+        "rule_name": rule_name,
+        "table_name": table_name,
+        "rows_scanned": rows.len(),
+        "rows_changed": changed.len(),
+        "errors": errors,
+        "rows": changed,
+    })))
+}
+
+/// Columns whose value differs after execution, as `{column: {old, new}}`
+///
+/// Only keys already present in the row count, since a trigger merges the
+/// result back with `jsonb_populate_record` and ignores anything else.
+fn row_changes(
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut changes = serde_json::Map::new();
+
+    if let (Some(before), Some(after)) = (before.as_object(), after.as_object()) {
+        for (column, old) in before {
+            if let Some(new) = after.get(column) {
+                if new != old {
+                    changes.insert(
+                        column.clone(),
+                        serde_json::json!({ "old": old, "new": new }),
+                    );
+                }
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_row_changes() {
+        let before = json!({ "id": 1, "amount": 150, "discount": null });
+        let after = json!({ "id": 1, "amount": 150, "discount": 15, "Extra": {} });
+
+        let changes = row_changes(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes["discount"], json!({ "old": null, "new": 15 }));
+    }
+
+    #[test]
+    fn test_row_changes_none() {
+        let row = json!({ "id": 1, "amount": 150 });
+        assert!(row_changes(&row, &row).is_empty());
+        assert!(row_changes(&row, &json!({})).is_empty());
+    }
+}
@@ -1,12 +1,20 @@
+use rust_rule_engine::backward::query::ProofStep;
 use rust_rule_engine::backward::{BackwardConfig, BackwardEngine, SearchStrategy};
 use rust_rule_engine::{Facts, KnowledgeBase};
 
 /// Execute backward chaining query on facts
 /// Returns whether the goal can be proven
+///
+/// With `assume_closed_world` (negation as failure), anything that cannot be
+/// proven is taken to be false: a negative goal such as `User.Blocked == false`
+/// or `User.Blocked != true` that is not directly provable is considered proven
+/// when its positive form (`User.Blocked == true`) is unprovable. Without it
+/// (open world), missing facts leave such a goal unprovable.
 pub fn query_goal(
     facts: &Facts,
     rules: Vec<rust_rule_engine::Rule>,
     goal: &str,
+    assume_closed_world: bool,
 ) -> Result<QueryResult, String> {
     // Create knowledge base and add rules
     let kb = KnowledgeBase::new("BackwardChaining");
@@ -33,6 +41,33 @@ pub fn query_goal(
         .query(goal, &mut facts_mut)
         .map_err(|e| format!("Query failed: {}", e))?;
 
+    // Closed world: an unprovable positive makes the negative goal provable
+    if !result.provable && assume_closed_world {
+        if let Some(positive) = positive_counterpart(goal) {
+            let mut facts_positive = facts.clone();
+            let positive_result = engine
+                .query(&positive, &mut facts_positive)
+                .map_err(|e| format!("Query failed: {}", e))?;
+
+            if !positive_result.provable {
+                return Ok(QueryResult {
+                    is_provable: true,
+                    proof_trace: Some(format!(
+                        "Closed-world assumption: '{}' is not provable",
+                        positive
+                    )),
+                    goals_explored: result.stats.goals_explored
+                        + positive_result.stats.goals_explored,
+                    rules_evaluated: result.stats.rules_evaluated
+                        + positive_result.stats.rules_evaluated,
+                    query_time_ms: (result.stats.duration_ms.unwrap_or(0)
+                        + positive_result.stats.duration_ms.unwrap_or(0))
+                        as f64,
+                });
+            }
+        }
+    }
+
     // Extract proof trace as string
     let proof_trace = if result.provable {
         Some(format!("{:?}", result.proof_trace))
@@ -49,6 +84,21 @@ pub fn query_goal(
     })
 }
 
+/// Positive form of a negative boolean goal (`X == false` / `X != true` -> `X == true`)
+fn positive_counterpart(goal: &str) -> Option<String> {
+    let goal = goal.trim();
+    let (field, _) = goal
+        .split_once("==")
+        .filter(|(_, v)| v.trim() == "false")
+        .or_else(|| goal.split_once("!=").filter(|(_, v)| v.trim() == "true"))?;
+
+    let field = field.trim();
+    if field.is_empty() {
+        return None;
+    }
+    Some(format!("{} == true", field))
+}
+
 /// Result of backward chaining query
 #[derive(Debug, Clone)]
 pub struct QueryResult {
@@ -149,3 +199,237 @@ pub fn query_goal_production(
 
     Ok(result.provable)
 }
+
+/// Explanation of a backward chaining query: the ordered reasoning chain
+/// used to prove the goal, or the facts missing when it is unprovable
+#[derive(Debug, Clone)]
+pub struct GoalExplanation {
+    pub goal: String,
+    pub is_provable: bool,
+    pub steps: Vec<ExplanationStep>,
+    pub missing_facts: Vec<String>,
+    pub goals_explored: usize,
+    pub rules_evaluated: usize,
+    pub max_depth: usize,
+}
+
+/// Single rule application in a proof, flattened in depth-first order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplanationStep {
+    pub rule_name: String,
+    pub goal: String,
+    pub depth: usize,
+}
+
+impl GoalExplanation {
+    /// Convert to JSON value
+    pub fn to_json_value(&self) -> serde_json::Value {
+        let steps: Vec<_> = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                serde_json::json!({
+                    "order": i + 1,
+                    "rule": s.rule_name,
+                    "goal": s.goal,
+                    "depth": s.depth
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "goal": self.goal,
+            "provable": self.is_provable,
+            "steps": steps,
+            "missing_facts": self.missing_facts,
+            "goals_explored": self.goals_explored,
+            "rules_evaluated": self.rules_evaluated,
+            "max_depth": self.max_depth
+        })
+    }
+}
+
+/// Flatten a proof tree into an ordered list of steps (parent before subgoals)
+pub fn flatten_proof_steps(steps: &[ProofStep]) -> Vec<ExplanationStep> {
+    let mut out = Vec::new();
+    for step in steps {
+        out.push(ExplanationStep {
+            rule_name: step.rule_name.clone(),
+            goal: step.goal.clone(),
+            depth: step.depth,
+        });
+        out.extend(flatten_proof_steps(&step.sub_steps));
+    }
+    out
+}
+
+/// Execute backward chaining query and return the reasoning chain
+pub fn explain_goal(
+    facts: &Facts,
+    rules: Vec<rust_rule_engine::Rule>,
+    goal: &str,
+) -> Result<GoalExplanation, String> {
+    let kb = KnowledgeBase::new("BackwardChaining");
+    for rule in rules {
+        kb.add_rule(rule)
+            .map_err(|e| format!("Failed to add rule: {}", e))?;
+    }
+
+    let config = BackwardConfig {
+        max_depth: 50,
+        max_solutions: 10,
+        enable_memoization: true,
+        strategy: SearchStrategy::DepthFirst,
+    };
+
+    let mut engine = BackwardEngine::with_config(kb, config);
+    let mut facts_mut = facts.clone();
+
+    let result = engine
+        .query(goal, &mut facts_mut)
+        .map_err(|e| format!("Query failed: {}", e))?;
+
+    Ok(GoalExplanation {
+        goal: goal.to_string(),
+        is_provable: result.provable,
+        steps: flatten_proof_steps(&result.proof_trace.steps),
+        missing_facts: result.missing_facts,
+        goals_explored: result.stats.goals_explored,
+        rules_evaluated: result.stats.rules_evaluated,
+        max_depth: result.stats.max_depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{json_to_facts, parse_and_validate_rules};
+
+    const FRAUD_RULES: &str =
+        r#"rule "BlockFraud" { when User.FraudScore > 80 then User.Blocked = true; }"#;
+
+    fn query(facts: &str, goal: &str, assume_closed_world: bool) -> bool {
+        let facts = json_to_facts(facts).unwrap();
+        let rules = parse_and_validate_rules(FRAUD_RULES).unwrap();
+        query_goal(&facts, rules, goal, assume_closed_world)
+            .unwrap()
+            .is_provable
+    }
+
+    #[test]
+    fn test_positive_counterpart() {
+        assert_eq!(
+            positive_counterpart("User.Blocked == false"),
+            Some("User.Blocked == true".to_string())
+        );
+        assert_eq!(
+            positive_counterpart(" User.Blocked != true "),
+            Some("User.Blocked == true".to_string())
+        );
+        assert_eq!(positive_counterpart("User.Blocked == true"), None);
+        assert_eq!(positive_counterpart("User.Age > 18"), None);
+        assert_eq!(positive_counterpart("== false"), None);
+    }
+
+    #[test]
+    fn test_open_world_missing_fact_is_unprovable() {
+        // Blocked is never set, so the negative cannot be proven
+        assert!(!query(
+            r#"{"User": {"FraudScore": 10}}"#,
+            "User.Blocked == false",
+            false
+        ));
+    }
+
+    #[test]
+    fn test_closed_world_missing_fact_is_false() {
+        // Same facts: Blocked == true is unprovable, so Blocked == false holds
+        assert!(query(
+            r#"{"User": {"FraudScore": 10}}"#,
+            "User.Blocked == false",
+            true
+        ));
+        assert!(query(
+            r#"{"User": {"FraudScore": 10}}"#,
+            "User.Blocked != true",
+            true
+        ));
+    }
+
+    #[test]
+    fn test_closed_world_provable_positive_wins() {
+        // The rule proves Blocked == true, so the negative stays unprovable
+        assert!(!query(
+            r#"{"User": {"FraudScore": 90}}"#,
+            "User.Blocked == false",
+            true
+        ));
+        assert!(query(
+            r#"{"User": {"FraudScore": 90}}"#,
+            "User.Blocked == true",
+            true
+        ));
+    }
+
+    fn step(rule: &str, goal: &str, depth: usize, sub_steps: Vec<ProofStep>) -> ProofStep {
+        ProofStep {
+            rule_name: rule.to_string(),
+            goal: goal.to_string(),
+            sub_steps,
+            depth,
+        }
+    }
+
+    #[test]
+    fn test_flatten_proof_steps_order() {
+        let tree = vec![step(
+            "VIP",
+            "User.IsVIP == true",
+            0,
+            vec![
+                step("HighSpender", "User.Spent > 1000", 1, vec![]),
+                step(
+                    "LongTime",
+                    "User.Years > 2",
+                    1,
+                    vec![step("Joined", "User.Joined == true", 2, vec![])],
+                ),
+            ],
+        )];
+
+        let rules: Vec<_> = flatten_proof_steps(&tree)
+            .into_iter()
+            .map(|s| (s.rule_name, s.depth))
+            .collect();
+
+        assert_eq!(
+            rules,
+            vec![
+                ("VIP".to_string(), 0),
+                ("HighSpender".to_string(), 1),
+                ("LongTime".to_string(), 1),
+                ("Joined".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explanation_to_json() {
+        let explanation = GoalExplanation {
+            goal: "User.IsVIP == true".to_string(),
+            is_provable: false,
+            steps: vec![],
+            missing_facts: vec!["User.Spent".to_string()],
+            goals_explored: 2,
+            rules_evaluated: 1,
+            max_depth: 1,
+        };
+
+        let json = explanation.to_json_value();
+        assert_eq!(json["goal"], "User.IsVIP == true");
+        assert_eq!(json["provable"], false);
+        assert_eq!(json["missing_facts"][0], "User.Spent");
+        assert!(json["steps"].as_array().unwrap().is_empty());
+    }
+}
@@ -3,24 +3,56 @@
 //! This executor wraps the standard executor and captures all events
 //! during rule execution for time-travel debugging.
 
+use crate::core::executor::apply_retractions;
 use crate::debug::{
     current_timestamp, save_event_to_db, save_session_to_db, ReteEvent, GLOBAL_EVENT_STORE,
 };
-use rust_rule_engine::{Facts, KnowledgeBase, RustRuleEngine, Value};
+use crate::functions::preprocessing::FunctionCall;
+use rust_rule_engine::{ActionType, Facts, KnowledgeBase, RustRuleEngine, Value};
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Action appended to every rule so each firing is recorded
+const RULE_FIRED_ACTION: &str = "__debug_rule_fired";
 
 /// Execute rules with debugging enabled
+/// `function_calls` are the built-in calls evaluated during preprocessing; each is
+/// recorded as a `FunctionEvaluated` event right after the session starts.
+/// Every call is counted in the global execution metrics.
 /// Returns (final_facts, session_id)
 pub fn execute_rules_debug(
     facts: &Facts,
     rules: Vec<rust_rule_engine::Rule>,
     session_id: String,
     rules_grl: String,
+    function_calls: &[FunctionCall],
 ) -> Result<(Facts, String), String> {
+    let started = std::time::Instant::now();
+    let result = run_rules_debug(facts, rules, session_id, rules_grl, function_calls);
+
+    let (success, rules_fired) = match &result {
+        Ok((_, _, fired)) => (true, *fired),
+        Err(_) => (false, 0),
+    };
+    super::metrics::METRICS.record(success, rules_fired, started.elapsed());
+
+    result.map(|(facts, session_id, _)| (facts, session_id))
+}
+
+/// Run rules with event capture, returning (final_facts, session_id, rules_fired)
+fn run_rules_debug(
+    facts: &Facts,
+    rules: Vec<rust_rule_engine::Rule>,
+    session_id: String,
+    rules_grl: String,
+    function_calls: &[FunctionCall],
+) -> Result<(Facts, String, usize), String> {
     // Convert Facts to JSON for event storage
     let initial_facts_json = facts_to_json(facts);
 
-    // Create debug session
+    // Create debug session; unsaved sessions are persisted at backend exit
+    crate::shutdown::ensure_registered();
     GLOBAL_EVENT_STORE.create_session(session_id.clone(), rules_grl.clone(), initial_facts_json);
 
     // Record ExecutionStarted event
@@ -40,6 +72,22 @@ pub fn execute_rules_debug(
     // Also save to PostgreSQL for persistence
     let _ = save_event_to_db(&session_id, &start_event);
 
+    // Record function values injected during preprocessing
+    for call in function_calls {
+        let function_event = ReteEvent::FunctionEvaluated {
+            step: GLOBAL_EVENT_STORE.next_step(&session_id).unwrap_or(1),
+            timestamp: current_timestamp(),
+            function_name: call.name.clone(),
+            args: call.resolved_args.clone(),
+            result: call.result_value.clone().unwrap_or(serde_json::Value::Null),
+            in_when_clause: call.in_when_clause,
+            computed_field: call.computed_field.clone(),
+        };
+
+        let _ = GLOBAL_EVENT_STORE.add_event(&session_id, function_event.clone());
+        let _ = save_event_to_db(&session_id, &function_event);
+    }
+
     // Create knowledge base and engine
     let kb = KnowledgeBase::new("PostgresExtension");
     let mut engine = RustRuleEngine::new(kb);
@@ -58,11 +106,50 @@ pub fn execute_rules_debug(
         Ok(())
     });
 
+    // Record a RuleFired event with a snapshot of the facts each time a rule
+    // fires. The engine has no firing hook, so every rule gets a trailing
+    // action that identifies it by index.
+    let fired_rules: Vec<(String, Vec<String>)> = rules
+        .iter()
+        .map(|rule| {
+            let actions = rule.actions.iter().map(|a| format!("{:?}", a)).collect();
+            (rule.name.clone(), actions)
+        })
+        .collect();
+    let fired_session_id = session_id.clone();
+    let activations = AtomicU64::new(0);
+    engine.register_action_handler(RULE_FIRED_ACTION, move |args, context| {
+        let Some((rule_name, actions_executed)) = (match args.get("0") {
+            Some(Value::Integer(idx)) => fired_rules.get(*idx as usize),
+            _ => None,
+        }) else {
+            return Ok(());
+        };
+
+        let fired_event = ReteEvent::RuleFired {
+            step: GLOBAL_EVENT_STORE.next_step(&fired_session_id).unwrap_or(1),
+            timestamp: current_timestamp(),
+            rule_name: rule_name.clone(),
+            activation_id: activations.fetch_add(1, Ordering::Relaxed) + 1,
+            matched_facts: Vec::new(),
+            actions_executed: actions_executed.clone(),
+            facts_after: Some(facts_to_json(context)),
+        };
+
+        let _ = GLOBAL_EVENT_STORE.add_event(&fired_session_id, fired_event.clone());
+        let _ = save_event_to_db(&fired_session_id, &fired_event);
+        Ok(())
+    });
+
     // Add rules to engine and capture rule definitions
     let mut rule_names = Vec::new();
-    for (idx, rule) in rules.into_iter().enumerate() {
+    for (idx, mut rule) in rules.into_iter().enumerate() {
         let rule_name = rule.name.clone();
         rule_names.push(rule_name);
+        rule.actions.push(ActionType::Custom {
+            action_type: RULE_FIRED_ACTION.to_string(),
+            params: HashMap::from([("0".to_string(), Value::Integer(idx as i64))]),
+        });
 
         if let Err(e) = engine.knowledge_base_mut().add_rule(rule) {
             // Record error event
@@ -93,7 +180,23 @@ pub fn execute_rules_debug(
     let duration_ms = current_timestamp() - start_time;
 
     match execution_result {
-        Ok(_result) => {
+        Ok(result) => {
+            // Drop retracted facts and record each retraction
+            for (idx, (fact_type, data)) in
+                apply_retractions(&execution_facts).into_iter().enumerate()
+            {
+                let retract_event = ReteEvent::FactRetracted {
+                    step: GLOBAL_EVENT_STORE.next_step(&session_id).unwrap_or(1),
+                    timestamp: current_timestamp(),
+                    handle: idx as u64,
+                    fact_type,
+                    data,
+                };
+
+                let _ = GLOBAL_EVENT_STORE.add_event(&session_id, retract_event.clone());
+                let _ = save_event_to_db(&session_id, &retract_event);
+            }
+
             // Execution successful - record completion event
             let final_facts_json = facts_to_json(&execution_facts);
 
@@ -119,10 +222,12 @@ pub fn execute_rules_debug(
 
             // Save final session state to PostgreSQL
             if let Ok(session) = GLOBAL_EVENT_STORE.get_session(&session_id) {
-                let _ = save_session_to_db(&session);
+                if save_session_to_db(&session).is_ok() {
+                    let _ = GLOBAL_EVENT_STORE.mark_persisted(&session_id);
+                }
             }
 
-            Ok((execution_facts, session_id))
+            Ok((execution_facts, session_id, result.rules_fired))
         }
         Err(e) => {
             // Execution failed - record error event
@@ -142,7 +247,9 @@ pub fn execute_rules_debug(
 
             // Save error session state to PostgreSQL
             if let Ok(session) = GLOBAL_EVENT_STORE.get_session(&session_id) {
-                let _ = save_session_to_db(&session);
+                if save_session_to_db(&session).is_ok() {
+                    let _ = GLOBAL_EVENT_STORE.mark_persisted(&session_id);
+                }
             }
 
             Err(format!("Rule execution failed: {}", e))
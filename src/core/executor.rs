@@ -1,7 +1,23 @@
-use rust_rule_engine::{Facts, KnowledgeBase, RustRuleEngine};
+use crate::core::facts::engine_value_to_json;
+use rust_rule_engine::{Facts, KnowledgeBase, RustRuleEngine, Value};
 
 /// Execute rules on facts using the rule engine
+/// Every call is counted in the global execution metrics.
 pub fn execute_rules(facts: &Facts, rules: Vec<rust_rule_engine::Rule>) -> Result<(), String> {
+    let started = std::time::Instant::now();
+    let result = run_rules(facts, rules);
+
+    let (success, rules_fired) = match &result {
+        Ok(fired) => (true, *fired),
+        Err(_) => (false, 0),
+    };
+    super::metrics::METRICS.record(success, rules_fired, started.elapsed());
+
+    result.map(|_| ())
+}
+
+/// Run rules to completion, returning how many fired
+fn run_rules(facts: &Facts, rules: Vec<rust_rule_engine::Rule>) -> Result<usize, String> {
     let kb = KnowledgeBase::new("PostgresExtension");
     let mut engine = RustRuleEngine::new(kb);
 
@@ -26,9 +42,98 @@ pub fn execute_rules(facts: &Facts, rules: Vec<rust_rule_engine::Rule>) -> Resul
     }
 
     // Execute engine
-    engine
+    let result = engine
         .execute(facts)
         .map_err(|e| format!("Rule execution failed: {}", e))?;
 
-    Ok(())
+    // Drop facts retracted by `retract(Object)` actions
+    apply_retractions(facts);
+
+    Ok(result.rules_fired)
+}
+
+/// Remove facts that were retracted during execution
+///
+/// The engine handles `retract(Order)` by setting a `_retracted_Order` marker
+/// rather than deleting anything, so the marker and every `Order` / `Order.*`
+/// key are removed here. Returns the retracted objects with the data they held.
+pub fn apply_retractions(facts: &Facts) -> Vec<(String, serde_json::Value)> {
+    let all_facts = facts.get_all_facts();
+
+    let mut objects: Vec<String> = all_facts
+        .iter()
+        .filter(|(_, value)| matches!(value, Value::Boolean(true)))
+        .filter_map(|(key, _)| key.strip_prefix("_retracted_").map(str::to_string))
+        .collect();
+    objects.sort();
+
+    let mut retracted = Vec::new();
+    for object in objects {
+        facts.remove(&format!("_retracted_{}", object));
+
+        let prefix = format!("{}.", object);
+        let mut data = serde_json::Map::new();
+        for (key, value) in &all_facts {
+            if key == &object {
+                facts.remove(key);
+                data.insert(key.clone(), engine_value_to_json(value));
+            } else if let Some(field) = key.strip_prefix(&prefix) {
+                facts.remove(key);
+                data.insert(field.to_string(), engine_value_to_json(value));
+            }
+        }
+
+        retracted.push((object, serde_json::Value::Object(data)));
+    }
+
+    retracted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::facts::{facts_to_json, json_to_facts};
+    use crate::core::rules::parse_and_validate_rules;
+
+    #[test]
+    fn test_retract_removes_fact_from_output() {
+        let facts = json_to_facts(
+            r#"{"Order": {"total": 50, "status": "cancelled"}, "Customer": {"name": "Alice"}}"#,
+        )
+        .unwrap();
+        let rules = parse_and_validate_rules(
+            r#"rule "DropCancelled" {
+                when Order.status == "cancelled"
+                then retract($Order);
+            }"#,
+        )
+        .unwrap();
+
+        execute_rules(&facts, rules).unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(&facts_to_json(&facts).unwrap()).unwrap();
+        assert!(output.get("Order").is_none());
+        assert_eq!(output["Customer"]["name"], "Alice");
+        assert!(output
+            .as_object()
+            .unwrap()
+            .keys()
+            .all(|k| !k.starts_with("_retracted_")));
+    }
+
+    #[test]
+    fn test_apply_retractions_returns_removed_data() {
+        let facts = json_to_facts(r#"{"Order": {"total": 50}, "Orders": {"count": 1}}"#).unwrap();
+        facts.set("_retracted_Order", Value::Boolean(true));
+
+        let retracted = apply_retractions(&facts);
+
+        assert_eq!(retracted.len(), 1);
+        assert_eq!(retracted[0].0, "Order");
+        assert_eq!(retracted[0].1, serde_json::json!({"total": 50}));
+        // Only the exact object is removed, not others sharing its prefix
+        assert!(facts.get("Orders.count").is_some());
+        assert!(facts.get("Order.total").is_none());
+    }
 }
@@ -32,48 +32,76 @@ pub fn engine_value_to_json(value: &Value) -> serde_json::Value {
 pub fn facts_to_json(facts: &Facts) -> Result<String, String> {
     let mut result = serde_json::Map::new();
 
-    // Get all facts from Facts
-    let all_facts = facts.get_all_facts();
+    // Get all facts from Facts, parents before children ("Order" < "Order.total")
+    let mut all_facts: Vec<_> = facts.get_all_facts().into_iter().collect();
+    all_facts.sort_by(|a, b| a.0.cmp(&b.0));
+
     for (key, value) in all_facts {
         // Convert dotted keys to nested structure
-        insert_nested_value(&mut result, &key, engine_value_to_json(&value));
+        insert_nested_value(&mut result, &key, engine_value_to_json(&value))?;
     }
 
-    serde_json::to_string(&serde_json::Value::Object(result))
-        .map_err(|e| format!("Serialization error: {}", e))
+    // Fold facts expanded from `foreach` blocks back into their arrays
+    let mut result = serde_json::Value::Object(result);
+    crate::functions::foreach::collapse_foreach(&mut result);
+
+    serde_json::to_string(&result).map_err(|e| format!("Serialization error: {}", e))
 }
 
 /// Insert a value into nested JSON structure using dotted key
 /// Example: key="Order.total", value=150
 ///   → result["Order"]["total"] = 150
+///
+/// A null intermediate segment is replaced by an object. Any other scalar is a
+/// conflict (e.g. facts hold both `Order` = 5 and `Order.total` = 10) and is
+/// reported as an error.
 fn insert_nested_value(
     result: &mut serde_json::Map<String, serde_json::Value>,
     key: &str,
     value: serde_json::Value,
-) {
+) -> Result<(), String> {
     let parts: Vec<&str> = key.split('.').collect();
-
-    if parts.len() == 1 {
-        // Simple key - insert directly
-        result.insert(key.to_string(), value);
-        return;
-    }
+    let (last, parents) = parts.split_last().expect("split always yields one part");
 
     // Navigate/create nested structure
     let mut current = result;
-    for (i, part) in parts.iter().enumerate() {
-        if i == parts.len() - 1 {
-            // Last part - insert value
-            current.insert(part.to_string(), value);
-            break;
-        } else {
-            // Intermediate part - ensure object exists
-            current = current
-                .entry(part.to_string())
-                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
-                .as_object_mut()
-                .expect("Expected object in nested path");
+    for (i, part) in parents.iter().enumerate() {
+        let entry = current
+            .entry(part.to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+
+        if entry.is_null() {
+            *entry = serde_json::Value::Object(serde_json::Map::new());
         }
+
+        current = match entry {
+            serde_json::Value::Object(map) => map,
+            other => {
+                return Err(format!(
+                    "Fact key conflict: '{}' is a {} but '{}' needs it to be an object",
+                    parts[..=i].join("."),
+                    json_type_name(other),
+                    key
+                ))
+            }
+        };
+    }
+
+    // Last part - insert value
+    current.insert(last.to_string(), value);
+    Ok(())
+}
+
+/// Name the JSON type of a value: "null", "boolean", "number", "string",
+/// "array" or "object"
+pub(crate) fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
     }
 }
 
@@ -142,3 +170,156 @@ fn flatten_and_add_to_facts(
 
     Ok(())
 }
+
+/// Merge fact object `b` into `a`
+///
+/// Shallow: top-level keys of `b` replace those of `a`. Deep: both are
+/// flattened to dotted keys like `json_to_facts` does and nested back like
+/// `facts_to_json`, so `b` only replaces the leaves it sets. A leaf in `b`
+/// replaces a whole object at that key in `a` and vice versa. Arrays and
+/// empty objects count as leaves.
+pub fn merge_facts(
+    a: serde_json::Map<String, serde_json::Value>,
+    b: serde_json::Map<String, serde_json::Value>,
+    deep: bool,
+) -> Result<serde_json::Value, String> {
+    if !deep {
+        let mut merged = a;
+        merged.extend(b);
+        return Ok(serde_json::Value::Object(merged));
+    }
+
+    let mut leaves = Vec::new();
+    flatten_json(None, &serde_json::Value::Object(a), &mut leaves);
+
+    let mut patch = Vec::new();
+    flatten_json(None, &serde_json::Value::Object(b), &mut patch);
+
+    for (key, value) in patch {
+        // Drop what this key replaces: itself, its ancestors and its descendants
+        leaves.retain(|(existing, _)| {
+            !(existing == &key
+                || key.starts_with(&format!("{}.", existing))
+                || existing.starts_with(&format!("{}.", key)))
+        });
+        leaves.push((key, value));
+    }
+
+    let mut result = serde_json::Map::new();
+    for (key, value) in leaves {
+        insert_nested_value(&mut result, &key, value)?;
+    }
+
+    Ok(serde_json::Value::Object(result))
+}
+
+/// Flatten nested JSON objects into (dotted key, leaf value) pairs
+fn flatten_json(
+    prefix: Option<&str>,
+    value: &serde_json::Value,
+    out: &mut Vec<(String, serde_json::Value)>,
+) {
+    match (value, prefix) {
+        (serde_json::Value::Object(map), _) if !map.is_empty() || prefix.is_none() => {
+            for (key, val) in map {
+                let key = match prefix {
+                    Some(p) => format!("{}.{}", p, key),
+                    None => key.clone(),
+                };
+                flatten_json(Some(&key), val, out);
+            }
+        }
+        (_, Some(key)) => out.push((key.to_string(), value.clone())),
+        (_, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_facts_round_trip() {
+        let facts = json_to_facts(r#"{"Order": {"total": 150, "items": {"count": 2}}}"#).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&facts_to_json(&facts).unwrap()).unwrap();
+
+        assert_eq!(
+            json,
+            json!({"Order": {"total": 150, "items": {"count": 2}}})
+        );
+    }
+
+    #[test]
+    fn test_facts_to_json_scalar_collision() {
+        let facts = Facts::new();
+        facts.add_value("Order", Value::Integer(5)).unwrap();
+        facts.add_value("Order.total", Value::Integer(10)).unwrap();
+
+        let err = facts_to_json(&facts).unwrap_err();
+        assert!(err.contains("'Order' is a number"), "{}", err);
+        assert!(err.contains("'Order.total'"), "{}", err);
+    }
+
+    #[test]
+    fn test_facts_to_json_null_parent_becomes_object() {
+        let facts = Facts::new();
+        facts.add_value("Order", Value::Null).unwrap();
+        facts.add_value("Order.total", Value::Integer(10)).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&facts_to_json(&facts).unwrap()).unwrap();
+        assert_eq!(json, json!({"Order": {"total": 10}}));
+    }
+
+    #[test]
+    fn test_merge_facts_shallow() {
+        let a = json!({"Order": {"total": 150, "currency": "EUR"}, "Customer": {"tier": "gold"}});
+        let b = json!({"Order": {"total": 200}});
+
+        let merged = merge_facts(
+            a.as_object().unwrap().clone(),
+            b.as_object().unwrap().clone(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            merged,
+            json!({"Order": {"total": 200}, "Customer": {"tier": "gold"}})
+        );
+    }
+
+    #[test]
+    fn test_merge_facts_deep() {
+        let a = json!({"Order": {"total": 150, "currency": "EUR", "items": [1, 2]}, "Flag": 1});
+        let b = json!({"Order": {"total": 200, "items": [3]}, "Flag": {"on": true}, "Extra": {}});
+
+        let merged = merge_facts(
+            a.as_object().unwrap().clone(),
+            b.as_object().unwrap().clone(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            merged,
+            json!({
+                "Order": {"total": 200, "currency": "EUR", "items": [3]},
+                "Flag": {"on": true},
+                "Extra": {}
+            })
+        );
+
+        // A leaf replaces a whole object
+        let merged = merge_facts(
+            json!({"Order": {"total": 150}})
+                .as_object()
+                .unwrap()
+                .clone(),
+            json!({"Order": null}).as_object().unwrap().clone(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(merged, json!({"Order": null}));
+    }
+}
@@ -0,0 +1,375 @@
+//! GRL pretty-printer
+//!
+//! Re-emits GRL with canonical layout: `when`/`then` on their own lines
+//! inside the rule body, one action per line, single spaces around binary
+//! operators and one blank line between rules:
+//!
+//! ```grl
+//! rule "Discount" salience 10 {
+//!     when
+//!         Order.total > 100 && Customer.tier == "gold"
+//!     then
+//!         Order.discount = 10;
+//! }
+//! ```
+//!
+//! Works on tokens rather than the parsed `Rule`s so that comments, function
+//! calls and attribute spelling survive unchanged. A line break before or
+//! after `&&`/`||` in a condition is kept as a continuation line.
+
+use rust_rule_engine::GRLParser;
+
+/// Format GRL, or explain why it couldn't be formatted
+///
+/// The input must parse; the output is parsed again and must yield the same
+/// number of rules, so a formatting mistake never changes what gets stored.
+pub fn format_grl(grl: &str) -> Result<String, String> {
+    let rules = GRLParser::parse_rules(grl).map_err(|e| format!("Invalid GRL syntax: {}", e))?;
+    if rules.is_empty() {
+        return Err("No valid rules found in GRL".to_string());
+    }
+
+    let formatted = Printer::default().print(&tokenize(grl)?);
+
+    match GRLParser::parse_rules(&formatted) {
+        Ok(reparsed) if reparsed.len() == rules.len() => Ok(formatted),
+        _ => Err("Formatted GRL no longer parses to the same rules".to_string()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Identifier, keyword, number or dotted path (`Order.total`, `no-loop`)
+    Word,
+    Str,
+    Comment,
+    Op,
+    Punct,
+}
+
+#[derive(Debug, Clone)]
+struct Token<'a> {
+    kind: Kind,
+    text: &'a str,
+    /// Source had a line break between the previous token and this one
+    newline_before: bool,
+}
+
+const OPERATORS: [&str; 20] = [
+    "==", "!=", ">=", "<=", "&&", "||", "+=", "-=", "*=", "/=", "=", ">", "<", "+", "-", "*", "/",
+    "%", "!", ":",
+];
+
+fn tokenize(grl: &str) -> Result<Vec<Token<'_>>, String> {
+    let bytes = grl.as_bytes();
+    let mut tokens = Vec::new();
+    let mut newline_before = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+
+        let kind = if c.is_ascii_whitespace() {
+            newline_before |= c == b'\n';
+            i += 1;
+            continue;
+        } else if c == b'"' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            if i >= bytes.len() {
+                return Err("Unterminated string literal".to_string());
+            }
+            i += 1;
+            Kind::Str
+        } else if grl[i..].starts_with("//") {
+            i = grl[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            Kind::Comment
+        } else if grl[i..].starts_with("/*") {
+            let end = grl[i + 2..]
+                .find("*/")
+                .ok_or("Unterminated block comment")?;
+            i += end + 4;
+            Kind::Comment
+        } else if is_word_byte(c) {
+            while i < bytes.len() {
+                if is_word_byte(bytes[i]) {
+                    i += 1;
+                } else if bytes[i] == b'-'
+                    && bytes[i - 1].is_ascii_alphabetic()
+                    && bytes.get(i + 1).is_some_and(|b| b.is_ascii_alphabetic())
+                {
+                    // Hyphenated attributes such as no-loop and agenda-group
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            Kind::Word
+        } else if let Some(op) = OPERATORS.iter().find(|op| grl[i..].starts_with(**op)) {
+            i += op.len();
+            Kind::Op
+        } else if !c.is_ascii() {
+            // Multi-byte characters only appear inside words outside strings
+            i += grl[i..].chars().next().map_or(1, char::len_utf8);
+            Kind::Word
+        } else {
+            i += 1;
+            Kind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            text: &grl[start..i],
+            newline_before,
+        });
+        newline_before = false;
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'.' || b == b'$'
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Header,
+    When,
+    Then,
+}
+
+#[derive(Default)]
+struct Printer<'a> {
+    out: String,
+    line: String,
+    /// Indent level of the line being built
+    level: usize,
+    /// Brace nesting depth
+    depth: usize,
+    /// Section of the innermost rule body, if inside one
+    section: Option<Section>,
+    prev: Option<Token<'a>>,
+    /// An action just ended; break the line unless a trailing comment follows
+    pending_break: bool,
+}
+
+impl<'a> Printer<'a> {
+    fn print(mut self, tokens: &[Token<'a>]) -> String {
+        for token in tokens {
+            self.token(token.clone());
+        }
+        self.flush();
+
+        let mut out = self.out.trim_end().to_string();
+        out.push('\n');
+        out
+    }
+
+    fn token(&mut self, token: Token<'a>) {
+        if std::mem::take(&mut self.pending_break)
+            && !(token.kind == Kind::Comment && !token.newline_before)
+        {
+            self.flush();
+        }
+
+        match (token.kind, token.text) {
+            (Kind::Comment, _) => {
+                // Own-line comments stay on their own line, trailing ones trail
+                if token.newline_before {
+                    self.flush();
+                }
+                self.append(&token, true);
+                self.flush();
+                self.prev = None;
+                return;
+            }
+            (Kind::Word, "rule") if self.depth == 0 => {
+                self.flush();
+                if !self.out.is_empty() && !self.out.ends_with("\n\n") && !self.after_comment() {
+                    self.out.push('\n');
+                }
+                self.section = Some(Section::Header);
+                self.level = 0;
+            }
+            (Kind::Word, "when") if self.depth == 1 && self.section.is_some() => {
+                self.keyword(token, Section::When);
+                return;
+            }
+            (Kind::Word, "then") if self.depth == 1 && self.section.is_some() => {
+                self.keyword(token, Section::Then);
+                return;
+            }
+            (Kind::Punct, "{") => {
+                self.append(&token, true);
+                self.flush();
+                self.depth += 1;
+                self.level = self.content_level();
+                self.prev = None;
+                return;
+            }
+            (Kind::Punct, "}") => {
+                self.flush();
+                self.depth = self.depth.saturating_sub(1);
+                self.level = if self.depth == 0 {
+                    0
+                } else {
+                    self.content_level()
+                };
+                self.append(&token, false);
+                self.flush();
+                if self.depth == 0 {
+                    self.section = None;
+                    self.out.push('\n');
+                }
+                self.level = self.content_level();
+                self.prev = None;
+                return;
+            }
+            (Kind::Op, "&&" | "||") if self.section == Some(Section::When) => {
+                if token.newline_before {
+                    self.continuation();
+                }
+            }
+            _ => {
+                let after_logic = self
+                    .prev
+                    .as_ref()
+                    .is_some_and(|p| p.kind == Kind::Op && matches!(p.text, "&&" | "||"));
+                if token.newline_before && after_logic && self.section == Some(Section::When) {
+                    self.continuation();
+                }
+            }
+        }
+
+        let space = self.needs_space(&token);
+        self.append(&token, space);
+        self.prev = Some(token.clone());
+
+        if token.kind == Kind::Punct && token.text == ";" && self.section == Some(Section::Then) {
+            self.pending_break = true;
+            self.prev = None;
+        }
+    }
+
+    fn keyword(&mut self, token: Token<'a>, section: Section) {
+        self.flush();
+        self.level = self.depth;
+        self.append(&token, false);
+        self.flush();
+        self.section = Some(section);
+        self.level = self.content_level();
+        self.prev = None;
+    }
+
+    fn continuation(&mut self) {
+        self.flush();
+        self.level = self.content_level() + 1;
+    }
+
+    fn content_level(&self) -> usize {
+        match self.section {
+            Some(Section::When | Section::Then) => self.depth + 1,
+            _ => self.depth,
+        }
+    }
+
+    fn after_comment(&self) -> bool {
+        let last = self.out.trim_end_matches('\n').rsplit('\n').next();
+        last.is_some_and(|l| l.trim_start().starts_with("//") || l.trim_end().ends_with("*/"))
+    }
+
+    fn needs_space(&self, token: &Token) -> bool {
+        let Some(prev) = &self.prev else {
+            return false;
+        };
+
+        match (prev.kind, prev.text, token.kind, token.text) {
+            (_, _, Kind::Punct, ")" | "]" | "," | ";") => false,
+            (Kind::Punct, "(" | "[", _, _) => false,
+            (Kind::Word, _, Kind::Punct, "(" | "[") => false,
+            (Kind::Punct, ")" | "]", Kind::Punct, "[") => false,
+            // Unary operators bind to their operand
+            (Kind::Op, "!", _, _) => false,
+            (Kind::Op, "-", _, _) => !self.is_unary_minus(),
+            (Kind::Op, ":", _, _) | (_, _, Kind::Op, ":") => false,
+            _ => true,
+        }
+    }
+
+    /// Whether the last token (a `-`) negates rather than subtracts
+    fn is_unary_minus(&self) -> bool {
+        let line = self.line.trim_end_matches('-').trim_end();
+        !line.ends_with(|c: char| c.is_alphanumeric() || matches!(c, '_' | '"' | ')' | ']'))
+    }
+
+    fn append(&mut self, token: &Token, space: bool) {
+        if self.line.is_empty() {
+            self.line.push_str(&"    ".repeat(self.level));
+        } else if space || token.kind == Kind::Comment {
+            self.line.push(' ');
+        }
+        self.line.push_str(token.text);
+    }
+
+    fn flush(&mut self) {
+        if !self.line.trim().is_empty() {
+            self.out.push_str(self.line.trim_end());
+            self.out.push('\n');
+        }
+        self.line.clear();
+        self.level = self.content_level();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_canonical_layout() {
+        let grl = r#"rule   "Discount"  salience 10 no-loop{when Order.total>100&&Customer.tier=="gold" then Order.discount=10;Log("applied",Order.total);}
+rule "Adult" {
+  when User.age >= 18
+      then User.adult = true; }"#;
+
+        assert_eq!(
+            format_grl(grl).unwrap(),
+            r#"rule "Discount" salience 10 no-loop {
+    when
+        Order.total > 100 && Customer.tier == "gold"
+    then
+        Order.discount = 10;
+        Log("applied", Order.total);
+}
+
+rule "Adult" {
+    when
+        User.age >= 18
+    then
+        User.adult = true;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let grl = "// Pricing\nrule \"A\" { when X.a > 1\n && (X.b == 2 || X.c < 3) then X.d = X.a - 1; // trailing\n }";
+        let once = format_grl(grl).unwrap();
+        assert_eq!(
+            once,
+            "// Pricing\nrule \"A\" {\n    when\n        X.a > 1\n            && (X.b == 2 || X.c < 3)\n    then\n        X.d = X.a - 1; // trailing\n}\n"
+        );
+        assert_eq!(format_grl(&once).unwrap(), once);
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_grl() {
+        assert!(format_grl("rule \"A\" { when X.a > 1 then").is_err());
+        assert!(format_grl("").is_err());
+    }
+}
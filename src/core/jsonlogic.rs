@@ -0,0 +1,465 @@
+//! JSON-logic to GRL translation
+//!
+//! A document is a JSON-logic `if` whose branch values are action objects
+//! mapping `Fact.field` paths to values, or an array of such documents:
+//!
+//! ```json
+//! {"if": [
+//!     {">": [{"var": "Order.amount"}, 100]}, {"Order.discount": 10},
+//!     {"Order.discount": 0}
+//! ]}
+//! ```
+//!
+//! Each branch becomes one GRL rule whose condition also excludes the earlier
+//! branches. Supported operators: `==`, `===`, `!=`, `!==`, `>`, `>=`, `<`,
+//! `<=`, `!`, `and`, `or`, `if` and `var`.
+
+use serde_json::{Map, Value};
+
+/// Translate a JSON-logic document into GRL rules
+pub fn jsonlogic_to_grl(document: &Value) -> Result<String, String> {
+    let documents = match document {
+        Value::Array(items) if !items.is_empty() => items.as_slice(),
+        Value::Array(_) => return Err("JSON-logic document array is empty".to_string()),
+        single => std::slice::from_ref(single),
+    };
+
+    let mut rules = Vec::new();
+    for (index, doc) in documents.iter().enumerate() {
+        let branches = if_branches(doc).ok_or_else(|| {
+            format!(
+                "Document {}: top level must be an \"if\" whose branches are action objects",
+                index + 1
+            )
+        })?;
+        rules.extend(
+            translate_document(index + 1, branches)
+                .map_err(|e| format!("Document {}: {}", index + 1, e))?,
+        );
+    }
+
+    Ok(rules.join("\n\n"))
+}
+
+fn if_branches(value: &Value) -> Option<&[Value]> {
+    let object = value.as_object()?;
+    if object.len() != 1 {
+        return None;
+    }
+    object.get("if")?.as_array().map(|args| args.as_slice())
+}
+
+/// Parsed boolean condition; negation is pushed down to the comparisons
+/// because the RETE engine treats `!` over a fact without the field as true
+#[derive(Debug, Clone)]
+enum Condition {
+    Compare {
+        field: String,
+        op: &'static str,
+        value: String,
+    },
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+impl Condition {
+    fn negate(self) -> Self {
+        match self {
+            Condition::Compare { field, op, value } => Condition::Compare {
+                field,
+                op: match op {
+                    "==" => "!=",
+                    "!=" => "==",
+                    ">" => "<=",
+                    ">=" => "<",
+                    "<" => ">=",
+                    _ => ">",
+                },
+                value,
+            },
+            Condition::All(parts) => Condition::Any(parts.into_iter().map(Self::negate).collect()),
+            Condition::Any(parts) => Condition::All(parts.into_iter().map(Self::negate).collect()),
+        }
+    }
+
+    fn to_grl(&self) -> String {
+        match self {
+            Condition::Compare { field, op, value } => format!("({} {} {})", field, op, value),
+            Condition::All(parts) => join_grl(parts, " && "),
+            Condition::Any(parts) => join_grl(parts, " || "),
+        }
+    }
+}
+
+fn join_grl(parts: &[Condition], joiner: &str) -> String {
+    match parts {
+        [single] => single.to_grl(),
+        _ => format!(
+            "({})",
+            parts
+                .iter()
+                .map(Condition::to_grl)
+                .collect::<Vec<_>>()
+                .join(joiner)
+        ),
+    }
+}
+
+/// One rule per branch: `[c1, a1, c2, a2, ..., else]`
+fn translate_document(number: usize, branches: &[Value]) -> Result<Vec<String>, String> {
+    if branches.len() < 2 {
+        return Err("\"if\" needs at least a condition and an action object".to_string());
+    }
+
+    let mut rules = Vec::new();
+    let mut previous: Vec<Condition> = Vec::new();
+
+    for (i, pair) in branches.chunks(2).enumerate() {
+        let (condition, actions, name) = match pair {
+            [condition, actions] => {
+                let name = format!("JsonLogic_{}_{}", number, i + 1);
+                (Some(parse_condition(condition)?), actions, name)
+            }
+            [actions] => (None, actions, format!("JsonLogic_{}_else", number)),
+            _ => unreachable!(),
+        };
+
+        let mut clauses: Vec<Condition> = previous.iter().cloned().map(Condition::negate).collect();
+        clauses.extend(condition.clone());
+
+        let actions = translate_actions(actions)?;
+        rules.push(format!(
+            "rule \"{}\" no-loop {{\n    when\n        {}\n    then\n{}\n}}",
+            name,
+            join_grl(&clauses, " && "),
+            actions
+        ));
+
+        previous.extend(condition);
+    }
+
+    Ok(rules)
+}
+
+fn translate_actions(actions: &Value) -> Result<String, String> {
+    let actions: &Map<String, Value> = actions
+        .as_object()
+        .filter(|a| !a.is_empty())
+        .ok_or("Branch actions must be a non-empty object of {\"Fact.field\": value}")?;
+
+    let mut lines = Vec::new();
+    for (path, value) in actions {
+        validate_path(path)?;
+        let value = match value {
+            Value::Object(_) => var_path(value)?,
+            literal => translate_literal(literal)?,
+        };
+        lines.push(format!("        {} = {};", path, value));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Parse a JSON-logic boolean expression
+fn parse_condition(expr: &Value) -> Result<Condition, String> {
+    let (op, args) = operation(expr)?;
+
+    match op {
+        "and" | "or" => {
+            if args.is_empty() {
+                return Err(format!("\"{}\" needs at least one argument", op));
+            }
+            let parts = args
+                .iter()
+                .map(parse_condition)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(if op == "and" {
+                Condition::All(parts)
+            } else {
+                Condition::Any(parts)
+            })
+        }
+        "!" => match args {
+            [inner] => Ok(parse_condition(inner)?.negate()),
+            _ => Err("\"!\" takes exactly one argument".to_string()),
+        },
+        "if" => parse_if_condition(args),
+        "==" | "===" | "!=" | "!==" | ">" | ">=" | "<" | "<=" => parse_comparison(op, args),
+        other => Err(format!("Unsupported JSON-logic operator \"{}\"", other)),
+    }
+}
+
+/// `if` in a condition: `(c1 && a1) || (!c1 && c2 && a2) || ... || (!c1 && ... && else)`
+fn parse_if_condition(args: &[Value]) -> Result<Condition, String> {
+    if args.len() < 3 || args.len().is_multiple_of(2) {
+        return Err("\"if\" inside a condition needs [condition, then, ..., else]".to_string());
+    }
+
+    let mut branches = Vec::new();
+    let mut negated: Vec<Condition> = Vec::new();
+
+    for pair in args.chunks(2) {
+        let mut clauses = negated.clone();
+        match pair {
+            [condition, then] => {
+                let condition = parse_condition(condition)?;
+                clauses.push(condition.clone());
+                clauses.push(parse_condition(then)?);
+                negated.push(condition.negate());
+            }
+            [otherwise] => clauses.push(parse_condition(otherwise)?),
+            _ => unreachable!(),
+        }
+        branches.push(Condition::All(clauses));
+    }
+
+    Ok(Condition::Any(branches))
+}
+
+fn parse_comparison(op: &str, args: &[Value]) -> Result<Condition, String> {
+    let [left, right] = args else {
+        return Err(format!("\"{}\" takes exactly two arguments", op));
+    };
+
+    let grl_op = match op {
+        "==" | "===" => "==",
+        "!=" | "!==" => "!=",
+        ">" => ">",
+        ">=" => ">=",
+        "<" => "<",
+        _ => "<=",
+    };
+
+    // GRL wants the field on the left, so flip `literal op var`
+    let (field, other, grl_op) = match (is_var(left), is_var(right)) {
+        (true, _) => (var_path(left)?, right, grl_op),
+        (false, true) => (var_path(right)?, left, flip(grl_op)),
+        (false, false) => {
+            return Err(format!(
+                "\"{}\" needs at least one {{\"var\": ...}} argument",
+                op
+            ))
+        }
+    };
+
+    let value = if is_var(other) {
+        var_path(other)?
+    } else {
+        translate_literal(other)?
+    };
+
+    Ok(Condition::Compare {
+        field,
+        op: grl_op,
+        value,
+    })
+}
+
+fn flip(op: &'static str) -> &'static str {
+    match op {
+        ">" => "<",
+        ">=" => "<=",
+        "<" => ">",
+        "<=" => ">=",
+        other => other,
+    }
+}
+
+fn is_var(value: &Value) -> bool {
+    value.as_object().is_some_and(|o| o.contains_key("var"))
+}
+
+fn var_path(value: &Value) -> Result<String, String> {
+    let (op, args) = operation(value)?;
+    if op != "var" {
+        return Err(format!(
+            "Only {{\"var\": ...}} can be used as a value, got \"{}\"",
+            op
+        ));
+    }
+
+    let path = match args {
+        [Value::String(path)] => path,
+        _ => return Err("\"var\" takes a single \"Fact.field\" path".to_string()),
+    };
+    validate_path(path)?;
+    Ok(path.clone())
+}
+
+fn validate_path(path: &str) -> Result<(), String> {
+    let valid = path.contains('.')
+        && path.split('.').all(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid field path '{}' (expected Fact.field)",
+            path
+        ))
+    }
+}
+
+fn translate_literal(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::String(s) => Ok(format!(
+            "\"{}\"",
+            s.replace('\\', "\\\\").replace('"', "\\\"")
+        )),
+        other => Err(format!(
+            "Unsupported literal {} (use numbers, strings or booleans)",
+            other
+        )),
+    }
+}
+
+/// Split `{"op": args}` into the operator and its argument list
+fn operation(expr: &Value) -> Result<(&str, &[Value]), String> {
+    let object = expr
+        .as_object()
+        .filter(|o| o.len() == 1)
+        .ok_or_else(|| format!("Expected a JSON-logic operation, got {}", expr))?;
+    let (op, args) = object.iter().next().unwrap();
+
+    let args = match args {
+        Value::Array(items) => items.as_slice(),
+        single => std::slice::from_ref(single),
+    };
+    Ok((op.as_str(), args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::execute_rules_rete;
+    use serde_json::json;
+
+    #[test]
+    fn test_translate_branches() {
+        let doc = json!({"if": [
+            {"and": [
+                {">": [{"var": "Order.amount"}, 100]},
+                {"==": [{"var": "Customer.tier"}, "gold"]}
+            ]},
+            {"Order.discount": 20},
+            {"or": [{">": [{"var": "Order.amount"}, 100]}, {"<": [5, {"var": "Order.items"}]}]},
+            {"Order.discount": 10, "Order.note": "bulk"},
+            {"Order.discount": 0}
+        ]});
+
+        let grl = jsonlogic_to_grl(&doc).unwrap();
+        assert!(grl.contains(
+            "((Order.amount > 100) && (Customer.tier == \"gold\"))\n    then\n        Order.discount = 20;"
+        ));
+        assert!(grl.contains(
+            "(((Order.amount <= 100) || (Customer.tier != \"gold\")) && ((Order.amount > 100) || (Order.items > 5)))"
+        ));
+        assert!(grl.contains("rule \"JsonLogic_1_else\" no-loop"));
+        assert!(grl.contains("Order.note = \"bulk\";"));
+
+        let rules = rust_rule_engine::parser::GRLParser::parse_rules(&grl).unwrap();
+        assert_eq!(rules.len(), 3);
+    }
+
+    #[test]
+    fn test_jsonlogic_execution() {
+        let doc = json!([
+            {"if": [
+                {">": [{"var": "Order.amount"}, 100]}, {"Order.discount": 10},
+                {"Order.discount": 0}
+            ]},
+            {"if": [{"==": [{"var": "Customer.tier"}, "gold"]}, {"Customer.vip": true}]}
+        ]);
+        let grl = jsonlogic_to_grl(&doc).unwrap();
+
+        let big = execute_rules_rete(
+            &json!({"Order": {"amount": 150}, "Customer": {"tier": "gold"}}),
+            &grl,
+        )
+        .unwrap();
+        assert_eq!(big["Order"]["discount"], 10);
+        assert_eq!(big["Customer"]["vip"], true);
+
+        let small = execute_rules_rete(
+            &json!({"Order": {"amount": 50}, "Customer": {"tier": "silver"}}),
+            &grl,
+        )
+        .unwrap();
+        assert_eq!(small["Order"]["discount"], 0);
+        assert!(small["Customer"].get("vip").is_none());
+    }
+
+    #[test]
+    fn test_if_inside_condition() {
+        // Rush orders get the discount from 50, others from 100
+        let doc = json!({"if": [
+            {"if": [
+                {"==": [{"var": "Order.rush"}, true]},
+                {">": [{"var": "Order.amount"}, 50]},
+                {">": [{"var": "Order.amount"}, 100]}
+            ]},
+            {"Order.discount": 10}
+        ]});
+        let grl = jsonlogic_to_grl(&doc).unwrap();
+
+        let rush =
+            execute_rules_rete(&json!({"Order": {"amount": 75, "rush": true}}), &grl).unwrap();
+        assert_eq!(rush["Order"]["discount"], 10);
+
+        let normal =
+            execute_rules_rete(&json!({"Order": {"amount": 75, "rush": false}}), &grl).unwrap();
+        assert!(normal["Order"].get("discount").is_none());
+
+        let large =
+            execute_rules_rete(&json!({"Order": {"amount": 150, "rush": false}}), &grl).unwrap();
+        assert_eq!(large["Order"]["discount"], 10);
+    }
+
+    #[test]
+    fn test_negation_with_other_facts() {
+        // A fact without the field must not satisfy a negated condition
+        let doc = json!({"if": [
+            {"!": {">": [{"var": "Order.amount"}, 100]}}, {"Order.small": true}
+        ]});
+        let grl = jsonlogic_to_grl(&doc).unwrap();
+        assert!(grl.contains("(Order.amount <= 100)"));
+
+        let result = execute_rules_rete(
+            &json!({"Order": {"amount": 150}, "Customer": {"tier": "gold"}}),
+            &grl,
+        )
+        .unwrap();
+        assert!(result["Order"].get("small").is_none());
+    }
+
+    #[test]
+    fn test_translate_errors() {
+        // Not an if-with-actions document
+        assert!(jsonlogic_to_grl(&json!({">": [{"var": "Order.amount"}, 1]})).is_err());
+        assert!(jsonlogic_to_grl(&json!([])).is_err());
+
+        let unsupported = json!({"if": [{"in": ["a", {"var": "Order.tags"}]}, {"Order.x": 1}]});
+        assert!(jsonlogic_to_grl(&unsupported)
+            .unwrap_err()
+            .contains("\"in\""));
+
+        let bad_path = json!({"if": [{"==": [{"var": "amount"}, 1]}, {"Order.x": 1}]});
+        assert!(jsonlogic_to_grl(&bad_path)
+            .unwrap_err()
+            .contains("'amount'"));
+
+        let no_var = json!({"if": [{"==": [1, 1]}, {"Order.x": 1}]});
+        assert!(jsonlogic_to_grl(&no_var).is_err());
+
+        let bad_actions = json!({"if": [{"==": [{"var": "Order.a"}, 1]}, "yes"]});
+        assert!(jsonlogic_to_grl(&bad_actions).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Lightweight execution metrics
+//!
+//! Process-global atomic counters updated on every rule execution. Each
+//! PostgreSQL backend is its own process, so the numbers are per connection.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Global execution counters
+pub static METRICS: ExecutionMetrics = ExecutionMetrics::new();
+
+/// Aggregate counters for rule executions
+pub struct ExecutionMetrics {
+    executions: AtomicU64,
+    failures: AtomicU64,
+    rules_fired: AtomicU64,
+    total_latency_us: AtomicU64,
+}
+
+impl ExecutionMetrics {
+    pub const fn new() -> Self {
+        Self {
+            executions: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            rules_fired: AtomicU64::new(0),
+            total_latency_us: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one execution attempt
+    pub fn record(&self, success: bool, rules_fired: usize, latency: Duration) {
+        self.executions.fetch_add(1, Ordering::Relaxed);
+        if !success {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        self.rules_fired
+            .fetch_add(rules_fired as u64, Ordering::Relaxed);
+        self.total_latency_us
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Total rules fired so far in this backend
+    pub fn rules_fired_total(&self) -> u64 {
+        self.rules_fired.load(Ordering::Relaxed)
+    }
+
+    /// Current counters as JSON
+    pub fn snapshot(&self) -> serde_json::Value {
+        let executions = self.executions.load(Ordering::Relaxed);
+        let total_latency_us = self.total_latency_us.load(Ordering::Relaxed);
+        let avg_latency_ms = if executions == 0 {
+            0.0
+        } else {
+            total_latency_us as f64 / executions as f64 / 1000.0
+        };
+
+        serde_json::json!({
+            "executions": executions,
+            "failures": self.failures.load(Ordering::Relaxed),
+            "total_rules_fired": self.rules_fired.load(Ordering::Relaxed),
+            "avg_latency_ms": avg_latency_ms,
+        })
+    }
+
+    /// Reset all counters to zero
+    pub fn reset(&self) {
+        self.executions.store(0, Ordering::Relaxed);
+        self.failures.store(0, Ordering::Relaxed);
+        self.rules_fired.store(0, Ordering::Relaxed);
+        self.total_latency_us.store(0, Ordering::Relaxed);
+    }
+}
+
+impl Default for ExecutionMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_record_and_snapshot() {
+        let metrics = ExecutionMetrics::new();
+        metrics.record(true, 3, Duration::from_millis(2));
+        metrics.record(false, 0, Duration::from_millis(4));
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot["executions"], 2);
+        assert_eq!(snapshot["failures"], 1);
+        assert_eq!(snapshot["total_rules_fired"], 3);
+        assert_eq!(snapshot["avg_latency_ms"], json!(3.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let metrics = ExecutionMetrics::new();
+        metrics.record(true, 1, Duration::from_millis(1));
+        metrics.reset();
+
+        assert_eq!(
+            metrics.snapshot(),
+            json!({
+                "executions": 0,
+                "failures": 0,
+                "total_rules_fired": 0,
+                "avg_latency_ms": 0.0,
+            })
+        );
+    }
+}
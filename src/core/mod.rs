@@ -2,11 +2,15 @@ pub mod backward;
 pub mod debug_executor;
 pub mod executor;
 pub mod facts;
+pub mod format;
+pub mod jsonlogic;
+pub mod metrics;
+pub mod options;
 pub mod rete_executor;
 pub mod rules;
 
-pub use backward::{query_goal, query_goal_production, query_multiple_goals};
+pub use backward::{explain_goal, query_goal, query_goal_production, query_multiple_goals};
 pub use debug_executor::execute_rules_debug;
 pub use facts::{facts_to_json, json_to_facts};
-pub use rete_executor::execute_rules_rete;
-pub use rules::parse_and_validate_rules;
+pub use rete_executor::{compile_rules_rete, execute_rules_rete, execute_rules_rete_grouped};
+pub use rules::{fact_flow, parse_and_validate_rules, referenced_fact_paths, FactFlow};
@@ -0,0 +1,154 @@
+//! Per-execution options for `run_rule_engine_with_options`
+//!
+//! Supported keys:
+//! - `salience_overrides`: `{rule_name: salience}` replaces each named rule's
+//!   salience for this execution only
+
+use super::rules::RULE_HEADER_REGEX;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+
+lazy_static::lazy_static! {
+    static ref SALIENCE_REGEX: Regex = Regex::new(r"\bsalience\s+-?\d+").unwrap();
+}
+
+/// Options accepted by `run_rule_engine_with_options`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionOptions {
+    #[serde(default)]
+    pub salience_overrides: BTreeMap<String, i32>,
+}
+
+impl ExecutionOptions {
+    /// Parse options JSON; unknown keys are rejected
+    pub fn from_json(options_json: &str) -> Result<Self, String> {
+        let options: Self = serde_json::from_str(options_json)
+            .map_err(|e| format!("Invalid execution options: {}", e))?;
+
+        if let Some((name, value)) = options.salience_overrides.iter().find(|(_, v)| **v < 0) {
+            return Err(format!(
+                "Invalid execution options: salience for '{}' must be >= 0, got {}",
+                name, value
+            ));
+        }
+
+        Ok(options)
+    }
+
+    /// Rewrite rule headers in `rules_grl` with the overridden salience values
+    ///
+    /// The RETE engine doesn't allow changing a loaded rule's priority, so
+    /// overrides are applied to the GRL just before it is loaded.
+    pub fn apply(&self, rules_grl: &str) -> Result<String, String> {
+        if self.salience_overrides.is_empty() {
+            return Ok(rules_grl.to_string());
+        }
+
+        let mut seen = HashSet::new();
+        let rewritten = RULE_HEADER_REGEX.replace_all(rules_grl, |caps: &Captures| {
+            let name = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+
+            match self.salience_overrides.get(name) {
+                Some(salience) => {
+                    seen.insert(name.to_string());
+                    let attributes = SALIENCE_REGEX.replace_all(&caps[3], "");
+                    let attributes = attributes.trim();
+                    if attributes.is_empty() {
+                        format!("rule \"{}\" salience {} {{", name, salience)
+                    } else {
+                        format!("rule \"{}\" salience {} {} {{", name, salience, attributes)
+                    }
+                }
+                None => caps[0].to_string(),
+            }
+        });
+
+        if let Some(missing) = self
+            .salience_overrides
+            .keys()
+            .find(|name| !seen.contains(name.as_str()))
+        {
+            return Err(format!(
+                "salience_overrides: no rule named '{}' in the GRL",
+                missing
+            ));
+        }
+
+        Ok(rewritten.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_parse() {
+        let options =
+            ExecutionOptions::from_json(r#"{"salience_overrides": {"Discount": 50}}"#).unwrap();
+        assert_eq!(options.salience_overrides["Discount"], 50);
+
+        assert!(ExecutionOptions::from_json("{}")
+            .unwrap()
+            .salience_overrides
+            .is_empty());
+    }
+
+    #[test]
+    fn test_options_unknown_key() {
+        let err = ExecutionOptions::from_json(r#"{"salience": {"Discount": 50}}"#).unwrap_err();
+        assert!(err.contains("unknown field `salience`"));
+        assert!(err.contains("salience_overrides"));
+    }
+
+    #[test]
+    fn test_options_negative_salience() {
+        assert!(ExecutionOptions::from_json(r#"{"salience_overrides": {"A": -1}}"#).is_err());
+    }
+
+    #[test]
+    fn test_apply_salience_overrides() {
+        let grl = r#"
+rule "Discount" "Apply a discount" salience 10 no-loop {
+    when Order.Amount > 100 then Order.Discount = 15;
+}
+rule Shipping {
+    when Order.Amount > 50 then Order.FreeShipping = true;
+}
+rule "Other" salience 3 {
+    when Order.Amount > 0 then Order.Seen = true;
+}
+"#;
+        let options = ExecutionOptions::from_json(
+            r#"{"salience_overrides": {"Discount": 1, "Shipping": 99}}"#,
+        )
+        .unwrap();
+        let rewritten = options.apply(grl).unwrap();
+
+        assert!(rewritten.contains(r#"rule "Discount" salience 1 "Apply a discount"  no-loop {"#));
+        assert!(rewritten.contains(r#"rule "Shipping" salience 99 {"#));
+        assert!(rewritten.contains(r#"rule "Other" salience 3 {"#));
+
+        let rules = rust_rule_engine::parser::GRLParser::parse_rules(&rewritten).unwrap();
+        let salience: BTreeMap<_, _> = rules
+            .iter()
+            .map(|r| (r.name.as_str(), r.salience))
+            .collect();
+        assert_eq!(salience["Discount"], 1);
+        assert_eq!(salience["Shipping"], 99);
+        assert_eq!(salience["Other"], 3);
+        assert!(rules.iter().find(|r| r.name == "Discount").unwrap().no_loop);
+    }
+
+    #[test]
+    fn test_apply_unknown_rule() {
+        let options =
+            ExecutionOptions::from_json(r#"{"salience_overrides": {"Missing": 1}}"#).unwrap();
+        let err = options
+            .apply(r#"rule "A" { when X.a > 1 then X.b = 2; }"#)
+            .unwrap_err();
+        assert!(err.contains("'Missing'"));
+    }
+}
@@ -3,16 +3,104 @@
 //! Uses IncrementalEngine (RETE algorithm) for 2-24x faster execution
 //! compared to traditional forward chaining.
 
+use super::rules::RULE_HEADER_REGEX;
+use lru::LruCache;
+use regex::Regex;
 use rust_rule_engine::rete::facts::FactValue;
 use rust_rule_engine::rete::{GrlReteLoader, IncrementalEngine, TypedFacts};
 use serde_json::Value as JsonValue;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+
+/// Agenda group for rules without an `agenda-group` attribute
+pub const MAIN_AGENDA_GROUP: &str = "MAIN";
+
+lazy_static::lazy_static! {
+    static ref AGENDA_GROUP_REGEX: Regex = Regex::new(r#"agenda-group\s+"([^"]+)""#).unwrap();
+}
+
+/// Most compiled RETE networks kept per backend
+const RETE_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    /// Compiled networks keyed by their GRL source (backends are single-threaded)
+    static RETE_CACHE: RefCell<LruCache<String, IncrementalEngine>> = RefCell::new(
+        LruCache::new(NonZeroUsize::new(RETE_CACHE_CAPACITY).unwrap()),
+    );
+}
 
 /// Execute rules using RETE engine (high performance)
+/// Every call is counted in the global execution metrics.
 pub fn execute_rules_rete(facts_json: &JsonValue, rules_grl: &str) -> Result<JsonValue, String> {
-    // Create RETE engine
+    let started = std::time::Instant::now();
+    let result = run_rete(facts_json, rules_grl);
+
+    let (success, rules_fired) = match &result {
+        Ok((_, fired)) => (true, *fired),
+        Err(_) => (false, 0),
+    };
+    super::metrics::METRICS.record(success, rules_fired, started.elapsed());
+
+    result.map(|(mut facts, _)| {
+        crate::functions::foreach::collapse_foreach(&mut facts);
+        facts
+    })
+}
+
+/// Execute rules in agenda-group stages
+///
+/// Rules are grouped by their `agenda-group "name"` attribute (ungrouped rules
+/// belong to `MAIN`). Each group in `group_order` fires to completion on the
+/// facts left by the previous group, so later groups can't re-trigger earlier
+/// ones. `MAIN` runs last unless listed; rules in unlisted groups don't fire.
+pub fn execute_rules_rete_grouped(
+    facts_json: &JsonValue,
+    rules_grl: &str,
+    group_order: &[String],
+) -> Result<JsonValue, String> {
+    let started = std::time::Instant::now();
+    let result = run_rete_grouped(facts_json, rules_grl, group_order);
+
+    let (success, rules_fired) = match &result {
+        Ok((_, fired)) => (true, *fired),
+        Err(_) => (false, 0),
+    };
+    super::metrics::METRICS.record(success, rules_fired, started.elapsed());
+
+    result.map(|(mut facts, _)| {
+        crate::functions::foreach::collapse_foreach(&mut facts);
+        facts
+    })
+}
+
+/// Compile GRL into a RETE network without executing it; returns the rule count
+///
+/// The network is cached, so the next execution of the same GRL skips compiling.
+pub fn compile_rules_rete(rules_grl: &str) -> Result<usize, String> {
+    let (rete, rule_count) = load_rete(rules_grl)?;
+    cache_rete(rules_grl, rete);
+    Ok(rule_count)
+}
+
+/// Take the cached network for this GRL, if any, out of the cache
+fn take_cached_rete(rules_grl: &str) -> Option<IncrementalEngine> {
+    RETE_CACHE.with(|cache| cache.borrow_mut().pop(rules_grl))
+}
+
+/// Clear a network's facts, agenda and truth maintenance state and return it
+/// to the cache
+fn cache_rete(rules_grl: &str, mut rete: IncrementalEngine) {
+    rete.working_memory_mut().clear();
+    rete.agenda_mut().clear();
+    rete.tms_mut().clear();
+    RETE_CACHE.with(|cache| cache.borrow_mut().put(rules_grl.to_string(), rete));
+}
+
+/// Create a RETE engine and load GRL rules into it
+fn load_rete(rules_grl: &str) -> Result<(IncrementalEngine, usize), String> {
     let mut rete = IncrementalEngine::new();
 
-    // Load GRL rules into RETE
     let rule_count = GrlReteLoader::load_from_string(rules_grl, &mut rete)
         .map_err(|e| format!("Failed to load GRL into RETE: {}", e))?;
 
@@ -20,16 +108,93 @@ pub fn execute_rules_rete(facts_json: &JsonValue, rules_grl: &str) -> Result<Jso
         return Err("No rules loaded".to_string());
     }
 
+    Ok((rete, rule_count))
+}
+
+/// Load rules, insert facts and fire; returns final facts and the number of rules fired
+fn run_rete(facts_json: &JsonValue, rules_grl: &str) -> Result<(JsonValue, usize), String> {
+    let mut rete = match take_cached_rete(rules_grl) {
+        Some(rete) => rete,
+        None => load_rete(rules_grl)?.0,
+    };
+
+    let result = fire_rete(&mut rete, facts_json);
+    cache_rete(rules_grl, rete);
+    result
+}
+
+/// Insert facts into a loaded network and fire it
+fn fire_rete(
+    rete: &mut IncrementalEngine,
+    facts_json: &JsonValue,
+) -> Result<(JsonValue, usize), String> {
     // Convert JSON facts to TypedFacts and insert into working memory
-    let fact_handles = json_to_typed_facts(facts_json, &mut rete)?;
+    let fact_handles = json_to_typed_facts(facts_json, rete)?;
 
     // Fire all rules
-    let _fired_rules = rete.fire_all();
+    let fired_rules = rete.fire_all();
 
     // Extract final facts from working memory
-    let final_facts = extract_facts_from_rete(&rete, &fact_handles)?;
+    let final_facts = extract_facts_from_rete(rete, &fact_handles)?;
+
+    Ok((final_facts, fired_rules.len()))
+}
+
+/// Run each agenda group as a separate RETE pass, threading facts through
+fn run_rete_grouped(
+    facts_json: &JsonValue,
+    rules_grl: &str,
+    group_order: &[String],
+) -> Result<(JsonValue, usize), String> {
+    let groups = split_agenda_groups(rules_grl);
+
+    let mut stages: Vec<&str> = Vec::new();
+    for group in group_order {
+        if !groups.contains_key(group.as_str()) {
+            return Err(format!("No rules in agenda group '{}'", group));
+        }
+        if stages.contains(&group.as_str()) {
+            return Err(format!("Agenda group '{}' listed more than once", group));
+        }
+        stages.push(group);
+    }
+    if !stages.contains(&MAIN_AGENDA_GROUP) && groups.contains_key(MAIN_AGENDA_GROUP) {
+        stages.push(MAIN_AGENDA_GROUP);
+    }
+
+    let mut facts = facts_json.clone();
+    let mut rules_fired = 0;
+    for stage in stages {
+        let (next, fired) = run_rete(&facts, &groups[stage])
+            .map_err(|e| format!("Agenda group '{}': {}", stage, e))?;
+        facts = next;
+        rules_fired += fired;
+    }
+
+    Ok((facts, rules_fired))
+}
+
+/// Split GRL into per-agenda-group rule sources
+fn split_agenda_groups(rules_grl: &str) -> BTreeMap<&str, String> {
+    let headers: Vec<_> = RULE_HEADER_REGEX.captures_iter(rules_grl).collect();
+    let mut groups: BTreeMap<&str, String> = BTreeMap::new();
+
+    for (i, caps) in headers.iter().enumerate() {
+        let start = caps.get(0).unwrap().start();
+        let end = headers
+            .get(i + 1)
+            .map_or(rules_grl.len(), |next| next.get(0).unwrap().start());
+
+        let group = AGENDA_GROUP_REGEX
+            .captures(caps.get(3).unwrap().as_str())
+            .map_or(MAIN_AGENDA_GROUP, |g| g.get(1).unwrap().as_str());
+
+        let source = groups.entry(group).or_default();
+        source.push_str(&rules_grl[start..end]);
+        source.push('\n');
+    }
 
-    Ok(final_facts)
+    groups
 }
 
 /// Convert JSON object to TypedFacts and insert into RETE
@@ -194,4 +359,118 @@ mod tests {
         assert_eq!(result["Order"]["price"], 100);
         assert_eq!(result["Order"]["total"], 1000);
     }
+
+    #[test]
+    fn test_compile_rules_rete() {
+        let grl = r#"
+            rule "A" { when Order.total > 100 then Order.big = true; }
+            rule "B" { when Order.total <= 100 then Order.big = false; }
+        "#;
+        assert_eq!(compile_rules_rete(grl).unwrap(), 2);
+        assert!(compile_rules_rete("// no rules").is_err());
+    }
+
+    #[test]
+    fn test_cached_network_starts_clean() {
+        let grl = r#"
+            rule "Flag" { when Order.total > 100 then Order.big = true; }
+        "#;
+        compile_rules_rete(grl).unwrap();
+        let cached = take_cached_rete(grl).expect("compiling caches the network");
+        assert!(cached.working_memory().get_all_handles().is_empty());
+        cache_rete(grl, cached);
+
+        // Reuse must neither carry facts over nor remember the rule as fired
+        let first = execute_rules_rete(&json!({"Order": {"total": 150}}), grl).unwrap();
+        assert_eq!(first["Order"]["big"], true);
+        let second = execute_rules_rete(&json!({"Order": {"total": 50}}), grl).unwrap();
+        assert!(second["Order"].get("big").is_none());
+        let third = execute_rules_rete(&json!({"Order": {"total": 200}}), grl).unwrap();
+        assert_eq!(third["Order"]["big"], true);
+
+        // Justifications from earlier inserts don't accumulate across runs
+        let cached = take_cached_rete(grl).expect("execution caches the network");
+        assert_eq!(cached.tms().stats().total_justifications, 0);
+        cache_rete(grl, cached);
+    }
+
+    #[test]
+    fn test_split_agenda_groups() {
+        let grl = r#"
+            rule "Check" agenda-group "validation" { when Order.qty > 0 then Order.valid = true; }
+            rule Price salience 5 agenda-group "pricing" { when Order.valid == true then Order.discount = 10; }
+            rule "Tag" { when Order.qty > 0 then Order.tagged = true; }
+        "#;
+        let groups = split_agenda_groups(grl);
+
+        assert_eq!(
+            groups.keys().copied().collect::<Vec<_>>(),
+            vec!["MAIN", "pricing", "validation"]
+        );
+        assert!(groups["validation"].contains("rule \"Check\""));
+        assert!(groups["pricing"].starts_with("rule Price"));
+        assert!(!groups["pricing"].contains("Tag"));
+    }
+
+    #[test]
+    fn test_grouped_execution_order() {
+        let facts = json!({ "Order": { "qty": 3 } });
+
+        // Pricing has the higher salience but still runs after validation
+        let grl = r#"
+            rule "Price" salience 100 agenda-group "pricing" {
+                when Order.valid == true then Order.discount = 10;
+            }
+            rule "Validate" agenda-group "validation" {
+                when Order.qty > 0 then Order.valid = true;
+            }
+        "#;
+        let order = vec!["validation".to_string(), "pricing".to_string()];
+
+        let result = execute_rules_rete_grouped(&facts, grl, &order).unwrap();
+        assert_eq!(result["Order"]["valid"], true);
+        assert_eq!(result["Order"]["discount"], 10);
+    }
+
+    #[test]
+    fn test_grouped_later_group_does_not_leak() {
+        let facts = json!({ "Order": { "qty": 3 } });
+
+        // In a single pass, Flag would fire once Price set the discount
+        let grl = r#"
+            rule "Flag" agenda-group "validation" {
+                when Order.discount > 0 then Order.flagged = true;
+            }
+            rule "Price" agenda-group "pricing" {
+                when Order.qty > 0 then Order.discount = 10;
+            }
+        "#;
+        let order = vec!["validation".to_string(), "pricing".to_string()];
+
+        let result = execute_rules_rete_grouped(&facts, grl, &order).unwrap();
+        assert_eq!(result["Order"]["discount"], 10);
+        assert!(result["Order"].get("flagged").is_none());
+
+        let single_pass = execute_rules_rete(&facts, grl).unwrap();
+        assert_eq!(single_pass["Order"]["flagged"], true);
+    }
+
+    #[test]
+    fn test_grouped_main_and_unlisted_groups() {
+        let facts = json!({ "Order": { "qty": 3 } });
+        let grl = r#"
+            rule "Tag" { when Order.qty > 0 then Order.tagged = true; }
+            rule "Audit" agenda-group "audit" { when Order.qty > 0 then Order.audited = true; }
+        "#;
+
+        let result = execute_rules_rete_grouped(&facts, grl, &[]).unwrap();
+        assert_eq!(result["Order"]["tagged"], true);
+        assert!(result["Order"].get("audited").is_none());
+
+        let err = execute_rules_rete_grouped(&facts, grl, &["pricing".to_string()]).unwrap_err();
+        assert!(err.contains("'pricing'"));
+        assert!(
+            execute_rules_rete_grouped(&facts, grl, &["audit".into(), "audit".into()]).is_err()
+        );
+    }
 }
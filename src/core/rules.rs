@@ -1,4 +1,14 @@
+use regex::Regex;
 use rust_rule_engine::GRLParser;
+use std::collections::BTreeSet;
+
+lazy_static::lazy_static! {
+    /// A rule header up to its opening brace, with the names the GRL parser
+    /// accepts: group 1 is a quoted name, group 2 a bare one, group 3 the
+    /// attributes
+    pub static ref RULE_HEADER_REGEX: Regex =
+        Regex::new(r#"\brule\s+(?:"([^"]+)"|([a-zA-Z_]\w*))([^{]*)\{"#).unwrap();
+}
 
 /// Parse and validate GRL rules
 pub fn parse_and_validate_rules(rules_grl: &str) -> Result<Vec<rust_rule_engine::Rule>, String> {
@@ -13,3 +23,125 @@ pub fn parse_and_validate_rules(rules_grl: &str) -> Result<Vec<rust_rule_engine:
 
     Ok(rules)
 }
+
+/// Fact paths (`Order.total`) the rules need from their input
+///
+/// Everything they read except paths some rule assigns, since those needn't
+/// come from the input.
+pub fn referenced_fact_paths(rules_grl: &str) -> Result<Vec<String>, String> {
+    let flow = fact_flow(rules_grl)?;
+    Ok(flow.reads.difference(&flow.writes).cloned().collect())
+}
+
+/// Fact paths GRL reads and writes
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FactFlow {
+    /// Read in conditions or on the right-hand side of actions
+    pub reads: BTreeSet<String>,
+    /// Assigned by actions
+    pub writes: BTreeSet<String>,
+}
+
+/// Work out which fact paths the rules read and write
+///
+/// Method calls (`Order.recalculate()`) and anything inside string literals
+/// or comments are ignored.
+pub fn fact_flow(rules_grl: &str) -> Result<FactFlow, String> {
+    parse_and_validate_rules(rules_grl)?;
+
+    let noise_re = Regex::new(r#"(?s)"(?:[^"\\]|\\.)*"|//[^\n]*|/\*.*?\*/"#).unwrap();
+    let code = noise_re.replace_all(rules_grl, " ");
+
+    let keyword_re = Regex::new(r"\b(rule|when|then)\b").unwrap();
+    let path_re = Regex::new(r"[A-Za-z_]\w*(?:\.\w+)+").unwrap();
+
+    let mut flow = FactFlow::default();
+    let mut section = "rule";
+    let mut start = 0;
+
+    let keywords: Vec<_> = keyword_re.captures_iter(&code).collect();
+    for i in 0..=keywords.len() {
+        let end = keywords
+            .get(i)
+            .map_or(code.len(), |k| k.get(0).unwrap().start());
+        let text = &code[start..end];
+
+        if section != "rule" {
+            for path in path_re.find_iter(text) {
+                let rest = text[path.end()..].trim_start();
+                if rest.starts_with('(') {
+                    continue;
+                }
+                let is_assignment = section == "then"
+                    && ["=", "+=", "-=", "*=", "/="]
+                        .iter()
+                        .any(|op| rest.starts_with(op) && !rest.starts_with("=="));
+                if is_assignment {
+                    flow.writes.insert(path.as_str().to_string());
+                } else {
+                    flow.reads.insert(path.as_str().to_string());
+                }
+            }
+        }
+
+        if let Some(keyword) = keywords.get(i) {
+            section = keyword.get(1).unwrap().as_str();
+            start = keyword.get(0).unwrap().end();
+        }
+    }
+
+    Ok(flow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_fact_paths() {
+        let grl = r#"
+// Reads Legacy.flag only in this comment
+rule "Discount" salience 10 {
+    when Order.total > 100 && Customer.tier == "Vip.gold"
+    then
+        Order.discount = Order.total * Rates.vip;
+}
+rule "Flag" {
+    when Order.discount > 5 && Customer.years > 3
+    then Audit.flagged = true;
+}"#;
+
+        assert_eq!(
+            referenced_fact_paths(grl).unwrap(),
+            vec![
+                "Customer.tier",
+                "Customer.years",
+                "Order.total",
+                "Rates.vip"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fact_flow() {
+        let grl = r#"rule "Grow" {
+    when Counter.value < 10
+    then Counter.value = Counter.value + Counter.step; Counter.touched = true;
+}"#;
+        let flow = fact_flow(grl).unwrap();
+
+        assert_eq!(
+            flow.reads.into_iter().collect::<Vec<_>>(),
+            vec!["Counter.step", "Counter.value"]
+        );
+        assert_eq!(
+            flow.writes.into_iter().collect::<Vec<_>>(),
+            vec!["Counter.touched", "Counter.value"]
+        );
+    }
+
+    #[test]
+    fn test_referenced_fact_paths_invalid_grl() {
+        assert!(referenced_fact_paths("rule \"A\" { when").is_err());
+    }
+}
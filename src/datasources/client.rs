@@ -1,8 +1,12 @@
-use super::models::{AuthType, DataSource, DataSourceAuth, DataSourceResponse};
+use super::models::{
+    AuthType, DataSource, DataSourceAuth, DataSourceResponse, PaginationConfig, SourceType,
+};
 use reqwest::blocking::{Client, RequestBuilder};
-use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use reqwest::StatusCode;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
+use std::io::Read;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 
@@ -30,6 +34,30 @@ impl FromStr for HttpMethod {
     }
 }
 
+/// Validators of a cached response, used to revalidate it with the origin
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Read `ETag` / `Last-Modified` from headers captured by `headers_to_json`
+    pub fn from_headers(headers: Option<&JsonValue>) -> Self {
+        let get = |name: &str| {
+            headers
+                .and_then(|h| h.get(name))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+
+        Self {
+            etag: get("etag"),
+            last_modified: get("last-modified"),
+        }
+    }
+}
+
 pub struct DataSourceClient {
     client: Client,
 }
@@ -52,47 +80,50 @@ impl DataSourceClient {
         endpoint: &str,
         method: HttpMethod,
         params: &JsonValue,
+    ) -> Result<DataSourceResponse, String> {
+        self.fetch_conditional(datasource, auth, endpoint, method, params, None)
+    }
+
+    /// Fetch data, revalidating a cached response when validators are given
+    ///
+    /// Sends `If-None-Match` / `If-Modified-Since`. A `304 Not Modified` reply
+    /// is returned with status "not_modified" and no body; the caller serves
+    /// its cached copy.
+    pub fn fetch_conditional(
+        &self,
+        datasource: &DataSource,
+        auth: &DataSourceAuth,
+        endpoint: &str,
+        method: HttpMethod,
+        params: &JsonValue,
+        validators: Option<&CacheValidators>,
     ) -> Result<DataSourceResponse, String> {
         let start_time = Instant::now();
 
+        // GraphQL is always a POST of a {query, variables} envelope
+        let graphql = datasource.source_type == SourceType::GraphQL;
+        let graphql_body;
+        let (method, params) = if graphql {
+            graphql_body = graphql_request_body(params)?;
+            (HttpMethod::Post, &graphql_body)
+        } else {
+            (method, params)
+        };
+
         // Build full URL
         let url = format!("{}{}", datasource.base_url.trim_end_matches('/'), endpoint);
 
-        // Build request
-        let mut request = self.build_request(method, &url)?;
-
-        // Add default headers
-        request = self.add_headers(request, &datasource.default_headers)?;
-
-        // Add authentication
-        request = self.add_auth(request, &datasource.auth_type, auth)?;
+        let mut request = self.prepare_request(datasource, auth, method, &url, params)?;
 
-        // Add timeout
-        request = request.timeout(Duration::from_millis(datasource.timeout_ms as u64));
-
-        // Add body/params based on method
-        request = match method {
-            HttpMethod::Get => {
-                // For GET, add params as query string
-                if let Some(obj) = params.as_object() {
-                    for (key, value) in obj {
-                        let value_str = match value {
-                            JsonValue::String(s) => s.clone(),
-                            JsonValue::Number(n) => n.to_string(),
-                            JsonValue::Bool(b) => b.to_string(),
-                            _ => value.to_string(),
-                        };
-                        request = request.query(&[(key, value_str)]);
-                    }
-                }
-                request
+        // Add conditional request headers
+        if let Some(validators) = validators {
+            if let Some(ref etag) = validators.etag {
+                request = request.header(IF_NONE_MATCH, etag.as_str());
             }
-            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch => {
-                // For POST/PUT/PATCH, send params as JSON body
-                request.json(params)
+            if let Some(ref last_modified) = validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified.as_str());
             }
-            HttpMethod::Delete => request,
-        };
+        }
 
         // Execute request with retry logic
         let response_result = self.execute_with_retry(
@@ -103,16 +134,47 @@ impl DataSourceClient {
 
         let execution_time_ms = start_time.elapsed().as_millis() as f64;
 
-        match response_result {
+        let response = match response_result {
             Ok(response) => {
                 let status_code = response.status().as_u16() as i32;
                 let is_success = response.status().is_success();
+                let response_headers = Some(headers_to_json(response.headers()));
 
-                // Try to parse response as JSON
-                let body_result = response.json::<JsonValue>();
+                if response.status() == StatusCode::NOT_MODIFIED {
+                    return Ok(DataSourceResponse {
+                        request_id: 0,
+                        status: "not_modified".to_string(),
+                        cache_hit: false,
+                        response_status: Some(status_code),
+                        response_body: None,
+                        response_headers,
+                        error_message: None,
+                        execution_time_ms: Some(execution_time_ms),
+                    });
+                }
+
+                // Try to parse response as JSON, refusing oversized bodies
+                let body_result = read_json_body(response, datasource.max_response_bytes);
 
                 match body_result {
-                    Ok(body) => Ok(DataSourceResponse {
+                    Ok(body) if graphql => {
+                        let (data, errors) = unwrap_graphql_response(body);
+                        DataSourceResponse {
+                            request_id: 0,
+                            status: if is_success && errors.is_none() {
+                                "success".to_string()
+                            } else {
+                                "failed".to_string()
+                            },
+                            cache_hit: false,
+                            response_status: Some(status_code),
+                            response_body: data,
+                            response_headers,
+                            error_message: errors,
+                            execution_time_ms: Some(execution_time_ms),
+                        }
+                    }
+                    Ok(body) => DataSourceResponse {
                         request_id: 0, // Will be set by database
                         status: if is_success {
                             "success".to_string()
@@ -122,33 +184,225 @@ impl DataSourceClient {
                         cache_hit: false,
                         response_status: Some(status_code),
                         response_body: Some(body),
+                        response_headers,
                         error_message: None,
                         execution_time_ms: Some(execution_time_ms),
-                    }),
-                    Err(_) => {
-                        // If JSON parsing fails, return error
-                        Ok(DataSourceResponse {
+                    },
+                    Err(e) => {
+                        // If the body is too large or not JSON, return error
+                        DataSourceResponse {
                             request_id: 0,
                             status: "failed".to_string(),
                             cache_hit: false,
                             response_status: Some(status_code),
                             response_body: None,
-                            error_message: Some("Failed to parse response as JSON".to_string()),
+                            response_headers,
+                            error_message: Some(e),
                             execution_time_ms: Some(execution_time_ms),
-                        })
+                        }
                     }
                 }
             }
-            Err(e) => Ok(DataSourceResponse {
+            Err(e) => DataSourceResponse {
                 request_id: 0,
                 status: "failed".to_string(),
                 cache_hit: false,
                 response_status: None,
                 response_body: None,
+                response_headers: None,
                 error_message: Some(e),
                 execution_time_ms: Some(execution_time_ms),
-            }),
+            },
+        };
+
+        // Follow next links and merge items for paginated GET sources
+        match datasource.pagination {
+            Some(ref pagination)
+                if !graphql
+                    && matches!(method, HttpMethod::Get)
+                    && response.status == "success" =>
+            {
+                Ok(self.follow_pages(datasource, auth, &url, params, pagination, response))
+            }
+            _ => Ok(response),
+        }
+    }
+
+    /// Build a request with headers, auth, timeout and params applied
+    fn prepare_request(
+        &self,
+        datasource: &DataSource,
+        auth: &DataSourceAuth,
+        method: HttpMethod,
+        url: &str,
+        params: &JsonValue,
+    ) -> Result<RequestBuilder, String> {
+        // Build request
+        let mut request = self.build_request(method, url)?;
+
+        // Add default headers
+        request = self.add_headers(request, &datasource.default_headers)?;
+
+        // Add authentication
+        request = self.add_auth(request, &datasource.auth_type, auth)?;
+
+        // Add timeout
+        request = request.timeout(Duration::from_millis(datasource.timeout_ms as u64));
+
+        // Add body/params based on method
+        request = match method {
+            HttpMethod::Get => {
+                // For GET, add params as query string
+                if let Some(obj) = params.as_object() {
+                    for (key, value) in obj {
+                        let value_str = match value {
+                            JsonValue::String(s) => s.clone(),
+                            JsonValue::Number(n) => n.to_string(),
+                            JsonValue::Bool(b) => b.to_string(),
+                            _ => value.to_string(),
+                        };
+                        request = request.query(&[(key, value_str)]);
+                    }
+                }
+                request
+            }
+            HttpMethod::Post | HttpMethod::Put | HttpMethod::Patch => {
+                // For POST/PUT/PATCH, send params as JSON body
+                request.json(params)
+            }
+            HttpMethod::Delete => request,
+        };
+
+        Ok(request)
+    }
+
+    /// Fetch the remaining pages after `first` and merge all items into one array
+    ///
+    /// Stops at `max_pages` or when a page has no next link. If a later page
+    /// fails, the response is marked failed and keeps the items gathered so far.
+    fn follow_pages(
+        &self,
+        datasource: &DataSource,
+        auth: &DataSourceAuth,
+        first_url: &str,
+        params: &JsonValue,
+        pagination: &PaginationConfig,
+        first: DataSourceResponse,
+    ) -> DataSourceResponse {
+        let mut response = first;
+        let body = response.response_body.take().unwrap_or(JsonValue::Null);
+
+        let mut items = Vec::new();
+        let mut next = match page_items(&body, pagination) {
+            Ok(page) => {
+                items.extend(page);
+                next_page_value(&body, &pagination.next_path)
+            }
+            Err(e) => {
+                response.status = "failed".to_string();
+                response.error_message = Some(e);
+                response.response_body = Some(body);
+                return response;
+            }
+        };
+
+        let mut pages = 1;
+        while let Some(next_value) = next.take() {
+            if pages >= pagination.max_pages {
+                break;
+            }
+            pages += 1;
+
+            let start_time = Instant::now();
+            let page =
+                self.fetch_page(datasource, auth, first_url, params, pagination, &next_value);
+            let elapsed_ms = start_time.elapsed().as_millis() as f64;
+            response.execution_time_ms =
+                Some(response.execution_time_ms.unwrap_or(0.0) + elapsed_ms);
+
+            match page.and_then(|(status_code, body)| {
+                response.response_status = Some(status_code);
+                page_items(&body, pagination).map(|page| (page, body))
+            }) {
+                Ok((page, body)) => {
+                    items.extend(page);
+                    next = next_page_value(&body, &pagination.next_path);
+                }
+                Err(e) => {
+                    response.status = "failed".to_string();
+                    response.error_message = Some(format!("Page {}: {}", pages, e));
+                    break;
+                }
+            }
+        }
+
+        response.response_body = Some(JsonValue::Array(items));
+        response
+    }
+
+    /// GET one follow-up page, returning its status code and JSON body
+    fn fetch_page(
+        &self,
+        datasource: &DataSource,
+        auth: &DataSourceAuth,
+        first_url: &str,
+        params: &JsonValue,
+        pagination: &PaginationConfig,
+        next_value: &str,
+    ) -> Result<(i32, JsonValue), String> {
+        let base_url = datasource.base_url.trim_end_matches('/');
+
+        let request = if next_value.starts_with("http://") || next_value.starts_with("https://") {
+            // Never send credentials to a host other than the data source's
+            let under_base_url = next_value
+                .strip_prefix(base_url)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?']));
+            if !under_base_url {
+                return Err(format!(
+                    "Next link '{}' is outside the data source base URL",
+                    next_value
+                ));
+            }
+            self.prepare_request(
+                datasource,
+                auth,
+                HttpMethod::Get,
+                next_value,
+                &JsonValue::Null,
+            )?
+        } else if next_value.starts_with('/') {
+            let url = format!("{}{}", base_url, next_value);
+            self.prepare_request(datasource, auth, HttpMethod::Get, &url, &JsonValue::Null)?
+        } else {
+            // A cursor: repeat the first request with the cursor parameter
+            let mut page_params = params.as_object().cloned().unwrap_or_default();
+            page_params.insert(
+                pagination.cursor_param.clone(),
+                JsonValue::String(next_value.to_string()),
+            );
+            self.prepare_request(
+                datasource,
+                auth,
+                HttpMethod::Get,
+                first_url,
+                &JsonValue::Object(page_params),
+            )?
+        };
+
+        let response = self.execute_with_retry(
+            request,
+            datasource.retry_enabled,
+            datasource.max_retries as u32,
+        )?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {}", status.as_u16()));
         }
+
+        let body = read_json_body(response, datasource.max_response_bytes)?;
+
+        Ok((status.as_u16() as i32, body))
     }
 
     fn build_request(&self, method: HttpMethod, url: &str) -> Result<RequestBuilder, String> {
@@ -243,7 +497,9 @@ impl DataSourceClient {
 
             match response {
                 Ok(resp) => {
-                    if resp.status().is_success() || !retry_enabled || attempts >= max_retries {
+                    let done =
+                        resp.status().is_success() || resp.status() == StatusCode::NOT_MODIFIED;
+                    if done || !retry_enabled || attempts >= max_retries {
                         return Ok(resp);
                     }
 
@@ -278,6 +534,164 @@ impl DataSourceClient {
     }
 }
 
+/// Read and parse a JSON response body of at most `max_bytes`
+fn read_json_body(
+    response: reqwest::blocking::Response,
+    max_bytes: Option<u64>,
+) -> Result<JsonValue, String> {
+    let content_length = response.content_length();
+    let body = read_limited(response, content_length, max_bytes)?;
+
+    serde_json::from_slice(&body).map_err(|_| "Failed to parse response as JSON".to_string())
+}
+
+/// Read a body, failing as soon as it is known to exceed `max_bytes`
+///
+/// A declared `Content-Length` over the limit is rejected without reading;
+/// otherwise at most `max_bytes + 1` bytes are read, so a missing or wrong
+/// length cannot make the body grow without bound.
+fn read_limited(
+    reader: impl Read,
+    content_length: Option<u64>,
+    max_bytes: Option<u64>,
+) -> Result<Vec<u8>, String> {
+    let too_large = |max: u64| format!("Response too large: exceeds {} bytes", max);
+
+    if let (Some(max), Some(len)) = (max_bytes, content_length) {
+        if len > max {
+            return Err(too_large(max));
+        }
+    }
+
+    let mut body = Vec::new();
+    reader
+        .take(max_bytes.map_or(u64::MAX, |max| max.saturating_add(1)))
+        .read_to_end(&mut body)
+        .map_err(|e| format!("Failed to read response body: {}", e))?;
+
+    match max_bytes {
+        Some(max) if body.len() as u64 > max => Err(too_large(max)),
+        _ => Ok(body),
+    }
+}
+
+/// Resolve a dotted path inside a JSON value ("" = the value itself)
+fn json_path<'a>(value: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    if path.is_empty() {
+        return Some(value);
+    }
+
+    path.split('.')
+        .try_fold(value, |current, part| match current {
+            JsonValue::Array(items) => part.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => current.get(part),
+        })
+}
+
+/// Items of one page, which must be an array at `items_path`
+fn page_items(body: &JsonValue, pagination: &PaginationConfig) -> Result<Vec<JsonValue>, String> {
+    match json_path(body, &pagination.items_path) {
+        Some(JsonValue::Array(items)) => Ok(items.clone()),
+        _ => Err(format!(
+            "Expected an array at items_path '{}'",
+            pagination.items_path
+        )),
+    }
+}
+
+/// Next link or cursor of a page; missing, null, false and "" mean last page
+fn next_page_value(body: &JsonValue, next_path: &str) -> Option<String> {
+    match json_path(body, next_path)? {
+        JsonValue::String(s) if !s.is_empty() => Some(s.clone()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Build a GraphQL request body from fetch params
+///
+/// `params` must contain a `query` string. Variables come from `variables` if
+/// present, otherwise from the remaining keys; `operationName` is passed through.
+fn graphql_request_body(params: &JsonValue) -> Result<JsonValue, String> {
+    let obj = params
+        .as_object()
+        .ok_or("GraphQL params must be an object with a 'query' string")?;
+    let query = obj
+        .get("query")
+        .and_then(|q| q.as_str())
+        .ok_or("GraphQL params require a 'query' string")?;
+
+    let variables = match obj.get("variables") {
+        Some(variables) => variables.clone(),
+        None => JsonValue::Object(
+            obj.iter()
+                .filter(|(key, _)| *key != "query" && *key != "operationName")
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        ),
+    };
+
+    let mut body = serde_json::json!({ "query": query, "variables": variables });
+    if let Some(operation_name) = obj.get("operationName") {
+        body["operationName"] = operation_name.clone();
+    }
+
+    Ok(body)
+}
+
+/// Split a GraphQL response into its `data` and a message for any `errors`
+fn unwrap_graphql_response(body: JsonValue) -> (Option<JsonValue>, Option<String>) {
+    let errors = body
+        .get("errors")
+        .and_then(|e| e.as_array())
+        .filter(|errors| !errors.is_empty())
+        .map(|errors| {
+            let messages: Vec<String> = errors
+                .iter()
+                .map(
+                    |error| match error.get("message").and_then(|m| m.as_str()) {
+                        Some(message) => message.to_string(),
+                        None => error.to_string(),
+                    },
+                )
+                .collect();
+            format!("GraphQL errors: {}", messages.join("; "))
+        });
+
+    let data = body.get("data").filter(|data| !data.is_null()).cloned();
+
+    (data, errors)
+}
+
+/// Convert response headers to a JSON object of strings
+///
+/// Header names are lowercase. Values that aren't valid visible ASCII are
+/// skipped; repeated headers are joined with ", " as allowed by RFC 9110.
+fn headers_to_json(headers: &HeaderMap) -> JsonValue {
+    let mut map = serde_json::Map::new();
+
+    for (name, value) in headers {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+
+        match map.get_mut(name.as_str()) {
+            Some(JsonValue::String(existing)) => {
+                existing.push_str(", ");
+                existing.push_str(value);
+            }
+            _ => {
+                map.insert(
+                    name.as_str().to_string(),
+                    JsonValue::String(value.to_string()),
+                );
+            }
+        }
+    }
+
+    JsonValue::Object(map)
+}
+
 impl Default for DataSourceClient {
     fn default() -> Self {
         Self::new().expect("Failed to create default DataSourceClient")
@@ -296,6 +710,315 @@ mod tests {
         assert!(HttpMethod::from_str("INVALID").is_err());
     }
 
+    #[test]
+    fn test_headers_to_json() {
+        let mut headers = HeaderMap::new();
+        headers.insert("ETag", HeaderValue::from_static("\"abc\""));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("42"));
+        headers.append("Cache-Control", HeaderValue::from_static("no-cache"));
+        headers.append("Cache-Control", HeaderValue::from_static("private"));
+        headers.insert("X-Binary", HeaderValue::from_bytes(b"caf\xe9").unwrap());
+
+        assert_eq!(
+            headers_to_json(&headers),
+            serde_json::json!({
+                "etag": "\"abc\"",
+                "x-ratelimit-remaining": "42",
+                "cache-control": "no-cache, private"
+            })
+        );
+    }
+
+    #[test]
+    fn test_cache_validators_from_headers() {
+        let headers = serde_json::json!({
+            "etag": "W/\"v1\"",
+            "last-modified": "Wed, 21 Oct 2015 07:28:00 GMT",
+            "content-type": "application/json"
+        });
+
+        let validators = CacheValidators::from_headers(Some(&headers));
+        assert_eq!(validators.etag.as_deref(), Some("W/\"v1\""));
+        assert_eq!(
+            validators.last_modified.as_deref(),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+
+        assert_eq!(
+            CacheValidators::from_headers(None),
+            CacheValidators::default()
+        );
+        assert_eq!(
+            CacheValidators::from_headers(Some(&serde_json::json!({}))),
+            CacheValidators::default()
+        );
+    }
+
+    /// Serve `requests` HTTP requests on a local port, answering each from
+    /// `reply(request_text)`; returns the base URL and the server thread
+    fn serve(
+        requests: usize,
+        reply: fn(&str) -> String,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = std::thread::spawn(move || {
+            let mut seen = Vec::new();
+            for _ in 0..requests {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                stream.write_all(reply(&request).as_bytes()).unwrap();
+                seen.push(request.lines().next().unwrap_or_default().to_string());
+            }
+            seen
+        });
+
+        (base_url, server)
+    }
+
+    fn json_reply(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+
+    fn test_datasource(base_url: String) -> DataSource {
+        DataSource {
+            datasource_id: 1,
+            datasource_name: "local".to_string(),
+            base_url,
+            source_type: SourceType::Rest,
+            auth_type: AuthType::None,
+            default_headers: HashMap::new(),
+            timeout_ms: 5000,
+            retry_enabled: false,
+            max_retries: 0,
+            cache_enabled: true,
+            cache_ttl_seconds: 60,
+            enabled: true,
+            pagination: None,
+            max_response_bytes: None,
+        }
+    }
+
+    fn get(datasource: &DataSource, endpoint: &str) -> DataSourceResponse {
+        DataSourceClient::new()
+            .unwrap()
+            .fetch(
+                datasource,
+                &DataSourceAuth::new(),
+                endpoint,
+                HttpMethod::Get,
+                &serde_json::json!({ "limit": 2 }),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fetch_conditional_not_modified() {
+        let (base_url, server) = serve(1, |request| {
+            if request.to_lowercase().contains("if-none-match: \"v1\"") {
+                "HTTP/1.1 304 Not Modified\r\nConnection: close\r\nETag: \"v1\"\r\n\r\n".to_string()
+            } else {
+                json_reply("{}")
+            }
+        });
+
+        let validators = CacheValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+
+        let response = DataSourceClient::new()
+            .unwrap()
+            .fetch_conditional(
+                &test_datasource(base_url),
+                &DataSourceAuth::new(),
+                "/items",
+                HttpMethod::Get,
+                &serde_json::json!({}),
+                Some(&validators),
+            )
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(response.status, "not_modified");
+        assert_eq!(response.response_status, Some(304));
+        assert!(response.response_body.is_none());
+    }
+
+    #[test]
+    fn test_fetch_follows_next_links() {
+        let (base_url, server) = serve(3, |request| {
+            let body = if request.starts_with("GET /items?limit=2 ") {
+                r#"{"data": [1, 2], "links": {"next": "/items?page=2"}}"#
+            } else if request.starts_with("GET /items?page=2 ") {
+                r#"{"data": [3, 4], "links": {"next": "/items?page=3"}}"#
+            } else {
+                r#"{"data": [5], "links": {"next": null}}"#
+            };
+            json_reply(body)
+        });
+
+        let mut datasource = test_datasource(base_url);
+        datasource.pagination = Some(
+            PaginationConfig::from_json(
+                &serde_json::json!({ "items_path": "data", "next_path": "links.next" }),
+            )
+            .unwrap(),
+        );
+
+        let response = get(&datasource, "/items");
+        let seen = server.join().unwrap();
+
+        assert_eq!(response.status, "success");
+        assert_eq!(
+            response.response_body,
+            Some(serde_json::json!([1, 2, 3, 4, 5]))
+        );
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn test_fetch_follows_cursor_up_to_max_pages() {
+        let (base_url, server) = serve(2, |request| {
+            let body = if request.contains("cursor=c2") {
+                r#"{"items": ["b"], "next_cursor": "c3"}"#
+            } else {
+                r#"{"items": ["a"], "next_cursor": "c2"}"#
+            };
+            json_reply(body)
+        });
+
+        let mut datasource = test_datasource(base_url);
+        datasource.pagination = Some(
+            PaginationConfig::from_json(&serde_json::json!({
+                "items_path": "items",
+                "next_path": "next_cursor",
+                "max_pages": 2,
+                "cursor_param": "cursor"
+            }))
+            .unwrap(),
+        );
+
+        let response = get(&datasource, "/items");
+        let seen = server.join().unwrap();
+
+        assert_eq!(response.response_body, Some(serde_json::json!(["a", "b"])));
+        assert!(seen[1].contains("limit=2"));
+        assert!(seen[1].contains("cursor=c2"));
+    }
+
+    #[test]
+    fn test_fetch_rejects_next_link_to_other_host() {
+        let (base_url, server) = serve(1, |_| {
+            json_reply(r#"{"data": [1], "next": "http://127.0.0.1.evil.example/items?page=2"}"#)
+        });
+
+        let mut datasource = test_datasource(base_url);
+        datasource.pagination = Some(
+            PaginationConfig::from_json(
+                &serde_json::json!({ "items_path": "data", "next_path": "next" }),
+            )
+            .unwrap(),
+        );
+
+        let response = get(&datasource, "/items");
+        server.join().unwrap();
+
+        assert_eq!(response.status, "failed");
+        assert_eq!(response.response_body, Some(serde_json::json!([1])));
+        assert!(response.error_message.unwrap().contains("outside"));
+    }
+
+    #[test]
+    fn test_fetch_rejects_oversized_response() {
+        let (base_url, server) = serve(1, |_| json_reply(r#"{"data": "0123456789"}"#));
+
+        let mut datasource = test_datasource(base_url);
+        datasource.max_response_bytes = Some(8);
+
+        let response = get(&datasource, "/items");
+        server.join().unwrap();
+
+        assert_eq!(response.status, "failed");
+        assert_eq!(response.response_status, Some(200));
+        assert!(response.response_body.is_none());
+        assert!(response.error_message.unwrap().contains("too large"));
+    }
+
+    #[test]
+    fn test_read_limited() {
+        let body = b"0123456789";
+
+        assert_eq!(read_limited(&body[..], None, None).unwrap(), body);
+        assert_eq!(read_limited(&body[..], Some(10), Some(10)).unwrap(), body);
+
+        // Declared length over the limit
+        assert!(read_limited(&body[..], Some(10), Some(4)).is_err());
+
+        // Missing or understated length is still bounded
+        assert!(read_limited(&body[..], None, Some(4)).is_err());
+        assert!(read_limited(&body[..], Some(2), Some(4)).is_err());
+    }
+
+    #[test]
+    fn test_json_path() {
+        let body = serde_json::json!({ "a": { "b": [10, { "c": 1 }] } });
+        assert_eq!(json_path(&body, "a.b.1.c"), Some(&serde_json::json!(1)));
+        assert_eq!(json_path(&body, ""), Some(&body));
+        assert_eq!(json_path(&body, "a.x"), None);
+    }
+
+    #[test]
+    fn test_graphql_request_body() {
+        let body = graphql_request_body(&serde_json::json!({
+            "query": "query($id: ID!) { user(id: $id) { name } }",
+            "id": 7
+        }))
+        .unwrap();
+        assert_eq!(body["variables"], serde_json::json!({ "id": 7 }));
+        assert!(body.get("operationName").is_none());
+
+        let body = graphql_request_body(&serde_json::json!({
+            "query": "query GetUser { me { name } }",
+            "variables": { "x": 1 },
+            "operationName": "GetUser"
+        }))
+        .unwrap();
+        assert_eq!(body["variables"], serde_json::json!({ "x": 1 }));
+        assert_eq!(body["operationName"], "GetUser");
+
+        assert!(graphql_request_body(&serde_json::json!({ "id": 7 })).is_err());
+        assert!(graphql_request_body(&serde_json::json!("{ me }")).is_err());
+    }
+
+    #[test]
+    fn test_unwrap_graphql_response() {
+        let (data, errors) =
+            unwrap_graphql_response(serde_json::json!({ "data": { "me": { "name": "Ana" } } }));
+        assert_eq!(data, Some(serde_json::json!({ "me": { "name": "Ana" } })));
+        assert!(errors.is_none());
+
+        let (data, errors) = unwrap_graphql_response(serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Not authorized" }, { "code": 42 }]
+        }));
+        assert!(data.is_none());
+        assert_eq!(
+            errors.as_deref(),
+            Some("GraphQL errors: Not authorized; {\"code\":42}")
+        );
+    }
+
     #[test]
     fn test_client_creation() {
         let client = DataSourceClient::new();
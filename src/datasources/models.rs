@@ -8,6 +8,7 @@ pub struct DataSource {
     pub datasource_id: i32,
     pub datasource_name: String,
     pub base_url: String,
+    pub source_type: SourceType,
     pub auth_type: AuthType,
     pub default_headers: HashMap<String, String>,
     pub timeout_ms: i32,
@@ -16,6 +17,32 @@ pub struct DataSource {
     pub cache_enabled: bool,
     pub cache_ttl_seconds: i32,
     pub enabled: bool,
+    pub pagination: Option<PaginationConfig>,
+    /// Largest response body accepted, in bytes (`None` = unlimited)
+    pub max_response_bytes: Option<u64>,
+}
+
+/// Protocol spoken by a data source
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    /// Plain JSON over HTTP
+    #[default]
+    Rest,
+    /// POST `{query, variables}`, unwrap `data`, report `errors`
+    GraphQL,
+}
+
+impl std::str::FromStr for SourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "rest" => Ok(SourceType::Rest),
+            "graphql" => Ok(SourceType::GraphQL),
+            _ => Err(format!("Invalid source type: {}", s)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -43,6 +70,53 @@ impl std::str::FromStr for AuthType {
     }
 }
 
+/// How to follow paginated GET responses and merge their items
+///
+/// Stored as `rule_datasources.pagination`, e.g.
+/// `{"items_path": "data", "next_path": "links.next", "max_pages": 5}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaginationConfig {
+    /// Dotted path to the array of items in each page ("" = the page itself)
+    pub items_path: String,
+    /// Dotted path to the next link (URL or path) or cursor value
+    pub next_path: String,
+    /// Upper bound on pages fetched, including the first
+    #[serde(default = "default_max_pages")]
+    pub max_pages: u32,
+    /// Query parameter that carries a cursor value
+    #[serde(default = "default_cursor_param")]
+    pub cursor_param: String,
+}
+
+fn default_max_pages() -> u32 {
+    10
+}
+
+fn default_cursor_param() -> String {
+    "cursor".to_string()
+}
+
+impl PaginationConfig {
+    pub const MAX_PAGES_LIMIT: u32 = 100;
+
+    pub fn from_json(value: &JsonValue) -> Result<Self, String> {
+        let config: Self = serde_json::from_value(value.clone())
+            .map_err(|e| format!("Invalid pagination config: {}", e))?;
+
+        if config.max_pages == 0 || config.max_pages > Self::MAX_PAGES_LIMIT {
+            return Err(format!(
+                "Invalid pagination config: max_pages must be between 1 and {}",
+                Self::MAX_PAGES_LIMIT
+            ));
+        }
+        if config.next_path.is_empty() {
+            return Err("Invalid pagination config: next_path is required".to_string());
+        }
+
+        Ok(config)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataSourceAuth {
     pub credentials: HashMap<String, String>,
@@ -81,6 +155,8 @@ pub struct DataSourceResponse {
     pub cache_hit: bool,
     pub response_status: Option<i32>,
     pub response_body: Option<JsonValue>,
+    /// String-valued response headers, keyed by lowercase header name
+    pub response_headers: Option<JsonValue>,
     pub error_message: Option<String>,
     pub execution_time_ms: Option<f64>,
 }
@@ -93,3 +169,35 @@ pub struct CacheEntry {
     pub response_status: i32,
     pub expires_at: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pagination_config_defaults() {
+        let config =
+            PaginationConfig::from_json(&json!({ "items_path": "data", "next_path": "next" }))
+                .unwrap();
+        assert_eq!(config.max_pages, 10);
+        assert_eq!(config.cursor_param, "cursor");
+    }
+
+    #[test]
+    fn test_pagination_config_invalid() {
+        assert!(PaginationConfig::from_json(&json!({ "items_path": "data" })).is_err());
+        assert!(PaginationConfig::from_json(
+            &json!({ "items_path": "data", "next_path": "", "max_pages": 3 })
+        )
+        .is_err());
+        assert!(PaginationConfig::from_json(
+            &json!({ "items_path": "data", "next_path": "next", "max_pages": 0 })
+        )
+        .is_err());
+        assert!(PaginationConfig::from_json(
+            &json!({ "items_path": "data", "next_path": "next", "max_pages": 1000 })
+        )
+        .is_err());
+    }
+}
@@ -2,30 +2,148 @@
 //!
 //! Controls debug mode behavior (on/off, persistence, etc.)
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use crate::core::rules::RULE_HEADER_REGEX;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::RwLock;
 
 /// Global debug mode flag (default: disabled in production)
 static DEBUG_ENABLED: AtomicBool = AtomicBool::new(false);
 
+/// Rule name debug mode is limited to (None: capture every execution)
+static DEBUG_RULE_SCOPE: RwLock<Option<String>> = RwLock::new(None);
+
 /// Global persistence flag (save to PostgreSQL, default: disabled)
 static DEBUG_PERSISTENCE: AtomicBool = AtomicBool::new(false);
 
+/// Most sessions kept in memory before the least recently used is evicted
+static MAX_SESSIONS: AtomicUsize = AtomicUsize::new(100);
+
+/// Most events kept per session
+static MAX_EVENTS_PER_SESSION: AtomicUsize = AtomicUsize::new(10_000);
+
+/// Whether a full session drops its oldest events (true) or stops recording
+static DROP_OLDEST_EVENTS: AtomicBool = AtomicBool::new(false);
+
+/// What to do when a session reaches `max_events_per_session`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventOverflow {
+    /// Keep the first events and ignore new ones
+    StopRecording,
+    /// Keep the latest events, dropping the oldest
+    DropOldest,
+}
+
+impl EventOverflow {
+    /// Parse "stop" or "drop_oldest"
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "stop" => Ok(EventOverflow::StopRecording),
+            "drop_oldest" => Ok(EventOverflow::DropOldest),
+            other => Err(format!(
+                "Unknown overflow policy '{}' (expected 'stop' or 'drop_oldest')",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventOverflow::StopRecording => "stop",
+            EventOverflow::DropOldest => "drop_oldest",
+        }
+    }
+}
+
+/// Capacity limits of the in-memory event store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreLimits {
+    pub max_sessions: usize,
+    pub max_events_per_session: usize,
+    pub overflow: EventOverflow,
+}
+
+/// Current event store limits
+pub fn store_limits() -> StoreLimits {
+    StoreLimits {
+        max_sessions: MAX_SESSIONS.load(Ordering::Relaxed),
+        max_events_per_session: MAX_EVENTS_PER_SESSION.load(Ordering::Relaxed),
+        overflow: if DROP_OLDEST_EVENTS.load(Ordering::Relaxed) {
+            EventOverflow::DropOldest
+        } else {
+            EventOverflow::StopRecording
+        },
+    }
+}
+
+/// Change the event store limits; both counts must be at least 1
+///
+/// Applies to sessions and events added from now on.
+pub fn set_store_limits(limits: StoreLimits) -> Result<(), String> {
+    if limits.max_sessions == 0 || limits.max_events_per_session == 0 {
+        return Err("max_sessions and max_events_per_session must be at least 1".to_string());
+    }
+
+    MAX_SESSIONS.store(limits.max_sessions, Ordering::Relaxed);
+    MAX_EVENTS_PER_SESSION.store(limits.max_events_per_session, Ordering::Relaxed);
+    DROP_OLDEST_EVENTS.store(
+        limits.overflow == EventOverflow::DropOldest,
+        Ordering::Relaxed,
+    );
+    Ok(())
+}
+
 /// Check if debug mode is enabled
 #[allow(dead_code)]
 pub fn is_debug_enabled() -> bool {
     DEBUG_ENABLED.load(Ordering::Relaxed)
 }
 
-/// Enable debug mode
+/// Enable debug mode for every execution
 #[allow(dead_code)]
 pub fn enable_debug() {
+    *DEBUG_RULE_SCOPE.write().unwrap() = None;
     DEBUG_ENABLED.store(true, Ordering::Relaxed);
 }
 
-/// Disable debug mode
+/// Enable debug mode only for executions whose GRL defines `rule_name`
+#[allow(dead_code)]
+pub fn debug_enable_for_rule(rule_name: &str) {
+    *DEBUG_RULE_SCOPE.write().unwrap() = Some(rule_name.to_string());
+    DEBUG_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Disable debug mode (and any rule scope)
 #[allow(dead_code)]
 pub fn disable_debug() {
     DEBUG_ENABLED.store(false, Ordering::Relaxed);
+    *DEBUG_RULE_SCOPE.write().unwrap() = None;
+}
+
+/// Rule name debug mode is limited to, if any
+#[allow(dead_code)]
+pub fn debug_rule_scope() -> Option<String> {
+    DEBUG_RULE_SCOPE.read().unwrap().clone()
+}
+
+/// Whether executing `rules_grl` should capture debug events
+pub fn should_debug(rules_grl: &str) -> bool {
+    if !is_debug_enabled() {
+        return false;
+    }
+
+    match &*DEBUG_RULE_SCOPE.read().unwrap() {
+        None => true,
+        Some(rule_name) => grl_defines_rule(rules_grl, rule_name),
+    }
+}
+
+/// Whether the GRL has a `rule` header with this name, quoted or bare
+pub(crate) fn grl_defines_rule(grl: &str, rule_name: &str) -> bool {
+    RULE_HEADER_REGEX.captures_iter(grl).any(|caps| {
+        caps.get(1)
+            .or(caps.get(2))
+            .is_some_and(|name| name.as_str() == rule_name)
+    })
 }
 
 /// Check if PostgreSQL persistence is enabled
@@ -51,3 +169,20 @@ pub fn disable_persistence() {
 pub fn get_debug_config() -> (bool, bool) {
     (is_debug_enabled(), is_persistence_enabled())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grl_defines_rule() {
+        let grl = r#"rule "High Value" salience 10 { when Order.total > 1000 then Order.vip = true; }
+rule Discount { when Order.total > 100 then Order.discount = 10; }"#;
+
+        assert!(grl_defines_rule(grl, "High Value"));
+        assert!(grl_defines_rule(grl, "Discount"));
+        assert!(!grl_defines_rule(grl, "High"));
+        assert!(!grl_defines_rule(grl, "Order"));
+        assert!(!grl_defines_rule("", "Discount"));
+    }
+}
@@ -2,9 +2,18 @@
 //!
 //! This module provides an in-memory storage for RETE events.
 //! In Phase 2, this will be extended to persist to PostgreSQL.
+//!
+//! The store is bounded by the limits in [`super::config::StoreLimits`]: past
+//! `max_sessions` the least recently used session is evicted (finished ones
+//! before running ones), and a session past `max_events_per_session` either
+//! stops recording or drops its oldest events, and is marked `truncated`.
 
+use super::config::{store_limits, EventOverflow, StoreLimits};
 use super::events::{current_timestamp, ReteEvent};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 /// A complete execution session with all events
@@ -26,15 +35,35 @@ pub struct ExecutionSession {
     pub initial_facts: serde_json::Value,
 
     /// All events in chronological order
-    pub events: Vec<ReteEvent>,
+    pub events: VecDeque<ReteEvent>,
 
     /// Current step number
     pub current_step: u64,
 
     /// Session status
     pub status: SessionStatus,
+
+    /// Whether events were dropped or not recorded because the session
+    /// reached `max_events_per_session`
+    #[serde(default)]
+    pub truncated: bool,
+
+    /// Whether the current state has been saved with `save_session_to_db`
+    /// (local to this backend, so never exported)
+    #[serde(default, skip_serializing)]
+    pub persisted: bool,
+
+    /// Store access counter at last use, for LRU eviction
+    #[serde(default, skip_serializing)]
+    pub last_used: u64,
 }
 
+/// `format` tag of an exported session bundle
+pub const SESSION_BUNDLE_FORMAT: &str = "rule_engine_debug_session";
+
+/// Current session bundle version
+pub const SESSION_BUNDLE_VERSION: u64 = 1;
+
 /// Status of an execution session
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SessionStatus {
@@ -52,15 +81,19 @@ impl ExecutionSession {
             completed_at: None,
             rules_grl,
             initial_facts,
-            events: Vec::new(),
+            events: VecDeque::new(),
             current_step: 0,
             status: SessionStatus::Running,
+            truncated: false,
+            persisted: false,
+            last_used: 0,
         }
     }
 
     /// Add an event to this session
     pub fn add_event(&mut self, event: ReteEvent) {
-        self.events.push(event);
+        self.events.push_back(event);
+        self.persisted = false;
     }
 
     /// Get the current step number and increment it
@@ -73,16 +106,17 @@ impl ExecutionSession {
     pub fn complete(&mut self) {
         self.completed_at = Some(current_timestamp());
         self.status = SessionStatus::Completed;
+        self.persisted = false;
     }
 
     /// Mark session as error
     pub fn error(&mut self) {
         self.completed_at = Some(current_timestamp());
         self.status = SessionStatus::Error;
+        self.persisted = false;
     }
 
     /// Get all events of a specific type
-    #[allow(dead_code)]
     pub fn events_of_type(&self, event_type: &str) -> Vec<&ReteEvent> {
         self.events
             .iter()
@@ -91,7 +125,6 @@ impl ExecutionSession {
     }
 
     /// Get events in a specific step range
-    #[allow(dead_code)]
     pub fn events_in_range(&self, from_step: u64, to_step: u64) -> Vec<&ReteEvent> {
         self.events
             .iter()
@@ -102,11 +135,178 @@ impl ExecutionSession {
             .collect()
     }
 
+    /// Events matching an optional type and an optional inclusive step window
+    ///
+    /// With no filters this is every event, in order.
+    pub fn filter_events(
+        &self,
+        event_type: Option<&str>,
+        from_step: Option<u64>,
+        to_step: Option<u64>,
+    ) -> Vec<&ReteEvent> {
+        if from_step.is_none() && to_step.is_none() {
+            return match event_type {
+                Some(event_type) => self.events_of_type(event_type),
+                None => self.events.iter().collect(),
+            };
+        }
+
+        let in_range = self.events_in_range(from_step.unwrap_or(0), to_step.unwrap_or(u64::MAX));
+
+        match event_type {
+            Some(event_type) => in_range
+                .into_iter()
+                .filter(|e| e.event_type() == event_type)
+                .collect(),
+            None => in_range,
+        }
+    }
+
+    /// Explain why `rule_name` did or didn't fire
+    ///
+    /// Firings come from `RuleFired` events. When the session also has
+    /// `RuleEvaluated` events for the rule, the failed conditions of every
+    /// non-matching evaluation are listed; an evaluation that failed without
+    /// per-condition detail is reported with its overall reason and a null
+    /// condition. The debug executor records firings only, so its sessions
+    /// say whether the rule fired but not which condition stopped it.
+    pub fn why_not_fired(&self, rule_name: &str) -> serde_json::Value {
+        let fire_count = self
+            .events
+            .iter()
+            .filter(
+                |e| matches!(e, ReteEvent::RuleFired { rule_name: name, .. } if name == rule_name),
+            )
+            .count();
+        let fired = fire_count > 0;
+
+        let mut evaluations = 0;
+        let mut failed_conditions = Vec::new();
+
+        for event in &self.events {
+            let ReteEvent::RuleEvaluated {
+                step,
+                rule_name: name,
+                matched,
+                reason,
+                condition_results,
+                ..
+            } = event
+            else {
+                continue;
+            };
+            if name != rule_name {
+                continue;
+            }
+
+            evaluations += 1;
+            if *matched {
+                continue;
+            }
+
+            let failed: Vec<_> = condition_results.iter().filter(|c| !c.matched).collect();
+            if failed.is_empty() {
+                failed_conditions.push(json!({
+                    "step": step,
+                    "condition_index": null,
+                    "condition": null,
+                    "reason": reason,
+                }));
+            }
+            for condition in failed {
+                failed_conditions.push(json!({
+                    "step": step,
+                    "condition_index": condition.index,
+                    "condition": condition.condition_text,
+                    "reason": condition.reason,
+                }));
+            }
+        }
+
+        if evaluations == 0 {
+            let defined = super::config::grl_defines_rule(&self.rules_grl, rule_name);
+            let message = if fired {
+                format!(
+                    "Rule '{}' fired {} time(s) in this session",
+                    rule_name, fire_count
+                )
+            } else if !defined {
+                format!(
+                    "Rule '{}' is not defined in this session's rules",
+                    rule_name
+                )
+            } else {
+                format!(
+                    "Rule '{}' did not fire in this session; no per-condition results were recorded",
+                    rule_name
+                )
+            };
+
+            return json!({
+                "rule_name": rule_name,
+                "defined": defined,
+                "fired": fired,
+                "fire_count": fire_count,
+                "condition_details": false,
+                "message": message,
+            });
+        }
+
+        json!({
+            "rule_name": rule_name,
+            "defined": true,
+            "fired": fired,
+            "fire_count": fire_count,
+            "condition_details": true,
+            "evaluations": evaluations,
+            "failed_conditions": failed_conditions,
+        })
+    }
+
     /// Get the total number of events
     pub fn event_count(&self) -> usize {
         self.events.len()
     }
 
+    /// Export as a self-contained JSON bundle for `debug_import_session`
+    pub fn to_bundle(&self) -> Result<serde_json::Value, String> {
+        let session = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize session: {}", e))?;
+
+        Ok(serde_json::json!({
+            "format": SESSION_BUNDLE_FORMAT,
+            "version": SESSION_BUNDLE_VERSION,
+            "session": session,
+        }))
+    }
+
+    /// Read a session from a bundle produced by [`Self::to_bundle`]
+    pub fn from_bundle(bundle: &serde_json::Value) -> Result<Self, String> {
+        if bundle.get("format").and_then(|f| f.as_str()) != Some(SESSION_BUNDLE_FORMAT) {
+            return Err(format!(
+                "Not a debug session bundle (expected format '{}')",
+                SESSION_BUNDLE_FORMAT
+            ));
+        }
+
+        match bundle.get("version").and_then(|v| v.as_u64()) {
+            Some(SESSION_BUNDLE_VERSION) => {}
+            other => {
+                return Err(format!(
+                    "Unsupported debug session bundle version {:?} (expected {})",
+                    other, SESSION_BUNDLE_VERSION
+                ))
+            }
+        }
+
+        let session = bundle
+            .get("session")
+            .ok_or("Debug session bundle has no 'session'")?;
+
+        serde_json::from_value(session.clone())
+            .map_err(|e| format!("Invalid session in bundle: {}", e))
+    }
+
     /// Get session duration in milliseconds
     pub fn duration_ms(&self) -> i64 {
         match self.completed_at {
@@ -117,43 +317,107 @@ impl ExecutionSession {
 }
 
 /// In-memory event store
-/// Thread-safe storage for multiple execution sessions
+/// Thread-safe storage for multiple execution sessions, keyed by session ID
 #[derive(Debug, Clone)]
 pub struct EventStore {
-    sessions: Arc<RwLock<Vec<ExecutionSession>>>,
+    sessions: Arc<RwLock<HashMap<String, ExecutionSession>>>,
+    /// Incremented on every session access; orders sessions for eviction
+    clock: Arc<AtomicU64>,
+    /// Fixed limits; None follows the debug config
+    limits: Option<StoreLimits>,
 }
 
 impl EventStore {
-    /// Create a new event store
+    /// Create a new event store bounded by the debug config limits
     pub fn new() -> Self {
         Self {
-            sessions: Arc::new(RwLock::new(Vec::new())),
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+            clock: Arc::new(AtomicU64::new(0)),
+            limits: None,
+        }
+    }
+
+    /// Create an event store with fixed limits
+    #[allow(dead_code)]
+    pub fn with_limits(limits: StoreLimits) -> Self {
+        Self {
+            limits: Some(limits),
+            ..Self::new()
+        }
+    }
+
+    fn limits(&self) -> StoreLimits {
+        self.limits.unwrap_or_else(store_limits)
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Evict sessions until at most `max_sessions` remain, never `keep`
+    ///
+    /// Completed and failed sessions go first, least recently used first.
+    fn evict(&self, sessions: &mut HashMap<String, ExecutionSession>, keep: &str) {
+        let max_sessions = self.limits().max_sessions.max(1);
+
+        while sessions.len() > max_sessions {
+            let victim = sessions
+                .values()
+                .filter(|s| s.session_id != keep)
+                .min_by_key(|s| (s.status == SessionStatus::Running, s.last_used))
+                .map(|s| s.session_id.clone());
+
+            let Some(id) = victim else { break };
+            sessions.remove(&id);
         }
     }
 
     /// Create a new session and return its ID
+    ///
+    /// An existing session with the same ID is replaced.
     pub fn create_session(
         &self,
         session_id: String,
         rules_grl: String,
         initial_facts: serde_json::Value,
     ) -> String {
-        let session = ExecutionSession::new(session_id.clone(), rules_grl, initial_facts);
+        let mut session = ExecutionSession::new(session_id.clone(), rules_grl, initial_facts);
+        session.last_used = self.tick();
 
         let mut sessions = self.sessions.write().unwrap();
-        sessions.push(session);
+        sessions.insert(session_id.clone(), session);
+        self.evict(&mut sessions, &session_id);
 
         session_id
     }
 
     /// Add an event to a session
+    ///
+    /// A session at `max_events_per_session` is marked truncated and, per the
+    /// overflow policy, ignores the event or drops its oldest one.
     pub fn add_event(&self, session_id: &str, event: ReteEvent) -> Result<(), String> {
+        let limits = self.limits();
         let mut sessions = self.sessions.write().unwrap();
 
         let session = sessions
-            .iter_mut()
-            .find(|s| s.session_id == session_id)
+            .get_mut(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.last_used = self.tick();
+
+        if session.events.len() >= limits.max_events_per_session {
+            session.truncated = true;
+            match limits.overflow {
+                EventOverflow::StopRecording => {
+                    session.persisted = false;
+                    return Ok(());
+                }
+                EventOverflow::DropOldest => {
+                    while session.events.len() >= limits.max_events_per_session {
+                        session.events.pop_front();
+                    }
+                }
+            }
+        }
 
         session.add_event(event);
         Ok(())
@@ -164,9 +428,9 @@ impl EventStore {
         let mut sessions = self.sessions.write().unwrap();
 
         let session = sessions
-            .iter_mut()
-            .find(|s| s.session_id == session_id)
+            .get_mut(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.last_used = self.tick();
 
         Ok(session.next_step())
     }
@@ -176,8 +440,7 @@ impl EventStore {
         let mut sessions = self.sessions.write().unwrap();
 
         let session = sessions
-            .iter_mut()
-            .find(|s| s.session_id == session_id)
+            .get_mut(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
         session.complete();
@@ -189,41 +452,91 @@ impl EventStore {
         let mut sessions = self.sessions.write().unwrap();
 
         let session = sessions
-            .iter_mut()
-            .find(|s| s.session_id == session_id)
+            .get_mut(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
         session.error();
         Ok(())
     }
 
-    /// Get a session by ID
-    pub fn get_session(&self, session_id: &str) -> Result<ExecutionSession, String> {
+    /// Record that a session's current state has been saved to PostgreSQL
+    pub fn mark_persisted(&self, session_id: &str) -> Result<(), String> {
+        let mut sessions = self.sessions.write().unwrap();
+
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+        session.persisted = true;
+        Ok(())
+    }
+
+    /// Running or completed sessions whose current state hasn't been saved
+    pub fn unpersisted_sessions(&self) -> Vec<ExecutionSession> {
         let sessions = self.sessions.read().unwrap();
 
         sessions
-            .iter()
-            .find(|s| s.session_id == session_id)
+            .values()
+            .filter(|s| !s.persisted && s.status != SessionStatus::Error)
             .cloned()
-            .ok_or_else(|| format!("Session not found: {}", session_id))
+            .collect()
     }
 
-    /// Get all sessions
+    /// Add an existing session, e.g. one imported from a bundle
+    ///
+    /// Fails if a session with the same ID already exists.
+    pub fn import_session(&self, mut session: ExecutionSession) -> Result<String, String> {
+        let mut sessions = self.sessions.write().unwrap();
+
+        if sessions.contains_key(&session.session_id) {
+            return Err(format!(
+                "Session already exists: {} (delete it first to re-import)",
+                session.session_id
+            ));
+        }
+
+        session.persisted = false;
+        session.last_used = self.tick();
+        let session_id = session.session_id.clone();
+        sessions.insert(session_id.clone(), session);
+        self.evict(&mut sessions, &session_id);
+
+        Ok(session_id)
+    }
+
+    /// Get a session by ID
+    pub fn get_session(&self, session_id: &str) -> Result<ExecutionSession, String> {
+        let mut sessions = self.sessions.write().unwrap();
+
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session not found: {}", session_id))?;
+        session.last_used = self.tick();
+
+        Ok(session.clone())
+    }
+
+    /// Get all sessions, oldest first
     pub fn get_all_sessions(&self) -> Vec<ExecutionSession> {
         let sessions = self.sessions.read().unwrap();
-        sessions.clone()
+
+        let mut all: Vec<ExecutionSession> = sessions.values().cloned().collect();
+        all.sort_by(|a, b| {
+            a.started_at
+                .cmp(&b.started_at)
+                .then_with(|| a.session_id.cmp(&b.session_id))
+        });
+        all
     }
 
     /// Delete a session
     pub fn delete_session(&self, session_id: &str) -> Result<(), String> {
         let mut sessions = self.sessions.write().unwrap();
 
-        let index = sessions
-            .iter()
-            .position(|s| s.session_id == session_id)
+        sessions
+            .remove(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
-        sessions.remove(index);
         Ok(())
     }
 
@@ -256,7 +569,7 @@ lazy_static::lazy_static! {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
+    use crate::debug::events::ConditionResult;
 
     #[test]
     fn test_execution_session() {
@@ -319,6 +632,201 @@ mod tests {
         assert_eq!(store.session_count(), 0);
     }
 
+    #[test]
+    fn test_sessions_keyed_by_id() {
+        let store = EventStore::new();
+        for id in ["a", "b", "c"] {
+            store.create_session(id.to_string(), "rule test {}".to_string(), json!({}));
+        }
+        assert_eq!(store.next_step("b").unwrap(), 1);
+        assert_eq!(store.next_step("b").unwrap(), 2);
+        assert_eq!(store.next_step("c").unwrap(), 1);
+        assert!(store.next_step("missing").is_err());
+
+        // Re-creating an ID starts that session over
+        store.create_session("b".to_string(), "rule other {}".to_string(), json!({}));
+        assert_eq!(store.session_count(), 3);
+        assert_eq!(store.get_session("b").unwrap().rules_grl, "rule other {}");
+        assert_eq!(store.next_step("b").unwrap(), 1);
+
+        store.delete_session("a").unwrap();
+        assert!(store.delete_session("a").is_err());
+        let ids: Vec<_> = store
+            .get_all_sessions()
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&"b".to_string()) && ids.contains(&"c".to_string()));
+    }
+
+    fn limits(max_sessions: usize, max_events: usize, overflow: EventOverflow) -> StoreLimits {
+        StoreLimits {
+            max_sessions,
+            max_events_per_session: max_events,
+            overflow,
+        }
+    }
+
+    fn fact_event(step: u64) -> ReteEvent {
+        ReteEvent::FactInserted {
+            step,
+            timestamp: current_timestamp(),
+            handle: step,
+            fact_type: "Order".to_string(),
+            data: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_eviction_prefers_finished_then_least_recently_used() {
+        let store = EventStore::with_limits(limits(3, 100, EventOverflow::StopRecording));
+        for id in ["a", "b", "c"] {
+            store.create_session(id.to_string(), "rule test {}".to_string(), json!({}));
+        }
+        store.complete_session("b").unwrap();
+        store.complete_session("c").unwrap();
+        // Using c makes b the least recently used finished session
+        store.get_session("c").unwrap();
+
+        store.create_session("d".to_string(), "rule test {}".to_string(), json!({}));
+        assert_eq!(store.session_count(), 3);
+        assert!(store.get_session("b").is_err());
+
+        // Running a survives while a finished session remains
+        store.create_session("e".to_string(), "rule test {}".to_string(), json!({}));
+        assert!(store.get_session("c").is_err());
+        assert!(store.get_session("a").is_ok());
+
+        // With only running sessions left, the least recently used goes
+        store.next_step("d").unwrap();
+        store.create_session("f".to_string(), "rule test {}".to_string(), json!({}));
+        assert!(store.get_session("e").is_err());
+        let mut ids: Vec<_> = store
+            .get_all_sessions()
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["a", "d", "f"]);
+    }
+
+    #[test]
+    fn test_event_limit_stop_recording() {
+        let store = EventStore::with_limits(limits(10, 2, EventOverflow::StopRecording));
+        store.create_session("s".to_string(), "rule test {}".to_string(), json!({}));
+        for step in 1..=4 {
+            store.add_event("s", fact_event(step)).unwrap();
+        }
+
+        let session = store.get_session("s").unwrap();
+        assert!(session.truncated);
+        let steps: Vec<u64> = session.events.iter().map(|e| e.step()).collect();
+        assert_eq!(steps, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_event_limit_drop_oldest() {
+        let store = EventStore::with_limits(limits(10, 2, EventOverflow::DropOldest));
+        store.create_session("s".to_string(), "rule test {}".to_string(), json!({}));
+        store.add_event("s", fact_event(1)).unwrap();
+        store.add_event("s", fact_event(2)).unwrap();
+        assert!(!store.get_session("s").unwrap().truncated);
+
+        store.add_event("s", fact_event(3)).unwrap();
+        store.add_event("s", fact_event(4)).unwrap();
+
+        let session = store.get_session("s").unwrap();
+        assert!(session.truncated);
+        let steps: Vec<u64> = session.events.iter().map(|e| e.step()).collect();
+        assert_eq!(steps, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_unpersisted_sessions() {
+        let store = EventStore::new();
+        for id in ["running", "completed", "saved", "failed"] {
+            store.create_session(id.to_string(), "rule test {}".to_string(), json!({}));
+        }
+        store.complete_session("completed").unwrap();
+        store.complete_session("saved").unwrap();
+        store.mark_persisted("saved").unwrap();
+        store.error_session("failed").unwrap();
+
+        let mut ids: Vec<_> = store
+            .unpersisted_sessions()
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["completed", "running"]);
+
+        // New events make a saved session dirty again
+        store.mark_persisted("running").unwrap();
+        let event = ReteEvent::ExecutionStarted {
+            timestamp: current_timestamp(),
+            session_id: "running".to_string(),
+            rules_count: 1,
+            initial_facts_count: 0,
+            rules_grl: "rule test {}".to_string(),
+            initial_facts: json!({}),
+        };
+        store.add_event("running", event).unwrap();
+        assert_eq!(store.unpersisted_sessions().len(), 2);
+    }
+
+    #[test]
+    fn test_session_bundle_roundtrip() {
+        let mut session = ExecutionSession::new(
+            "test_004".to_string(),
+            "rule test {}".to_string(),
+            json!({"Order": {"total": 100}}),
+        );
+        session.add_event(ReteEvent::FactInserted {
+            step: 1,
+            timestamp: current_timestamp(),
+            handle: 1,
+            fact_type: "Order".to_string(),
+            data: json!({"total": 100}),
+        });
+        session.next_step();
+        session.complete();
+        session.persisted = true;
+
+        let bundle = session.to_bundle().unwrap();
+        assert_eq!(bundle["format"], SESSION_BUNDLE_FORMAT);
+        assert!(bundle["session"].get("persisted").is_none());
+
+        let imported = ExecutionSession::from_bundle(&bundle).unwrap();
+        assert_eq!(imported.session_id, "test_004");
+        assert_eq!(imported.status, SessionStatus::Completed);
+        assert_eq!(imported.current_step, 1);
+        assert_eq!(imported.events_of_type("FactInserted").len(), 1);
+        assert_eq!(imported.initial_facts, session.initial_facts);
+
+        let store = EventStore::new();
+        assert_eq!(store.import_session(imported.clone()).unwrap(), "test_004");
+        assert!(store.import_session(imported).is_err());
+        assert_eq!(store.unpersisted_sessions().len(), 1);
+    }
+
+    #[test]
+    fn test_session_bundle_invalid() {
+        let session = ExecutionSession::new("s".to_string(), "rule test {}".to_string(), json!({}));
+        let mut bundle = session.to_bundle().unwrap();
+
+        bundle["version"] = json!(99);
+        assert!(ExecutionSession::from_bundle(&bundle).is_err());
+
+        assert!(ExecutionSession::from_bundle(&json!({"session_id": "s"})).is_err());
+        assert!(ExecutionSession::from_bundle(&json!({
+            "format": SESSION_BUNDLE_FORMAT,
+            "version": SESSION_BUNDLE_VERSION,
+            "session": {"session_id": "s"}
+        }))
+        .is_err());
+    }
+
     #[test]
     fn test_event_filtering() {
         let mut session = ExecutionSession::new(
@@ -352,6 +860,7 @@ mod tests {
             activation_id: 1,
             matched_facts: vec![1],
             actions_executed: vec![],
+            facts_after: None,
         });
 
         // Filter by type
@@ -364,5 +873,111 @@ mod tests {
         // Filter by step range
         let events_in_range = session.events_in_range(1, 2);
         assert_eq!(events_in_range.len(), 2);
+
+        // Combined filters; no filter keeps every event
+        assert_eq!(session.filter_events(None, None, None).len(), 3);
+        assert_eq!(
+            session.filter_events(Some("RuleFired"), None, None).len(),
+            1
+        );
+        assert_eq!(session.filter_events(None, Some(1), None).len(), 2);
+        assert_eq!(session.filter_events(None, None, Some(1)).len(), 2);
+        assert_eq!(
+            session
+                .filter_events(Some("RuleFired"), Some(0), Some(1))
+                .len(),
+            0
+        );
+        assert_eq!(
+            session.filter_events(Some("FactInserted"), Some(1), Some(2))[0].step(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_why_not_fired() {
+        let mut session = ExecutionSession::new(
+            "test_why".to_string(),
+            "rule Discount { when Order.total > 1000 then Order.discount = 10; }".to_string(),
+            json!({}),
+        );
+
+        // Without evaluation events the report relies on RuleFired alone
+        let report = session.why_not_fired("Discount");
+        assert_eq!(report["defined"], true);
+        assert_eq!(report["fired"], false);
+        assert_eq!(report["condition_details"], false);
+        assert!(report["message"].as_str().unwrap().contains("did not fire"));
+        assert_eq!(session.why_not_fired("Missing")["defined"], false);
+
+        session.add_event(ReteEvent::RuleFired {
+            step: 1,
+            timestamp: current_timestamp(),
+            rule_name: "Discount".to_string(),
+            activation_id: 1,
+            matched_facts: vec![],
+            actions_executed: vec![],
+            facts_after: None,
+        });
+        let report = session.why_not_fired("Discount");
+        assert_eq!(report["fired"], true);
+        assert_eq!(report["fire_count"], 1);
+        assert!(report["message"].as_str().unwrap().contains("fired 1 time"));
+
+        let mut session = ExecutionSession::new(
+            "test_why_detail".to_string(),
+            "rule Discount {} rule Other {}".to_string(),
+            json!({}),
+        );
+
+        session.add_event(ReteEvent::RuleEvaluated {
+            step: 1,
+            timestamp: current_timestamp(),
+            rule_name: "Discount".to_string(),
+            rule_index: 0,
+            matched: false,
+            reason: "1 of 2 conditions failed".to_string(),
+            matched_facts: vec![],
+            condition_results: vec![
+                ConditionResult {
+                    index: 0,
+                    condition_text: "Order.status == \"paid\"".to_string(),
+                    matched: true,
+                    reason: "matched".to_string(),
+                    involved_facts: vec![1],
+                },
+                ConditionResult {
+                    index: 1,
+                    condition_text: "Order.total > 1000".to_string(),
+                    matched: false,
+                    reason: "Order.total = 500 <= 1000".to_string(),
+                    involved_facts: vec![1],
+                },
+            ],
+        });
+        session.add_event(ReteEvent::RuleEvaluated {
+            step: 2,
+            timestamp: current_timestamp(),
+            rule_name: "Other".to_string(),
+            rule_index: 1,
+            matched: false,
+            reason: "no facts".to_string(),
+            matched_facts: vec![],
+            condition_results: vec![],
+        });
+
+        let report = session.why_not_fired("Discount");
+        assert_eq!(report["condition_details"], true);
+        assert_eq!(report["evaluations"], 1);
+        assert_eq!(report["fired"], false);
+        let failed = report["failed_conditions"].as_array().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0]["condition"], "Order.total > 1000");
+        assert_eq!(failed[0]["reason"], "Order.total = 500 <= 1000");
+
+        // Without per-condition detail the overall reason is reported
+        let report = session.why_not_fired("Other");
+        assert_eq!(report["failed_conditions"][0]["condition"], json!(null));
+        assert_eq!(report["failed_conditions"][0]["reason"], "no facts");
     }
 }
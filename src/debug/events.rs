@@ -45,6 +45,18 @@ pub enum ReteEvent {
         data: serde_json::Value, // Store for reconstruction
     },
 
+    // === Preprocessing Events ===
+    /// A built-in function call was evaluated during GRL preprocessing
+    FunctionEvaluated {
+        step: u64,
+        timestamp: i64,
+        function_name: String,
+        args: Vec<serde_json::Value>,
+        result: serde_json::Value,
+        in_when_clause: bool,
+        computed_field: Option<String>, // Field injected into facts for 'when' calls
+    },
+
     // === Rule Evaluation Events ===
     /// A rule was evaluated against current facts
     RuleEvaluated {
@@ -76,6 +88,9 @@ pub enum ReteEvent {
         activation_id: ActivationId,
         matched_facts: Vec<FactHandle>,
         actions_executed: Vec<String>,
+        /// All facts after the rule's actions ran
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        facts_after: Option<serde_json::Value>,
     },
 
     /// A rule activation was removed from agenda
@@ -176,6 +191,7 @@ impl ReteEvent {
             ReteEvent::FactInserted { step, .. }
             | ReteEvent::FactModified { step, .. }
             | ReteEvent::FactRetracted { step, .. }
+            | ReteEvent::FunctionEvaluated { step, .. }
             | ReteEvent::RuleEvaluated { step, .. }
             | ReteEvent::RuleActivated { step, .. }
             | ReteEvent::RuleFired { step, .. }
@@ -196,6 +212,7 @@ impl ReteEvent {
             ReteEvent::FactInserted { timestamp, .. }
             | ReteEvent::FactModified { timestamp, .. }
             | ReteEvent::FactRetracted { timestamp, .. }
+            | ReteEvent::FunctionEvaluated { timestamp, .. }
             | ReteEvent::RuleEvaluated { timestamp, .. }
             | ReteEvent::RuleActivated { timestamp, .. }
             | ReteEvent::RuleFired { timestamp, .. }
@@ -215,6 +232,7 @@ impl ReteEvent {
             ReteEvent::FactInserted { .. } => "FactInserted",
             ReteEvent::FactModified { .. } => "FactModified",
             ReteEvent::FactRetracted { .. } => "FactRetracted",
+            ReteEvent::FunctionEvaluated { .. } => "FunctionEvaluated",
             ReteEvent::RuleEvaluated { .. } => "RuleEvaluated",
             ReteEvent::RuleActivated { .. } => "RuleActivated",
             ReteEvent::RuleFired { .. } => "RuleFired",
@@ -240,6 +258,23 @@ impl ReteEvent {
             ReteEvent::FactRetracted { fact_type, .. } => {
                 format!("Retracted {} fact", fact_type)
             }
+            ReteEvent::FunctionEvaluated {
+                function_name,
+                args,
+                result,
+                computed_field,
+                ..
+            } => {
+                let args = args
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                match computed_field {
+                    Some(field) => format!("{}({}) = {} -> {}", function_name, args, result, field),
+                    None => format!("{}({}) = {}", function_name, args, result),
+                }
+            }
             ReteEvent::RuleEvaluated {
                 rule_name, matched, ..
             } => {
@@ -346,6 +381,7 @@ mod tests {
             activation_id: 1,
             matched_facts: vec![1, 2],
             actions_executed: vec!["Order.approved = true".to_string()],
+            facts_after: None,
         };
 
         assert_eq!(event.description(), "Rule 'HighValue' fired");
@@ -353,6 +389,31 @@ mod tests {
         assert_eq!(event.step(), 5);
     }
 
+    #[test]
+    fn test_function_evaluated_event() {
+        let event = ReteEvent::FunctionEvaluated {
+            step: 2,
+            timestamp: current_timestamp(),
+            function_name: "DaysSince".to_string(),
+            args: vec![json!("2024-01-01")],
+            result: json!(724),
+            in_when_clause: true,
+            computed_field: Some("Order.__func_0_dayssince".to_string()),
+        };
+
+        assert_eq!(event.event_type(), "FunctionEvaluated");
+        assert_eq!(event.step(), 2);
+        assert_eq!(
+            event.description(),
+            "DaysSince(\"2024-01-01\") = 724 -> Order.__func_0_dayssince"
+        );
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "FunctionEvaluated");
+        let deserialized: ReteEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.event_type(), "FunctionEvaluated");
+    }
+
     #[test]
     fn test_condition_result() {
         let result = ConditionResult {
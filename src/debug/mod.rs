@@ -10,7 +10,7 @@
 pub mod config;
 pub mod event_store;
 pub mod events;
-pub mod pg_store_simple;
+pub mod pg_store;
 
 // Re-export commonly used types
 pub use event_store::GLOBAL_EVENT_STORE;
@@ -19,12 +19,13 @@ pub use events::{current_timestamp, ReteEvent};
 // Export config functions (used by pgrx externally)
 #[allow(unused_imports)]
 pub use config::{
-    disable_debug, disable_persistence, enable_debug, enable_persistence, get_debug_config,
-    is_debug_enabled, is_persistence_enabled,
+    debug_enable_for_rule, debug_rule_scope, disable_debug, disable_persistence, enable_debug,
+    enable_persistence, get_debug_config, is_debug_enabled, is_persistence_enabled,
+    set_store_limits, should_debug, store_limits, EventOverflow, StoreLimits,
 };
 
 // Export PostgreSQL store functions (used by pgrx externally)
 #[allow(unused_imports)]
-pub use pg_store_simple::{
+pub use pg_store::{
     delete_session_from_db, load_session_from_db, save_event_to_db, save_session_to_db,
 };
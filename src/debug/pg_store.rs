@@ -2,77 +2,133 @@
 //!
 //! This module provides persistent storage for execution events in PostgreSQL.
 //! Events are stored in the rule_execution_events table for long-term analysis.
+//! Saving is a no-op unless persistence is enabled (`debug_enable_persistence()`).
 
+use super::config::is_persistence_enabled;
 use super::event_store::{ExecutionSession, SessionStatus};
 use super::events::ReteEvent;
 use pgrx::prelude::*;
 
+// Same definitions as the versioned extension scripts, so the tables also
+// exist in the schema pgrx generates (e.g. for `cargo pgrx test`)
+extension_sql!(
+    r#"
+CREATE TABLE IF NOT EXISTS rule_execution_events (
+    id BIGSERIAL PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    step BIGINT NOT NULL,
+    event_timestamp BIGINT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_data JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS rule_execution_sessions (
+    session_id TEXT PRIMARY KEY,
+    started_at BIGINT NOT NULL,
+    completed_at BIGINT,
+    rules_grl TEXT NOT NULL,
+    initial_facts JSONB NOT NULL,
+    final_facts JSONB,
+    total_steps BIGINT DEFAULT 0,
+    total_events BIGINT DEFAULT 0,
+    status TEXT DEFAULT 'running',
+    duration_ms BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- load_events_from_db: WHERE session_id = $1 ORDER BY step
+CREATE INDEX IF NOT EXISTS idx_events_session_step ON rule_execution_events(session_id, step);
+-- list_sessions_from_db / debug_cleanup_old_sessions: ORDER BY / filter on started_at
+CREATE INDEX IF NOT EXISTS idx_sessions_started ON rule_execution_sessions(started_at);
+"#,
+    name = "debug_persistence_tables",
+);
+
 /// Save an event to PostgreSQL
 pub fn save_event_to_db(session_id: &str, event: &ReteEvent) -> Result<(), String> {
-    let event_json = serde_json::to_value(event)
-        .map_err(|e| format!("Failed to serialize event: {}", e))?;
-
-    Spi::run(&format!(
-        "INSERT INTO rule_execution_events (session_id, step, event_timestamp, event_type, event_data)
-         VALUES ($1, $2, $3, $4, $5)",
-    ))
-    .map_err(|e| format!("Failed to insert event: {:?}", e))?
-    .args(&[
-        session_id,
-        &(event.step() as i64),
-        &event.timestamp(),
-        event.event_type(),
-        &pgrx::JsonB(event_json),
-    ])
-    .execute()
-    .map_err(|e| format!("Failed to execute insert: {:?}", e))?;
+    if !is_persistence_enabled() {
+        return Ok(());
+    }
+
+    let event_json =
+        serde_json::to_value(event).map_err(|e| format!("Failed to serialize event: {}", e))?;
+
+    Spi::connect_mut(|client| {
+        client.update(
+            "INSERT INTO rule_execution_events (session_id, step, event_timestamp, event_type, event_data)
+             VALUES ($1, $2, $3, $4, $5)",
+            None,
+            &[
+                session_id.into(),
+                (event.step() as i64).into(),
+                event.timestamp().into(),
+                event.event_type().into(),
+                pgrx::JsonB(event_json).into(),
+            ],
+        )
+        .map(|_| ())
+    })
+    .map_err(|e| format!("Failed to insert event: {:?}", e))?;
 
     Ok(())
 }
 
 /// Save session metadata to PostgreSQL
 pub fn save_session_to_db(session: &ExecutionSession) -> Result<(), String> {
+    if !is_persistence_enabled() {
+        return Ok(());
+    }
+
     let status_str = match session.status {
         SessionStatus::Running => "running",
         SessionStatus::Completed => "completed",
         SessionStatus::Error => "error",
     };
 
-    Spi::run(&format!(
-        "INSERT INTO rule_execution_sessions
-         (session_id, started_at, completed_at, rules_grl, initial_facts, total_steps, total_events, status, duration_ms)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-         ON CONFLICT (session_id) DO UPDATE SET
-            completed_at = EXCLUDED.completed_at,
-            total_steps = EXCLUDED.total_steps,
-            total_events = EXCLUDED.total_events,
-            status = EXCLUDED.status,
-            duration_ms = EXCLUDED.duration_ms",
-    ))
-    .map_err(|e| format!("Failed to prepare session insert: {:?}", e))?
-    .args(&[
-        &session.session_id,
-        &session.started_at,
-        &session.completed_at,
-        &session.rules_grl,
-        &pgrx::JsonB(session.initial_facts.clone()),
-        &(session.current_step as i64),
-        &(session.event_count() as i64),
-        status_str,
-        &session.duration_ms(),
-    ])
-    .execute()
-    .map_err(|e| format!("Failed to execute session insert: {:?}", e))?;
+    Spi::connect_mut(|client| {
+        client.update(
+            "INSERT INTO rule_execution_sessions
+             (session_id, started_at, completed_at, rules_grl, initial_facts, total_steps, total_events, status, duration_ms)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             ON CONFLICT (session_id) DO UPDATE SET
+                completed_at = EXCLUDED.completed_at,
+                total_steps = EXCLUDED.total_steps,
+                total_events = EXCLUDED.total_events,
+                status = EXCLUDED.status,
+                duration_ms = EXCLUDED.duration_ms",
+            None,
+            &[
+                session.session_id.as_str().into(),
+                session.started_at.into(),
+                session.completed_at.into(),
+                session.rules_grl.as_str().into(),
+                pgrx::JsonB(session.initial_facts.clone()).into(),
+                (session.current_step as i64).into(),
+                (session.event_count() as i64).into(),
+                status_str.into(),
+                session.duration_ms().into(),
+            ],
+        )
+        .map(|_| ())
+    })
+    .map_err(|e| format!("Failed to save session: {:?}", e))?;
 
     Ok(())
 }
 
 /// Load session from PostgreSQL
 pub fn load_session_from_db(session_id: &str) -> Result<ExecutionSession, String> {
-    let result = Spi::get_one::<pgrx::JsonB>(&format!(
-        "SELECT row_to_json(s) FROM rule_execution_sessions s WHERE session_id = $1"
-    ))
-    .args(&[session_id])
+    let result = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT to_jsonb(s) FROM rule_execution_sessions s WHERE session_id = $1",
+                None,
+                &[session_id.into()],
+            )?
+            .first()
+            .get_one::<pgrx::JsonB>()
+    })
     .map_err(|e| format!("Failed to load session: {:?}", e))?
     .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
@@ -93,9 +149,7 @@ pub fn load_session_from_db(session_id: &str) -> Result<ExecutionSession, String
         .to_string();
     let initial_facts = session_data["initial_facts"].clone();
     let total_steps = session_data["total_steps"].as_i64().unwrap_or(0) as u64;
-    let status_str = session_data["status"]
-        .as_str()
-        .ok_or("Missing status")?;
+    let status_str = session_data["status"].as_str().ok_or("Missing status")?;
 
     let status = match status_str {
         "running" => SessionStatus::Running,
@@ -105,7 +159,7 @@ pub fn load_session_from_db(session_id: &str) -> Result<ExecutionSession, String
     };
 
     // Load events for this session
-    let events = load_events_from_db(&session_id)?;
+    let events = load_events_from_db(&session_id)?.into();
 
     Ok(ExecutionSession {
         session_id,
@@ -116,26 +170,30 @@ pub fn load_session_from_db(session_id: &str) -> Result<ExecutionSession, String
         events,
         current_step: total_steps,
         status,
+        truncated: false,
+        persisted: true,
+        last_used: 0,
     })
 }
 
-/// Load all events for a session from PostgreSQL
+/// Load all events for a session from PostgreSQL, in step order
 pub fn load_events_from_db(session_id: &str) -> Result<Vec<ReteEvent>, String> {
-    let mut events = Vec::new();
-
     Spi::connect(|client| {
-        let query = "SELECT event_data FROM rule_execution_events
-                     WHERE session_id = $1
-                     ORDER BY step ASC";
-
-        let mut cursor = client
-            .open_cursor(query, Some(1))
-            .args(&[session_id]);
-
-        while let Some(row) = cursor.next() {
-            let event_json: pgrx::JsonB = row["event_data"]
-                .value()
-                .ok_or("Missing event_data")?
+        let rows = client
+            .select(
+                "SELECT event_data FROM rule_execution_events
+                 WHERE session_id = $1
+                 ORDER BY step ASC, id ASC",
+                None,
+                &[session_id.into()],
+            )
+            .map_err(|e| format!("Failed to load events: {:?}", e))?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let event_json = row
+                .get::<pgrx::JsonB>(1)
+                .map_err(|e| format!("Failed to read event_data: {:?}", e))?
                 .ok_or("Null event_data")?;
 
             let event: ReteEvent = serde_json::from_value(event_json.0)
@@ -148,54 +206,194 @@ pub fn load_events_from_db(session_id: &str) -> Result<Vec<ReteEvent>, String> {
     })
 }
 
-/// List all sessions from PostgreSQL
+/// List the 100 most recent sessions from PostgreSQL
+#[allow(dead_code)]
 pub fn list_sessions_from_db() -> Result<Vec<ExecutionSession>, String> {
-    let mut sessions = Vec::new();
-
-    Spi::connect(|client| {
-        let query = "SELECT session_id FROM rule_execution_sessions ORDER BY started_at DESC LIMIT 100";
-
-        let mut cursor = client.open_cursor(query, None);
-
-        while let Some(row) = cursor.next() {
-            let session_id: String = row["session_id"]
-                .value()
-                .ok_or("Missing session_id")?
-                .ok_or("Null session_id")?;
-
-            // Load full session (could be optimized to avoid loading all events)
-            if let Ok(session) = load_session_from_db(&session_id) {
-                sessions.push(session);
-            }
-        }
-
-        Ok(sessions)
+    let session_ids = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT session_id FROM rule_execution_sessions ORDER BY started_at DESC LIMIT 100",
+                None,
+                &[],
+            )?
+            .map(|row| row.get::<String>(1))
+            .collect::<Result<Vec<_>, _>>()
     })
+    .map_err(|e| format!("Failed to list sessions: {:?}", e))?;
+
+    // Load full sessions (could be optimized to avoid loading all events)
+    Ok(session_ids
+        .into_iter()
+        .flatten()
+        .filter_map(|session_id| load_session_from_db(&session_id).ok())
+        .collect())
 }
 
 /// Delete session and its events from PostgreSQL
 pub fn delete_session_from_db(session_id: &str) -> Result<(), String> {
-    // Events will be deleted via CASCADE
-    Spi::run("DELETE FROM rule_execution_sessions WHERE session_id = $1")
-        .map_err(|e| format!("Failed to delete session: {:?}", e))?
-        .args(&[session_id])
-        .execute()
-        .map_err(|e| format!("Failed to execute delete: {:?}", e))?;
-
-    Ok(())
+    // Events aren't tied to sessions by a foreign key, so delete both
+    Spi::run_with_args(
+        "WITH deleted_events AS (
+            DELETE FROM rule_execution_events WHERE session_id = $1
+         )
+         DELETE FROM rule_execution_sessions WHERE session_id = $1",
+        &[session_id.into()],
+    )
+    .map_err(|e| format!("Failed to delete session: {:?}", e))
 }
 
 /// Clear all debugging data from PostgreSQL
+#[allow(dead_code)]
 pub fn clear_all_sessions_from_db() -> Result<(), String> {
     Spi::run("TRUNCATE TABLE rule_execution_events, rule_execution_sessions CASCADE")
-        .map_err(|e| format!("Failed to truncate tables: {:?}", e))?
-        .execute()
-        .map_err(|e| format!("Failed to execute truncate: {:?}", e))?;
+        .map_err(|e| format!("Failed to truncate tables: {:?}", e))
+}
 
-    Ok(())
+/// Delete sessions started before `cutoff_ms` (epoch milliseconds) and their events
+///
+/// Returns the number of sessions removed. Events aren't tied to sessions by a
+/// foreign key, so they are deleted explicitly in the same statement.
+pub fn delete_sessions_before(cutoff_ms: i64) -> Result<i64, String> {
+    Spi::connect_mut(|client| -> Result<Option<i64>, pgrx::spi::SpiError> {
+        // Compare against a precomputed cutoff so idx_sessions_started is usable
+        client
+            .update(
+                "WITH old_sessions AS (
+                    DELETE FROM rule_execution_sessions
+                    WHERE started_at < $1
+                    RETURNING session_id
+                 ), old_events AS (
+                    DELETE FROM rule_execution_events e
+                    USING old_sessions s
+                    WHERE e.session_id = s.session_id
+                 )
+                 SELECT COUNT(*) FROM old_sessions",
+                None,
+                &[cutoff_ms.into()],
+            )?
+            .first()
+            .get_one::<i64>()
+    })
+    .map(|count| count.unwrap_or(0))
+    .map_err(|e| format!("Failed to delete old sessions: {:?}", e))
 }
 
-#[cfg(test)]
+/// Delete persisted debug sessions older than `older_than_days`
+///
+/// Returns the number of sessions removed.
+#[pg_extern]
+pub fn debug_cleanup_old_sessions(older_than_days: i32) -> Result<i64, String> {
+    if older_than_days < 0 {
+        return Err(format!(
+            "older_than_days must be >= 0, got {}",
+            older_than_days
+        ));
+    }
+
+    let cutoff_ms = super::events::current_timestamp() - i64::from(older_than_days) * 86_400_000;
+    delete_sessions_before(cutoff_ms)
+}
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pg_schema]
 mod tests {
-    // Tests require PostgreSQL connection, will be integration tests
+    use pgrx::prelude::*;
+
+    fn insert_session(session_id: &str, started_at: i64) {
+        Spi::run_with_args(
+            "INSERT INTO rule_execution_sessions (session_id, started_at, rules_grl, initial_facts)
+             VALUES ($1, $2, '', '{}'::jsonb)",
+            &[session_id.into(), started_at.into()],
+        )
+        .unwrap();
+        Spi::run_with_args(
+            "INSERT INTO rule_execution_events (session_id, step, event_timestamp, event_type, event_data)
+             VALUES ($1, 1, $2, 'FactInserted', '{}'::jsonb)",
+            &[session_id.into(), started_at.into()],
+        )
+        .unwrap();
+    }
+
+    fn count(sql: &str) -> i64 {
+        Spi::get_one::<i64>(sql).unwrap().unwrap_or(0)
+    }
+
+    #[pg_test]
+    fn test_load_events_in_step_order() {
+        use crate::debug::{enable_persistence, ReteEvent};
+
+        enable_persistence();
+
+        // Saved out of order; loading sorts by step
+        for step in [2, 1] {
+            let event = ReteEvent::FactInserted {
+                step,
+                timestamp: 1_000 + step as i64,
+                handle: step,
+                fact_type: "Order".to_string(),
+                data: serde_json::json!({"total": step * 100}),
+            };
+            super::save_event_to_db("load_events", &event).unwrap();
+        }
+
+        let events = super::load_events_from_db("load_events").unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].step(), 1);
+        assert_eq!(events[1].step(), 2);
+        assert_eq!(events[0].event_type(), "FactInserted");
+
+        assert!(super::load_events_from_db("missing").unwrap().is_empty());
+    }
+
+    fn plan(sql: &str) -> String {
+        Spi::explain(sql).unwrap().0.to_string()
+    }
+
+    #[pg_test]
+    fn test_debug_queries_use_indexes() {
+        // Tables are tiny here, so take sequential scans off the table
+        Spi::run("SET LOCAL enable_seqscan = off").unwrap();
+
+        assert!(plan(
+            "SELECT event_data FROM rule_execution_events
+             WHERE session_id = 'x' ORDER BY step ASC, id ASC"
+        )
+        .contains("idx_events_session_step"));
+        assert!(plan(
+            "SELECT session_id FROM rule_execution_sessions ORDER BY started_at DESC LIMIT 100"
+        )
+        .contains("idx_sessions_started"));
+        assert!(
+            plan("SELECT session_id FROM rule_execution_sessions WHERE started_at < 0")
+                .contains("idx_sessions_started")
+        );
+    }
+
+    #[pg_test]
+    fn test_cleanup_old_sessions() {
+        let now = super::super::events::current_timestamp();
+        insert_session("cleanup_old", now - 40 * 86_400_000);
+        insert_session("cleanup_new", now - 86_400_000);
+
+        assert_eq!(super::debug_cleanup_old_sessions(30), Ok(1));
+
+        assert_eq!(
+            count("SELECT COUNT(*) FROM rule_execution_sessions WHERE session_id = 'cleanup_new'"),
+            1
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM rule_execution_sessions WHERE session_id = 'cleanup_old'"),
+            0
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM rule_execution_events WHERE session_id = 'cleanup_old'"),
+            0
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM rule_execution_events WHERE session_id = 'cleanup_new'"),
+            1
+        );
+
+        assert!(super::debug_cleanup_old_sessions(-1).is_err());
+    }
 }
@@ -1,34 +0,0 @@
-//! Simple PostgreSQL event store using direct SQL
-//!
-//! Simplified implementation using basic SPI execute
-
-use super::event_store::ExecutionSession;
-use super::events::ReteEvent;
-
-/// Save an event to PostgreSQL (simplified - just log for now)
-/// Full PostgreSQL integration will be completed in production deployment
-pub fn save_event_to_db(_session_id: &str, _event: &ReteEvent) -> Result<(), String> {
-    // TODO: Implement PostgreSQL persistence
-    // For now, events are only stored in-memory via GLOBAL_EVENT_STORE
-    // PostgreSQL tables are created but not yet populated
-    Ok(())
-}
-
-/// Save session metadata to PostgreSQL (simplified)
-pub fn save_session_to_db(_session: &ExecutionSession) -> Result<(), String> {
-    // TODO: Implement PostgreSQL persistence
-    // For now, sessions are only stored in-memory
-    Ok(())
-}
-
-/// Load session from PostgreSQL (simplified)
-#[allow(dead_code)]
-pub fn load_session_from_db(session_id: &str) -> Result<ExecutionSession, String> {
-    Err(format!("Session not found in DB: {}", session_id))
-}
-
-/// Delete session from PostgreSQL (simplified)
-#[allow(dead_code)]
-pub fn delete_session_from_db(_session_id: &str) -> Result<(), String> {
-    Ok(())
-}
@@ -34,7 +34,6 @@ pub const INVALID_JSON: ErrorCode = ErrorCode {
     default_message: "Invalid JSON syntax in facts",
 };
 
-#[allow(dead_code)]
 pub const NON_OBJECT_JSON: ErrorCode = ErrorCode {
     code: "ERR006",
     default_message: "Facts must be a JSON object, not an array or primitive",
@@ -71,3 +70,28 @@ pub const SERIALIZATION_FAILED: ErrorCode = ErrorCode {
     code: "ERR012",
     default_message: "Failed to serialize result",
 };
+
+pub const RULE_NOT_FOUND: ErrorCode = ErrorCode {
+    code: "ERR013",
+    default_message: "Rule not found",
+};
+
+pub const INVALID_INPUT: ErrorCode = ErrorCode {
+    code: "ERR014",
+    default_message: "Invalid input",
+};
+
+pub const DATABASE_ERROR: ErrorCode = ErrorCode {
+    code: "ERR015",
+    default_message: "Database error",
+};
+
+pub const SCHEMA_MISMATCH: ErrorCode = ErrorCode {
+    code: "ERR016",
+    default_message: "Facts do not match the declared schema",
+};
+
+pub const VERSION_MISMATCH: ErrorCode = ErrorCode {
+    code: "ERR017",
+    default_message: "Resolved rule version differs from the expected version",
+};
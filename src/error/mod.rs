@@ -12,19 +12,47 @@ pub enum RuleEngineError {
     InvalidInput(String),
     /// Database error
     DatabaseError(String),
+    /// The version that would run isn't the one the caller expected
+    VersionMismatch(String),
     /// Execution error from rust-rule-engine
     ExecutionError(rust_rule_engine::RuleEngineError),
 }
 
-impl fmt::Display for RuleEngineError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl RuleEngineError {
+    /// Machine-readable error code for this variant
+    pub fn error_code(&self) -> &'static ErrorCode {
+        match self {
+            RuleEngineError::RuleNotFound(_) => &codes::RULE_NOT_FOUND,
+            RuleEngineError::InvalidInput(_) => &codes::INVALID_INPUT,
+            RuleEngineError::DatabaseError(_) => &codes::DATABASE_ERROR,
+            RuleEngineError::VersionMismatch(_) => &codes::VERSION_MISMATCH,
+            RuleEngineError::ExecutionError(_) => &codes::EXECUTION_FAILED,
+        }
+    }
+
+    /// Human-readable error message
+    pub fn message(&self) -> String {
         match self {
-            RuleEngineError::RuleNotFound(msg) => write!(f, "Rule not found: {}", msg),
-            RuleEngineError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
-            RuleEngineError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
-            RuleEngineError::ExecutionError(e) => write!(f, "Execution error: {}", e),
+            RuleEngineError::RuleNotFound(msg) => format!("Rule not found: {}", msg),
+            RuleEngineError::InvalidInput(msg) => format!("Invalid input: {}", msg),
+            RuleEngineError::DatabaseError(msg) => format!("Database error: {}", msg),
+            RuleEngineError::VersionMismatch(msg) => format!("Version mismatch: {}", msg),
+            RuleEngineError::ExecutionError(e) => format!("Execution error: {}", e),
         }
     }
+
+    /// JSON error response: {error, error_code, timestamp}
+    pub fn to_json(&self) -> String {
+        create_error_response(self.error_code(), &self.message())
+    }
+}
+
+/// Renders the coded JSON so errors raised from `#[pg_extern]` functions
+/// carry a stable error code clients can branch on
+impl fmt::Display for RuleEngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
 }
 
 impl std::error::Error for RuleEngineError {}
@@ -48,7 +76,6 @@ impl From<pgrx::spi::SpiError> for RuleEngineError {
 }
 
 /// Create a JSON error response with code, message, and timestamp
-#[allow(dead_code)]
 pub fn create_error_response(error_code: &ErrorCode, message: &str) -> String {
     serde_json::json!({
         "error": message,
@@ -68,3 +95,47 @@ pub fn create_custom_error(error_code: &ErrorCode, custom_message: String) -> St
 pub fn create_default_error(error_code: &ErrorCode) -> String {
     create_error_response(error_code, error_code.default_message)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_per_variant() {
+        assert_eq!(
+            RuleEngineError::RuleNotFound("x".into()).error_code().code,
+            "ERR013"
+        );
+        assert_eq!(
+            RuleEngineError::InvalidInput("x".into()).error_code().code,
+            "ERR014"
+        );
+        assert_eq!(
+            RuleEngineError::DatabaseError("x".into()).error_code().code,
+            "ERR015"
+        );
+        assert_eq!(
+            RuleEngineError::VersionMismatch("x".into())
+                .error_code()
+                .code,
+            "ERR017"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let err = RuleEngineError::RuleNotFound("Rule 'discount' (default) not found".into());
+        let json: serde_json::Value = serde_json::from_str(&err.to_json()).unwrap();
+
+        assert_eq!(json["error_code"], "ERR013");
+        assert_eq!(
+            json["error"],
+            "Rule not found: Rule 'discount' (default) not found"
+        );
+        assert!(json["timestamp"].is_string());
+
+        // Display carries the same coded JSON
+        let displayed: serde_json::Value = serde_json::from_str(&err.to_string()).unwrap();
+        assert_eq!(displayed["error_code"], "ERR013");
+    }
+}
@@ -0,0 +1,138 @@
+/// Array built-in functions
+///
+/// Arrays can only be injected, not inlined as GRL literals: use these in a
+/// `when` clause, where the result is injected as a computed fact field, or
+/// inside composite `then` values. A bare `then` assignment of an array is
+/// rewritten to `nil`.
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Sort an array of numbers or strings, returning a new array
+/// Usage: Sort(Order.amounts, "desc")
+///
+/// Order is "asc" (default) or "desc". Mixing numbers and strings, or
+/// sorting any other element type, is an error.
+pub fn sort(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err("Sort requires 1-2 arguments: array, order".to_string());
+    }
+
+    let items = args[0]
+        .as_array()
+        .ok_or("Sort: first argument must be an array")?;
+
+    let descending = match args.get(1) {
+        None | Some(Value::Null) => false,
+        Some(Value::String(order)) => match order.to_lowercase().as_str() {
+            "asc" => false,
+            "desc" => true,
+            other => {
+                return Err(format!(
+                    "Sort: unknown order '{}' (expected asc or desc)",
+                    other
+                ))
+            }
+        },
+        Some(_) => return Err("Sort: order must be \"asc\" or \"desc\"".to_string()),
+    };
+
+    let mut sorted = items.clone();
+    if sorted.iter().all(Value::is_number) {
+        sorted.sort_by(|a, b| compare_numbers(a, b));
+    } else if sorted.iter().all(Value::is_string) {
+        sorted.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    } else {
+        return Err("Sort: array must contain only numbers or only strings".to_string());
+    }
+
+    if descending {
+        sorted.reverse();
+    }
+
+    Ok(Value::Array(sorted))
+}
+
+/// Remove duplicate elements, keeping the first occurrence of each
+/// Usage: Distinct(Order.tags)
+///
+/// Elements are compared with JSON equality, so 1 and 1.0 are distinct.
+pub fn distinct(args: &[Value]) -> Result<Value, String> {
+    let items = args
+        .first()
+        .ok_or("Distinct requires 1 argument: array")?
+        .as_array()
+        .ok_or("Distinct: argument must be an array")?;
+
+    let mut unique: Vec<Value> = Vec::with_capacity(items.len());
+    for item in items {
+        if !unique.contains(item) {
+            unique.push(item.clone());
+        }
+    }
+
+    Ok(Value::Array(unique))
+}
+
+fn compare_numbers(a: &Value, b: &Value) -> Ordering {
+    let a = a.as_f64().unwrap_or(f64::NAN);
+    let b = b.as_f64().unwrap_or(f64::NAN);
+    a.total_cmp(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sort_numbers() {
+        assert_eq!(
+            sort(&[json!([3, 1.5, 10, -2])]).unwrap(),
+            json!([-2, 1.5, 3, 10])
+        );
+        assert_eq!(
+            sort(&[json!([3, 1.5, 10, -2]), json!("desc")]).unwrap(),
+            json!([10, 3, 1.5, -2])
+        );
+        assert_eq!(sort(&[json!([])]).unwrap(), json!([]));
+    }
+
+    #[test]
+    fn test_sort_strings() {
+        assert_eq!(
+            sort(&[json!(["pear", "apple", "fig"]), json!("asc")]).unwrap(),
+            json!(["apple", "fig", "pear"])
+        );
+        assert_eq!(
+            sort(&[json!(["pear", "apple", "fig"]), json!("DESC")]).unwrap(),
+            json!(["pear", "fig", "apple"])
+        );
+    }
+
+    #[test]
+    fn test_distinct() {
+        assert_eq!(
+            distinct(&[json!([3, 1, 3, 2, 1])]).unwrap(),
+            json!([3, 1, 2])
+        );
+        assert_eq!(
+            distinct(&[json!(["vip", "new", "vip", "urgent", "new"])]).unwrap(),
+            json!(["vip", "new", "urgent"])
+        );
+        assert_eq!(
+            distinct(&[json!([{"id": 1}, {"id": 1}, "1", 1])]).unwrap(),
+            json!([{"id": 1}, "1", 1])
+        );
+        assert!(distinct(&[]).is_err());
+        assert!(distinct(&[json!("vip")]).is_err());
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        assert!(sort(&[]).is_err());
+        assert!(sort(&[json!("not an array")]).is_err());
+        assert!(sort(&[json!([1, "a"])]).is_err());
+        assert!(sort(&[json!([{"a": 1}])]).is_err());
+        assert!(sort(&[json!([1, 2]), json!("sideways")]).is_err());
+    }
+}
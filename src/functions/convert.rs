@@ -0,0 +1,118 @@
+/// Type coercion built-in functions, for facts that arrive loosely typed
+use serde_json::{Number, Value};
+
+/// Convert a string, boolean or number to a number
+/// Usage: ToNumber("42") -> 42, ToNumber(" 4.5 ") -> 4.5, ToNumber(true) -> 1
+pub fn to_number(args: &[Value]) -> Result<Value, String> {
+    let value = args.first().ok_or("ToNumber requires 1 argument: value")?;
+
+    match value {
+        Value::Number(_) => Ok(value.clone()),
+        Value::Bool(b) => Ok(Value::from(i64::from(*b))),
+        Value::String(s) => {
+            let text = s.trim();
+            if let Ok(n) = text.parse::<i64>() {
+                return Ok(Value::from(n));
+            }
+            text.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+                .ok_or_else(|| format!("ToNumber: '{}' is not a number", s))
+        }
+        other => Err(format!("ToNumber: cannot convert {} to a number", other)),
+    }
+}
+
+/// Convert a scalar to its string form; strings are returned unchanged
+/// Usage: ToString(42) -> "42", ToString(true) -> "true", ToString(null) -> "null"
+pub fn to_string(args: &[Value]) -> Result<Value, String> {
+    let value = args.first().ok_or("ToString requires 1 argument: value")?;
+
+    match value {
+        Value::String(_) => Ok(value.clone()),
+        Value::Number(n) => Ok(Value::String(n.to_string())),
+        Value::Bool(b) => Ok(Value::String(b.to_string())),
+        Value::Null => Ok(Value::String("null".to_string())),
+        _ => Err(
+            "ToString: argument must be a scalar (use JsonStringify for arrays and objects)"
+                .to_string(),
+        ),
+    }
+}
+
+/// Convert "true"/"false" (any case), "1"/"0", 1/0 or a boolean to a boolean
+/// Usage: ToBool("TRUE") -> true, ToBool(0) -> false
+pub fn to_bool(args: &[Value]) -> Result<Value, String> {
+    let value = args.first().ok_or("ToBool requires 1 argument: value")?;
+
+    let result = match value {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => match n.as_f64() {
+            Some(x) if x == 1.0 => Some(true),
+            Some(x) if x == 0.0 => Some(false),
+            _ => None,
+        },
+        Value::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" => Some(true),
+            "false" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    result
+        .map(Value::Bool)
+        .ok_or_else(|| format!("ToBool: cannot convert {} to a boolean", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_number() {
+        assert_eq!(to_number(&[json!("42")]).unwrap(), json!(42));
+        assert_eq!(to_number(&[json!(" 4.5 ")]).unwrap(), json!(4.5));
+        assert_eq!(to_number(&[json!("-7")]).unwrap(), json!(-7));
+        assert_eq!(to_number(&[json!(3.5)]).unwrap(), json!(3.5));
+        assert_eq!(to_number(&[json!(true)]).unwrap(), json!(1));
+        assert_eq!(to_number(&[json!(false)]).unwrap(), json!(0));
+
+        assert!(to_number(&[json!("42abc")]).is_err());
+        assert!(to_number(&[json!("")]).is_err());
+        // Parses as f64 but isn't representable in JSON
+        assert!(to_number(&[json!("NaN")]).is_err());
+        assert!(to_number(&[Value::Null]).is_err());
+        assert!(to_number(&[json!([1])]).is_err());
+        assert!(to_number(&[]).is_err());
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&[json!(42)]).unwrap(), json!("42"));
+        assert_eq!(to_string(&[json!(4.5)]).unwrap(), json!("4.5"));
+        assert_eq!(to_string(&[json!(true)]).unwrap(), json!("true"));
+        assert_eq!(to_string(&[json!("already")]).unwrap(), json!("already"));
+        assert_eq!(to_string(&[Value::Null]).unwrap(), json!("null"));
+
+        assert!(to_string(&[json!({"a": 1})]).is_err());
+        assert!(to_string(&[json!([1])]).is_err());
+    }
+
+    #[test]
+    fn test_to_bool() {
+        assert_eq!(to_bool(&[json!("TRUE")]).unwrap(), json!(true));
+        assert_eq!(to_bool(&[json!(" False ")]).unwrap(), json!(false));
+        assert_eq!(to_bool(&[json!("1")]).unwrap(), json!(true));
+        assert_eq!(to_bool(&[json!("0")]).unwrap(), json!(false));
+        assert_eq!(to_bool(&[json!(1)]).unwrap(), json!(true));
+        assert_eq!(to_bool(&[json!(0.0)]).unwrap(), json!(false));
+        assert_eq!(to_bool(&[json!(true)]).unwrap(), json!(true));
+
+        assert!(to_bool(&[json!("yes")]).is_err());
+        assert!(to_bool(&[json!(2)]).is_err());
+        assert!(to_bool(&[Value::Null]).is_err());
+    }
+}
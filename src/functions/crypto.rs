@@ -0,0 +1,149 @@
+/// Encryption built-in functions: AES-256-GCM with keys from rule_crypto_keys
+///
+/// Ciphertext is base64 of the 12-byte random nonce followed by the
+/// encrypted data and its 16-byte authentication tag, so any change to it
+/// makes Decrypt fail rather than return altered plaintext.
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::Value;
+
+const NONCE_LEN: usize = 12;
+
+/// Encrypt a string with a named key
+/// Usage: Encrypt(Customer.ssn, "pii") -> "q3J0...=="
+pub fn encrypt(args: &[Value]) -> Result<Value, String> {
+    let (plaintext, key_name) = crypto_args("Encrypt", "plaintext", args)?;
+    let key = load_key(key_name)?;
+    encrypt_with_key(&key, plaintext).map(Value::String)
+}
+
+/// Decrypt a string produced by Encrypt with the same key
+/// Usage: Decrypt(Customer.ssnEncrypted, "pii") -> "123-45-6789"
+pub fn decrypt(args: &[Value]) -> Result<Value, String> {
+    let (ciphertext, key_name) = crypto_args("Decrypt", "ciphertext", args)?;
+    let key = load_key(key_name)?;
+    decrypt_with_key(&key, ciphertext).map(Value::String)
+}
+
+fn crypto_args<'a>(
+    name: &str,
+    first: &str,
+    args: &'a [Value],
+) -> Result<(&'a str, &'a str), String> {
+    if args.len() < 2 {
+        return Err(format!(
+            "{} requires 2 arguments: {}, key name",
+            name, first
+        ));
+    }
+
+    let text = args[0]
+        .as_str()
+        .ok_or_else(|| format!("{}: {} must be a string", name, first))?;
+    let key_name = args[1]
+        .as_str()
+        .ok_or_else(|| format!("{}: key name must be a string", name))?;
+
+    Ok((text, key_name))
+}
+
+/// Fetch a key's 32 bytes from rule_crypto_keys
+fn load_key(key_name: &str) -> Result<Vec<u8>, String> {
+    pgrx::Spi::connect(|client| {
+        client
+            .select(
+                "SELECT key_material FROM rule_crypto_keys WHERE key_name = $1",
+                None,
+                &[key_name.into()],
+            )?
+            .first()
+            .get_one::<Vec<u8>>()
+    })
+    .map_err(|e| format!("Failed to load key '{}': {}", key_name, e))?
+    .ok_or_else(|| {
+        format!(
+            "Encryption key '{}' not found in rule_crypto_keys",
+            key_name
+        )
+    })
+}
+
+fn cipher(key: &[u8]) -> Result<Aes256Gcm, String> {
+    Aes256Gcm::new_from_slice(key)
+        .map_err(|_| format!("Encryption key must be 32 bytes, got {}", key.len()))
+}
+
+fn encrypt_with_key(key: &[u8], plaintext: &str) -> Result<String, String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(key)?
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Encrypt: encryption failed".to_string())?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(BASE64.encode(sealed))
+}
+
+fn decrypt_with_key(key: &[u8], encoded: &str) -> Result<String, String> {
+    let sealed = BASE64
+        .decode(encoded)
+        .map_err(|e| format!("Decrypt: ciphertext is not valid base64: {}", e))?;
+    if sealed.len() < NONCE_LEN {
+        return Err("Decrypt: ciphertext is too short".to_string());
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+    let plaintext = cipher(key)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| {
+            "Decrypt: authentication failed (ciphertext was modified or the key is wrong)"
+                .to_string()
+        })?;
+
+    String::from_utf8(plaintext).map_err(|_| "Decrypt: plaintext is not valid UTF-8".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_round_trip() {
+        let sealed = encrypt_with_key(&KEY, "123-45-6789").unwrap();
+        assert_ne!(sealed, "123-45-6789");
+        assert_eq!(decrypt_with_key(&KEY, &sealed).unwrap(), "123-45-6789");
+
+        // Fresh nonce per call
+        assert_ne!(
+            encrypt_with_key(&KEY, "x").unwrap(),
+            encrypt_with_key(&KEY, "x").unwrap()
+        );
+        let empty = encrypt_with_key(&KEY, "").unwrap();
+        assert_eq!(decrypt_with_key(&KEY, &empty).unwrap(), "");
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_fails() {
+        let mut sealed = BASE64
+            .decode(encrypt_with_key(&KEY, "secret").unwrap())
+            .unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+
+        let err = decrypt_with_key(&KEY, &BASE64.encode(&sealed)).unwrap_err();
+        assert!(err.contains("authentication failed"), "{}", err);
+    }
+
+    #[test]
+    fn test_wrong_key_and_bad_input() {
+        let sealed = encrypt_with_key(&KEY, "secret").unwrap();
+        assert!(decrypt_with_key(&[8; 32], &sealed).is_err());
+        assert!(decrypt_with_key(&KEY, "not base64!").is_err());
+        assert!(decrypt_with_key(&KEY, "AAAA").is_err());
+        assert!(encrypt_with_key(&[7; 16], "secret").is_err());
+    }
+}
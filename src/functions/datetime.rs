@@ -1,6 +1,51 @@
 /// Date/time built-in functions
-use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
 use serde_json::Value;
+use std::cell::Cell;
+
+/// Source of a fixed "now"; returning None falls back to the real clock
+pub type ClockSource = fn() -> Option<DateTime<Utc>>;
+
+thread_local! {
+    /// Clock used by Now, Today and DaysSince (backends are single-threaded)
+    static CLOCK: Cell<Option<ClockSource>> = const { Cell::new(None) };
+}
+
+/// Install (or with None, remove) a clock override for this thread
+///
+/// The extension installs one that reads `rule_engine.fixed_now`.
+pub fn set_clock_source(source: Option<ClockSource>) {
+    CLOCK.with(|clock| clock.set(source));
+}
+
+/// Current time, honouring the clock override
+fn current_time() -> DateTime<Utc> {
+    CLOCK
+        .with(|clock| clock.get())
+        .and_then(|source| source())
+        .unwrap_or_else(Utc::now)
+}
+
+/// Parse a fixed timestamp: RFC 3339, `YYYY-MM-DD HH:MM:SS` (UTC) or
+/// `YYYY-MM-DD` (midnight UTC)
+pub fn parse_fixed_now(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    if let Ok(ts) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S") {
+        return Ok(ts.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+
+    Err(format!(
+        "Invalid fixed time '{}' (expected RFC 3339, YYYY-MM-DD HH:MM:SS or YYYY-MM-DD)",
+        value
+    ))
+}
 
 /// Calculate days since a given date
 /// Usage: DaysSince("2024-01-01")
@@ -16,7 +61,7 @@ pub fn days_since(args: &[Value]) -> Result<Value, String> {
     let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
         .map_err(|e| format!("Invalid date format: {}", e))?;
 
-    let now = Utc::now().date_naive();
+    let now = current_time().date_naive();
     let days = now.signed_duration_since(date).num_days();
 
     Ok(Value::Number(days.into()))
@@ -69,14 +114,14 @@ pub fn format_date(args: &[Value]) -> Result<Value, String> {
 /// Get current timestamp
 /// Usage: Now()
 pub fn now(_args: &[Value]) -> Result<Value, String> {
-    let now: DateTime<Utc> = Utc::now();
+    let now: DateTime<Utc> = current_time();
     Ok(Value::String(now.to_rfc3339()))
 }
 
 /// Get current date (without time)
 /// Usage: Today()
 pub fn today(_args: &[Value]) -> Result<Value, String> {
-    let today = Utc::now().date_naive();
+    let today = current_time().date_naive();
     Ok(Value::String(today.format("%Y-%m-%d").to_string()))
 }
 
@@ -112,4 +157,36 @@ mod tests {
         // Should be in YYYY-MM-DD format
         assert!(result.unwrap().as_str().unwrap().contains("-"));
     }
+
+    fn fixed_clock() -> Option<DateTime<Utc>> {
+        Some(parse_fixed_now("2025-03-01T12:30:00Z").unwrap())
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        set_clock_source(Some(fixed_clock));
+
+        assert_eq!(today(&[]).unwrap(), json!("2025-03-01"));
+        assert_eq!(now(&[]).unwrap(), json!("2025-03-01T12:30:00+00:00"));
+        assert_eq!(days_since(&[json!("2025-02-01")]).unwrap(), json!(28));
+
+        // A source returning None means the real clock
+        set_clock_source(Some(|| None));
+        assert_ne!(today(&[]).unwrap(), json!("2025-03-01"));
+
+        set_clock_source(None);
+        assert_ne!(today(&[]).unwrap(), json!("2025-03-01"));
+    }
+
+    #[test]
+    fn test_parse_fixed_now() {
+        let expected = parse_fixed_now("2025-03-01T00:00:00Z").unwrap();
+        assert_eq!(parse_fixed_now("2025-03-01").unwrap(), expected);
+        assert_eq!(parse_fixed_now("2025-03-01 00:00:00").unwrap(), expected);
+        assert_eq!(
+            parse_fixed_now("2025-03-01T02:00:00+02:00").unwrap(),
+            expected
+        );
+        assert!(parse_fixed_now("yesterday").is_err());
+    }
 }
@@ -0,0 +1,293 @@
+/// `foreach` expansion for rules over arrays of objects
+///
+/// ```text
+/// foreach(Order.items as item) {
+///     rule "BigItem" { when item.price > 100 then item.flagged = true; }
+/// }
+/// ```
+///
+/// Before the rules are loaded, every element of `Order.items` becomes a
+/// top-level fact (`item_0`, `item_1`, ...) and the rules in the block are
+/// instantiated once per element, with `item.` rewritten to that fact and the
+/// index appended to the rule name (`BigItem_0`, `BigItem_1`, ...).
+/// `collapse_foreach` moves the element facts back into the array after
+/// execution.
+///
+/// Limits:
+/// - Only arrays of objects can be iterated; other elements are an error.
+/// - An element is a flat fact, so `item.a.b` doesn't reach nested objects.
+/// - Blocks can't be nested, and each rule instance sees a single element
+///   (no joins across elements).
+/// - Elements can be modified, not added or removed.
+/// - At most `MAX_FOREACH_ELEMENTS` elements per block.
+use regex::Regex;
+use serde_json::Value;
+
+/// Maximum number of array elements a single `foreach` block expands to
+pub const MAX_FOREACH_ELEMENTS: usize = 1000;
+
+/// Field recording which array an element fact came from
+const PATH_FIELD: &str = "__foreach_path";
+/// Field recording the element's position in that array
+const INDEX_FIELD: &str = "__foreach_index";
+
+lazy_static::lazy_static! {
+    static ref FOREACH_REGEX: Regex =
+        Regex::new(r"\bforeach\s*\(\s*([A-Za-z_][\w.]*)\s+as\s+([A-Za-z_]\w*)\s*\)\s*\{").unwrap();
+    static ref RULE_NAME_REGEX: Regex =
+        Regex::new(r#"\brule\s+(?:"([^"]+)"|([A-Za-z_]\w*))"#).unwrap();
+}
+
+/// Expand every `foreach` block in `grl_code`, injecting one fact per element
+///
+/// Returns the GRL with each block replaced by its per-element rules. Code
+/// without `foreach` is returned unchanged.
+pub fn expand_foreach(grl_code: &str, facts: &mut Value) -> Result<String, String> {
+    if !grl_code.contains("foreach") {
+        return Ok(grl_code.to_string());
+    }
+
+    let mut output = String::with_capacity(grl_code.len());
+    let mut rest = grl_code;
+
+    while let Some(cap) = FOREACH_REGEX.captures(rest) {
+        let header = cap.get(0).expect("group 0 always matches");
+        let path = &cap[1];
+        let alias = &cap[2];
+
+        let body_len = matching_brace(&rest[header.end()..])
+            .ok_or_else(|| format!("foreach({} as {}): missing closing '}}'", path, alias))?;
+        let body = &rest[header.end()..header.end() + body_len];
+        if FOREACH_REGEX.is_match(body) {
+            return Err(format!(
+                "foreach({} as {}): nested foreach blocks are not supported",
+                path, alias
+            ));
+        }
+
+        output.push_str(&rest[..header.start()]);
+        output.push_str(&expand_block(path, alias, body, facts)?);
+        rest = &rest[header.end() + body_len + 1..];
+    }
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Inject the element facts for one block and instantiate its rules
+fn expand_block(path: &str, alias: &str, body: &str, facts: &mut Value) -> Result<String, String> {
+    let elements = lookup_path(facts, path)
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("foreach: '{}' is not an array in facts", path))?
+        .clone();
+
+    if elements.len() > MAX_FOREACH_ELEMENTS {
+        return Err(format!(
+            "foreach: '{}' has {} elements (max {})",
+            path,
+            elements.len(),
+            MAX_FOREACH_ELEMENTS
+        ));
+    }
+
+    let facts_obj = facts
+        .as_object_mut()
+        .ok_or("foreach: facts must be a JSON object")?;
+    let alias_regex = Regex::new(&format!(r"\b{}\.", regex::escape(alias)))
+        .map_err(|e| format!("Regex error: {}", e))?;
+
+    let mut expanded = String::new();
+    for (index, element) in elements.into_iter().enumerate() {
+        let mut element = match element {
+            Value::Object(map) => map,
+            other => {
+                return Err(format!(
+                    "foreach: '{}[{}]' is {}, expected an object",
+                    path, index, other
+                ))
+            }
+        };
+
+        let fact_name = format!("{}_{}", alias, index);
+        if facts_obj.contains_key(&fact_name) {
+            return Err(format!(
+                "foreach: fact '{}' already exists; choose another alias than '{}'",
+                fact_name, alias
+            ));
+        }
+        element.insert(PATH_FIELD.to_string(), Value::String(path.to_string()));
+        element.insert(INDEX_FIELD.to_string(), Value::from(index));
+        facts_obj.insert(fact_name.clone(), Value::Object(element));
+
+        let instance = alias_regex.replace_all(body, format!("{}.", fact_name).as_str());
+        let instance =
+            RULE_NAME_REGEX.replace_all(&instance, |cap: &regex::Captures| match cap.get(1) {
+                Some(quoted) => format!("rule \"{}_{}\"", quoted.as_str(), index),
+                None => format!("rule {}_{}", &cap[2], index),
+            });
+        expanded.push_str(&instance);
+    }
+
+    Ok(expanded)
+}
+
+/// Move element facts injected by `expand_foreach` back into their arrays
+///
+/// Element facts are recognised by their bookkeeping fields, so this is a
+/// no-op for facts that never went through a `foreach` expansion.
+pub fn collapse_foreach(facts: &mut Value) {
+    let Some(facts_obj) = facts.as_object_mut() else {
+        return;
+    };
+
+    let element_keys: Vec<String> = facts_obj
+        .iter()
+        .filter(|(_, value)| value.get(PATH_FIELD).is_some())
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    for key in element_keys {
+        let Some(Value::Object(mut element)) = facts_obj.remove(&key) else {
+            continue;
+        };
+        let path = element.remove(PATH_FIELD);
+        let index = element.remove(INDEX_FIELD);
+
+        let (Some(Value::String(path)), Some(index)) = (path, index.and_then(|i| i.as_u64()))
+        else {
+            continue;
+        };
+
+        if let Some(slot) = lookup_path_mut(facts_obj, &path)
+            .and_then(Value::as_array_mut)
+            .and_then(|array| array.get_mut(index as usize))
+        {
+            *slot = Value::Object(element);
+        }
+    }
+}
+
+/// Length of the block body, up to (not including) its closing brace
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 1;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn lookup_path<'a>(facts: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(facts, |value, part| value.get(part))
+}
+
+fn lookup_path_mut<'a>(
+    facts: &'a mut serde_json::Map<String, Value>,
+    path: &str,
+) -> Option<&'a mut Value> {
+    let mut parts = path.split('.');
+    let first = facts.get_mut(parts.next()?)?;
+    parts.try_fold(first, |value, part| value.get_mut(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GRL: &str = r#"
+foreach(Order.items as item) {
+    rule "BigItem" { when item.price > 100 then item.flagged = true; }
+}
+rule "Total" { when Order.total > 0 then Order.checked = true; }
+"#;
+
+    #[test]
+    fn test_expand_three_elements() {
+        let mut facts = json!({
+            "Order": {
+                "total": 450,
+                "items": [{"price": 50}, {"price": 150}, {"price": 250}]
+            }
+        });
+
+        let grl = expand_foreach(GRL, &mut facts).unwrap();
+
+        for i in 0..3 {
+            assert!(grl.contains(&format!(
+                "rule \"BigItem_{0}\" {{ when item_{0}.price > 100 then item_{0}.flagged = true; }}",
+                i
+            )));
+            assert_eq!(facts[format!("item_{}", i)][INDEX_FIELD], json!(i));
+        }
+        assert!(!grl.contains("foreach"));
+        assert!(grl.contains("rule \"Total\""));
+        assert_eq!(facts["item_1"]["price"], 150);
+        assert_eq!(facts["item_1"][PATH_FIELD], "Order.items");
+    }
+
+    #[test]
+    fn test_collapse_writes_elements_back() {
+        let mut facts = json!({"Order": {"items": [{"price": 50}, {"price": 150}]}});
+        expand_foreach(GRL, &mut facts).unwrap();
+
+        facts["item_1"]["flagged"] = json!(true);
+        collapse_foreach(&mut facts);
+
+        assert_eq!(
+            facts,
+            json!({"Order": {"items": [{"price": 50}, {"price": 150, "flagged": true}]}})
+        );
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let mut facts = json!({"Order": {"items": [1, 2]}});
+        assert!(expand_foreach(GRL, &mut facts)
+            .unwrap_err()
+            .contains("expected an object"));
+
+        let mut facts = json!({"Order": {"items": "none"}});
+        assert!(expand_foreach(GRL, &mut facts)
+            .unwrap_err()
+            .contains("not an array"));
+
+        let mut facts = json!({"Order": {"items": []}});
+        assert!(expand_foreach("foreach(Order.items as item) { rule A {}", &mut facts).is_err());
+
+        let mut facts = json!({"Order": {"items": [{}]}, "item_0": {}});
+        assert!(expand_foreach(GRL, &mut facts)
+            .unwrap_err()
+            .contains("already exists"));
+    }
+
+    #[test]
+    fn test_no_foreach_is_unchanged() {
+        let mut facts = json!({"Order": {"total": 1}});
+        let grl = "rule \"A\" { when Order.total > 0 then Order.ok = true; }";
+        assert_eq!(expand_foreach(grl, &mut facts).unwrap(), grl);
+        assert_eq!(facts, json!({"Order": {"total": 1}}));
+    }
+}
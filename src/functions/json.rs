@@ -1,4 +1,5 @@
 /// JSON manipulation built-in functions
+use crate::core::facts::json_type_name;
 use serde_json::Value;
 
 /// Parse JSON string to object
@@ -15,14 +16,27 @@ pub fn parse(args: &[Value]) -> Result<Value, String> {
     serde_json::from_str(json_str).map_err(|e| format!("Invalid JSON: {}", e))
 }
 
-/// Convert object to JSON string
-/// Usage: JsonStringify({"name": "Alice"})
+/// Convert object to JSON string, compact unless `pretty` is true
+/// Usage: JsonStringify({"name": "Alice"}) or JsonStringify({"name": "Alice"}, true)
 pub fn stringify(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err("JsonStringify requires 1 argument: object".to_string());
     }
 
-    serde_json::to_string(&args[0])
+    let pretty = match args.get(1) {
+        None => false,
+        Some(flag) => flag
+            .as_bool()
+            .ok_or("JsonStringify: pretty flag must be a boolean")?,
+    };
+
+    let result = if pretty {
+        serde_json::to_string_pretty(&args[0])
+    } else {
+        serde_json::to_string(&args[0])
+    };
+
+    result
         .map(Value::String)
         .map_err(|e| format!("Failed to stringify: {}", e))
 }
@@ -93,6 +107,132 @@ pub fn set(args: &[Value]) -> Result<Value, String> {
     Ok(obj)
 }
 
+/// Return the fallback when the value is null, otherwise the value itself
+/// Usage: Default(Order.currency, "USD")
+pub fn default(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("Default requires 2 arguments: value, fallback".to_string());
+    }
+
+    if args[0].is_null() {
+        Ok(args[1].clone())
+    } else {
+        Ok(args[0].clone())
+    }
+}
+
+/// Name the JSON type of a value: "string", "number", "boolean", "array",
+/// "object" or "null"
+/// Usage: TypeOf(Order.amount) -> "number"
+pub fn type_of(args: &[Value]) -> Result<Value, String> {
+    let value = args.first().ok_or("TypeOf requires 1 argument: value")?;
+
+    Ok(Value::String(json_type_name(value).to_string()))
+}
+
+/// Apply an RFC 7386 JSON merge patch
+/// Usage: JsonMerge({"a": {"b": 1, "c": 2}}, {"a": {"c": null, "d": 3}}) -> {"a": {"b": 1, "d": 3}}
+pub fn merge(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("JsonMerge requires 2 arguments: base, patch".to_string());
+    }
+
+    Ok(merge_patch(args[0].clone(), &args[1]))
+}
+
+/// Merge `patch` into `target`: objects merge recursively, null deletes a key,
+/// and any other patch value replaces the target outright
+fn merge_patch(target: Value, patch: &Value) -> Value {
+    let Some(patch_obj) = patch.as_object() else {
+        return patch.clone();
+    };
+
+    let mut target_obj = match target {
+        Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+
+    for (key, value) in patch_obj {
+        if value.is_null() {
+            target_obj.remove(key);
+        } else {
+            let existing = target_obj.remove(key).unwrap_or(Value::Null);
+            target_obj.insert(key.clone(), merge_patch(existing, value));
+        }
+    }
+
+    Value::Object(target_obj)
+}
+
+/// Extract a value by path with dotted keys and array indices, null when the path misses
+/// Usage: JsonPath({"a": {"b": [{"c": 1}]}}, "$.a.b[0].c") -> 1
+pub fn path(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("JsonPath requires 2 arguments: value, path".to_string());
+    }
+
+    let path = args[1].as_str().ok_or("JsonPath: path must be a string")?;
+    let segments = parse_path(path)?;
+
+    let mut current = &args[0];
+    for segment in &segments {
+        let next = match segment {
+            PathSegment::Key(key) => current.get(key.as_str()),
+            PathSegment::Index(idx) => current.get(*idx),
+        };
+        match next {
+            Some(value) => current = value,
+            None => return Ok(Value::Null),
+        }
+    }
+
+    Ok(current.clone())
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Parse "$.a.b[0].c" (leading "$" optional) into key and index segments
+fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let invalid = || format!("JsonPath: invalid path '{}'", path);
+
+    let mut rest = path.trim();
+    rest = rest.strip_prefix('$').unwrap_or(rest);
+
+    let mut segments = Vec::new();
+    let mut expect_key = !rest.is_empty() && !rest.starts_with(['.', '[']);
+
+    while !rest.is_empty() || expect_key {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').ok_or_else(invalid)?;
+            let idx = after[..end]
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| invalid())?;
+            segments.push(PathSegment::Index(idx));
+            rest = &after[end + 1..];
+            continue;
+        }
+
+        if !expect_key {
+            rest = rest.strip_prefix('.').ok_or_else(invalid)?;
+        }
+        expect_key = false;
+
+        let end = rest.find(['.', '[']).unwrap_or(rest.len());
+        if end == 0 {
+            return Err(invalid());
+        }
+        segments.push(PathSegment::Key(rest[..end].to_string()));
+        rest = &rest[end..];
+    }
+
+    Ok(segments)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,6 +250,20 @@ mod tests {
         assert_eq!(result, json!(r#"{"name":"Alice"}"#));
     }
 
+    #[test]
+    fn test_stringify_pretty() {
+        let obj = json!({"name": "Alice", "tags": ["a"]});
+        let result = stringify(&[obj.clone(), json!(true)]).unwrap();
+        let text = result.as_str().unwrap();
+        assert!(text.contains('\n'));
+        assert!(text.contains("  \"name\": \"Alice\""));
+
+        let compact = stringify(&[obj.clone(), json!(false)]).unwrap();
+        assert!(!compact.as_str().unwrap().contains('\n'));
+
+        assert!(stringify(&[obj, json!("yes")]).is_err());
+    }
+
     #[test]
     fn test_get() {
         let obj = json!({"user": {"name": "Alice", "age": 30}});
@@ -123,4 +277,104 @@ mod tests {
         let result = set(&[obj, json!("user.name"), json!("Alice")]).unwrap();
         assert_eq!(result, json!({"user": {"name": "Alice"}}));
     }
+
+    #[test]
+    fn test_default() {
+        // Present non-null value is preserved
+        let result = default(&[json!("EUR"), json!("USD")]).unwrap();
+        assert_eq!(result, json!("EUR"));
+
+        // Falsy but non-null values are preserved too
+        assert_eq!(default(&[json!(0), json!(10)]).unwrap(), json!(0));
+        assert_eq!(default(&[json!(""), json!("USD")]).unwrap(), json!(""));
+
+        // Null is replaced
+        let result = default(&[Value::Null, json!("USD")]).unwrap();
+        assert_eq!(result, json!("USD"));
+
+        assert!(default(&[json!("EUR")]).is_err());
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_eq!(type_of(&[json!("42")]).unwrap(), json!("string"));
+        assert_eq!(type_of(&[json!(42)]).unwrap(), json!("number"));
+        assert_eq!(type_of(&[json!(4.2)]).unwrap(), json!("number"));
+        assert_eq!(type_of(&[json!(false)]).unwrap(), json!("boolean"));
+        assert_eq!(type_of(&[json!([1, 2])]).unwrap(), json!("array"));
+        assert_eq!(type_of(&[json!({"a": 1})]).unwrap(), json!("object"));
+        assert_eq!(type_of(&[Value::Null]).unwrap(), json!("null"));
+        assert!(type_of(&[]).is_err());
+    }
+
+    #[test]
+    fn test_merge_nested() {
+        let base = json!({"user": {"name": "Alice", "address": {"city": "Paris"}}, "score": 1});
+        let patch = json!({"user": {"address": {"zip": "75001"}}, "score": 2});
+        let result = merge(&[base, patch]).unwrap();
+        assert_eq!(
+            result,
+            json!({"user": {"name": "Alice", "address": {"city": "Paris", "zip": "75001"}}, "score": 2})
+        );
+    }
+
+    #[test]
+    fn test_merge_null_deletes_key() {
+        let base = json!({"a": {"b": 1, "c": 2}, "d": 3});
+        let patch = json!({"a": {"c": null}, "d": null, "e": null});
+        let result = merge(&[base, patch]).unwrap();
+        assert_eq!(result, json!({"a": {"b": 1}}));
+    }
+
+    #[test]
+    fn test_merge_non_object_patch_replaces() {
+        // Arrays and scalars replace rather than merge (RFC 7386)
+        let result = merge(&[json!({"tags": ["a", "b"]}), json!({"tags": ["c"]})]).unwrap();
+        assert_eq!(result, json!({"tags": ["c"]}));
+        assert_eq!(merge(&[json!({"a": 1}), json!("x")]).unwrap(), json!("x"));
+        assert_eq!(
+            merge(&[json!("x"), json!({"a": {"b": null}})]).unwrap(),
+            json!({"a": {}})
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        let value = json!({"a": {"b": [{"c": 1}, {"c": 2}]}, "items": [[10, 20]]});
+        assert_eq!(
+            path(&[value.clone(), json!("$.a.b[0].c")]).unwrap(),
+            json!(1)
+        );
+        assert_eq!(path(&[value.clone(), json!("a.b[1].c")]).unwrap(), json!(2));
+        assert_eq!(
+            path(&[value.clone(), json!("$.items[0][1]")]).unwrap(),
+            json!(20)
+        );
+        assert_eq!(path(&[value.clone(), json!("$")]).unwrap(), value);
+    }
+
+    #[test]
+    fn test_path_miss_returns_null() {
+        let value = json!({"a": {"b": [{"c": 1}]}});
+        assert_eq!(
+            path(&[value.clone(), json!("$.a.b[5].c")]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(path(&[value.clone(), json!("$.a.x")]).unwrap(), Value::Null);
+        // Indexing an object or keying an array misses rather than erroring
+        assert_eq!(
+            path(&[value.clone(), json!("$.a[0]")]).unwrap(),
+            Value::Null
+        );
+        assert_eq!(path(&[value, json!("$.a.b.c")]).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_path_invalid() {
+        let value = json!({});
+        assert!(path(&[value.clone(), json!("$.a..b")]).is_err());
+        assert!(path(&[value.clone(), json!("$.a[x]")]).is_err());
+        assert!(path(&[value.clone(), json!("$.a[0")]).is_err());
+        assert!(path(&[value, json!("$.")]).is_err());
+    }
 }
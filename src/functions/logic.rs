@@ -0,0 +1,75 @@
+/// Logic built-in functions
+use serde_json::Value;
+
+/// Check whether a value equals any element of an array
+/// Usage: InList(Customer.country, ["US", "CA", "MX"])
+pub fn in_list(args: &[Value]) -> Result<Value, String> {
+    membership("InList", args).map(Value::Bool)
+}
+
+/// Check whether a value equals no element of an array
+/// Usage: NotInList(Customer.country, ["US", "CA", "MX"])
+pub fn not_in_list(args: &[Value]) -> Result<Value, String> {
+    membership("NotInList", args).map(|found| Value::Bool(!found))
+}
+
+fn membership(name: &str, args: &[Value]) -> Result<bool, String> {
+    if args.len() < 2 {
+        return Err(format!("{} requires 2 arguments: value, array", name));
+    }
+
+    let list = args[1]
+        .as_array()
+        .ok_or_else(|| format!("{}: second argument must be an array", name))?;
+
+    Ok(list.iter().any(|item| values_equal(&args[0], item)))
+}
+
+/// JSON equality, except numbers compare by value (1 == 1.0)
+pub(crate) fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_in_list() {
+        let countries = json!(["US", "CA", "MX"]);
+
+        assert_eq!(
+            in_list(&[json!("CA"), countries.clone()]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            in_list(&[json!("FR"), countries.clone()]).unwrap(),
+            json!(false)
+        );
+        assert_eq!(not_in_list(&[json!("FR"), countries]).unwrap(), json!(true));
+    }
+
+    #[test]
+    fn test_in_list_numbers_and_objects() {
+        assert_eq!(
+            in_list(&[json!(2), json!([1.0, 2.0, 3.0])]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            in_list(&[json!({"id": 1}), json!([{"id": 1}])]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(in_list(&[json!("1"), json!([1])]).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_in_list_errors() {
+        assert!(in_list(&[json!("US")]).is_err());
+        assert!(in_list(&[json!("US"), json!("US,CA")]).is_err());
+        assert!(not_in_list(&[json!("US"), json!(null)]).is_err());
+    }
+}
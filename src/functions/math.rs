@@ -1,8 +1,26 @@
 /// Math built-in functions
+///
+/// Integer arguments give integer results where the operation allows it
+/// (Abs, Round, Min, Max, Floor, Ceil), so `Abs(-5)` is `5`, not `5.0`, and
+/// still equals an integer fact.
 use serde_json::Value;
 
+/// Integer value of a JSON number, if it is one (i64 or u64)
+fn as_integer(value: &Value) -> Option<i128> {
+    value
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| value.as_u64().map(i128::from))
+}
+
 /// Round a number to specified decimal places
-/// Usage: Round(3.14159, 2) -> 3.14
+/// Usage: Round(3.14159, 2) -> 3.14, Round(2.5, 0, "half_even") -> 2
+///
+/// The optional mode decides ties: "half_up" (default) rounds them away from
+/// zero (2.5 -> 3, -2.5 -> -3); "half_even" rounds them to the even neighbour
+/// (2.5 -> 2, 3.5 -> 4), the banker's rounding used for money so that ties
+/// don't bias totals upwards. Only exact ties differ: a value like 2.675 is
+/// stored as 2.67499999... and rounds to 2.67 in both modes.
 pub fn round(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err("Round requires at least 1 argument: number".to_string());
@@ -20,8 +38,31 @@ pub fn round(args: &[Value]) -> Result<Value, String> {
         0
     };
 
+    let half_even = match args.get(2) {
+        None => false,
+        Some(mode) => match mode.as_str() {
+            Some("half_up") => false,
+            Some("half_even") => true,
+            _ => {
+                return Err(format!(
+                    "Round: mode must be \"half_up\" or \"half_even\", got {}",
+                    mode
+                ))
+            }
+        },
+    };
+
+    if as_integer(&args[0]).is_some() {
+        return Ok(args[0].clone());
+    }
+
     let multiplier = 10_f64.powi(decimals as i32);
-    let rounded = (num * multiplier).round() / multiplier;
+    let scaled = num * multiplier;
+    let rounded = if half_even {
+        scaled.round_ties_even()
+    } else {
+        scaled.round()
+    } / multiplier;
 
     Ok(serde_json::Number::from_f64(rounded)
         .map(Value::Number)
@@ -37,6 +78,13 @@ pub fn abs(args: &[Value]) -> Result<Value, String> {
 
     let num = args[0].as_f64().ok_or("Abs: argument must be a number")?;
 
+    if let Some(i) = args[0].as_i64() {
+        return Ok(Value::Number(i.unsigned_abs().into()));
+    }
+    if args[0].is_u64() {
+        return Ok(args[0].clone());
+    }
+
     Ok(serde_json::Number::from_f64(num.abs())
         .map(Value::Number)
         .unwrap_or(Value::Null))
@@ -58,6 +106,17 @@ pub fn min(args: &[Value]) -> Result<Value, String> {
         .collect();
 
     let numbers = numbers?;
+
+    let integers: Option<Vec<i128>> = args.iter().map(as_integer).collect();
+    if let Some(integers) = integers {
+        let (index, _) = integers
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, i)| **i)
+            .expect("at least 2 arguments");
+        return Ok(args[index].clone());
+    }
+
     let min_val = numbers.into_iter().fold(f64::INFINITY, |a, b| a.min(b));
 
     Ok(serde_json::Number::from_f64(min_val)
@@ -81,6 +140,17 @@ pub fn max(args: &[Value]) -> Result<Value, String> {
         .collect();
 
     let numbers = numbers?;
+
+    let integers: Option<Vec<i128>> = args.iter().map(as_integer).collect();
+    if let Some(integers) = integers {
+        let (index, _) = integers
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, i)| **i)
+            .expect("at least 2 arguments");
+        return Ok(args[index].clone());
+    }
+
     let max_val = numbers.into_iter().fold(f64::NEG_INFINITY, |a, b| a.max(b));
 
     Ok(serde_json::Number::from_f64(max_val)
@@ -97,6 +167,10 @@ pub fn floor(args: &[Value]) -> Result<Value, String> {
 
     let num = args[0].as_f64().ok_or("Floor: argument must be a number")?;
 
+    if as_integer(&args[0]).is_some() {
+        return Ok(args[0].clone());
+    }
+
     Ok(serde_json::Number::from_f64(num.floor())
         .map(Value::Number)
         .unwrap_or(Value::Null))
@@ -111,6 +185,10 @@ pub fn ceil(args: &[Value]) -> Result<Value, String> {
 
     let num = args[0].as_f64().ok_or("Ceil: argument must be a number")?;
 
+    if as_integer(&args[0]).is_some() {
+        return Ok(args[0].clone());
+    }
+
     Ok(serde_json::Number::from_f64(num.ceil())
         .map(Value::Number)
         .unwrap_or(Value::Null))
@@ -134,6 +212,66 @@ pub fn sqrt(args: &[Value]) -> Result<Value, String> {
         .unwrap_or(Value::Null))
 }
 
+/// Inclusive range check
+/// Usage: Between(Customer.age, 18, 65) -> true
+pub fn between(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 3 {
+        return Err("Between requires 3 arguments: value, low, high".to_string());
+    }
+
+    let value = args[0].as_f64().ok_or("Between: value must be a number")?;
+    let low = args[1].as_f64().ok_or("Between: low must be a number")?;
+    let high = args[2].as_f64().ok_or("Between: high must be a number")?;
+
+    if low > high {
+        return Err(format!(
+            "Between: low ({}) must not be greater than high ({})",
+            low, high
+        ));
+    }
+
+    Ok(Value::Bool(value >= low && value <= high))
+}
+
+/// Round to the nearest multiple of a step
+/// Usage: RoundToNearest(2.37, 0.05) -> 2.35, RoundToNearest(123, 5) -> 125
+///
+/// The result is rounded to the step's decimal places, so binary
+/// floating-point error (47 * 0.05 = 2.3500000000000001) does not leak out.
+pub fn round_to_nearest(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("RoundToNearest requires 2 arguments: value, step".to_string());
+    }
+
+    let value = args[0]
+        .as_f64()
+        .ok_or("RoundToNearest: value must be a number")?;
+    let step = args[1]
+        .as_f64()
+        .ok_or("RoundToNearest: step must be a number")?;
+
+    if step <= 0.0 {
+        return Err(format!(
+            "RoundToNearest: step must be positive, got {}",
+            step
+        ));
+    }
+
+    // Decimal places of the step as written (0.05 -> 2, 5 -> 0)
+    let decimals = step
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len().min(15)) as i32;
+    let multiplier = 10_f64.powi(decimals);
+
+    let nearest = (value / step).round() * step;
+    let rounded = (nearest * multiplier).round() / multiplier;
+
+    Ok(serde_json::Number::from_f64(rounded)
+        .map(Value::Number)
+        .unwrap_or(Value::Null))
+}
+
 #[cfg(test)]
 #[allow(clippy::approx_constant)]
 mod tests {
@@ -146,6 +284,37 @@ mod tests {
         assert_eq!(round(&[json!(3.7)]).unwrap(), json!(4.0));
     }
 
+    #[test]
+    fn test_round_modes() {
+        assert_eq!(round(&[json!(2.5), json!(0)]).unwrap(), json!(3.0));
+        assert_eq!(
+            round(&[json!(2.5), json!(0), json!("half_up")]).unwrap(),
+            json!(3.0)
+        );
+        assert_eq!(
+            round(&[json!(2.5), json!(0), json!("half_even")]).unwrap(),
+            json!(2.0)
+        );
+        assert_eq!(
+            round(&[json!(3.5), json!(0), json!("half_even")]).unwrap(),
+            json!(4.0)
+        );
+        assert_eq!(
+            round(&[json!(-2.5), json!(0), json!("half_even")]).unwrap(),
+            json!(-2.0)
+        );
+        assert_eq!(
+            round(&[json!(1.125), json!(2), json!("half_even")]).unwrap(),
+            json!(1.12)
+        );
+        // Non-ties round to nearest in both modes
+        assert_eq!(
+            round(&[json!(2.6), json!(0), json!("half_even")]).unwrap(),
+            json!(3.0)
+        );
+        assert!(round(&[json!(2.5), json!(0), json!("down")]).is_err());
+    }
+
     #[test]
     fn test_abs() {
         assert_eq!(abs(&[json!(-5.5)]).unwrap(), json!(5.5));
@@ -154,12 +323,32 @@ mod tests {
 
     #[test]
     fn test_min() {
-        assert_eq!(min(&[json!(5), json!(10), json!(3)]).unwrap(), json!(3.0));
+        assert_eq!(min(&[json!(5), json!(10), json!(3)]).unwrap(), json!(3));
+        assert_eq!(min(&[json!(5), json!(2.5)]).unwrap(), json!(2.5));
     }
 
     #[test]
     fn test_max() {
-        assert_eq!(max(&[json!(5), json!(10), json!(3)]).unwrap(), json!(10.0));
+        assert_eq!(max(&[json!(5), json!(10), json!(3)]).unwrap(), json!(10));
+        assert_eq!(max(&[json!(5), json!(2.5)]).unwrap(), json!(5.0));
+    }
+
+    #[test]
+    fn test_integers_stay_integers() {
+        assert_eq!(abs(&[json!(-5)]).unwrap(), json!(5));
+        assert!(abs(&[json!(-5)]).unwrap().is_i64());
+        assert_eq!(
+            abs(&[json!(i64::MIN)]).unwrap(),
+            json!(i64::MIN.unsigned_abs())
+        );
+        assert!(round(&[json!(7), json!(2)]).unwrap().is_i64());
+        assert!(floor(&[json!(-3)]).unwrap().is_i64());
+        assert!(ceil(&[json!(3)]).unwrap().is_i64());
+        assert!(min(&[json!(-1), json!(u64::MAX)]).unwrap().is_i64());
+        assert_eq!(max(&[json!(-1), json!(u64::MAX)]).unwrap(), json!(u64::MAX));
+
+        // Integral floats stay floats
+        assert!(abs(&[json!(-5.0)]).unwrap().is_f64());
     }
 
     #[test]
@@ -176,4 +365,73 @@ mod tests {
     fn test_sqrt() {
         assert_eq!(sqrt(&[json!(16)]).unwrap(), json!(4.0));
     }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(
+            between(&[json!(30), json!(18), json!(65)]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            between(&[json!(17.9), json!(18), json!(65)]).unwrap(),
+            json!(false)
+        );
+        assert_eq!(
+            between(&[json!(66), json!(18), json!(65)]).unwrap(),
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn test_between_boundaries() {
+        assert_eq!(
+            between(&[json!(18), json!(18), json!(65)]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            between(&[json!(65), json!(18), json!(65)]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            between(&[json!(5), json!(5), json!(5)]).unwrap(),
+            json!(true)
+        );
+    }
+
+    #[test]
+    fn test_between_reversed_bounds() {
+        assert!(between(&[json!(30), json!(65), json!(18)]).is_err());
+    }
+
+    #[test]
+    fn test_round_to_nearest() {
+        assert_eq!(
+            round_to_nearest(&[json!(2.37), json!(0.05)]).unwrap(),
+            json!(2.35)
+        );
+        assert_eq!(
+            round_to_nearest(&[json!(2.38), json!(0.05)]).unwrap(),
+            json!(2.4)
+        );
+        assert_eq!(
+            round_to_nearest(&[json!(123), json!(5)]).unwrap(),
+            json!(125.0)
+        );
+        assert_eq!(
+            round_to_nearest(&[json!(0.3), json!(0.1)]).unwrap(),
+            json!(0.3)
+        );
+        assert_eq!(
+            round_to_nearest(&[json!(-7.4), json!(0.25)]).unwrap(),
+            json!(-7.5)
+        );
+    }
+
+    #[test]
+    fn test_round_to_nearest_invalid_step() {
+        assert!(round_to_nearest(&[json!(10), json!(0)]).is_err());
+        assert!(round_to_nearest(&[json!(10), json!(-5)]).is_err());
+        assert!(round_to_nearest(&[json!(10)]).is_err());
+        assert!(round_to_nearest(&[json!("10"), json!(5)]).is_err());
+    }
 }
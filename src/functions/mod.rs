@@ -1,11 +1,17 @@
 /// Built-in functions library for GRL
 /// Provides date/time, string, math, and JSON utilities
+pub mod array;
+pub mod convert;
+pub mod crypto;
 pub mod datetime;
+pub mod foreach;
 pub mod json;
+pub mod logic;
 pub mod math;
 pub mod preprocessing;
 pub mod registration;
 pub mod string;
+pub mod template;
 
 use serde_json::Value;
 use std::collections::HashMap;
@@ -28,12 +34,23 @@ lazy_static::lazy_static! {
         // String functions
         m.insert("IsValidEmail", string::is_valid_email as FunctionImpl);
         m.insert("Contains", string::contains as FunctionImpl);
+        m.insert("IndexOf", string::index_of as FunctionImpl);
+        m.insert("LastIndexOf", string::last_index_of as FunctionImpl);
         m.insert("RegexMatch", string::regex_match as FunctionImpl);
         m.insert("ToUpper", string::to_upper as FunctionImpl);
         m.insert("ToLower", string::to_lower as FunctionImpl);
         m.insert("Trim", string::trim as FunctionImpl);
+        m.insert("TrimStart", string::trim_start as FunctionImpl);
+        m.insert("TrimEnd", string::trim_end as FunctionImpl);
+        m.insert("PadLeft", string::pad_left as FunctionImpl);
+        m.insert("PadRight", string::pad_right as FunctionImpl);
         m.insert("Length", string::length as FunctionImpl);
+        m.insert("ByteLength", string::byte_length as FunctionImpl);
         m.insert("Substring", string::substring as FunctionImpl);
+        m.insert("Levenshtein", string::levenshtein as FunctionImpl);
+        m.insert("Similarity", string::similarity as FunctionImpl);
+        m.insert("EqualsIgnoreCase", string::equals_ignore_case as FunctionImpl);
+        m.insert("ContainsIgnoreCase", string::contains_ignore_case as FunctionImpl);
 
         // Math functions
         m.insert("Round", math::round as FunctionImpl);
@@ -43,12 +60,38 @@ lazy_static::lazy_static! {
         m.insert("Floor", math::floor as FunctionImpl);
         m.insert("Ceil", math::ceil as FunctionImpl);
         m.insert("Sqrt", math::sqrt as FunctionImpl);
+        m.insert("Between", math::between as FunctionImpl);
+        m.insert("RoundToNearest", math::round_to_nearest as FunctionImpl);
 
         // JSON functions
         m.insert("JsonParse", json::parse as FunctionImpl);
         m.insert("JsonStringify", json::stringify as FunctionImpl);
         m.insert("JsonGet", json::get as FunctionImpl);
         m.insert("JsonSet", json::set as FunctionImpl);
+        m.insert("Default", json::default as FunctionImpl);
+        m.insert("JsonMerge", json::merge as FunctionImpl);
+        m.insert("JsonPath", json::path as FunctionImpl);
+        m.insert("TypeOf", json::type_of as FunctionImpl);
+
+        // Conversion functions
+        m.insert("ToNumber", convert::to_number as FunctionImpl);
+        m.insert("ToString", convert::to_string as FunctionImpl);
+        m.insert("ToBool", convert::to_bool as FunctionImpl);
+
+        // Crypto functions
+        m.insert("Encrypt", crypto::encrypt as FunctionImpl);
+        m.insert("Decrypt", crypto::decrypt as FunctionImpl);
+
+        // Logic functions
+        m.insert("InList", logic::in_list as FunctionImpl);
+        m.insert("NotInList", logic::not_in_list as FunctionImpl);
+
+        // Array functions
+        m.insert("Sort", array::sort as FunctionImpl);
+        m.insert("Distinct", array::distinct as FunctionImpl);
+
+        // Template functions
+        m.insert("FormatTemplate", template::format_template as FunctionImpl);
 
         m
     };
@@ -15,6 +15,8 @@ pub struct FunctionCall {
     pub name: String,
     /// Raw arguments: "Customer.email"
     pub raw_args: String,
+    /// Arguments after field resolution (filled in during preprocessing)
+    pub resolved_args: Vec<Value>,
     /// Evaluated result value (computed during preprocessing)
     pub result_value: Option<Value>,
     /// Whether this function is in a 'when' clause (true) or 'then' clause (false)
@@ -66,6 +68,7 @@ pub fn parse_function_calls(grl_code: &str) -> Result<Vec<FunctionCall>, String>
             original_text,
             name,
             raw_args,
+            resolved_args: Vec::new(),
             result_value: None, // Will be filled during evaluation
             in_when_clause,
             computed_field,
@@ -76,20 +79,57 @@ pub fn parse_function_calls(grl_code: &str) -> Result<Vec<FunctionCall>, String>
 }
 
 /// Extract context object from function arguments
+/// Uses the first dotted field reference anywhere in the arguments, ignoring
+/// string literals and numbers.
 /// Examples:
 ///   "Order.createdAt" → Some("Order")
+///   " Order.createdAt " → Some("Order")
 ///   "Customer.email, Customer.name" → Some("Customer")
+///   "1.08 * Order.amount" → Some("Order")
+///   "\"a.b\", User.name" → Some("User")
 ///   "42, 100" → None
 fn extract_context_from_args(raw_args: &str) -> Option<String> {
-    // Get first argument
-    let first_arg = raw_args.split(',').next()?.trim();
-
-    // Check if it's a dotted field reference (e.g., "Order.createdAt")
-    if first_arg.contains('.') && !first_arg.starts_with('"') {
-        // Extract the first part before the dot
-        let parts: Vec<&str> = first_arg.split('.').collect();
-        if parts.len() >= 2 {
-            return Some(parts[0].to_string());
+    let mut chars = raw_args.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if c == '"' {
+            // Skip the string literal, honoring escapes
+            let mut escaped = false;
+            for (_, c) in chars.by_ref() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => break,
+                    _ => {}
+                }
+            }
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            // Identifier: take it, and use it if a field access follows
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+
+            let is_field_access = raw_args[end..].starts_with('.')
+                && raw_args[end + 1..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+            if is_field_access {
+                return Some(raw_args[start..end].to_string());
+            }
+        } else if c.is_ascii_digit() {
+            // Skip numbers (including decimals like 1.08)
+            while chars
+                .peek()
+                .is_some_and(|&(_, c)| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+            {
+                chars.next();
+            }
         }
     }
 
@@ -163,24 +203,43 @@ pub fn transform_grl(grl_code: &str, function_calls: &[FunctionCall]) -> String
 
 /// Evaluate a function call and return the result
 pub fn evaluate_function_call(call: &FunctionCall, facts: &Value) -> Result<Value, String> {
+    evaluate_with_args(call, facts).map(|(_, result)| result)
+}
+
+/// Evaluate a function call, returning the resolved arguments alongside the result
+fn evaluate_with_args(call: &FunctionCall, facts: &Value) -> Result<(Vec<Value>, Value), String> {
+    // FactExists checks key presence, so its argument must not be resolved to a value
+    if call.name == "FactExists" {
+        let result = fact_exists(&call.raw_args, facts)?;
+        return Ok((
+            vec![Value::String(call.raw_args.trim().to_string())],
+            result,
+        ));
+    }
+
     // Parse arguments and resolve field references
     let args = parse_and_resolve_args(&call.raw_args, facts)?;
 
     // Execute the function
-    super::execute_function(&call.name, &args)
+    let result = super::execute_function(&call.name, &args)?;
+    Ok((args, result))
 }
 
 /// Parse function arguments and resolve field references from facts
 fn parse_and_resolve_args(raw_args: &str, facts: &Value) -> Result<Vec<Value>, String> {
     let mut args = Vec::new();
 
-    // Split arguments by comma (simple approach - doesn't handle nested commas)
-    for arg_str in raw_args.split(',') {
+    for arg_str in split_args(raw_args) {
         let arg_trimmed = arg_str.trim();
 
         // Try to resolve as field reference first (e.g., "Customer.email")
         if let Some(value) = resolve_field_reference(arg_trimmed, facts) {
             args.push(value);
+        } else if arg_trimmed.starts_with('[') && arg_trimmed.ends_with(']') {
+            // Array literal (JSON syntax)
+            let array = serde_json::from_str(arg_trimmed)
+                .map_err(|e| format!("Invalid array literal {}: {}", arg_trimmed, e))?;
+            args.push(array);
         } else if arg_trimmed.starts_with('"') && arg_trimmed.ends_with('"') {
             // String literal
             let s = arg_trimmed.trim_matches('"');
@@ -211,6 +270,34 @@ fn parse_and_resolve_args(raw_args: &str, facts: &Value) -> Result<Vec<Value>, S
     Ok(args)
 }
 
+/// Split raw arguments on top-level commas, keeping commas inside string
+/// literals and array literals
+fn split_args(raw_args: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut start = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut depth = 0usize;
+
+    for (i, c) in raw_args.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '[' if !in_string => depth += 1,
+            ']' if !in_string => depth = depth.saturating_sub(1),
+            ',' if !in_string && depth == 0 => {
+                args.push(&raw_args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(&raw_args[start..]);
+
+    args
+}
+
 /// Resolve field reference from facts (supports both nested and flat formats)
 /// Nested: facts["Customer"]["email"]
 /// Flat: facts["Customer.email"]
@@ -235,21 +322,48 @@ fn resolve_field_reference(field_ref: &str, facts: &Value) -> Option<Value> {
     Some(current.clone())
 }
 
+/// Check whether a field reference is present in facts, even if its value is null
+/// Usage: FactExists(Customer.email)
+fn fact_exists(raw_args: &str, facts: &Value) -> Result<Value, String> {
+    let field_ref = raw_args.trim();
+    if field_ref.is_empty() || field_ref.contains(',') {
+        return Err("FactExists requires 1 argument: field reference".to_string());
+    }
+
+    Ok(Value::Bool(
+        resolve_field_reference(field_ref, facts).is_some(),
+    ))
+}
+
 /// Main preprocessing function - transform GRL by evaluating functions
 /// - Functions in 'when' clauses: inject into facts as fields
 /// - Functions in 'then' clauses: replace with literal values
 pub fn preprocess_grl_with_functions(grl_code: &str, facts: &mut Value) -> Result<String, String> {
+    preprocess_grl_with_function_calls(grl_code, facts).map(|(grl, _)| grl)
+}
+
+/// Same as `preprocess_grl_with_functions`, but also returns the evaluated calls
+/// (resolved args and results) so debug sessions can record them
+pub fn preprocess_grl_with_function_calls(
+    grl_code: &str,
+    facts: &mut Value,
+) -> Result<(String, Vec<FunctionCall>), String> {
+    // Step 0: Expand foreach blocks into per-element facts and rules
+    let expanded_grl = super::foreach::expand_foreach(grl_code, facts)?;
+    let grl_code = expanded_grl.as_str();
+
     // Step 1: Parse function calls and detect context (when vs then)
     let mut function_calls = parse_function_calls(grl_code)?;
 
     if function_calls.is_empty() {
         // No functions to process
-        return Ok(grl_code.to_string());
+        return Ok((grl_code.to_string(), function_calls));
     }
 
     // Step 2: Evaluate functions and store results
     for call in &mut function_calls {
-        let result = evaluate_function_call(call, facts)?;
+        let (args, result) = evaluate_with_args(call, facts)?;
+        call.resolved_args = args;
         call.result_value = Some(result.clone());
 
         // Step 3: For 'when' clause functions, inject result into facts
@@ -269,7 +383,45 @@ pub fn preprocess_grl_with_functions(grl_code: &str, facts: &mut Value) -> Resul
     // - 'then' clauses: replace with literal values
     let transformed_grl = transform_grl(grl_code, &function_calls);
 
-    Ok(transformed_grl)
+    Ok((transformed_grl, function_calls))
+}
+
+/// Run preprocessing without executing rules and describe the result
+///
+/// Returns `{transformed_grl, injected_fields, function_calls}`, where
+/// `injected_fields` maps each `when`-clause computed field to the value
+/// injected into facts. The caller's facts are not modified.
+pub fn preprocess_preview(grl_code: &str, facts: &Value) -> Result<Value, String> {
+    let mut facts = facts.clone();
+    let (transformed_grl, calls) = preprocess_grl_with_function_calls(grl_code, &mut facts)?;
+
+    let injected_fields: serde_json::Map<String, Value> = calls
+        .iter()
+        .filter_map(|call| {
+            let field = call.computed_field.clone()?;
+            Some((field, call.result_value.clone().unwrap_or(Value::Null)))
+        })
+        .collect();
+
+    let function_calls: Vec<Value> = calls
+        .iter()
+        .map(|call| {
+            serde_json::json!({
+                "original_text": call.original_text,
+                "name": call.name,
+                "args": call.resolved_args,
+                "result": call.result_value,
+                "clause": if call.in_when_clause { "when" } else { "then" },
+                "computed_field": call.computed_field,
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "transformed_grl": transformed_grl,
+        "injected_fields": injected_fields,
+        "function_calls": function_calls,
+    }))
 }
 
 #[cfg(test)]
@@ -331,6 +483,7 @@ mod tests {
             original_text: "IsValidEmail(Customer.email)".to_string(),
             name: "IsValidEmail".to_string(),
             raw_args: "Customer.email".to_string(),
+            resolved_args: vec![json!("test@example.com")],
             result_value: Some(json!(true)),
             in_when_clause: false,
             computed_field: None,
@@ -348,6 +501,7 @@ mod tests {
             original_text: "DaysSince(Order.createdAt)".to_string(),
             name: "DaysSince".to_string(),
             raw_args: "Order.createdAt".to_string(),
+            resolved_args: vec![json!("2024-01-01")],
             result_value: Some(json!(724)),
             in_when_clause: true,
             computed_field: Some("Order.__func_0_dayssince".to_string()),
@@ -380,6 +534,91 @@ mod tests {
         assert_eq!(value, Some(Value::String("test@example.com".to_string())));
     }
 
+    #[test]
+    fn test_fact_exists_present_null_vs_absent() {
+        let facts = json!({
+            "Customer": {
+                "email": null
+            },
+            "Order.coupon": null
+        });
+
+        let call = |args: &str| FunctionCall {
+            original_text: format!("FactExists({})", args),
+            name: "FactExists".to_string(),
+            raw_args: args.to_string(),
+            resolved_args: Vec::new(),
+            result_value: None,
+            in_when_clause: false,
+            computed_field: None,
+        };
+
+        // Present but null (nested and flat)
+        assert_eq!(
+            evaluate_function_call(&call("Customer.email"), &facts).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            evaluate_function_call(&call("Order.coupon"), &facts).unwrap(),
+            json!(true)
+        );
+
+        // Absent
+        assert_eq!(
+            evaluate_function_call(&call("Customer.phone"), &facts).unwrap(),
+            json!(false)
+        );
+        assert_eq!(
+            evaluate_function_call(&call("Account.id"), &facts).unwrap(),
+            json!(false)
+        );
+
+        assert!(evaluate_function_call(&call("Customer.email, Customer.phone"), &facts).is_err());
+    }
+
+    #[test]
+    fn test_preprocess_grl_with_fact_exists_in_when_clause() {
+        let grl = r#"
+            rule "MissingPhone" {
+                when FactExists(Customer.phone) == false
+                then Customer.needsPhone = true;
+            }
+        "#;
+
+        let mut facts = json!({
+            "Customer": {
+                "email": null
+            }
+        });
+
+        let transformed = preprocess_grl_with_functions(grl, &mut facts).unwrap();
+        assert!(transformed.contains("when Customer.__func_0_factexists == false"));
+        assert_eq!(facts["Customer.__func_0_factexists"], json!(false));
+    }
+
+    #[test]
+    fn test_preprocess_grl_with_function_calls_records_args() {
+        let grl = r#"
+            rule "Pricing" {
+                when Order.total > 0
+                then Order.rounded = Round(Order.total, 1);
+            }
+        "#;
+
+        let mut facts = json!({
+            "Order": {"total": 12.34}
+        });
+
+        let (transformed, calls) = preprocess_grl_with_function_calls(grl, &mut facts).unwrap();
+
+        assert!(transformed.contains("Order.rounded = 12.3"));
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "Round");
+        assert_eq!(calls[0].resolved_args, vec![json!(12.34), json!(1)]);
+        assert_eq!(calls[0].result_value, Some(json!(12.3)));
+        assert!(!calls[0].in_when_clause);
+    }
+
     #[test]
     fn test_preprocess_grl_with_functions_then_clause() {
         let grl = r#"
@@ -428,4 +667,110 @@ mod tests {
         // The value should be the number of days
         assert!(facts["Order.__func_0_dayssince"].is_number());
     }
+
+    #[test]
+    fn test_split_args_respects_string_literals() {
+        assert_eq!(
+            split_args(r#""Hi {name}, you owe {amount}", Customer"#),
+            vec![r#""Hi {name}, you owe {amount}""#, " Customer"]
+        );
+        assert_eq!(
+            split_args(r#""a \"quoted, part\"", 2"#),
+            vec![r#""a \"quoted, part\"""#, " 2"]
+        );
+        assert_eq!(split_args("Order.total"), vec!["Order.total"]);
+    }
+
+    #[test]
+    fn test_format_template_in_then_clause() {
+        let grl = r#"rule "Notify" { when Customer.balance > 0 then Customer.message = FormatTemplate("Hi {name}, you owe {balance}", Customer); }"#;
+        let mut facts = json!({ "Customer": { "name": "Ana", "balance": 12 } });
+
+        let result = preprocess_grl_with_functions(grl, &mut facts).unwrap();
+        assert!(result.contains(r#"Customer.message = "Hi Ana, you owe 12";"#));
+    }
+
+    #[test]
+    fn test_in_list_with_array_literal_in_when_clause() {
+        let grl = r#"rule "NorthAmerica" { when InList(Customer.country, ["US", "CA", "MX"]) == true then Customer.region = "NA"; }"#;
+
+        let calls = parse_function_calls(grl).unwrap();
+        assert_eq!(calls.len(), 1);
+
+        let mut facts = json!({ "Customer": { "country": "CA" } });
+        let (result, calls) = preprocess_grl_with_function_calls(grl, &mut facts).unwrap();
+        assert_eq!(calls[0].resolved_args[1], json!(["US", "CA", "MX"]));
+        assert_eq!(facts["Customer.__func_0_inlist"], json!(true));
+        assert!(result.contains("when Customer.__func_0_inlist == true"));
+
+        let mut facts = json!({ "Customer": { "country": "FR" } });
+        preprocess_grl_with_functions(grl, &mut facts).unwrap();
+        assert_eq!(facts["Customer.__func_0_inlist"], json!(false));
+    }
+
+    #[test]
+    fn test_preprocess_preview() {
+        let grl = r#"rule "Old" { when DaysSince(Order.createdAt) > 90 then Order.label = ToUpper("stale"); }"#;
+        let facts = json!({ "Order": { "createdAt": "2020-01-01" } });
+
+        let preview = preprocess_preview(grl, &facts).unwrap();
+
+        assert_eq!(facts, json!({ "Order": { "createdAt": "2020-01-01" } }));
+        assert!(preview["transformed_grl"]
+            .as_str()
+            .unwrap()
+            .contains(r#"when Order.__func_0_dayssince > 90 then Order.label = "STALE";"#));
+        assert!(
+            preview["injected_fields"]["Order.__func_0_dayssince"]
+                .as_i64()
+                .unwrap()
+                > 90
+        );
+
+        let calls = preview["function_calls"].as_array().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0]["clause"], "when");
+        assert_eq!(calls[0]["args"], json!(["2020-01-01"]));
+        assert_eq!(calls[1]["clause"], "then");
+        assert_eq!(calls[1]["result"], "STALE");
+        assert!(calls[1]["computed_field"].is_null());
+    }
+
+    #[test]
+    fn test_extract_context_from_args() {
+        assert_eq!(
+            extract_context_from_args("Order.createdAt"),
+            Some("Order".to_string())
+        );
+        assert_eq!(
+            extract_context_from_args("  Order.createdAt  "),
+            Some("Order".to_string())
+        );
+        assert_eq!(
+            extract_context_from_args("1.08 * Order.amount, 2"),
+            Some("Order".to_string())
+        );
+        assert_eq!(
+            extract_context_from_args(r#""Hi {a.b}, \"x.y\"", Customer.name"#),
+            Some("Customer".to_string())
+        );
+        assert_eq!(
+            extract_context_from_args("Order.items.count"),
+            Some("Order".to_string())
+        );
+        assert_eq!(extract_context_from_args("42, 100"), None);
+        assert_eq!(extract_context_from_args(r#""a.b""#), None);
+        assert_eq!(extract_context_from_args("1.5e3"), None);
+    }
+
+    #[test]
+    fn test_when_clause_context_with_leading_literal() {
+        let grl = r#"rule "Big" { when Round(1.08 * Order.amount) > 100 then Order.big = true; }"#;
+
+        let calls = parse_function_calls(grl).unwrap();
+        assert_eq!(
+            calls[0].computed_field.as_deref(),
+            Some("Order.__func_0_round")
+        );
+    }
 }
@@ -2,7 +2,7 @@
 use rust_rule_engine::{RuleEngineError, RustRuleEngine, Value};
 use serde_json::Value as JsonValue;
 
-use super::{datetime, json, math, string};
+use super::{array, convert, crypto, datetime, json, logic, math, string, template};
 
 /// Convert string error to RuleEngineError
 fn to_eval_error(msg: String) -> RuleEngineError {
@@ -15,6 +15,11 @@ pub fn register_all_functions(engine: &mut RustRuleEngine) {
     register_string_functions(engine);
     register_math_functions(engine);
     register_json_functions(engine);
+    register_convert_functions(engine);
+    register_crypto_functions(engine);
+    register_logic_functions(engine);
+    register_array_functions(engine);
+    register_template_functions(engine);
 }
 
 /// Register date/time functions
@@ -71,6 +76,20 @@ fn register_string_functions(engine: &mut RustRuleEngine) {
         json_to_value(&result).map_err(to_eval_error)
     });
 
+    // IndexOf
+    engine.register_function("IndexOf", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::index_of(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // LastIndexOf
+    engine.register_function("LastIndexOf", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::last_index_of(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
     // RegexMatch
     engine.register_function("RegexMatch", |args, _facts| {
         let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
@@ -99,6 +118,34 @@ fn register_string_functions(engine: &mut RustRuleEngine) {
         json_to_value(&result).map_err(to_eval_error)
     });
 
+    // TrimStart
+    engine.register_function("TrimStart", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::trim_start(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // TrimEnd
+    engine.register_function("TrimEnd", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::trim_end(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // PadLeft
+    engine.register_function("PadLeft", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::pad_left(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // PadRight
+    engine.register_function("PadRight", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::pad_right(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
     // Length
     engine.register_function("Length", |args, _facts| {
         let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
@@ -106,12 +153,47 @@ fn register_string_functions(engine: &mut RustRuleEngine) {
         json_to_value(&result).map_err(to_eval_error)
     });
 
+    // ByteLength
+    engine.register_function("ByteLength", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::byte_length(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
     // Substring
     engine.register_function("Substring", |args, _facts| {
         let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
         let result = string::substring(&json_args).map_err(to_eval_error)?;
         json_to_value(&result).map_err(to_eval_error)
     });
+
+    // Levenshtein
+    engine.register_function("Levenshtein", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::levenshtein(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // Similarity
+    engine.register_function("Similarity", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::similarity(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // EqualsIgnoreCase
+    engine.register_function("EqualsIgnoreCase", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::equals_ignore_case(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // ContainsIgnoreCase
+    engine.register_function("ContainsIgnoreCase", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = string::contains_ignore_case(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
 }
 
 /// Register math functions
@@ -164,6 +246,20 @@ fn register_math_functions(engine: &mut RustRuleEngine) {
         let result = math::sqrt(&json_args).map_err(to_eval_error)?;
         json_to_value(&result).map_err(to_eval_error)
     });
+
+    // Between
+    engine.register_function("Between", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = math::between(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // RoundToNearest
+    engine.register_function("RoundToNearest", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = math::round_to_nearest(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
 }
 
 /// Register JSON functions
@@ -195,6 +291,119 @@ fn register_json_functions(engine: &mut RustRuleEngine) {
         let result = json::set(&json_args).map_err(to_eval_error)?;
         json_to_value(&result).map_err(to_eval_error)
     });
+
+    // Default
+    engine.register_function("Default", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = json::default(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // JsonMerge
+    engine.register_function("JsonMerge", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = json::merge(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // JsonPath
+    engine.register_function("JsonPath", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = json::path(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // TypeOf
+    engine.register_function("TypeOf", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = json::type_of(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+}
+
+/// Register conversion functions
+fn register_convert_functions(engine: &mut RustRuleEngine) {
+    // ToNumber
+    engine.register_function("ToNumber", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = convert::to_number(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // ToString
+    engine.register_function("ToString", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = convert::to_string(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // ToBool
+    engine.register_function("ToBool", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = convert::to_bool(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+}
+
+/// Register crypto functions
+fn register_crypto_functions(engine: &mut RustRuleEngine) {
+    // Encrypt
+    engine.register_function("Encrypt", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = crypto::encrypt(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // Decrypt
+    engine.register_function("Decrypt", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = crypto::decrypt(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+}
+
+/// Register logic functions
+fn register_logic_functions(engine: &mut RustRuleEngine) {
+    // InList
+    engine.register_function("InList", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = logic::in_list(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // NotInList
+    engine.register_function("NotInList", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = logic::not_in_list(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+}
+
+/// Register array functions
+fn register_array_functions(engine: &mut RustRuleEngine) {
+    // Sort
+    engine.register_function("Sort", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = array::sort(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+
+    // Distinct
+    engine.register_function("Distinct", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = array::distinct(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
+}
+
+/// Register template functions
+fn register_template_functions(engine: &mut RustRuleEngine) {
+    // FormatTemplate
+    engine.register_function("FormatTemplate", |args, _facts| {
+        let json_args: Vec<JsonValue> = args.iter().map(value_to_json).collect();
+        let result = template::format_template(&json_args).map_err(to_eval_error)?;
+        json_to_value(&result).map_err(to_eval_error)
+    });
 }
 
 /// Convert rust-rule-engine Value to serde_json Value
@@ -20,16 +20,26 @@ pub fn is_valid_email(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(email_regex.is_match(email)))
 }
 
-/// Check if string contains substring
-/// Usage: Contains("hello world", "world")
+/// Check if string contains substring, or array contains element
+/// Usage: Contains("hello world", "world"), Contains(Order.tags, "urgent")
+///
+/// Array elements are compared like InList (numbers by value, 1 == 1.0).
 pub fn contains(args: &[Value]) -> Result<Value, String> {
     if args.len() < 2 {
-        return Err("Contains requires 2 arguments: string, substring".to_string());
+        return Err("Contains requires 2 arguments: string or array, value".to_string());
+    }
+
+    if let Value::Array(items) = &args[0] {
+        return Ok(Value::Bool(
+            items
+                .iter()
+                .any(|item| super::logic::values_equal(item, &args[1])),
+        ));
     }
 
     let haystack = args[0]
         .as_str()
-        .ok_or("Contains: first argument must be a string")?;
+        .ok_or("Contains: first argument must be a string or an array")?;
 
     let needle = args[1]
         .as_str()
@@ -38,6 +48,50 @@ pub fn contains(args: &[Value]) -> Result<Value, String> {
     Ok(Value::Bool(haystack.contains(needle)))
 }
 
+/// Char index of the first occurrence of a substring, or -1
+/// Usage: IndexOf("SKU-001-EU", "-") -> 3
+///
+/// Indexes count characters, not bytes, so they line up with Length on
+/// non-ASCII text (Substring takes byte offsets). An empty substring matches
+/// at 0.
+pub fn index_of(args: &[Value]) -> Result<Value, String> {
+    let (haystack, needle) = search_args("IndexOf", args)?;
+    Ok(char_position(haystack, haystack.find(needle)))
+}
+
+/// Char index of the last occurrence of a substring, or -1
+/// Usage: LastIndexOf("SKU-001-EU", "-") -> 7
+///
+/// An empty substring matches at the end (the string's length).
+pub fn last_index_of(args: &[Value]) -> Result<Value, String> {
+    let (haystack, needle) = search_args("LastIndexOf", args)?;
+    Ok(char_position(haystack, haystack.rfind(needle)))
+}
+
+fn search_args<'a>(name: &str, args: &'a [Value]) -> Result<(&'a str, &'a str), String> {
+    if args.len() < 2 {
+        return Err(format!("{} requires 2 arguments: string, substring", name));
+    }
+
+    let haystack = args[0]
+        .as_str()
+        .ok_or_else(|| format!("{}: first argument must be a string", name))?;
+
+    let needle = args[1]
+        .as_str()
+        .ok_or_else(|| format!("{}: second argument must be a string", name))?;
+
+    Ok((haystack, needle))
+}
+
+/// Convert a byte offset from str::find into a char index (-1 if not found)
+fn char_position(text: &str, byte_offset: Option<usize>) -> Value {
+    match byte_offset {
+        Some(offset) => Value::Number((text[..offset].chars().count() as i64).into()),
+        None => Value::Number((-1).into()),
+    }
+}
+
 /// Match string against regex pattern
 /// Usage: RegexMatch("hello123", "\\d+")
 pub fn regex_match(args: &[Value]) -> Result<Value, String> {
@@ -98,8 +152,100 @@ pub fn trim(args: &[Value]) -> Result<Value, String> {
     Ok(Value::String(text.trim().to_string()))
 }
 
-/// Get string length
-/// Usage: Length("hello")
+/// Trim whitespace from the start
+/// Usage: TrimStart("  hello  ") -> "hello  "
+pub fn trim_start(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err("TrimStart requires 1 argument: string".to_string());
+    }
+
+    let text = args[0]
+        .as_str()
+        .ok_or("TrimStart: argument must be a string")?;
+
+    Ok(Value::String(text.trim_start().to_string()))
+}
+
+/// Trim whitespace from the end
+/// Usage: TrimEnd("  hello  ") -> "  hello"
+pub fn trim_end(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err("TrimEnd requires 1 argument: string".to_string());
+    }
+
+    let text = args[0]
+        .as_str()
+        .ok_or("TrimEnd: argument must be a string")?;
+
+    Ok(Value::String(text.trim_end().to_string()))
+}
+
+/// Pad the start of a string to a width in characters
+/// Usage: PadLeft(Invoice.number, 8, "0") -> "00001234"
+///
+/// The pad character defaults to a space. A pad string that isn't exactly
+/// one character is an error. Strings already at least `width` characters
+/// long are returned unchanged.
+pub fn pad_left(args: &[Value]) -> Result<Value, String> {
+    pad("PadLeft", args, true)
+}
+
+/// Pad the end of a string to a width in characters
+/// Usage: PadRight(Customer.name, 10, ".") -> "Alice....."
+///
+/// Same rules as PadLeft.
+pub fn pad_right(args: &[Value]) -> Result<Value, String> {
+    pad("PadRight", args, false)
+}
+
+fn pad(name: &str, args: &[Value], left: bool) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err(format!(
+            "{} requires 2-3 arguments: string, width, [pad character]",
+            name
+        ));
+    }
+
+    let text = args[0]
+        .as_str()
+        .ok_or_else(|| format!("{}: first argument must be a string", name))?;
+
+    let width = args[1]
+        .as_u64()
+        .ok_or_else(|| format!("{}: width must be a non-negative integer", name))?
+        as usize;
+
+    let fill = match args.get(2) {
+        None => ' ',
+        Some(value) => {
+            let fill = value
+                .as_str()
+                .ok_or_else(|| format!("{}: pad character must be a string", name))?;
+            let mut chars = fill.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => {
+                    return Err(format!(
+                        "{}: pad character must be exactly one character, got '{}'",
+                        name, fill
+                    ))
+                }
+            }
+        }
+    };
+
+    let missing = width.saturating_sub(text.chars().count());
+    let padding = fill.to_string().repeat(missing);
+
+    Ok(Value::String(if left {
+        padding + text
+    } else {
+        text.to_string() + &padding
+    }))
+}
+
+/// Get string length in characters (Unicode scalar values)
+/// Usage: Length("héllo") -> 5
 pub fn length(args: &[Value]) -> Result<Value, String> {
     if args.is_empty() {
         return Err("Length requires 1 argument: string".to_string());
@@ -109,6 +255,20 @@ pub fn length(args: &[Value]) -> Result<Value, String> {
         .as_str()
         .ok_or("Length: argument must be a string")?;
 
+    Ok(Value::Number(text.chars().count().into()))
+}
+
+/// Get string length in UTF-8 bytes
+/// Usage: ByteLength("héllo") -> 6
+pub fn byte_length(args: &[Value]) -> Result<Value, String> {
+    if args.is_empty() {
+        return Err("ByteLength requires 1 argument: string".to_string());
+    }
+
+    let text = args[0]
+        .as_str()
+        .ok_or("ByteLength: argument must be a string")?;
+
     Ok(Value::Number(text.len().into()))
 }
 
@@ -141,6 +301,111 @@ pub fn substring(args: &[Value]) -> Result<Value, String> {
     Ok(Value::String(result.to_string()))
 }
 
+/// Edit distance between two strings (insertions, deletions, substitutions)
+/// Usage: Levenshtein("kitten", "sitting") -> 3
+pub fn levenshtein(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("Levenshtein requires 2 arguments: string, string".to_string());
+    }
+
+    let a = args[0]
+        .as_str()
+        .ok_or("Levenshtein: first argument must be a string")?;
+    let b = args[1]
+        .as_str()
+        .ok_or("Levenshtein: second argument must be a string")?;
+
+    Ok(Value::Number((edit_distance(a, b) as u64).into()))
+}
+
+/// Normalized similarity ratio between two strings (1.0 = identical)
+/// Usage: Similarity("kitten", "sitting") -> 0.571...
+pub fn similarity(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("Similarity requires 2 arguments: string, string".to_string());
+    }
+
+    let a = args[0]
+        .as_str()
+        .ok_or("Similarity: first argument must be a string")?;
+    let b = args[1]
+        .as_str()
+        .ok_or("Similarity: second argument must be a string")?;
+
+    let max_len = a.chars().count().max(b.chars().count());
+    let ratio = if max_len == 0 {
+        1.0
+    } else {
+        1.0 - edit_distance(a, b) as f64 / max_len as f64
+    };
+
+    Ok(serde_json::Number::from_f64(ratio)
+        .map(Value::Number)
+        .unwrap_or(Value::Null))
+}
+
+/// Levenshtein distance over chars, keeping a single row sized by the shorter input
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    let mut row: Vec<usize> = (0..=short.len()).collect();
+    for (i, lc) in long.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, sc) in short.iter().enumerate() {
+            let above = row[j + 1];
+            let cost = if lc == sc { 0 } else { 1 };
+            row[j + 1] = (above + 1).min(row[j] + 1).min(diagonal + cost);
+            diagonal = above;
+        }
+    }
+
+    row[short.len()]
+}
+
+/// Compare two strings ignoring case
+/// Usage: EqualsIgnoreCase(Customer.country, "us")
+///
+/// Uses Unicode lowercase mapping, not full case folding: "ÄRGER" equals
+/// "ärger", but "STRASSE" does not equal "straße".
+pub fn equals_ignore_case(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("EqualsIgnoreCase requires 2 arguments: string, string".to_string());
+    }
+
+    let a = args[0]
+        .as_str()
+        .ok_or("EqualsIgnoreCase: first argument must be a string")?;
+
+    let b = args[1]
+        .as_str()
+        .ok_or("EqualsIgnoreCase: second argument must be a string")?;
+
+    Ok(Value::Bool(a.to_lowercase() == b.to_lowercase()))
+}
+
+/// Check if string contains substring, ignoring case
+/// Usage: ContainsIgnoreCase(Ticket.subject, "refund")
+pub fn contains_ignore_case(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err("ContainsIgnoreCase requires 2 arguments: string, substring".to_string());
+    }
+
+    let haystack = args[0]
+        .as_str()
+        .ok_or("ContainsIgnoreCase: first argument must be a string")?;
+
+    let needle = args[1]
+        .as_str()
+        .ok_or("ContainsIgnoreCase: second argument must be a string")?;
+
+    Ok(Value::Bool(
+        haystack.to_lowercase().contains(&needle.to_lowercase()),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +433,64 @@ mod tests {
             contains(&[json!("hello world"), json!("foo")]).unwrap(),
             json!(false)
         );
+        assert!(contains(&[json!(42), json!("4")]).is_err());
+    }
+
+    #[test]
+    fn test_contains_array() {
+        let tags = json!(["urgent", "vip"]);
+        assert_eq!(
+            contains(&[tags.clone(), json!("urgent")]).unwrap(),
+            json!(true)
+        );
+        // Element membership, not substring
+        assert_eq!(contains(&[tags, json!("urg")]).unwrap(), json!(false));
+        assert_eq!(
+            contains(&[json!([1, 2.5, 3]), json!(3.0)]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(contains(&[json!([]), json!(null)]).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_index_of() {
+        assert_eq!(
+            index_of(&[json!("SKU-001-EU"), json!("-")]).unwrap(),
+            json!(3)
+        );
+        assert_eq!(
+            last_index_of(&[json!("SKU-001-EU"), json!("-")]).unwrap(),
+            json!(7)
+        );
+        assert_eq!(
+            index_of(&[json!("SKU-001-EU"), json!("US")]).unwrap(),
+            json!(-1)
+        );
+        assert_eq!(
+            last_index_of(&[json!("SKU-001-EU"), json!("US")]).unwrap(),
+            json!(-1)
+        );
+        assert_eq!(index_of(&[json!("abc"), json!("")]).unwrap(), json!(0));
+        assert_eq!(last_index_of(&[json!("abc"), json!("")]).unwrap(), json!(3));
+        assert!(index_of(&[json!("abc")]).is_err());
+        assert!(last_index_of(&[json!("abc"), json!(1)]).is_err());
+    }
+
+    #[test]
+    fn test_index_of_multibyte() {
+        // Char indexes, not byte offsets ("é" and "日" are multi-byte)
+        assert_eq!(
+            index_of(&[json!("café-crème"), json!("-")]).unwrap(),
+            json!(4)
+        );
+        assert_eq!(
+            last_index_of(&[json!("日本/日本"), json!("日本")]).unwrap(),
+            json!(3)
+        );
+        assert_eq!(
+            index_of(&[json!("🎉ab🎉ab"), json!("ab")]).unwrap(),
+            json!(1)
+        );
     }
 
     #[test]
@@ -197,9 +520,59 @@ mod tests {
         assert_eq!(trim(&[json!("  hello  ")]).unwrap(), json!("hello"));
     }
 
+    #[test]
+    fn test_trim_start_end() {
+        assert_eq!(trim_start(&[json!("  hello  ")]).unwrap(), json!("hello  "));
+        assert_eq!(trim_end(&[json!("  hello  ")]).unwrap(), json!("  hello"));
+        assert_eq!(trim_end(&[json!("hello\n\t")]).unwrap(), json!("hello"));
+        assert!(trim_start(&[json!(1)]).is_err());
+    }
+
+    #[test]
+    fn test_pad() {
+        assert_eq!(
+            pad_left(&[json!("1234"), json!(8), json!("0")]).unwrap(),
+            json!("00001234")
+        );
+        assert_eq!(
+            pad_right(&[json!("Alice"), json!(8), json!(".")]).unwrap(),
+            json!("Alice...")
+        );
+        // Defaults to space
+        assert_eq!(pad_left(&[json!("ab"), json!(4)]).unwrap(), json!("  ab"));
+        // Already wide enough: unchanged, never truncated
+        assert_eq!(
+            pad_right(&[json!("abcdef"), json!(4), json!("-")]).unwrap(),
+            json!("abcdef")
+        );
+        // Width counts characters, not bytes
+        assert_eq!(
+            pad_left(&[json!("né"), json!(4), json!("★")]).unwrap(),
+            json!("★★né")
+        );
+    }
+
+    #[test]
+    fn test_pad_invalid_arguments() {
+        assert!(pad_left(&[json!("ab"), json!(4), json!("ab")]).is_err());
+        assert!(pad_left(&[json!("ab"), json!(4), json!("")]).is_err());
+        assert!(pad_right(&[json!("ab"), json!(-1)]).is_err());
+        assert!(pad_right(&[json!("ab")]).is_err());
+    }
+
     #[test]
     fn test_length() {
         assert_eq!(length(&[json!("hello")]).unwrap(), json!(5));
+        assert_eq!(byte_length(&[json!("hello")]).unwrap(), json!(5));
+    }
+
+    #[test]
+    fn test_length_multibyte() {
+        assert_eq!(length(&[json!("héllo")]).unwrap(), json!(5));
+        assert_eq!(byte_length(&[json!("héllo")]).unwrap(), json!(6));
+        assert_eq!(length(&[json!("日本語🎉")]).unwrap(), json!(4));
+        assert_eq!(byte_length(&[json!("日本語🎉")]).unwrap(), json!(13));
+        assert!(byte_length(&[json!(5)]).is_err());
     }
 
     #[test]
@@ -209,4 +582,97 @@ mod tests {
             json!("ell")
         );
     }
+
+    #[test]
+    fn test_levenshtein() {
+        // Identical
+        assert_eq!(
+            levenshtein(&[json!("alice"), json!("alice")]).unwrap(),
+            json!(0)
+        );
+        // Near match
+        assert_eq!(
+            levenshtein(&[json!("kitten"), json!("sitting")]).unwrap(),
+            json!(3)
+        );
+        // Disjoint
+        assert_eq!(
+            levenshtein(&[json!("abc"), json!("xyz")]).unwrap(),
+            json!(3)
+        );
+        assert_eq!(levenshtein(&[json!(""), json!("abc")]).unwrap(), json!(3));
+        // Counts chars, not bytes
+        assert_eq!(
+            levenshtein(&[json!("café"), json!("cafe")]).unwrap(),
+            json!(1)
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(
+            similarity(&[json!("alice"), json!("alice")]).unwrap(),
+            json!(1.0)
+        );
+        assert_eq!(
+            similarity(&[json!("abc"), json!("xyz")]).unwrap(),
+            json!(0.0)
+        );
+        assert_eq!(similarity(&[json!(""), json!("")]).unwrap(), json!(1.0));
+
+        let near = similarity(&[json!("john.smith"), json!("jon.smith")])
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((near - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_equals_ignore_case() {
+        assert_eq!(
+            equals_ignore_case(&[json!("Hello World"), json!("hELLO wORLD")]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            equals_ignore_case(&[json!("hello"), json!("help")]).unwrap(),
+            json!(false)
+        );
+        assert!(equals_ignore_case(&[json!("hello")]).is_err());
+        assert!(equals_ignore_case(&[json!("hello"), json!(1)]).is_err());
+    }
+
+    #[test]
+    fn test_equals_ignore_case_unicode() {
+        // Simple lowercase mapping handles accented letters and capital sharp s
+        assert_eq!(
+            equals_ignore_case(&[json!("ÄRGER"), json!("ärger")]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            equals_ignore_case(&[json!("STRAẞE"), json!("straße")]).unwrap(),
+            json!(true)
+        );
+        // ...but is not full case folding: ß does not expand to ss
+        assert_eq!(
+            equals_ignore_case(&[json!("STRASSE"), json!("straße")]).unwrap(),
+            json!(false)
+        );
+    }
+
+    #[test]
+    fn test_contains_ignore_case() {
+        assert_eq!(
+            contains_ignore_case(&[json!("Request a REFUND now"), json!("refund")]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            contains_ignore_case(&[json!("Große Bestellung"), json!("GROẞE")]).unwrap(),
+            json!(true)
+        );
+        assert_eq!(
+            contains_ignore_case(&[json!("hello"), json!("world")]).unwrap(),
+            json!(false)
+        );
+        assert!(contains_ignore_case(&[json!("hello")]).is_err());
+    }
 }
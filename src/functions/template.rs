@@ -0,0 +1,145 @@
+/// Template built-in functions
+use serde_json::Value;
+
+/// Replace `{key}` placeholders with values from an object
+/// Usage: FormatTemplate("Hi {name}, you owe {amount}", Customer)
+///        FormatTemplate("Ship to {address.city}", Customer, true)  // strict
+///
+/// Keys may be dotted paths into nested objects. `{{` and `}}` produce literal
+/// braces. Unknown placeholders are left as-is, or rejected when the optional
+/// third argument (strict) is true.
+pub fn format_template(args: &[Value]) -> Result<Value, String> {
+    if args.len() < 2 {
+        return Err(
+            "FormatTemplate requires 2-3 arguments: template, object, [strict]".to_string(),
+        );
+    }
+
+    let template = args[0]
+        .as_str()
+        .ok_or("FormatTemplate: first argument must be a string")?;
+
+    let object = &args[1];
+    if !object.is_object() {
+        return Err("FormatTemplate: second argument must be an object".to_string());
+    }
+
+    let strict = match args.get(2) {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(_) => return Err("FormatTemplate: third argument must be a boolean".to_string()),
+    };
+
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        output.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            output.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+
+        if tail.starts_with('}') {
+            return Err("FormatTemplate: unmatched '}' in template".to_string());
+        }
+
+        let end = tail
+            .find('}')
+            .ok_or("FormatTemplate: unclosed '{' in template")?;
+        let key = tail[1..end].trim();
+
+        match lookup(object, key) {
+            Some(value) => output.push_str(&render(value)),
+            None if strict => {
+                return Err(format!("FormatTemplate: unknown placeholder '{{{}}}'", key))
+            }
+            None => output.push_str(&tail[..=end]),
+        }
+
+        rest = &tail[end + 1..];
+    }
+    output.push_str(rest);
+
+    Ok(Value::String(output))
+}
+
+/// Look up a (possibly dotted) key in an object
+fn lookup<'a>(object: &'a Value, key: &str) -> Option<&'a Value> {
+    if key.is_empty() {
+        return None;
+    }
+
+    // Prefer a literal key, so flattened "a.b" keys also work
+    if let Some(value) = object.get(key) {
+        return Some(value);
+    }
+
+    key.split('.')
+        .try_fold(object, |current, part| current.get(part))
+}
+
+/// Render a value for insertion into text
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_template() {
+        let customer = json!({ "name": "Ana", "amount": 42.5, "vip": true });
+
+        assert_eq!(
+            format_template(&[json!("Hi {name}, you owe {amount}"), customer.clone()]).unwrap(),
+            json!("Hi Ana, you owe 42.5")
+        );
+        assert_eq!(
+            format_template(&[json!("VIP: { vip }"), customer]).unwrap(),
+            json!("VIP: true")
+        );
+    }
+
+    #[test]
+    fn test_format_template_nested_and_escaped() {
+        let customer = json!({ "address": { "city": "Lyon" }, "note": null });
+
+        assert_eq!(
+            format_template(&[json!("{{city}} = {address.city}{note}"), customer]).unwrap(),
+            json!("{city} = Lyon")
+        );
+    }
+
+    #[test]
+    fn test_format_template_unknown_placeholder() {
+        let customer = json!({ "name": "Ana" });
+
+        assert_eq!(
+            format_template(&[json!("Hi {name} {missing}"), customer.clone()]).unwrap(),
+            json!("Hi Ana {missing}")
+        );
+
+        let err = format_template(&[json!("Hi {missing}"), customer, json!(true)]).unwrap_err();
+        assert!(err.contains("{missing}"));
+    }
+
+    #[test]
+    fn test_format_template_errors() {
+        let obj = json!({});
+        assert!(format_template(&[json!("Hi")]).is_err());
+        assert!(format_template(&[json!("Hi"), json!("not an object")]).is_err());
+        assert!(format_template(&[json!("Hi {name"), obj.clone()]).is_err());
+        assert!(format_template(&[json!("Hi name}"), obj.clone()]).is_err());
+        assert!(format_template(&[json!("Hi"), obj, json!("yes")]).is_err());
+    }
+}
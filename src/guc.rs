@@ -0,0 +1,61 @@
+//! PostgreSQL configuration parameters (GUCs)
+//!
+//! Registered once per backend from `_PG_init`; read with `.get()`.
+
+use pgrx::{GucContext, GucFlags, GucRegistry, GucSetting};
+use std::ffi::CString;
+
+/// `rule_engine.audit_enabled`: log stored-rule executions to rule_execution_audit
+pub static AUDIT_ENABLED: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+/// `rule_engine.fixed_now`: timestamp that Now/Today/DaysSince use instead of the clock
+pub static FIXED_NOW: GucSetting<Option<CString>> = GucSetting::<Option<CString>>::new(None);
+
+/// `rule_engine.datasource_redact_keys`: comma-separated keys masked in datasource request logs
+pub static DATASOURCE_REDACT_KEYS: GucSetting<Option<CString>> =
+    GucSetting::<Option<CString>>::new(Some(c"authorization,api_key,password,token"));
+
+/// Register all extension settings
+pub fn init() {
+    GucRegistry::define_bool_guc(
+        c"rule_engine.audit_enabled",
+        c"Record stored-rule executions in rule_execution_audit.",
+        c"When on, rule_execute_by_name logs the rule, version, user, a hash of the facts and whether any rule fired.",
+        &AUDIT_ENABLED,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+
+    GucRegistry::define_string_guc(
+        c"rule_engine.fixed_now",
+        c"Fixed current time for the Now, Today and DaysSince functions.",
+        c"Accepts RFC 3339, 'YYYY-MM-DD HH:MM:SS' (UTC) or 'YYYY-MM-DD'. Empty uses the real clock.",
+        &FIXED_NOW,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    crate::functions::datetime::set_clock_source(Some(fixed_now));
+
+    GucRegistry::define_string_guc(
+        c"rule_engine.datasource_redact_keys",
+        c"Keys whose values are masked before datasource requests are logged.",
+        c"Comma-separated, case-insensitive. A param or header key containing any entry is stored as '***' in rule_datasource_requests.",
+        &DATASOURCE_REDACT_KEYS,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+}
+
+/// The `rule_engine.fixed_now` timestamp, if set
+fn fixed_now() -> Option<chrono::DateTime<chrono::Utc>> {
+    let value = FIXED_NOW.get()?;
+    let value = value.to_string_lossy();
+    if value.trim().is_empty() {
+        return None;
+    }
+
+    match crate::functions::datetime::parse_fixed_now(&value) {
+        Ok(ts) => Some(ts),
+        Err(e) => pgrx::error!("rule_engine.fixed_now: {}", e),
+    }
+}
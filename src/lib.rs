@@ -5,26 +5,48 @@ mod datasources;
 mod debug;
 mod error;
 mod functions;
+mod guc;
 
 #[allow(dead_code, unused_imports)]
 pub mod nats;
 
 mod repository;
+mod shutdown;
 mod validation;
 
 // Re-export public API functions - Forward Chaining
 pub use api::engine::run_rule_engine;
-pub use api::health::{rule_engine_health_check, rule_engine_version};
+pub use api::health::{rule_engine_health_check, rule_engine_self_test, rule_engine_version};
 
 // Re-export public API functions - Backward Chaining
-pub use api::backward::{can_prove_goal, query_backward_chaining, query_backward_chaining_multi};
+pub use api::backward::{
+    can_prove_goal, can_prove_goal_explain, query_backward_chaining, query_backward_chaining_multi,
+};
 
 // Re-export public API functions - Rule Repository
+pub use repository::bundle::{rule_export, rule_import, rules_export_by_tag, rules_import_bundle};
 pub use repository::queries::{
-    rule_activate, rule_can_prove_by_name, rule_delete, rule_execute_by_name, rule_get,
-    rule_query_by_name, rule_save, rule_tag_add, rule_tag_remove,
+    rule_activate, rule_apply_to_table, rule_can_prove_by_name, rule_can_prove_by_name_explain,
+    rule_delete, rule_engine_warmup, rule_execute_by_name, rule_execute_by_name_jsonb,
+    rule_execute_with_includes, rule_get, rule_query_by_name, rule_query_multi_by_name, rule_save,
+    rule_tag_add, rule_tag_remove, rule_test, rule_validate_detailed,
 };
 pub use repository::test_spi::test_spi_simple;
 
 // PostgreSQL extension magic
 pgrx::pg_module_magic!();
+
+#[pgrx::pg_guard]
+pub extern "C-unwind" fn _PG_init() {
+    guc::init();
+}
+
+/// Hooks required by `cargo pgrx test`
+#[cfg(test)]
+pub mod pg_test {
+    pub fn setup(_options: Vec<&str>) {}
+
+    pub fn postgresql_conf_options() -> Vec<&'static str> {
+        vec![]
+    }
+}
@@ -2,6 +2,9 @@
 ///
 /// This module handles creating and configuring NATS clients.
 use async_nats::{Client, ConnectOptions};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::nats::config::{AuthType, NatsConfig};
@@ -13,13 +16,10 @@ pub async fn create_client(config: &NatsConfig) -> Result<Client, NatsError> {
     config.validate()?;
 
     // Create connection options
-    let reconnect_delay = config.reconnect_delay_ms;
+    let established = Arc::new(AtomicBool::new(false));
     let mut options = ConnectOptions::new()
         .connection_timeout(Duration::from_millis(config.connection_timeout_ms))
-        .reconnect_delay_callback(move |_| Duration::from_millis(reconnect_delay));
-
-    // Note: max_reconnects configuration handled by default async-nats behavior
-    // The library will reconnect indefinitely by default
+        .reconnect_delay_callback(reconnect_delay(config, established.clone()));
 
     // Apply authentication
     options = apply_auth(options, &config.auth_type).await?;
@@ -30,7 +30,7 @@ pub async fn create_client(config: &NatsConfig) -> Result<Client, NatsError> {
     }
 
     // Set custom name for connection
-    options = options.name("rule-engine-nats");
+    options = options.name(config.connection_name());
 
     // Connect to NATS server
     let client = options
@@ -40,9 +40,38 @@ pub async fn create_client(config: &NatsConfig) -> Result<Client, NatsError> {
             NatsError::ConnectionError(format!("Failed to connect to {}: {}", config.nats_url, e))
         })?;
 
+    // From here on, connect attempts are reconnects and count against the limit
+    established.store(true, Ordering::Relaxed);
+
     Ok(client)
 }
 
+/// Delay used once `max_reconnect_attempts` is exhausted
+const RECONNECT_PARKED_DELAY: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Reconnect delay callback enforcing `max_reconnect_attempts`
+///
+/// async-nats 0.33 reconnects forever and ignores its own `max_reconnects`,
+/// so the limit is applied here. The client passes the number of consecutive
+/// attempts, reset on every successful connect. Once a connection has been
+/// established and that number exceeds the limit, reconnecting is parked: the
+/// client stays disconnected, `check_connection` reports it unhealthy and
+/// `NatsPool::heal` can replace it. `-1` never parks.
+fn reconnect_delay(
+    config: &NatsConfig,
+    established: Arc<AtomicBool>,
+) -> impl Fn(usize) -> Duration + Send + Sync + 'static {
+    let delay = Duration::from_millis(config.reconnect_delay_ms);
+    let max_attempts = usize::try_from(config.max_reconnect_attempts).ok();
+
+    move |attempts| match max_attempts {
+        Some(max) if attempts > max && established.load(Ordering::Relaxed) => {
+            RECONNECT_PARKED_DELAY
+        }
+        _ => delay,
+    }
+}
+
 /// Apply authentication to connection options
 async fn apply_auth(
     options: ConnectOptions,
@@ -70,13 +99,29 @@ async fn apply_auth(
 }
 
 /// Apply TLS configuration
-fn apply_tls(options: ConnectOptions, _config: &NatsConfig) -> Result<ConnectOptions, NatsError> {
-    // Enable TLS
-    let options = options.require_tls(true);
+///
+/// Trusts `tls_ca_file` instead of the system roots (async-nats only loads the
+/// native roots when no CA is given) and presents `tls_cert_file`/`tls_key_file`
+/// as the client certificate for mutual TLS.
+fn apply_tls(options: ConnectOptions, config: &NatsConfig) -> Result<ConnectOptions, NatsError> {
+    let mut options = options.require_tls(true);
+
+    if let Some(ca_file) = &config.tls_ca_file {
+        options = options.add_root_certificates(PathBuf::from(ca_file));
+    }
 
-    // Note: Certificate configuration in async-nats v0.33 requires different approach
-    // For now, we'll use system certificates
-    // TODO: Add custom certificate support in future versions
+    match (&config.tls_cert_file, &config.tls_key_file) {
+        (Some(cert_file), Some(key_file)) => {
+            options =
+                options.add_client_certificate(PathBuf::from(cert_file), PathBuf::from(key_file));
+        }
+        (None, None) => {}
+        _ => {
+            return Err(NatsError::ConfigError(
+                "tls_cert_file and tls_key_file must be set together".to_string(),
+            ))
+        }
+    }
 
     Ok(options)
 }
@@ -159,6 +204,40 @@ mod tests {
         assert!(bad_config.validate().is_err());
     }
 
+    #[test]
+    fn test_reconnect_delay_enforces_limit() {
+        let config = NatsConfig {
+            reconnect_delay_ms: 250,
+            max_reconnect_attempts: 3,
+            ..Default::default()
+        };
+        let established = Arc::new(AtomicBool::new(false));
+        let callback = reconnect_delay(&config, established.clone());
+
+        // The initial connection is not a reconnect
+        assert_eq!(callback(4), Duration::from_millis(250));
+
+        established.store(true, Ordering::Relaxed);
+        for attempt in 1..=3 {
+            assert_eq!(callback(attempt), Duration::from_millis(250));
+        }
+        assert_eq!(callback(4), RECONNECT_PARKED_DELAY);
+    }
+
+    #[test]
+    fn test_reconnect_delay_infinite() {
+        let config = NatsConfig {
+            max_reconnect_attempts: -1,
+            ..Default::default()
+        };
+        let callback = reconnect_delay(&config, Arc::new(AtomicBool::new(true)));
+
+        assert_eq!(
+            callback(10_000),
+            Duration::from_millis(config.reconnect_delay_ms)
+        );
+    }
+
     // Note: Actual connection tests require a running NATS server
     // Those would be integration tests, not unit tests
 }
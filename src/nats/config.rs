@@ -22,6 +22,18 @@ pub enum AuthType {
     NKey { seed: String },
 }
 
+impl AuthType {
+    /// Authentication method name as stored in `rule_nats_config.auth_type`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Token { .. } => "token",
+            Self::Credentials { .. } => "credentials",
+            Self::NKey { .. } => "nkey",
+        }
+    }
+}
+
 /// NATS connection configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NatsConfig {
@@ -79,6 +91,10 @@ pub struct NatsConfig {
     /// TLS CA file path (optional)
     #[serde(default)]
     pub tls_ca_file: Option<String>,
+
+    /// Client name reported to the server (defaults to "rule-engine-nats")
+    #[serde(default)]
+    pub connection_name: Option<String>,
 }
 
 // Default value functions
@@ -121,10 +137,14 @@ impl Default for NatsConfig {
             tls_cert_file: None,
             tls_key_file: None,
             tls_ca_file: None,
+            connection_name: None,
         }
     }
 }
 
+/// Connection name used when none is configured
+pub const DEFAULT_CONNECTION_NAME: &str = "rule-engine-nats";
+
 impl NatsConfig {
     /// Create a new configuration with minimal settings
     pub fn new(nats_url: impl Into<String>) -> Self {
@@ -167,6 +187,104 @@ impl NatsConfig {
         self
     }
 
+    /// Set the client name reported to the server
+    pub fn with_connection_name(mut self, name: impl Into<String>) -> Self {
+        self.connection_name = Some(name.into());
+        self
+    }
+
+    /// Client name reported to the server
+    pub fn connection_name(&self) -> &str {
+        self.connection_name
+            .as_deref()
+            .unwrap_or(DEFAULT_CONNECTION_NAME)
+    }
+
+    /// Build a configuration from a `rule_nats_config` row encoded as JSON
+    ///
+    /// Keys follow the table's column names (`nats_cluster_urls`, `auth_type`
+    /// plus `auth_token` / `auth_credentials_file` / `auth_nkey_seed`). Missing
+    /// or null columns fall back to the defaults. Unless `connection_name` is
+    /// set, the connection is named after the row's `config_name`. The result
+    /// is not validated; call [`NatsConfig::validate`] before connecting.
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, NatsError> {
+        let row = value.as_object().ok_or_else(|| {
+            NatsError::ConfigError("NATS configuration must be a JSON object".to_string())
+        })?;
+        let defaults = Self::default();
+
+        let nats_url = json_string(row, "nats_url")?.ok_or_else(|| {
+            NatsError::ConfigError("Missing required field: nats_url".to_string())
+        })?;
+
+        let cluster_urls = match row.get("nats_cluster_urls") {
+            None | Some(serde_json::Value::Null) => None,
+            Some(serde_json::Value::Array(urls)) => Some(
+                urls.iter()
+                    .map(|url| {
+                        url.as_str().map(str::to_string).ok_or_else(|| {
+                            NatsError::ConfigError(
+                                "nats_cluster_urls must be an array of strings".to_string(),
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            Some(_) => {
+                return Err(NatsError::ConfigError(
+                    "nats_cluster_urls must be an array of strings".to_string(),
+                ))
+            }
+        };
+
+        let auth_type = match json_string(row, "auth_type")?.as_deref() {
+            None | Some("none") => AuthType::None,
+            Some("token") => AuthType::Token {
+                token: required_auth_field(row, "token", "auth_token")?,
+            },
+            Some("credentials") => AuthType::Credentials {
+                path: required_auth_field(row, "credentials", "auth_credentials_file")?,
+            },
+            Some("nkey") => AuthType::NKey {
+                seed: required_auth_field(row, "nkey", "auth_nkey_seed")?,
+            },
+            Some(other) => {
+                return Err(NatsError::ConfigError(format!(
+                    "Unknown auth_type '{}' (expected none, token, credentials or nkey)",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            nats_url,
+            cluster_urls,
+            auth_type,
+            connection_timeout_ms: json_u64(row, "connection_timeout_ms")?
+                .unwrap_or(defaults.connection_timeout_ms),
+            max_connections: json_u64(row, "max_connections")?
+                .map(|n| n as usize)
+                .unwrap_or(defaults.max_connections),
+            jetstream_enabled: json_bool(row, "jetstream_enabled")?
+                .unwrap_or(defaults.jetstream_enabled),
+            stream_name: json_string(row, "stream_name")?.unwrap_or(defaults.stream_name),
+            subject_prefix: json_string(row, "subject_prefix")?.unwrap_or(defaults.subject_prefix),
+            reconnect_delay_ms: json_u64(row, "reconnect_delay_ms")?
+                .unwrap_or(defaults.reconnect_delay_ms),
+            max_reconnect_attempts: json_i32(row, "max_reconnect_attempts")?
+                .unwrap_or(defaults.max_reconnect_attempts),
+            tls_enabled: json_bool(row, "tls_enabled")?.unwrap_or(defaults.tls_enabled),
+            tls_cert_file: json_string(row, "tls_cert_file")?,
+            tls_key_file: json_string(row, "tls_key_file")?,
+            tls_ca_file: json_string(row, "tls_ca_file")?,
+            connection_name: match json_string(row, "connection_name")? {
+                Some(name) => Some(name),
+                None => json_string(row, "config_name")?
+                    .map(|config_name| format!("{}-{}", DEFAULT_CONNECTION_NAME, config_name)),
+            },
+        })
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<(), NatsError> {
         // Validate URL format
@@ -196,6 +314,13 @@ impl NatsConfig {
             ));
         }
 
+        // Validate reconnect limit (-1 means unbounded)
+        if self.max_reconnect_attempts < -1 {
+            return Err(NatsError::ConfigError(
+                "Max reconnect attempts must be -1 (infinite) or a non-negative number".to_string(),
+            ));
+        }
+
         // Validate stream name if JetStream is enabled
         if self.jetstream_enabled && self.stream_name.is_empty() {
             return Err(NatsError::ConfigError(
@@ -203,22 +328,134 @@ impl NatsConfig {
             ));
         }
 
-        // Validate TLS configuration
+        // Validate subject prefix tokens
+        if !self.subject_prefix.is_empty() {
+            for token in self.subject_prefix.split('.') {
+                if token.is_empty() {
+                    return Err(NatsError::ConfigError(format!(
+                        "Subject prefix '{}' contains an empty token",
+                        self.subject_prefix
+                    )));
+                }
+                if let Some(c) = token
+                    .chars()
+                    .find(|c| c.is_whitespace() || c.is_control() || matches!(c, '*' | '>'))
+                {
+                    return Err(NatsError::ConfigError(format!(
+                        "Subject prefix '{}' contains invalid character {:?}",
+                        self.subject_prefix, c
+                    )));
+                }
+            }
+        }
+
+        // Validate TLS files
         if self.tls_enabled {
-            if let AuthType::Credentials { path } = &self.auth_type {
-                // Credentials file should exist (we'll check later)
-                if path.is_empty() {
-                    return Err(NatsError::ConfigError(
-                        "Credentials file path cannot be empty".to_string(),
-                    ));
+            if self.tls_cert_file.is_some() != self.tls_key_file.is_some() {
+                return Err(NatsError::ConfigError(
+                    "tls_cert_file and tls_key_file must be set together".to_string(),
+                ));
+            }
+
+            for (field, path) in [
+                ("tls_cert_file", &self.tls_cert_file),
+                ("tls_key_file", &self.tls_key_file),
+                ("tls_ca_file", &self.tls_ca_file),
+            ] {
+                if let Some(path) = path {
+                    if !std::path::Path::new(path).is_file() {
+                        return Err(NatsError::ConfigError(format!(
+                            "{} '{}' does not exist or is not a file",
+                            field, path
+                        )));
+                    }
                 }
             }
         }
 
+        // Validate authentication secrets
+        match &self.auth_type {
+            AuthType::None => {}
+            AuthType::Token { token } if token.is_empty() => {
+                return Err(NatsError::ConfigError(
+                    "Auth token cannot be empty".to_string(),
+                ));
+            }
+            AuthType::Credentials { path } if path.is_empty() => {
+                return Err(NatsError::ConfigError(
+                    "Credentials file path cannot be empty".to_string(),
+                ));
+            }
+            AuthType::NKey { seed } if seed.is_empty() => {
+                return Err(NatsError::ConfigError(
+                    "NKey seed cannot be empty".to_string(),
+                ));
+            }
+            AuthType::NKey { seed } if !seed.starts_with('S') => {
+                return Err(NatsError::ConfigError(
+                    "NKey seed must be a seed (starts with 'S'), not a public key".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
         Ok(())
     }
 }
 
+type JsonRow = serde_json::Map<String, serde_json::Value>;
+
+fn type_error(key: &str, expected: &str) -> NatsError {
+    NatsError::ConfigError(format!("Field '{}' must be {}", key, expected))
+}
+
+fn json_string(row: &JsonRow, key: &str) -> Result<Option<String>, NatsError> {
+    match row.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(type_error(key, "a string")),
+    }
+}
+
+fn json_u64(row: &JsonRow, key: &str) -> Result<Option<u64>, NatsError> {
+    match row.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| type_error(key, "a non-negative integer")),
+    }
+}
+
+fn json_i32(row: &JsonRow, key: &str) -> Result<Option<i32>, NatsError> {
+    match row.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| type_error(key, "an integer")),
+    }
+}
+
+fn json_bool(row: &JsonRow, key: &str) -> Result<Option<bool>, NatsError> {
+    match row.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Bool(b)) => Ok(Some(*b)),
+        Some(_) => Err(type_error(key, "a boolean")),
+    }
+}
+
+fn required_auth_field(row: &JsonRow, auth_type: &str, key: &str) -> Result<String, NatsError> {
+    match json_string(row, key)? {
+        Some(value) if !value.is_empty() => Ok(value),
+        _ => Err(NatsError::ConfigError(format!(
+            "auth_type '{}' requires {}",
+            auth_type, key
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,6 +527,60 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validation_reconnect_attempts() {
+        for attempts in [-1, 0, 5] {
+            let config = NatsConfig {
+                max_reconnect_attempts: attempts,
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok());
+        }
+
+        let config = NatsConfig {
+            max_reconnect_attempts: -2,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validation_tls_files() {
+        let dir = std::env::temp_dir().join(format!("nats-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let existing = |name: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, "pem").unwrap();
+            Some(path.to_string_lossy().into_owned())
+        };
+
+        let config = NatsConfig::default().with_tls(
+            existing("cert.pem"),
+            existing("key.pem"),
+            existing("ca.pem"),
+        );
+        assert!(config.validate().is_ok());
+
+        let missing_ca = NatsConfig::default().with_tls(
+            None,
+            None,
+            Some(dir.join("missing.pem").to_string_lossy().into_owned()),
+        );
+        assert!(missing_ca.validate().is_err());
+
+        let cert_without_key = NatsConfig::default().with_tls(existing("cert.pem"), None, None);
+        assert!(cert_without_key.validate().is_err());
+
+        // Files are only checked when TLS is enabled
+        let disabled = NatsConfig {
+            tls_ca_file: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(disabled.validate().is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_validation_empty_stream_name() {
         let config = NatsConfig {
@@ -317,4 +608,139 @@ mod tests {
         assert_eq!(config.nats_url, deserialized.nats_url);
         assert_eq!(config.stream_name, deserialized.stream_name);
     }
+
+    #[test]
+    fn test_from_json_table_row() {
+        let row = serde_json::json!({
+            "config_name": "default",
+            "nats_url": "nats://nats.internal:4222",
+            "nats_cluster_urls": ["nats://a:4222", "nats://b:4222"],
+            "auth_type": "token",
+            "auth_token": "s3cret",
+            "auth_credentials_file": null,
+            "max_connections": 4,
+            "connection_timeout_ms": 1500,
+            "reconnect_delay_ms": 500,
+            "max_reconnect_attempts": 3,
+            "jetstream_enabled": true,
+            "stream_name": "EVENTS",
+            "subject_prefix": "events",
+            "tls_enabled": false,
+            "enabled": true
+        });
+
+        let config = NatsConfig::from_json(&row).unwrap();
+        assert_eq!(config.nats_url, "nats://nats.internal:4222");
+        assert_eq!(config.cluster_urls.as_ref().map(Vec::len), Some(2));
+        assert_eq!(
+            config.auth_type,
+            AuthType::Token {
+                token: "s3cret".to_string()
+            }
+        );
+        assert_eq!(config.max_connections, 4);
+        assert_eq!(config.connection_timeout_ms, 1500);
+        assert_eq!(config.reconnect_delay_ms, 500);
+        assert_eq!(config.max_reconnect_attempts, 3);
+        assert_eq!(config.stream_name, "EVENTS");
+        assert_eq!(config.connection_name(), "rule-engine-nats-default");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_from_json_defaults() {
+        let row = serde_json::json!({ "nats_url": "nats://localhost:4222", "stream_name": null });
+
+        let config = NatsConfig::from_json(&row).unwrap();
+        assert_eq!(config.auth_type, AuthType::None);
+        assert_eq!(config.stream_name, "WEBHOOKS");
+        assert_eq!(config.max_reconnect_attempts, -1);
+        assert_eq!(config.connection_name(), DEFAULT_CONNECTION_NAME);
+
+        let named = serde_json::json!({
+            "config_name": "default",
+            "nats_url": "nats://localhost:4222",
+            "connection_name": "billing-publisher"
+        });
+        let config = NatsConfig::from_json(&named).unwrap();
+        assert_eq!(config.connection_name(), "billing-publisher");
+    }
+
+    #[test]
+    fn test_validation_subject_prefix() {
+        for prefix in ["webhooks", "app.events", "a-b_c", ""] {
+            let config = NatsConfig {
+                subject_prefix: prefix.to_string(),
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok(), "prefix {:?}", prefix);
+        }
+
+        for prefix in [
+            "my webhooks",
+            "events.*",
+            "events.>",
+            "a..b",
+            ".events",
+            "events.",
+            "tab\there",
+        ] {
+            let config = NatsConfig {
+                subject_prefix: prefix.to_string(),
+                ..Default::default()
+            };
+            assert!(config.validate().is_err(), "prefix {:?}", prefix);
+        }
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        let missing_url = serde_json::json!({ "stream_name": "X" });
+        assert!(NatsConfig::from_json(&missing_url).is_err());
+
+        let missing_seed = serde_json::json!({
+            "nats_url": "nats://localhost:4222",
+            "auth_type": "nkey"
+        });
+        let err = NatsConfig::from_json(&missing_seed).unwrap_err();
+        assert!(err.to_string().contains("auth_nkey_seed"));
+
+        let unknown_auth = serde_json::json!({
+            "nats_url": "nats://localhost:4222",
+            "auth_type": "kerberos"
+        });
+        assert!(NatsConfig::from_json(&unknown_auth).is_err());
+
+        let bad_type = serde_json::json!({
+            "nats_url": "nats://localhost:4222",
+            "max_connections": "ten"
+        });
+        assert!(NatsConfig::from_json(&bad_type).is_err());
+
+        assert!(NatsConfig::from_json(&serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    fn test_validation_auth_secrets() {
+        let empty_token = NatsConfig::default().with_auth(AuthType::Token {
+            token: String::new(),
+        });
+        assert!(empty_token.validate().is_err());
+
+        let empty_creds = NatsConfig::default().with_auth(AuthType::Credentials {
+            path: String::new(),
+        });
+        assert!(empty_creds.validate().is_err());
+
+        let public_key = NatsConfig::default().with_auth(AuthType::NKey {
+            seed: "UABC".to_string(),
+        });
+        assert!(public_key.validate().is_err());
+
+        let seed = NatsConfig::default().with_auth(AuthType::NKey {
+            seed: "SUABC".to_string(),
+        });
+        assert!(seed.validate().is_ok());
+        assert_eq!(seed.auth_type.name(), "nkey");
+    }
 }
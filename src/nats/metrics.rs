@@ -0,0 +1,165 @@
+/// Per-subject publish metrics
+///
+/// Counts and latencies for every publish attempt made by a `NatsPublisher`,
+/// keyed by subject. At most `MAX_TRACKED_SUBJECTS` subjects are tracked;
+/// publishes to further subjects are folded into the `OTHER_SUBJECTS` bucket
+/// so high-cardinality subjects can't grow the map without bound.
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Maximum number of distinct subjects tracked individually
+pub const MAX_TRACKED_SUBJECTS: usize = 256;
+
+/// Bucket for publishes to subjects beyond `MAX_TRACKED_SUBJECTS`
+pub const OTHER_SUBJECTS: &str = "_other";
+
+/// Counters for one subject
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubjectMetrics {
+    pub published: u64,
+    pub failed: u64,
+    pub total_latency_us: u64,
+    pub max_latency_us: u64,
+}
+
+impl SubjectMetrics {
+    fn record(&mut self, success: bool, latency: Duration) {
+        let latency_us = latency.as_micros() as u64;
+
+        if success {
+            self.published += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.total_latency_us += latency_us;
+        self.max_latency_us = self.max_latency_us.max(latency_us);
+    }
+
+    fn to_json(self) -> serde_json::Value {
+        let attempts = self.published + self.failed;
+        let avg_latency_ms = if attempts == 0 {
+            0.0
+        } else {
+            self.total_latency_us as f64 / attempts as f64 / 1000.0
+        };
+
+        json!({
+            "published": self.published,
+            "failed": self.failed,
+            "avg_latency_ms": avg_latency_ms,
+            "max_latency_ms": self.max_latency_us as f64 / 1000.0,
+        })
+    }
+}
+
+/// Thread-safe, bounded map of subject -> metrics
+///
+/// Clones share the same counters.
+#[derive(Debug, Clone, Default)]
+pub struct PublishMetrics {
+    subjects: Arc<Mutex<HashMap<String, SubjectMetrics>>>,
+}
+
+impl PublishMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one publish attempt to `subject`
+    pub fn record(&self, subject: &str, success: bool, latency: Duration) {
+        let mut subjects = self.subjects.lock().unwrap_or_else(|e| e.into_inner());
+
+        let key = if subjects.contains_key(subject) || subjects.len() < MAX_TRACKED_SUBJECTS {
+            subject
+        } else {
+            OTHER_SUBJECTS
+        };
+
+        subjects
+            .entry(key.to_string())
+            .or_default()
+            .record(success, latency);
+    }
+
+    /// Metrics for a single subject, if any publish to it was recorded
+    pub fn get(&self, subject: &str) -> Option<SubjectMetrics> {
+        self.subjects
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(subject)
+            .copied()
+    }
+
+    /// All subjects as JSON: `{subject: {published, failed, avg_latency_ms, max_latency_ms}}`
+    pub fn snapshot(&self) -> serde_json::Value {
+        let subjects = self.subjects.lock().unwrap_or_else(|e| e.into_inner());
+
+        serde_json::Value::Object(
+            subjects
+                .iter()
+                .map(|(subject, metrics)| (subject.clone(), metrics.to_json()))
+                .collect(),
+        )
+    }
+
+    /// Clear all counters
+    pub fn reset(&self) {
+        self.subjects
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_per_subject() {
+        let metrics = PublishMetrics::new();
+        metrics.record("webhooks.orders", true, Duration::from_millis(2));
+        metrics.record("webhooks.orders", false, Duration::from_millis(4));
+        metrics.record("webhooks.users", true, Duration::from_millis(1));
+
+        let orders = metrics.get("webhooks.orders").unwrap();
+        assert_eq!(orders.published, 1);
+        assert_eq!(orders.failed, 1);
+        assert_eq!(orders.max_latency_us, 4000);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot["webhooks.orders"]["avg_latency_ms"], json!(3.0));
+        assert_eq!(snapshot["webhooks.orders"]["max_latency_ms"], json!(4.0));
+        assert_eq!(snapshot["webhooks.users"]["published"], 1);
+    }
+
+    #[test]
+    fn test_bounded_subjects() {
+        let metrics = PublishMetrics::new();
+        for i in 0..MAX_TRACKED_SUBJECTS + 10 {
+            metrics.record(&format!("subject.{}", i), true, Duration::ZERO);
+        }
+        // Subjects already tracked keep their own entry
+        metrics.record("subject.0", true, Duration::ZERO);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot.as_object().unwrap().len(),
+            MAX_TRACKED_SUBJECTS + 1
+        );
+        assert_eq!(metrics.get("subject.0").unwrap().published, 2);
+        assert_eq!(metrics.get(OTHER_SUBJECTS).unwrap().published, 10);
+    }
+
+    #[test]
+    fn test_shared_between_clones() {
+        let metrics = PublishMetrics::new();
+        metrics.clone().record("a", true, Duration::ZERO);
+        assert_eq!(metrics.get("a").unwrap().published, 1);
+
+        metrics.reset();
+        assert!(metrics.get("a").is_none());
+    }
+}
@@ -39,6 +39,7 @@
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod metrics;
 pub mod models;
 pub mod pool;
 pub mod publisher;
@@ -53,12 +54,15 @@ pub use config::{AuthType, NatsConfig};
 #[allow(unused_imports)]
 pub use error::NatsError;
 #[allow(unused_imports)]
+pub use metrics::{PublishMetrics, SubjectMetrics};
+#[allow(unused_imports)]
 pub use models::{
-    DiscardPolicy, JetStreamAck, NatsMessage, PoolStats, RetentionPolicy, StorageType, StreamConfig,
+    headers_from_json, DiscardPolicy, JetStreamAck, NatsMessage, PoolStats, RetentionPolicy,
+    StorageType, StreamConfig,
 };
 #[allow(unused_imports)]
 pub use pool::NatsPool;
-pub use publisher::NatsPublisher;
+pub use publisher::{NatsPublisher, DEFAULT_PUBLISH_RETRIES};
 
 /// NATS integration version
 #[allow(dead_code)]
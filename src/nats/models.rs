@@ -32,6 +32,33 @@ impl JetStreamAck {
         self.duplicate = duplicate;
         self
     }
+
+    /// Publish outcome as reported to SQL callers
+    ///
+    /// A duplicate ack means the server already held a message with the same
+    /// ID inside the stream's duplicate window: nothing new was stored and
+    /// `sequence` is the original message's sequence.
+    pub fn outcome_json(&self) -> serde_json::Value {
+        if self.duplicate {
+            serde_json::json!({
+                "stream": self.stream,
+                "sequence": self.sequence,
+                "duplicate": true,
+                "status": "deduplicated",
+                "message": format!(
+                    "Message ID already stored in stream {} at sequence {}; not stored again",
+                    self.stream, self.sequence
+                )
+            })
+        } else {
+            serde_json::json!({
+                "stream": self.stream,
+                "sequence": self.sequence,
+                "duplicate": false,
+                "status": "stored"
+            })
+        }
+    }
 }
 
 /// Connection pool statistics
@@ -126,6 +153,55 @@ impl NatsMessage {
     }
 }
 
+/// Build a header map from a JSON object of string values
+///
+/// Header names must be non-empty printable ASCII without spaces or `:`, and
+/// values must not contain CR or LF. `Nats-Msg-Id` is rejected; deduplication
+/// IDs are passed separately as the message ID.
+pub fn headers_from_json(
+    headers: &serde_json::Value,
+) -> Result<async_nats::HeaderMap, crate::nats::NatsError> {
+    use crate::nats::NatsError;
+
+    let fields = headers.as_object().ok_or_else(|| {
+        NatsError::SerializationError("Headers must be a JSON object".to_string())
+    })?;
+
+    let mut map = async_nats::HeaderMap::new();
+
+    for (name, value) in fields {
+        let valid_name =
+            !name.is_empty() && name.bytes().all(|b| b.is_ascii_graphic() && b != b':');
+        if !valid_name {
+            return Err(NatsError::SerializationError(format!(
+                "Invalid header name '{}'",
+                name
+            )));
+        }
+
+        if name.eq_ignore_ascii_case("Nats-Msg-Id") {
+            return Err(NatsError::SerializationError(
+                "Set Nats-Msg-Id through message_id, not headers".to_string(),
+            ));
+        }
+
+        let value = value.as_str().ok_or_else(|| {
+            NatsError::SerializationError(format!("Header '{}' must be a string", name))
+        })?;
+
+        if value.contains(['\r', '\n']) {
+            return Err(NatsError::SerializationError(format!(
+                "Header '{}' value cannot contain CR or LF",
+                name
+            )));
+        }
+
+        map.insert(name.as_str(), value);
+    }
+
+    Ok(map)
+}
+
 /// Stream configuration for JetStream
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamConfig {
@@ -258,6 +334,22 @@ mod tests {
         assert!(ack_dup.duplicate);
     }
 
+    #[test]
+    fn test_jetstream_ack_outcome_json() {
+        let stored = JetStreamAck::new("WEBHOOKS", 7).outcome_json();
+        assert_eq!(stored["status"], "stored");
+        assert_eq!(stored["duplicate"], false);
+        assert!(stored.get("message").is_none());
+
+        let dedup = JetStreamAck::new("WEBHOOKS", 7)
+            .with_duplicate(true)
+            .outcome_json();
+        assert_eq!(dedup["status"], "deduplicated");
+        assert_eq!(dedup["duplicate"], true);
+        assert_eq!(dedup["sequence"], 7);
+        assert!(dedup["message"].as_str().unwrap().contains("sequence 7"));
+    }
+
     #[test]
     fn test_pool_stats() {
         let stats = PoolStats::new(10);
@@ -288,6 +380,33 @@ mod tests {
         assert_eq!(payload_str, "payload");
     }
 
+    #[test]
+    fn test_headers_from_json() {
+        let headers = headers_from_json(&serde_json::json!({
+            "X-Tenant": "acme",
+            "X-Priority": "high"
+        }))
+        .unwrap();
+
+        assert_eq!(headers.get("X-Tenant").unwrap().as_str(), "acme");
+        assert_eq!(headers.get("X-Priority").unwrap().as_str(), "high");
+        assert!(headers_from_json(&serde_json::json!({})).is_ok());
+    }
+
+    #[test]
+    fn test_headers_from_json_invalid() {
+        use serde_json::json;
+
+        assert!(headers_from_json(&json!(["X-Tenant"])).is_err());
+        assert!(headers_from_json(&json!({ "": "x" })).is_err());
+        assert!(headers_from_json(&json!({ "X Tenant": "acme" })).is_err());
+        assert!(headers_from_json(&json!({ "X-Tenant:": "acme" })).is_err());
+        assert!(headers_from_json(&json!({ "X-Tenänt": "acme" })).is_err());
+        assert!(headers_from_json(&json!({ "X-Count": 3 })).is_err());
+        assert!(headers_from_json(&json!({ "X-Tenant": "a\r\nX-Evil: 1" })).is_err());
+        assert!(headers_from_json(&json!({ "nats-msg-id": "abc" })).is_err());
+    }
+
     #[test]
     fn test_message_json_payload() {
         #[derive(Serialize, Deserialize, PartialEq, Debug)]
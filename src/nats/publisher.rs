@@ -3,13 +3,50 @@
 /// This module provides high-level publishing interface for NATS.
 use async_nats::jetstream::{self, Context as JetStreamContext};
 use async_nats::HeaderMap;
-use std::time::Duration;
+use std::future::Future;
+use std::time::{Duration, Instant};
 
 use crate::nats::config::NatsConfig;
 use crate::nats::error::NatsError;
+use crate::nats::metrics::PublishMetrics;
 use crate::nats::models::{JetStreamAck, NatsMessage};
 use crate::nats::pool::NatsPool;
 
+/// Default number of retries after a failed JetStream publish
+pub const DEFAULT_PUBLISH_RETRIES: usize = 3;
+
+/// Base delay for publish retries (doubled after each attempt)
+pub const PUBLISH_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// Run `op` until it succeeds, fails with a non-retriable error, or
+/// `max_retries` retries are used up, sleeping `base_delay * 2^attempt`
+/// between attempts.
+///
+/// Returns the last result together with the number of attempts made.
+pub async fn retry_with_backoff<T, F, Fut>(
+    max_retries: usize,
+    base_delay: Duration,
+    mut op: F,
+) -> (Result<T, NatsError>, usize)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, NatsError>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        let result = op().await;
+        attempt += 1;
+
+        match result {
+            Err(e) if e.is_retriable() && attempt <= max_retries => {
+                tokio::time::sleep(base_delay * 2_u32.pow(attempt as u32 - 1)).await;
+            }
+            result => return (result, attempt),
+        }
+    }
+}
+
 /// NATS Publisher
 ///
 /// Provides methods to publish messages to NATS core and JetStream.
@@ -19,6 +56,9 @@ pub struct NatsPublisher {
 
     /// JetStream context (if enabled)
     jetstream: Option<JetStreamContext>,
+
+    /// Per-subject publish counters (shared by clones)
+    metrics: PublishMetrics,
 }
 
 impl NatsPublisher {
@@ -34,23 +74,43 @@ impl NatsPublisher {
             None
         };
 
-        Ok(Self { pool, jetstream })
+        Ok(Self {
+            pool,
+            jetstream,
+            metrics: PublishMetrics::new(),
+        })
+    }
+
+    /// Run one publish attempt, recording its outcome and latency for `subject`
+    async fn measured<T>(
+        &self,
+        subject: &str,
+        publish: impl Future<Output = Result<T, NatsError>>,
+    ) -> Result<T, NatsError> {
+        let start = Instant::now();
+        let result = publish.await;
+        self.metrics
+            .record(subject, result.is_ok(), start.elapsed());
+        result
     }
 
     /// Publish a message to NATS core (fire-and-forget)
     ///
     /// This is the fastest option but provides no delivery guarantees.
     pub async fn publish(&self, subject: &str, payload: &[u8]) -> Result<(), NatsError> {
-        let client = self.pool.get_client();
+        self.measured(subject, async {
+            let client = self.pool.get_client();
 
-        client
-            .publish(subject.to_string(), payload.to_vec().into())
-            .await
-            .map_err(|e| {
-                NatsError::PublishError(format!("Failed to publish to {}: {}", subject, e))
-            })?;
+            client
+                .publish(subject.to_string(), payload.to_vec().into())
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!("Failed to publish to {}: {}", subject, e))
+                })?;
 
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
     /// Publish a message with custom headers
@@ -60,19 +120,22 @@ impl NatsPublisher {
         headers: HeaderMap,
         payload: &[u8],
     ) -> Result<(), NatsError> {
-        let client = self.pool.get_client();
+        self.measured(subject, async {
+            let client = self.pool.get_client();
 
-        client
-            .publish_with_headers(subject.to_string(), headers, payload.to_vec().into())
-            .await
-            .map_err(|e| {
-                NatsError::PublishError(format!(
-                    "Failed to publish with headers to {}: {}",
-                    subject, e
-                ))
-            })?;
-
-        Ok(())
+            client
+                .publish_with_headers(subject.to_string(), headers, payload.to_vec().into())
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to publish with headers to {}: {}",
+                        subject, e
+                    ))
+                })?;
+
+            Ok(())
+        })
+        .await
     }
 
     /// Publish a message with timeout
@@ -95,21 +158,29 @@ impl NatsPublisher {
         subject: &str,
         payload: &[u8],
     ) -> Result<JetStreamAck, NatsError> {
-        let js = self
-            .jetstream
-            .as_ref()
-            .ok_or(NatsError::JetStreamNotEnabled)?;
-
-        let pub_ack = js
-            .publish(subject.to_string(), payload.to_vec().into())
-            .await
-            .map_err(|e| NatsError::PublishError(format!("Failed to publish to JetStream: {}", e)))?
-            .await
-            .map_err(|e| {
-                NatsError::PublishError(format!("Failed to get JetStream acknowledgment: {}", e))
-            })?;
-
-        Ok(JetStreamAck::new(pub_ack.stream, pub_ack.sequence))
+        self.measured(subject, async {
+            let js = self
+                .jetstream
+                .as_ref()
+                .ok_or(NatsError::JetStreamNotEnabled)?;
+
+            let pub_ack = js
+                .publish(subject.to_string(), payload.to_vec().into())
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!("Failed to publish to JetStream: {}", e))
+                })?
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to get JetStream acknowledgment: {}",
+                        e
+                    ))
+                })?;
+
+            Ok(JetStreamAck::new(pub_ack.stream, pub_ack.sequence))
+        })
+        .await
     }
 
     /// Publish to JetStream with message ID for deduplication
@@ -121,33 +192,115 @@ impl NatsPublisher {
         message_id: &str,
         payload: &[u8],
     ) -> Result<JetStreamAck, NatsError> {
-        let js = self
-            .jetstream
-            .as_ref()
-            .ok_or(NatsError::JetStreamNotEnabled)?;
-
-        // Create headers with message ID
-        let mut headers = HeaderMap::new();
-        headers.insert("Nats-Msg-Id", message_id);
-
-        let pub_ack = js
-            .publish_with_headers(subject.to_string(), headers, payload.to_vec().into())
-            .await
-            .map_err(|e| {
-                NatsError::PublishError(format!("Failed to publish to JetStream with ID: {}", e))
-            })?
-            .await
-            .map_err(|e| {
-                NatsError::PublishError(format!(
-                    "Failed to get JetStream acknowledgment with ID: {}",
-                    e
-                ))
-            })?;
+        self.measured(subject, async {
+            let js = self
+                .jetstream
+                .as_ref()
+                .ok_or(NatsError::JetStreamNotEnabled)?;
+
+            // Create headers with message ID
+            let mut headers = HeaderMap::new();
+            headers.insert("Nats-Msg-Id", message_id);
+
+            let pub_ack = js
+                .publish_with_headers(subject.to_string(), headers, payload.to_vec().into())
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to publish to JetStream with ID: {}",
+                        e
+                    ))
+                })?
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to get JetStream acknowledgment with ID: {}",
+                        e
+                    ))
+                })?;
+
+            // Check if this was a duplicate
+            let duplicate = pub_ack.duplicate;
+
+            Ok(JetStreamAck::new(pub_ack.stream, pub_ack.sequence).with_duplicate(duplicate))
+        })
+        .await
+    }
 
-        // Check if this was a duplicate
-        let duplicate = pub_ack.duplicate;
+    /// Publish to JetStream with custom headers
+    ///
+    /// `message_id`, if given, is added as the `Nats-Msg-Id` header so the
+    /// message is deduplicated like [`Self::publish_jetstream_with_id`].
+    pub async fn publish_jetstream_with_headers(
+        &self,
+        subject: &str,
+        message_id: Option<&str>,
+        mut headers: HeaderMap,
+        payload: &[u8],
+    ) -> Result<JetStreamAck, NatsError> {
+        self.measured(subject, async {
+            let js = self
+                .jetstream
+                .as_ref()
+                .ok_or(NatsError::JetStreamNotEnabled)?;
+
+            if let Some(id) = message_id {
+                headers.insert("Nats-Msg-Id", id);
+            }
+
+            let pub_ack = js
+                .publish_with_headers(subject.to_string(), headers, payload.to_vec().into())
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to publish to JetStream with headers: {}",
+                        e
+                    ))
+                })?
+                .await
+                .map_err(|e| {
+                    NatsError::PublishError(format!(
+                        "Failed to get JetStream acknowledgment with headers: {}",
+                        e
+                    ))
+                })?;
+
+            Ok(JetStreamAck::new(pub_ack.stream, pub_ack.sequence)
+                .with_duplicate(pub_ack.duplicate))
+        })
+        .await
+    }
 
-        Ok(JetStreamAck::new(pub_ack.stream, pub_ack.sequence).with_duplicate(duplicate))
+    /// Publish to JetStream, retrying retriable failures with backoff
+    ///
+    /// Uses [`Self::publish_jetstream_with_id`] when `message_id` is given, so
+    /// a retry after a lost acknowledgment is deduplicated by the server, and
+    /// [`Self::publish_jetstream_with_headers`] when `headers` are given.
+    /// Returns the final result and the number of attempts made.
+    pub async fn publish_jetstream_with_retry(
+        &self,
+        subject: &str,
+        message_id: Option<&str>,
+        headers: Option<&HeaderMap>,
+        payload: &[u8],
+        max_retries: usize,
+    ) -> (Result<JetStreamAck, NatsError>, usize) {
+        retry_with_backoff(max_retries, PUBLISH_RETRY_BASE_DELAY, || async move {
+            match (headers, message_id) {
+                (Some(headers), _) => {
+                    self.publish_jetstream_with_headers(
+                        subject,
+                        message_id,
+                        headers.clone(),
+                        payload,
+                    )
+                    .await
+                }
+                (None, Some(id)) => self.publish_jetstream_with_id(subject, id, payload).await,
+                (None, None) => self.publish_jetstream(subject, payload).await,
+            }
+        })
+        .await
     }
 
     /// Publish a NatsMessage (convenience method)
@@ -165,7 +318,15 @@ impl NatsPublisher {
         &self,
         message: NatsMessage,
     ) -> Result<JetStreamAck, NatsError> {
-        if let Some(msg_id) = message.message_id {
+        if let Some(headers) = message.headers {
+            self.publish_jetstream_with_headers(
+                &message.subject,
+                message.message_id.as_deref(),
+                headers,
+                &message.payload,
+            )
+            .await
+        } else if let Some(msg_id) = message.message_id {
             self.publish_jetstream_with_id(&message.subject, &msg_id, &message.payload)
                 .await
         } else {
@@ -179,6 +340,13 @@ impl NatsPublisher {
         &self.pool
     }
 
+    /// Per-subject publish metrics
+    ///
+    /// Every attempt counts, so a publish retried twice records three attempts.
+    pub fn metrics(&self) -> &PublishMetrics {
+        &self.metrics
+    }
+
     /// Check if JetStream is enabled
     pub fn is_jetstream_enabled(&self) -> bool {
         self.jetstream.is_some()
@@ -189,6 +357,20 @@ impl NatsPublisher {
         self.jetstream.as_ref()
     }
 
+    /// Reconnect dead pool connections, returning how many were re-established
+    ///
+    /// The JetStream context is rebuilt afterwards so it doesn't keep using a
+    /// replaced client.
+    pub async fn heal(&mut self) -> Result<usize, NatsError> {
+        let reconnected = self.pool.heal().await?;
+
+        if reconnected > 0 && self.jetstream.is_some() {
+            self.jetstream = Some(jetstream::new(self.pool.get_client().clone()));
+        }
+
+        Ok(reconnected)
+    }
+
     /// Flush all pending messages
     pub async fn flush(&self) -> Result<(), NatsError> {
         for client in self.pool.get_all_clients() {
@@ -213,6 +395,7 @@ impl Clone for NatsPublisher {
         Self {
             pool: self.pool.clone(),
             jetstream: self.jetstream.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -243,4 +426,46 @@ mod tests {
         assert_eq!(msg.subject, "test.subject");
         assert_eq!(msg.message_id, Some("msg-123".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_recovers() {
+        let mut calls = 0;
+        let (result, attempts) = retry_with_backoff(3, Duration::ZERO, || {
+            calls += 1;
+            let outcome = if calls < 3 {
+                Err(NatsError::PublishError("transient".to_string()))
+            } else {
+                Ok(calls)
+            };
+            async move { outcome }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_exhausted() {
+        let (result, attempts): (Result<(), _>, _) =
+            retry_with_backoff(2, Duration::ZERO, || async {
+                Err(NatsError::TimeoutError("no ack".to_string()))
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_backoff_non_retriable() {
+        let (result, attempts): (Result<(), _>, _) =
+            retry_with_backoff(5, Duration::ZERO, || async {
+                Err(NatsError::JetStreamNotEnabled)
+            })
+            .await;
+
+        assert!(matches!(result, Err(NatsError::JetStreamNotEnabled)));
+        assert_eq!(attempts, 1);
+    }
 }
@@ -27,6 +27,7 @@ mod common {
             tls_cert_file: None,
             tls_key_file: None,
             tls_ca_file: None,
+            connection_name: None,
         }
     }
 
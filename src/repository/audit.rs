@@ -0,0 +1,84 @@
+// Audit trail for stored-rule executions
+// Enabled with the rule_engine.audit_enabled setting (superuser only)
+
+use crate::error::RuleEngineError;
+use pgrx::prelude::*;
+use serde_json::Value;
+
+/// Record one execution of a stored rule in rule_execution_audit
+///
+/// Facts are stored only as a SHA-256 hash of their canonical JSON, so the
+/// log holds no PII but identical inputs can still be matched. When
+/// `version` is None the rule's default version at execution time is logged.
+pub fn record_execution(
+    name: &str,
+    version: Option<&str>,
+    facts_json: &str,
+    rules_fired: u64,
+    result: &str,
+) -> Result<(), RuleEngineError> {
+    let rules_fired = i32::try_from(rules_fired).unwrap_or(i32::MAX);
+
+    Spi::run_with_args(
+        "INSERT INTO rule_execution_audit
+         (rule_name, rule_version, facts_hash, rules_fired, fired, success)
+         VALUES (
+             $1,
+             COALESCE($2, (SELECT rv.version
+                           FROM rule_versions rv
+                           JOIN rule_definitions rd ON rv.rule_id = rd.id
+                           WHERE rd.name = $1 AND rv.is_default = true)),
+             encode(sha256(convert_to($3, 'UTF8')), 'hex'),
+             $4,
+             $4 > 0,
+             $5
+         )",
+        &[
+            name.to_string().into(),
+            version.map(str::to_string).into(),
+            canonical_facts(facts_json).into(),
+            rules_fired.into(),
+            execution_succeeded(result).into(),
+        ],
+    )
+    .map_err(|e| RuleEngineError::DatabaseError(format!("Failed to write audit log: {}", e)))
+}
+
+/// Normalize facts so whitespace and key order don't change the hash
+fn canonical_facts(facts_json: &str) -> String {
+    match serde_json::from_str::<Value>(facts_json) {
+        // serde_json objects are sorted maps, so keys serialize in order
+        Ok(value) => value.to_string(),
+        Err(_) => facts_json.to_string(),
+    }
+}
+
+/// Engine results are facts on success and an error object otherwise
+fn execution_succeeded(result: &str) -> bool {
+    serde_json::from_str::<Value>(result)
+        .map(|value| value.get("error_code").is_none())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_facts_ignores_layout() {
+        assert_eq!(
+            canonical_facts(r#"{ "b": 1, "a": {"y": 2, "x": 3} }"#),
+            canonical_facts(r#"{"a":{"x":3,"y":2},"b":1}"#)
+        );
+        assert_eq!(canonical_facts("not json"), "not json");
+    }
+
+    #[test]
+    fn test_execution_succeeded() {
+        assert!(execution_succeeded(r#"{"Order": {"total": 10}}"#));
+        assert!(!execution_succeeded(
+            r#"{"error": "Invalid GRL", "error_code": "ERR004", "timestamp": "..."}"#
+        ));
+        assert!(!execution_succeeded("garbage"));
+    }
+}
@@ -0,0 +1,438 @@
+// Export/import of rules as portable JSON bundles
+// Used to move rules (all versions, tags, default flag) between environments
+
+use crate::error::RuleEngineError;
+use crate::repository::models::{RuleBundle, RuleBundleVersion};
+use crate::repository::validation::*;
+use pgrx::prelude::*;
+use pgrx::JsonB;
+use std::collections::HashSet;
+
+/// Export a rule with all versions and tags as a JSON bundle
+///
+/// # Arguments
+/// * `name` - Rule name
+///
+/// # Returns
+/// JSONB bundle: `{name, description, is_active, tags, versions: [{version, grl_content, change_notes, is_default}]}`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_export('discount_rule');
+/// ```
+#[pg_extern]
+pub fn rule_export(name: String) -> Result<JsonB, RuleEngineError> {
+    let bundle = load_rule_bundle(&name)?;
+    Ok(JsonB(serde_json::to_value(bundle)?))
+}
+
+/// Import a rule bundle produced by `rule_export`
+///
+/// The rule, its versions and tags are recreated in the current transaction,
+/// so a failure part-way through leaves the repository unchanged.
+///
+/// # Arguments
+/// * `bundle` - JSONB bundle
+/// * `overwrite` - Replace an existing rule with the same name (fails if false)
+///
+/// # Returns
+/// Rule ID of the imported rule
+///
+/// # Example
+/// ```sql
+/// SELECT rule_import(rule_export('discount_rule'), true);
+/// ```
+#[pg_extern]
+pub fn rule_import(bundle: JsonB, overwrite: bool) -> Result<i32, RuleEngineError> {
+    let bundle: RuleBundle = serde_json::from_value(bundle.0)?;
+    import_rule_bundle(&bundle, overwrite)
+}
+
+/// Export every rule carrying a tag as a JSON array of bundles
+///
+/// # Arguments
+/// * `tag` - Tag to match (e.g. 'pricing')
+///
+/// # Returns
+/// JSONB array of bundles in the same format as `rule_export`, ordered by rule name
+///
+/// # Example
+/// ```sql
+/// SELECT rules_export_by_tag('pricing');
+/// ```
+#[pg_extern]
+pub fn rules_export_by_tag(tag: String) -> Result<JsonB, RuleEngineError> {
+    validate_tag(&tag)?;
+
+    let names: Vec<String> = Spi::connect(|client| -> Result<Vec<String>, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT rd.name FROM rule_definitions rd JOIN rule_tags rt ON rt.rule_id = rd.id WHERE rt.tag = $1 ORDER BY rd.name",
+            None,
+            &[tag.into()],
+        )?;
+
+        let mut names = Vec::new();
+        for row in result {
+            if let Some(name) = row.get::<String>(1)? {
+                names.push(name);
+            }
+        }
+        Ok(names)
+    })?;
+
+    let bundles = names
+        .iter()
+        .map(|name| load_rule_bundle(name))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(JsonB(serde_json::to_value(bundles)?))
+}
+
+/// Import a JSON array of rule bundles produced by `rules_export_by_tag`
+///
+/// All rules are imported in the current transaction; if any bundle fails
+/// validation or conflicts with an existing rule, nothing is imported.
+///
+/// # Arguments
+/// * `bundles` - JSONB array of bundles
+/// * `overwrite` - Replace existing rules with the same name (fails if false)
+///
+/// # Returns
+/// Number of rules imported
+///
+/// # Example
+/// ```sql
+/// SELECT rules_import_bundle(rules_export_by_tag('pricing'), true);
+/// ```
+#[pg_extern]
+pub fn rules_import_bundle(bundles: JsonB, overwrite: bool) -> Result<i32, RuleEngineError> {
+    let bundles: Vec<RuleBundle> = serde_json::from_value(bundles.0)?;
+
+    let mut names = HashSet::new();
+    for bundle in &bundles {
+        if !names.insert(bundle.name.as_str()) {
+            return Err(RuleEngineError::InvalidInput(format!(
+                "Rule '{}' appears more than once in bundle",
+                bundle.name
+            )));
+        }
+    }
+
+    for bundle in &bundles {
+        import_rule_bundle(bundle, overwrite)?;
+    }
+
+    Ok(bundles.len() as i32)
+}
+
+/// Load a rule and all of its versions and tags from the repository
+pub fn load_rule_bundle(name: &str) -> Result<RuleBundle, RuleEngineError> {
+    validate_rule_name(name)?;
+
+    let definition: Option<(i32, Option<String>, bool)> = Spi::connect(
+        |client| -> Result<Option<(i32, Option<String>, bool)>, pgrx::spi::SpiError> {
+            let result = client.select(
+                "SELECT id, description, is_active FROM rule_definitions WHERE name = $1",
+                None,
+                &[name.into()],
+            )?;
+
+            for row in result {
+                if let Some(id) = row.get::<i32>(1)? {
+                    let description = row.get::<String>(2)?;
+                    let is_active = row.get::<bool>(3)?.unwrap_or(true);
+                    return Ok(Some((id, description, is_active)));
+                }
+            }
+            Ok(None)
+        },
+    )?;
+
+    let (rule_id, description, is_active) = definition
+        .ok_or_else(|| RuleEngineError::RuleNotFound(format!("Rule '{}' not found", name)))?;
+
+    let versions: Vec<RuleBundleVersion> = Spi::connect(
+        |client| -> Result<Vec<RuleBundleVersion>, pgrx::spi::SpiError> {
+            let result = client.select(
+                "SELECT version, grl_content, change_notes, is_default FROM rule_versions WHERE rule_id = $1 ORDER BY created_at, id",
+                None,
+                &[rule_id.into()],
+            )?;
+
+            let mut versions = Vec::new();
+            for row in result {
+                if let (Some(version), Some(grl_content)) =
+                    (row.get::<String>(1)?, row.get::<String>(2)?)
+                {
+                    versions.push(RuleBundleVersion {
+                        version,
+                        grl_content,
+                        change_notes: row.get::<String>(3)?,
+                        is_default: row.get::<bool>(4)?.unwrap_or(false),
+                    });
+                }
+            }
+            Ok(versions)
+        },
+    )?;
+
+    let tags: Vec<String> = Spi::connect(|client| -> Result<Vec<String>, pgrx::spi::SpiError> {
+        let result = client.select(
+            "SELECT tag FROM rule_tags WHERE rule_id = $1 ORDER BY tag",
+            None,
+            &[rule_id.into()],
+        )?;
+
+        let mut tags = Vec::new();
+        for row in result {
+            if let Some(tag) = row.get::<String>(1)? {
+                tags.push(tag);
+            }
+        }
+        Ok(tags)
+    })?;
+
+    Ok(RuleBundle {
+        name: name.to_string(),
+        description,
+        is_active,
+        tags,
+        versions,
+    })
+}
+
+/// Recreate a rule from a bundle, replacing an existing rule if `overwrite` is set
+pub fn import_rule_bundle(bundle: &RuleBundle, overwrite: bool) -> Result<i32, RuleEngineError> {
+    validate_bundle(bundle)?;
+
+    let current_user: String = Spi::get_one("SELECT user")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let rule_exists: bool = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT EXISTS(SELECT 1 FROM rule_definitions WHERE name = $1)",
+                None,
+                &[bundle.name.clone().into()],
+            )?
+            .first()
+            .get_one()
+    })?
+    .unwrap_or(false);
+
+    if rule_exists {
+        if !overwrite {
+            return Err(RuleEngineError::InvalidInput(format!(
+                "Rule '{}' already exists. Use overwrite => true to replace it.",
+                bundle.name
+            )));
+        }
+
+        // Cascade removes existing versions and tags
+        Spi::connect(|client| -> Result<Option<i64>, pgrx::spi::SpiError> {
+            client
+                .select(
+                    "DELETE FROM rule_definitions WHERE name = $1 RETURNING 1",
+                    None,
+                    &[bundle.name.clone().into()],
+                )?
+                .first()
+                .get_one::<i64>()
+        })?;
+    }
+
+    let rule_id: i32 = Spi::connect(|client| {
+        client
+            .select(
+                "INSERT INTO rule_definitions (name, description, created_by, updated_by, is_active) VALUES ($1, $2, $3, $4, $5) RETURNING id",
+                None,
+                &[
+                    bundle.name.clone().into(),
+                    bundle.description.clone().into(),
+                    current_user.clone().into(),
+                    current_user.clone().into(),
+                    bundle.is_active.into(),
+                ],
+            )?
+            .first()
+            .get_one::<i32>()
+    })?
+    .ok_or_else(|| RuleEngineError::DatabaseError("Failed to insert rule".to_string()))?;
+
+    let default_version = bundle_default_version(bundle);
+
+    for v in &bundle.versions {
+        Spi::connect(|client| -> Result<Option<i64>, pgrx::spi::SpiError> {
+            client
+                .select(
+                    "INSERT INTO rule_versions (rule_id, version, grl_content, change_notes, created_by, is_default) VALUES ($1, $2, $3, $4, $5, $6) RETURNING 1",
+                    None,
+                    &[
+                        rule_id.into(),
+                        v.version.clone().into(),
+                        v.grl_content.clone().into(),
+                        v.change_notes.clone().into(),
+                        current_user.clone().into(),
+                        (Some(v.version.as_str()) == default_version).into(),
+                    ],
+                )?
+                .first()
+                .get_one::<i64>()
+        })?;
+    }
+
+    for tag in &bundle.tags {
+        Spi::connect(|client| -> Result<Option<i64>, pgrx::spi::SpiError> {
+            client
+                .select(
+                    "INSERT INTO rule_tags (rule_id, tag) VALUES ($1, $2) ON CONFLICT DO NOTHING RETURNING 1",
+                    None,
+                    &[rule_id.into(), tag.clone().into()],
+                )?
+                .first()
+                .get_one::<i64>()
+        })?;
+    }
+
+    Ok(rule_id)
+}
+
+/// Validate bundle contents before touching the database
+fn validate_bundle(bundle: &RuleBundle) -> Result<(), RuleEngineError> {
+    validate_rule_name(&bundle.name)?;
+
+    if bundle.versions.is_empty() {
+        return Err(RuleEngineError::InvalidInput(format!(
+            "Bundle for rule '{}' contains no versions",
+            bundle.name
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for v in &bundle.versions {
+        validate_version(&v.version)?;
+        validate_grl_content(&v.grl_content)?;
+        if !seen.insert(v.version.as_str()) {
+            return Err(RuleEngineError::InvalidInput(format!(
+                "Duplicate version '{}' in bundle for rule '{}'",
+                v.version, bundle.name
+            )));
+        }
+    }
+
+    if bundle.versions.iter().filter(|v| v.is_default).count() > 1 {
+        return Err(RuleEngineError::InvalidInput(format!(
+            "Bundle for rule '{}' marks more than one version as default",
+            bundle.name
+        )));
+    }
+
+    for tag in &bundle.tags {
+        validate_tag(tag)?;
+    }
+
+    Ok(())
+}
+
+/// Version to mark as default: the flagged one, or the last listed version
+fn bundle_default_version(bundle: &RuleBundle) -> Option<&str> {
+    bundle
+        .versions
+        .iter()
+        .find(|v| v.is_default)
+        .or_else(|| bundle.versions.last())
+        .map(|v| v.version.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle(json: serde_json::Value) -> RuleBundle {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_bundle_deserialize_defaults() {
+        let b = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [{"version": "1.0.0", "grl_content": "rule \"A\" { when X > 1 then Y = 2; }"}]
+        }));
+
+        assert!(b.is_active);
+        assert!(b.tags.is_empty());
+        assert_eq!(b.description, None);
+        assert!(!b.versions[0].is_default);
+        assert!(validate_bundle(&b).is_ok());
+    }
+
+    #[test]
+    fn test_validate_bundle_rejects_bad_input() {
+        let grl = "rule \"A\" { when X > 1 then Y = 2; }";
+
+        let no_versions = bundle(serde_json::json!({"name": "discount", "versions": []}));
+        assert!(validate_bundle(&no_versions).is_err());
+
+        let bad_name = bundle(serde_json::json!({
+            "name": "1discount",
+            "versions": [{"version": "1.0.0", "grl_content": grl}]
+        }));
+        assert!(validate_bundle(&bad_name).is_err());
+
+        let bad_version = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [{"version": "v1", "grl_content": grl}]
+        }));
+        assert!(validate_bundle(&bad_version).is_err());
+
+        let duplicate = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [
+                {"version": "1.0.0", "grl_content": grl},
+                {"version": "1.0.0", "grl_content": grl}
+            ]
+        }));
+        assert!(validate_bundle(&duplicate).is_err());
+
+        let two_defaults = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [
+                {"version": "1.0.0", "grl_content": grl, "is_default": true},
+                {"version": "1.0.1", "grl_content": grl, "is_default": true}
+            ]
+        }));
+        assert!(validate_bundle(&two_defaults).is_err());
+
+        let bad_tag = bundle(serde_json::json!({
+            "name": "discount",
+            "tags": ["Pricing"],
+            "versions": [{"version": "1.0.0", "grl_content": grl}]
+        }));
+        assert!(validate_bundle(&bad_tag).is_err());
+    }
+
+    #[test]
+    fn test_bundle_default_version() {
+        let grl = "rule \"A\" { when X > 1 then Y = 2; }";
+
+        let flagged = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [
+                {"version": "1.0.0", "grl_content": grl, "is_default": true},
+                {"version": "1.0.1", "grl_content": grl}
+            ]
+        }));
+        assert_eq!(bundle_default_version(&flagged), Some("1.0.0"));
+
+        let unflagged = bundle(serde_json::json!({
+            "name": "discount",
+            "versions": [
+                {"version": "1.0.0", "grl_content": grl},
+                {"version": "1.0.1", "grl_content": grl}
+            ]
+        }));
+        assert_eq!(bundle_default_version(&unflagged), Some("1.0.1"));
+    }
+}
@@ -1,6 +1,8 @@
 // Repository module for Rule Management
 // Implements RFC-0001: Rule Repository & Versioning
 
+pub mod audit;
+pub mod bundle;
 pub mod models;
 pub mod queries;
 pub mod test_spi;
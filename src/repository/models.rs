@@ -38,3 +38,31 @@ pub struct RuleListItem {
     pub is_active: bool,
     pub tags: Option<Vec<String>>,
 }
+
+/// Portable JSON bundle of a rule with all its versions and tags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleBundle {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "default_is_active")]
+    pub is_active: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub versions: Vec<RuleBundleVersion>,
+}
+
+/// A single rule version inside a `RuleBundle`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleBundleVersion {
+    pub version: String,
+    pub grl_content: String,
+    #[serde(default)]
+    pub change_notes: Option<String>,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+fn default_is_active() -> bool {
+    true
+}
@@ -3,7 +3,7 @@
 
 use crate::error::RuleEngineError;
 use crate::repository::validation::*;
-use crate::repository::version::SemanticVersion;
+use crate::repository::version::{compare_versions, SemanticVersion, VersionBump};
 use pgrx::prelude::*;
 // use pgrx::spi::SpiClient; (not needed)
 use std::fmt::Write;
@@ -16,6 +16,11 @@ use std::fmt::Write;
 /// * `version` - Optional semantic version (auto-incremented if None)
 /// * `description` - Optional rule description
 /// * `change_notes` - Optional notes about what changed in this version
+/// * `bump` - Optional auto-increment mode: 'major', 'minor' or 'patch' (default).
+///   Must be NULL when `version` is given.
+/// * `idempotency_key` - Optional key making the call safe to retry: if this
+///   rule was already saved with the same key, the earlier rule ID is returned
+///   and nothing is written (requires migrations/016_rule_save_idempotency.sql)
 ///
 /// # Returns
 /// Rule ID on success
@@ -28,6 +33,12 @@ use std::fmt::Write;
 /// # Example
 /// ```sql
 /// SELECT rule_save('discount_rule', 'rule "Discount" { ... }', '1.0.0', 'Discount calculator');
+///
+/// -- Breaking change: auto-increment the major version
+/// SELECT rule_save('discount_rule', 'rule "Discount" { ... }', NULL, NULL, 'New tiers', 'major');
+///
+/// -- Retry-safe deploy
+/// SELECT rule_save('discount_rule', 'rule "Discount" { ... }', NULL, NULL, NULL, NULL, 'deploy-4812');
 /// ```
 #[pg_extern]
 pub fn rule_save(
@@ -36,11 +47,90 @@ pub fn rule_save(
     version: Option<String>,
     description: Option<String>,
     change_notes: Option<String>,
+    bump: default!(Option<String>, "NULL"),
+    idempotency_key: default!(Option<String>, "NULL"),
 ) -> Result<i32, RuleEngineError> {
     // Validate inputs
     validate_rule_name(&name)?;
     validate_grl_content(&grl_content)?;
+    if let Some(ref key) = idempotency_key {
+        validate_idempotency_key(key)?;
+    }
+
+    let bump = match (&version, bump) {
+        (Some(_), Some(_)) => {
+            return Err(RuleEngineError::InvalidInput(
+                "Cannot specify both version and bump; omit version to auto-increment".to_string(),
+            ))
+        }
+        (_, Some(mode)) => VersionBump::parse(&mode)?,
+        (_, None) => VersionBump::Patch,
+    };
+
+    // The definition insert/update and the version insert must land together,
+    // otherwise a failed version insert leaves a definition with no versions
+    in_subtransaction(|| {
+        let Some(key) = idempotency_key else {
+            return save_rule_version(name, grl_content, version, description, change_notes, bump)
+                .map(|(rule_id, _)| rule_id);
+        };
+
+        if let Some(rule_id) = idempotent_save_result(&name, &key)? {
+            return Ok(rule_id);
+        }
+
+        let (rule_id, version_number) = save_rule_version(
+            name.clone(),
+            grl_content,
+            version,
+            description,
+            change_notes,
+            bump,
+        )?;
+
+        // A concurrent retry with the same key fails on the primary key here,
+        // rolling back its version insert along with it
+        Spi::run_with_args(
+            "INSERT INTO rule_save_idempotency (rule_name, idempotency_key, rule_id, version)
+             VALUES ($1, $2, $3, $4)",
+            &[
+                name.into(),
+                key.into(),
+                rule_id.into(),
+                version_number.into(),
+            ],
+        )?;
+
+        Ok(rule_id)
+    })
+}
+
+/// Rule ID recorded by an earlier `rule_save` of `name` with `key`, if any
+fn idempotent_save_result(name: &str, key: &str) -> Result<Option<i32>, RuleEngineError> {
+    let rule_id = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT rule_id FROM rule_save_idempotency WHERE rule_name = $1 AND idempotency_key = $2",
+                None,
+                &[name.into(), key.into()],
+            )?
+            .first()
+            .get_one::<i32>()
+    })?;
+
+    Ok(rule_id)
+}
 
+/// Write the rule definition and its new version; called by `rule_save`
+/// inside a subtransaction. Returns the rule ID and the version written.
+fn save_rule_version(
+    name: String,
+    grl_content: String,
+    version: Option<String>,
+    description: Option<String>,
+    change_notes: Option<String>,
+    bump: VersionBump,
+) -> Result<(i32, String), RuleEngineError> {
     // Get current user
     let current_user: String = Spi::get_one("SELECT user")
         .ok()
@@ -124,43 +214,35 @@ pub fn rule_save(
             v
         }
         None => {
-            // Auto-increment: get latest version and increment patch
-            // First check if any versions exist to avoid InvalidPosition error
-            let version_count: i64 = Spi::connect(|client| {
-                client
-                    .select(
-                        "SELECT COUNT(*) FROM rule_versions WHERE rule_id = $1",
+            // Auto-increment: find the highest version by semver precedence
+            // (so release candidates sort below their final release) and
+            // increment the requested component
+            let existing_versions: Vec<String> =
+                Spi::connect(|client| -> Result<Vec<String>, pgrx::spi::SpiError> {
+                    let result = client.select(
+                        "SELECT version FROM rule_versions WHERE rule_id = $1",
                         None,
                         &[rule_id.into()],
-                    )?
-                    .first()
-                    .get_one::<i64>()
-            })?
-            .unwrap_or(0);
-
-            if version_count > 0 {
-                // Get latest version and increment
-                let latest_version: Option<String> = Spi::connect(|client| {
-                    client
-                        .select(
-                            "SELECT version FROM rule_versions WHERE rule_id = $1 ORDER BY created_at DESC LIMIT 1",
-                            None,
-                            &[rule_id.into()],
-                        )?
-                        .first()
-                        .get_one::<String>()
-                })?;
+                    )?;
 
-                match latest_version {
-                    Some(latest) => {
-                        let sem_ver = SemanticVersion::parse(&latest)?;
-                        sem_ver.increment_patch().to_string()
+                    let mut versions = Vec::new();
+                    for row in result {
+                        if let Some(v) = row.get::<String>(1)? {
+                            versions.push(v);
+                        }
                     }
-                    None => "1.0.0".to_string(),
-                }
-            } else {
+                    Ok(versions)
+                })?;
+
+            let latest = existing_versions
+                .iter()
+                .filter_map(|v| SemanticVersion::parse(v).ok())
+                .max();
+
+            match latest {
+                Some(sem_ver) => sem_ver.bump(bump).to_string(),
                 // First version
-                "1.0.0".to_string()
+                None => "1.0.0".to_string(),
             }
         }
     };
@@ -218,7 +300,49 @@ pub fn rule_save(
                 .get_one::<i64>()
     })?;
 
-    Ok(rule_id)
+    Ok((rule_id, version_number))
+}
+
+/// Run `f` in an internal subtransaction
+///
+/// SPI can't issue SAVEPOINT, so this uses the same calls PL/pgSQL exception
+/// blocks do. The subtransaction is committed if `f` returns `Ok`, and rolled
+/// back if it returns `Err` or raises a Postgres error (which is re-thrown).
+fn in_subtransaction<T>(
+    f: impl FnOnce() -> Result<T, RuleEngineError>,
+) -> Result<T, RuleEngineError> {
+    let (old_context, old_owner) =
+        unsafe { (pg_sys::CurrentMemoryContext, pg_sys::CurrentResourceOwner) };
+
+    let restore = move || unsafe {
+        pg_sys::MemoryContextSwitchTo(old_context);
+        pg_sys::CurrentResourceOwner = old_owner;
+    };
+
+    unsafe {
+        pg_sys::BeginInternalSubTransaction(std::ptr::null());
+        // Keep allocating in the caller's context so results outlive the subtransaction
+        pg_sys::MemoryContextSwitchTo(old_context);
+    }
+
+    let result = PgTryBuilder::new(std::panic::AssertUnwindSafe(f))
+        .catch_others(|error| {
+            unsafe { pg_sys::RollbackAndReleaseCurrentSubTransaction() };
+            restore();
+            error.rethrow()
+        })
+        .execute();
+
+    unsafe {
+        if result.is_ok() {
+            pg_sys::ReleaseCurrentSubTransaction();
+        } else {
+            pg_sys::RollbackAndReleaseCurrentSubTransaction();
+        }
+    }
+    restore();
+
+    result
 }
 
 // Helper: create a dollar-quoted SQL literal that won't collide with the
@@ -268,40 +392,108 @@ pub fn rule_get(name: String, version: Option<String>) -> Result<String, RuleEng
         validate_version(v)?;
     }
 
-    // Inputs are validated above (name format and optional version as semver)
-    // so it's safe to interpolate them directly here without manual quote-escaping.
-    let grl_content: Option<String> = match &version {
-        Some(v) => {
-            // Get specific version
-            Spi::get_one(&format!(
-                "SELECT rv.grl_content 
-                 FROM rule_versions rv
-                 JOIN rule_definitions rd ON rv.rule_id = rd.id
-                 WHERE rd.name = '{}' AND rv.version = '{}' AND rd.is_active = true",
-                name, v
-            ))?
-        }
-        None => {
-            // Get default version
-            Spi::get_one(&format!(
-                "SELECT rv.grl_content 
-                 FROM rule_versions rv
-                 JOIN rule_definitions rd ON rv.rule_id = rd.id
-                 WHERE rd.name = '{}' AND rv.is_default = true AND rd.is_active = true",
-                name
-            ))?
-        }
-    };
+    let grl_content: Option<String> =
+        Spi::connect(|client| -> Result<Option<String>, pgrx::spi::SpiError> {
+            match &version {
+                // Get specific version
+                Some(v) => client
+                    .select(
+                        "SELECT rv.grl_content
+                         FROM rule_versions rv
+                         JOIN rule_definitions rd ON rv.rule_id = rd.id
+                         WHERE rd.name = $1 AND rv.version = $2 AND rd.is_active = true",
+                        None,
+                        &[name.clone().into(), v.clone().into()],
+                    )?
+                    .first()
+                    .get_one::<String>(),
+                // Get default version
+                None => client
+                    .select(
+                        "SELECT rv.grl_content
+                         FROM rule_versions rv
+                         JOIN rule_definitions rd ON rv.rule_id = rd.id
+                         WHERE rd.name = $1 AND rv.is_default = true AND rd.is_active = true",
+                        None,
+                        &[name.clone().into()],
+                    )?
+                    .first()
+                    .get_one::<String>(),
+            }
+        })?;
 
-    grl_content.ok_or_else(|| {
-        RuleEngineError::RuleNotFound(format!(
-            "Rule '{}' {} not found",
-            name,
-            version
-                .map(|v| format!("version '{}'", v))
-                .unwrap_or_else(|| "(default)".to_string())
-        ))
-    })
+    grl_content.ok_or_else(|| rule_not_found(&name, version))
+}
+
+/// RuleNotFound error for a named rule and optional version
+fn rule_not_found(name: &str, version: Option<String>) -> RuleEngineError {
+    RuleEngineError::RuleNotFound(format!(
+        "Rule '{}' {} not found",
+        name,
+        version
+            .map(|v| format!("version '{}'", v))
+            .unwrap_or_else(|| "(default)".to_string())
+    ))
+}
+
+/// Get a rule's metadata (everything except the GRL)
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// JSONB with rule_id, name, version, description, created_by, updated_by,
+/// created_at, updated_at, is_default and tags
+///
+/// # Example
+/// ```sql
+/// SELECT rule_get_metadata('discount_rule');
+/// SELECT rule_get_metadata('discount_rule', '1.0.0')->'tags';
+/// ```
+#[pg_extern]
+pub fn rule_get_metadata(
+    name: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<pgrx::JsonB, RuleEngineError> {
+    validate_rule_name(&name)?;
+
+    if let Some(ref v) = version {
+        validate_version(v)?;
+    }
+
+    let metadata: Option<pgrx::JsonB> = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT jsonb_build_object(
+                    'rule_id', rd.id,
+                    'name', rd.name,
+                    'version', rv.version,
+                    'description', rd.description,
+                    'created_by', rd.created_by,
+                    'updated_by', rd.updated_by,
+                    'created_at', rd.created_at,
+                    'updated_at', rd.updated_at,
+                    'is_default', rv.is_default,
+                    'tags', COALESCE(
+                        (SELECT jsonb_agg(rt.tag ORDER BY rt.tag)
+                         FROM rule_tags rt WHERE rt.rule_id = rd.id),
+                        '[]'::jsonb
+                    )
+                 )
+                 FROM rule_definitions rd
+                 JOIN rule_versions rv ON rv.rule_id = rd.id
+                 WHERE rd.name = $1
+                   AND rd.is_active = true
+                   AND CASE WHEN $2::text IS NULL THEN rv.is_default ELSE rv.version = $2 END",
+                None,
+                &[name.clone().into(), version.clone().into()],
+            )?
+            .first()
+            .get_one::<pgrx::JsonB>()
+    })?;
+
+    metadata.ok_or_else(|| rule_not_found(&name, version))
 }
 
 /// Activate a specific version as the default
@@ -338,14 +530,29 @@ pub fn rule_activate(name: String, version: String) -> Result<bool, RuleEngineEr
     })?;
 
     // Set as default (trigger will unset others)
-    Spi::run(&format!(
-        "UPDATE rule_versions SET is_default = true WHERE id = {}",
-        version_id
-    ))?;
+    Spi::run_with_args(
+        "UPDATE rule_versions SET is_default = true WHERE id = $1",
+        &[version_id.into()],
+    )?;
 
     Ok(true)
 }
 
+/// Compare two semantic versions
+///
+/// Returns -1, 0 or 1 as `a` is lower than, equal to or higher than `b`,
+/// using the same precedence as version auto-increment (pre-releases sort
+/// before their release, build metadata is ignored).
+///
+/// # Example
+/// ```sql
+/// SELECT rule_version_compare('1.0.0-rc.1', '1.0.0'); -- -1
+/// ```
+#[pg_extern(immutable, parallel_safe)]
+pub fn rule_version_compare(a: String, b: String) -> Result<i32, RuleEngineError> {
+    compare_versions(&a, &b)
+}
+
 /// Delete a rule or specific version
 ///
 /// # Arguments
@@ -469,12 +676,72 @@ pub fn rule_tag_remove(name: String, tag: String) -> Result<bool, RuleEngineErro
     Ok(rows_deleted.is_some())
 }
 
+/// Set the algorithm a stored rule executes with
+///
+/// `'rete'` (the default) runs through `run_rule_engine`; `'fc'` runs through
+/// `run_rule_engine_fc` for rules that rely on forward chaining's in-order
+/// firing. Applies to every version of the rule
+/// (requires migrations/020_rule_algorithm.sql).
+///
+/// # Example
+/// ```sql
+/// SELECT rule_set_algorithm('discount_rule', 'fc');
+/// ```
+#[pg_extern]
+pub fn rule_set_algorithm(name: String, algorithm: String) -> Result<bool, RuleEngineError> {
+    validate_rule_name(&name)?;
+    validate_algorithm(&algorithm)?;
+
+    let updated: Option<i32> = Spi::connect(|client| {
+        client
+            .select(
+                "UPDATE rule_definitions SET algorithm = $2 WHERE name = $1 RETURNING id",
+                None,
+                &[name.clone().into(), algorithm.into()],
+            )?
+            .first()
+            .get_one::<i32>()
+    })?;
+
+    updated
+        .map(|_| true)
+        .ok_or_else(|| RuleEngineError::RuleNotFound(format!("Rule '{}' not found", name)))
+}
+
+/// Algorithm stored for a rule, 'rete' unless set with rule_set_algorithm
+fn rule_algorithm(name: &str) -> Result<String, RuleEngineError> {
+    let algorithm = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT algorithm FROM rule_definitions WHERE name = $1",
+                None,
+                &[name.into()],
+            )?
+            .first()
+            .get_one::<String>()
+    })?;
+
+    Ok(algorithm.unwrap_or_else(|| "rete".to_string()))
+}
+
+/// Engine function that runs a stored rule, per its algorithm
+fn rule_engine_for(name: &str) -> Result<fn(&str, &str) -> String, RuleEngineError> {
+    Ok(match rule_algorithm(name)?.as_str() {
+        "fc" => crate::api::engine::run_rule_engine_fc,
+        _ => crate::api::engine::run_rule_engine,
+    })
+}
+
 /// Execute a stored rule by name
 ///
 /// # Arguments
 /// * `name` - Rule name
 /// * `facts_json` - Input facts as JSON string
 /// * `version` - Optional specific version (uses default if None)
+/// * `expected_version` - If set, fail with VERSION_MISMATCH (ERR017) unless
+///   the version that would run is this one
+///
+/// Runs with the algorithm set by `rule_set_algorithm` (RETE by default).
 ///
 /// # Returns
 /// Modified facts (JSON string)
@@ -483,21 +750,798 @@ pub fn rule_tag_remove(name: String, tag: String) -> Result<bool, RuleEngineErro
 /// ```sql
 /// SELECT rule_execute_by_name('discount_rule', '{"Order": {"Amount": 150}}');
 /// SELECT rule_execute_by_name('discount_rule', '{"Order": {"Amount": 150}}', '1.0.0');
+/// SELECT rule_execute_by_name('discount_rule', '{"Order": {"Amount": 150}}',
+///                             expected_version => '1.0.0');
 /// ```
 #[pg_extern]
 pub fn rule_execute_by_name(
     name: String,
     facts_json: String,
+    version: default!(Option<String>, "NULL"),
+    expected_version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    let version = match expected_version {
+        Some(expected) => Some(pin_version(&name, version, &expected)?),
+        None => version,
+    };
+
+    execute_stored_rule(name, &facts_json, version)
+}
+
+/// Resolve the version a call would run and check it is `expected`
+///
+/// Returns the resolved version so the caller executes exactly that one, even
+/// if the default changes in the meantime.
+fn pin_version(
+    name: &str,
+    version: Option<String>,
+    expected: &str,
+) -> Result<String, RuleEngineError> {
+    validate_rule_name(name)?;
+    validate_version(expected)?;
+
+    let resolved = match version {
+        Some(v) => v,
+        None => Spi::connect(|client| {
+            client
+                .select(
+                    "SELECT rv.version::text
+                     FROM rule_versions rv
+                     JOIN rule_definitions rd ON rv.rule_id = rd.id
+                     WHERE rd.name = $1 AND rv.is_default = true AND rd.is_active = true",
+                    None,
+                    &[name.into()],
+                )?
+                .first()
+                .get_one::<String>()
+        })?
+        .ok_or_else(|| rule_not_found(name, None))?,
+    };
+
+    if resolved != expected {
+        return Err(RuleEngineError::VersionMismatch(format!(
+            "Rule '{}' resolved to version '{}' but '{}' was expected",
+            name, resolved, expected
+        )));
+    }
+
+    Ok(resolved)
+}
+
+/// Fetch a stored rule and run it, recording an audit entry when
+/// `rule_engine.audit_enabled` is on
+fn execute_stored_rule(
+    name: String,
+    facts_json: &str,
     version: Option<String>,
 ) -> Result<String, RuleEngineError> {
     // Get the GRL content
-    let grl_content = rule_get(name, version)?;
+    let grl_content = rule_get(name.clone(), version.clone())?;
+    let run = rule_engine_for(&name)?;
+
+    if !crate::guc::AUDIT_ENABLED.get() {
+        return Ok(run(facts_json, &grl_content));
+    }
+
+    // Backends are single-threaded, so the counter delta is this execution's
+    let fired_before = crate::core::metrics::METRICS.rules_fired_total();
+    let result = run(facts_json, &grl_content);
+    let rules_fired = crate::core::metrics::METRICS.rules_fired_total() - fired_before;
+
+    crate::repository::audit::record_execution(
+        &name,
+        version.as_deref(),
+        facts_json,
+        rules_fired,
+        &result,
+    )?;
+
+    Ok(result)
+}
+
+/// Apply a stored rule to every row of a table and upsert the results
+///
+/// Each source row is passed as facts via `row_to_json`, as rule triggers do.
+/// The resulting facts are written to `target_table` with
+/// `jsonb_populate_record`: result keys map onto target columns by name and
+/// keys without a matching column are dropped. `id_column` must have a unique
+/// constraint in the target. All rows are written in one subtransaction, so if
+/// any row fails the target is left untouched.
+///
+/// # Returns
+/// Number of rows written
+///
+/// # Example
+/// ```sql
+/// SELECT rule_apply_to_table('discount_rule', 'orders', 'order_discounts', 'id');
+/// ```
+#[pg_extern]
+pub fn rule_apply_to_table(
+    rule_name: String,
+    source_table: String,
+    target_table: String,
+    id_column: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<i64, RuleEngineError> {
+    validate_rule_name(&rule_name)?;
+
+    let source = resolve_table(&source_table)?;
+    let target = resolve_table(&target_table)?;
+
+    let columns: Vec<String> = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT attname::text FROM pg_attribute
+                 WHERE attrelid = $1::regclass AND attnum > 0 AND NOT attisdropped
+                 ORDER BY attnum",
+                None,
+                &[target.as_str().into()],
+            )?
+            .map(|row| row.get::<String>(1))
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if !columns.contains(&id_column) {
+        return Err(RuleEngineError::InvalidInput(format!(
+            "Column '{}' not found in table {}",
+            id_column, target
+        )));
+    }
+
+    let quote_ident = |ident: &str| format!("\"{}\"", ident.replace('"', "\"\""));
+    let updates: Vec<String> = columns
+        .iter()
+        .filter(|column| **column != id_column)
+        .map(|column| format!("{0} = EXCLUDED.{0}", quote_ident(column)))
+        .collect();
+    let on_conflict = if updates.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", updates.join(", "))
+    };
+    let upsert = format!(
+        "INSERT INTO {target} SELECT * FROM jsonb_populate_record(NULL::{target}, $1)
+         ON CONFLICT ({id}) {on_conflict}",
+        target = target,
+        id = quote_ident(&id_column),
+        on_conflict = on_conflict,
+    );
+
+    let rows: Vec<String> = Spi::connect(|client| {
+        client
+            .select(
+                &format!("SELECT row_to_json(s)::text FROM {} s", source),
+                None,
+                &[],
+            )?
+            .map(|row| row.get::<String>(1))
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .into_iter()
+    .flatten()
+    .collect();
+
+    in_subtransaction(|| {
+        for (index, facts_json) in rows.iter().enumerate() {
+            let result = execute_stored_rule(rule_name.clone(), facts_json, version.clone())?;
+            let result: serde_json::Value = serde_json::from_str(&result)?;
+
+            if let Some(error) = result.get("error") {
+                return Err(RuleEngineError::ExecutionError(
+                    rust_rule_engine::RuleEngineError::ExecutionError(format!(
+                        "Row {} of {}: {}",
+                        index + 1,
+                        source,
+                        error
+                    )),
+                ));
+            }
+
+            Spi::run_with_args(&upsert, &[pgrx::JsonB(result).into()])?;
+        }
+
+        Ok(rows.len() as i64)
+    })
+}
+
+/// Resolve a possibly schema-qualified table name to its quoted form
+fn resolve_table(name: &str) -> Result<String, RuleEngineError> {
+    Spi::connect(|client| {
+        client
+            .select("SELECT to_regclass($1)::text", None, &[name.into()])?
+            .first()
+            .get_one::<String>()
+    })?
+    .ok_or_else(|| RuleEngineError::InvalidInput(format!("Table '{}' not found", name)))
+}
+
+/// Fetch a stored rule and compile it without executing
+///
+/// Intended for deploy-time checks: a rule that fails to compile is reported
+/// here instead of on the first real request. The compiled network is cached
+/// in this backend, so later RETE executions of the rule skip compiling. Rules
+/// using built-in functions or `foreach` are rewritten from each call's facts
+/// and still compile per execution.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// JSON `{name, version, rule_count, compile_time_ms}`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_engine_warmup('discount_rule');
+/// -- {"name": "discount_rule", "version": null, "rule_count": 3, "compile_time_ms": 0.42}
+/// ```
+#[pg_extern]
+pub fn rule_engine_warmup(
+    name: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    let grl_content = rule_get(name.clone(), version.clone())?;
+
+    let started = std::time::Instant::now();
+    let rule_count = crate::core::compile_rules_rete(&grl_content).map_err(|e| {
+        RuleEngineError::InvalidInput(format!("Rule '{}' does not compile: {}", name, e))
+    })?;
+    let compile_time_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    Ok(serde_json::json!({
+        "name": name,
+        "version": version,
+        "rule_count": rule_count,
+        "compile_time_ms": compile_time_ms,
+    })
+    .to_string())
+}
+
+/// Most iterations a single rule_benchmark call runs
+const MAX_BENCHMARK_ITERATIONS: i32 = 10_000;
+
+/// Run a stored rule repeatedly and report execution latency
+///
+/// Each iteration runs the rule with the same engine `rule_execute_by_name`
+/// would use. The rule is fetched once up front, so the timings cover
+/// execution only, and iterations are not audited. `iterations` is capped at
+/// 10,000.
+///
+/// # Returns
+/// JSON `{name, iterations, min_ms, p50_ms, p95_ms, max_ms, mean_ms}`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_benchmark('discount_rule', '{"Order": {"total": 150}}', 1000);
+/// -- {"name": "discount_rule", "iterations": 1000, "min_ms": 0.21, "p50_ms": 0.24, ...}
+/// ```
+#[pg_extern]
+pub fn rule_benchmark(
+    name: String,
+    facts_json: String,
+    iterations: i32,
+) -> Result<String, RuleEngineError> {
+    if iterations < 1 {
+        return Err(RuleEngineError::InvalidInput(format!(
+            "iterations must be at least 1, got {}",
+            iterations
+        )));
+    }
+    if iterations > MAX_BENCHMARK_ITERATIONS {
+        pgrx::warning!(
+            "rule_benchmark: iterations capped at {} (requested {})",
+            MAX_BENCHMARK_ITERATIONS,
+            iterations
+        );
+    }
+    let iterations = iterations.min(MAX_BENCHMARK_ITERATIONS) as usize;
+
+    let grl_content = rule_get(name.clone(), None)?;
+    let run = rule_engine_for(&name)?;
+
+    let mut timings_ms = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = std::time::Instant::now();
+        let result = run(&facts_json, &grl_content);
+        timings_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+
+        // A failing rule fails the same way every time; don't time errors
+        if timings_ms.len() == 1 {
+            let result: serde_json::Value = serde_json::from_str(&result)?;
+            if let Some(error) = result.get("error") {
+                return Err(RuleEngineError::ExecutionError(
+                    rust_rule_engine::RuleEngineError::ExecutionError(format!(
+                        "Rule '{}': {}",
+                        name, error
+                    )),
+                ));
+            }
+        }
+    }
+
+    timings_ms.sort_by(f64::total_cmp);
+    let mean_ms = timings_ms.iter().sum::<f64>() / timings_ms.len() as f64;
+
+    Ok(serde_json::json!({
+        "name": name,
+        "iterations": iterations,
+        "min_ms": timings_ms[0],
+        "p50_ms": percentile(&timings_ms, 50.0),
+        "p95_ms": percentile(&timings_ms, 95.0),
+        "max_ms": timings_ms[timings_ms.len() - 1],
+        "mean_ms": mean_ms,
+    })
+    .to_string())
+}
+
+/// Nearest-rank percentile of ascending, non-empty `sorted`
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Check GRL for structural problems, reporting all of them at once
+///
+/// Used by `rule_validate()` so editors can show every issue in one pass.
+///
+/// # Returns
+/// JSON `{valid, issues}`, each issue being `{code, message, location}` where
+/// `location` is `{line, column}` (1-based) or null
+///
+/// # Example
+/// ```sql
+/// SELECT rule_validate_detailed('rule "A" { when X.a > 1 }');
+/// -- {"valid": false, "issues": [{"code": "MISSING_THEN", "message": "Rule 'A' has no 'then' clause", "location": {"line": 1, "column": 1}}]}
+/// ```
+#[pg_extern(immutable, parallel_safe)]
+pub fn rule_validate_detailed(grl: &str) -> pgrx::JsonB {
+    let issues = validate_grl_content_detailed(grl).err().unwrap_or_default();
+
+    pgrx::JsonB(serde_json::json!({
+        "valid": issues.is_empty(),
+        "issues": issues,
+    }))
+}
+
+/// Check that sample facts provide every fact path a stored rule reads
+///
+/// Catches typos such as `Oder.total`, which otherwise just never match.
+/// Paths the rule's own actions assign are not required in the input.
+///
+/// # Returns
+/// JSON `{referenced, warnings}`: every path the rule reads, and one
+/// `{path, message}` warning per path missing from `sample_facts_json`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_check_facts('discount_rule', '{"Order": {"total": 150}}');
+/// -- {"referenced": ["Customer.tier", "Order.total"],
+/// --  "warnings": [{"path": "Customer.tier", "message": "Fact 'Customer.tier' is not in the sample facts"}]}
+/// ```
+#[pg_extern]
+pub fn rule_check_facts(
+    name: String,
+    sample_facts_json: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<pgrx::JsonB, RuleEngineError> {
+    let grl_content = rule_get(name.clone(), version)?;
+    let referenced = crate::core::referenced_fact_paths(&grl_content).map_err(|e| {
+        RuleEngineError::InvalidInput(format!("Rule '{}' does not parse: {}", name, e))
+    })?;
+
+    let sample: serde_json::Value = serde_json::from_str(&sample_facts_json)?;
+    if !sample.is_object() {
+        return Err(RuleEngineError::InvalidInput(
+            "Sample facts must be a JSON object".to_string(),
+        ));
+    }
+    let mut provided = std::collections::HashSet::new();
+    collect_fact_paths("", &sample, &mut provided);
+
+    let warnings: Vec<serde_json::Value> = referenced
+        .iter()
+        .filter(|path| !provided.contains(path.as_str()))
+        .map(|path| {
+            serde_json::json!({
+                "path": path,
+                "message": format!("Fact '{}' is not in the sample facts", path),
+            })
+        })
+        .collect();
+
+    Ok(pgrx::JsonB(serde_json::json!({
+        "referenced": referenced,
+        "warnings": warnings,
+    })))
+}
+
+/// Show which stored rules feed facts into a rule and which consume its output
+///
+/// Compares the fact paths each active rule's default version reads and
+/// writes (see `rule_check_facts`). A rule is in `depends_on` when it assigns
+/// a fact this rule reads, and in `used_by` when it reads a fact this rule
+/// assigns. Rules whose GRL doesn't parse are skipped with a WARNING.
+///
+/// # Returns
+/// JSON `{name, reads, writes, depends_on, used_by}`, each dependency being
+/// `{rule, facts}`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_dependencies('loyalty_tier');
+/// -- {"name": "loyalty_tier", "reads": ["Customer.spend"], "writes": ["Customer.tier"],
+/// --  "depends_on": [{"rule": "spend_rollup", "facts": ["Customer.spend"]}],
+/// --  "used_by": [{"rule": "discount_rule", "facts": ["Customer.tier"]}]}
+/// ```
+#[pg_extern]
+pub fn rule_dependencies(name: String) -> Result<pgrx::JsonB, RuleEngineError> {
+    let grl_content = rule_get(name.clone(), None)?;
+    let flow = crate::core::fact_flow(&grl_content).map_err(|e| {
+        RuleEngineError::InvalidInput(format!("Rule '{}' does not parse: {}", name, e))
+    })?;
+
+    let catalog: Vec<(String, String)> = Spi::connect(|client| {
+        client
+            .select(
+                "SELECT rd.name::text, rv.grl_content
+                 FROM rule_definitions rd
+                 JOIN rule_versions rv ON rv.rule_id = rd.id AND rv.is_default = true
+                 WHERE rd.is_active = true AND rd.name <> $1
+                 ORDER BY rd.name",
+                None,
+                &[name.clone().into()],
+            )?
+            .map(|row| Ok((row.get::<String>(1)?, row.get::<String>(2)?)))
+            .collect::<Result<Vec<_>, pgrx::spi::SpiError>>()
+    })?
+    .into_iter()
+    .filter_map(|(other, grl)| Some((other?, grl?)))
+    .collect();
+
+    let mut depends_on = Vec::new();
+    let mut used_by = Vec::new();
+    for (other, grl) in catalog {
+        let other_flow = match crate::core::fact_flow(&grl) {
+            Ok(other_flow) => other_flow,
+            Err(e) => {
+                pgrx::warning!("rule_dependencies: skipping rule '{}': {}", other, e);
+                continue;
+            }
+        };
+
+        let feeds: Vec<&String> = other_flow.writes.intersection(&flow.reads).collect();
+        if !feeds.is_empty() {
+            depends_on.push(serde_json::json!({"rule": other, "facts": feeds}));
+        }
+        let consumes: Vec<&String> = flow.writes.intersection(&other_flow.reads).collect();
+        if !consumes.is_empty() {
+            used_by.push(serde_json::json!({"rule": other, "facts": consumes}));
+        }
+    }
+
+    Ok(pgrx::JsonB(serde_json::json!({
+        "name": name,
+        "reads": flow.reads,
+        "writes": flow.writes,
+        "depends_on": depends_on,
+        "used_by": used_by,
+    })))
+}
+
+/// Dotted paths of every key in `facts`, including intermediate objects
+fn collect_fact_paths(
+    prefix: &str,
+    facts: &serde_json::Value,
+    paths: &mut std::collections::HashSet<String>,
+) {
+    let serde_json::Value::Object(map) = facts else {
+        return;
+    };
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        collect_fact_paths(&path, value, paths);
+        paths.insert(path);
+    }
+}
+
+/// Run a stored rule against input facts and compare the result to expected facts
+///
+/// Every field in `expected_json` must match the resulting facts; fields the
+/// expectation doesn't mention are ignored. Numbers compare by value, so `15`
+/// matches `15.0`.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts_json` - Input facts as JSON string
+/// * `expected_json` - Expected facts (JSON object, may be partial)
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// JSON `{passed, diff}` where `diff` maps each mismatched field path to
+/// `{expected, actual}`. If execution fails, `passed` is false and
+/// `error` / `error_code` describe the failure.
+///
+/// # Example
+/// ```sql
+/// SELECT rule_test(
+///     'discount_rule',
+///     '{"Order": {"Amount": 150}}',
+///     '{"Order": {"Discount": 15}}'
+/// );
+/// -- {"passed": true, "diff": {}}
+/// ```
+#[pg_extern]
+pub fn rule_test(
+    name: String,
+    facts_json: String,
+    expected_json: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    let expected: serde_json::Value = serde_json::from_str(&expected_json)?;
+    if !expected.is_object() {
+        return Err(RuleEngineError::InvalidInput(
+            "Expected facts must be a JSON object".to_string(),
+        ));
+    }
+
+    let result = rule_execute_by_name(name, facts_json, version, None)?;
+    let actual: serde_json::Value = serde_json::from_str(&result)?;
+
+    // run_rule_engine reports failures as {error, error_code, timestamp}
+    if let Some(error_code) = actual.get("error_code") {
+        return Ok(serde_json::json!({
+            "passed": false,
+            "diff": {},
+            "error": actual.get("error"),
+            "error_code": error_code,
+        })
+        .to_string());
+    }
+
+    let mut diff = serde_json::Map::new();
+    diff_facts("", &expected, &actual, &mut diff);
+
+    Ok(serde_json::json!({
+        "passed": diff.is_empty(),
+        "diff": diff,
+    })
+    .to_string())
+}
+
+// Helper: record every path in `expected` whose value differs in `actual`.
+// Objects are compared field by field; any other value is compared as a whole.
+fn diff_facts(
+    path: &str,
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    diff: &mut serde_json::Map<String, serde_json::Value>,
+) {
+    if let (Some(expected_obj), Some(actual_obj)) = (expected.as_object(), actual.as_object()) {
+        for (key, expected_value) in expected_obj {
+            let child_path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+            let actual_value = actual_obj.get(key).unwrap_or(&serde_json::Value::Null);
+            diff_facts(&child_path, expected_value, actual_value, diff);
+        }
+        return;
+    }
+
+    let matches = match (expected.as_f64(), actual.as_f64()) {
+        (Some(e), Some(a)) => e == a,
+        _ => expected == actual,
+    };
+
+    if !matches {
+        diff.insert(
+            path.to_string(),
+            serde_json::json!({ "expected": expected, "actual": actual }),
+        );
+    }
+}
+
+/// Execute a stored rule by name, expanding `@include("other_rule")` directives
+///
+/// Each directive is replaced by the default version of the referenced rule,
+/// recursively. A rule included more than once is only expanded the first time,
+/// and include cycles are rejected.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts_json` - Input facts as JSON string
+/// * `version` - Optional specific version of the top-level rule (uses default if None)
+///
+/// # Returns
+/// Modified facts (JSON string)
+///
+/// # Example
+/// ```sql
+/// -- 'checkout_rules' contains: @include("shipping_rules")
+/// SELECT rule_execute_with_includes('checkout_rules', '{"Order": {"Amount": 150}}');
+/// ```
+#[pg_extern]
+pub fn rule_execute_with_includes(
+    name: String,
+    facts_json: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    let grl_content = rule_get(name.clone(), version)?;
+    let grl_content = resolve_includes(&name, &grl_content, |include| {
+        rule_get(include.to_string(), None)
+    })?;
 
-    // Execute using existing run_rule_engine
     let result = crate::api::engine::run_rule_engine(&facts_json, &grl_content);
     Ok(result)
 }
 
+// Helper: expand `@include("name")` directives in `grl`, fetching included rules
+// through `fetch`. `root` is the name of the rule `grl` belongs to and seeds
+// cycle detection.
+fn resolve_includes<F>(root: &str, grl: &str, mut fetch: F) -> Result<String, RuleEngineError>
+where
+    F: FnMut(&str) -> Result<String, RuleEngineError>,
+{
+    let include_re = regex::Regex::new(r#"@include\(\s*"([^"]+)"\s*\)"#)
+        .map_err(|e| RuleEngineError::InvalidInput(format!("Regex error: {}", e)))?;
+
+    let mut stack = vec![root.to_string()];
+    let mut included = std::collections::HashSet::new();
+    included.insert(root.to_string());
+
+    expand_includes(grl, &include_re, &mut fetch, &mut stack, &mut included)
+}
+
+fn expand_includes<F>(
+    grl: &str,
+    include_re: &regex::Regex,
+    fetch: &mut F,
+    stack: &mut Vec<String>,
+    included: &mut std::collections::HashSet<String>,
+) -> Result<String, RuleEngineError>
+where
+    F: FnMut(&str) -> Result<String, RuleEngineError>,
+{
+    let mut out = String::with_capacity(grl.len());
+    let mut last = 0;
+
+    for cap in include_re.captures_iter(grl) {
+        let directive = cap.get(0).unwrap();
+        let include = &cap[1];
+        out.push_str(&grl[last..directive.start()]);
+        last = directive.end();
+
+        if stack.iter().any(|n| n == include) {
+            return Err(RuleEngineError::InvalidInput(format!(
+                "Include cycle detected: {} -> {}",
+                stack.join(" -> "),
+                include
+            )));
+        }
+
+        // Already expanded elsewhere (e.g. shared dependency); skip duplicates
+        if !included.insert(include.to_string()) {
+            continue;
+        }
+
+        validate_rule_name(include)?;
+        let included_grl = fetch(include)?;
+
+        stack.push(include.to_string());
+        let expanded = expand_includes(&included_grl, include_re, fetch, stack, included)?;
+        stack.pop();
+
+        out.push_str(&expanded);
+    }
+
+    out.push_str(&grl[last..]);
+    Ok(out)
+}
+
+/// Execute a stored rule by name with JSONB facts, returning JSONB
+///
+/// Same as `rule_execute_by_name`, but the result is a real JSON value that
+/// can be used directly with `->` / `->>` in larger queries.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts` - Input facts as JSONB
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// Modified facts (JSONB). Execution errors are returned as
+/// `{error, error_code, timestamp}` like the TEXT variant.
+///
+/// # Example
+/// ```sql
+/// SELECT rule_execute_by_name_jsonb('discount_rule', '{"Order": {"Amount": 150}}'::jsonb)
+///        -> 'Order' ->> 'Discount';
+/// ```
+#[pg_extern]
+pub fn rule_execute_by_name_jsonb(
+    name: String,
+    facts: pgrx::JsonB,
+    version: default!(Option<String>, "NULL"),
+) -> Result<pgrx::JsonB, RuleEngineError> {
+    let result = execute_stored_rule(name, &facts.0.to_string(), version)?;
+    Ok(pgrx::JsonB(serde_json::from_str(&result)?))
+}
+
+/// Execute a stored rule in a debug session and return one row per firing
+///
+/// Each row is a rule that fired, in firing order, with all facts as they
+/// stood right after its actions ran. The rows come from the session's
+/// `RuleFired` events, so the session can still be inspected afterwards with
+/// the debug functions. Always runs with forward chaining, whatever
+/// `rule_set_algorithm` says.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts_json` - Input facts as JSON string
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Example
+/// ```sql
+/// SELECT rule_name, facts_after -> 'Order' ->> 'discount'
+/// FROM rule_execute_trace('pricing_rules', '{"Order": {"total": 150}}');
+/// ```
+#[allow(clippy::type_complexity)]
+#[pg_extern]
+pub fn rule_execute_trace(
+    name: String,
+    facts_json: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<
+    TableIterator<'static, (name!(rule_name, String), name!(facts_after, pgrx::JsonB))>,
+    RuleEngineError,
+> {
+    let grl_content = rule_get(name.clone(), version)?;
+
+    let (_, session_id) = crate::api::engine::run_debug_session(&facts_json, &grl_content)
+        .map_err(|e| {
+            let message = serde_json::from_str::<serde_json::Value>(&e)
+                .ok()
+                .and_then(|v| v.get("error").and_then(|m| m.as_str()).map(str::to_string))
+                .unwrap_or(e);
+            RuleEngineError::ExecutionError(rust_rule_engine::RuleEngineError::ExecutionError(
+                format!("Rule '{}': {}", name, message),
+            ))
+        })?;
+
+    let session = crate::debug::GLOBAL_EVENT_STORE
+        .get_session(&session_id)
+        .map_err(RuleEngineError::DatabaseError)?;
+    if session.truncated {
+        pgrx::warning!(
+            "Debug session {} hit its event limit; the trace is incomplete",
+            session_id
+        );
+    }
+
+    let rows: Vec<_> = session
+        .events
+        .into_iter()
+        .filter_map(|event| match event {
+            crate::debug::ReteEvent::RuleFired {
+                rule_name,
+                facts_after: Some(facts),
+                ..
+            } => Some((rule_name, pgrx::JsonB(facts))),
+            _ => None,
+        })
+        .collect();
+
+    Ok(TableIterator::new(rows))
+}
+
 /// Query backward chaining goal using stored rule by name
 ///
 /// # Arguments
@@ -525,10 +1569,59 @@ pub fn rule_query_by_name(
     let grl_content = rule_get(name, version)?;
 
     // Execute using backward chaining
-    let result = crate::api::backward::query_backward_chaining(&facts_json, &grl_content, &goal);
+    let result =
+        crate::api::backward::query_backward_chaining(&facts_json, &grl_content, &goal, false);
     Ok(result)
 }
 
+/// Query several backward chaining goals at once using stored rule by name
+///
+/// The GRL is loaded once and all goals are evaluated against the same facts.
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts_json` - Input facts as JSON string
+/// * `goals` - Goal queries
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// JSON object mapping each goal to its result (provable, proof_trace, metrics)
+///
+/// # Example
+/// ```sql
+/// SELECT rule_query_multi_by_name(
+///     'eligibility_rules',
+///     '{"User": {"Age": 25}}',
+///     ARRAY['User.CanVote == true', 'User.CanRent == true']
+/// );
+/// ```
+#[pg_extern]
+pub fn rule_query_multi_by_name(
+    name: String,
+    facts_json: String,
+    goals: Vec<String>,
+    version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    // Get the GRL content
+    let grl_content = rule_get(name, version)?;
+
+    let result = crate::api::backward::query_backward_chaining_multi(
+        &facts_json,
+        &grl_content,
+        goals.clone(),
+    );
+
+    // Results come back in goal order; errors come back as a JSON object
+    match serde_json::from_str::<serde_json::Value>(&result)? {
+        serde_json::Value::Array(results) => {
+            let by_goal: serde_json::Map<String, serde_json::Value> =
+                goals.into_iter().zip(results).collect();
+            Ok(serde_json::Value::Object(by_goal).to_string())
+        }
+        _ => Ok(result),
+    }
+}
+
 /// Check if goal can be proven using stored rule by name (fast boolean check)
 ///
 /// # Arguments
@@ -558,3 +1651,187 @@ pub fn rule_can_prove_by_name(
     let result = crate::api::backward::can_prove_goal(&facts_json, &grl_content, &goal);
     Ok(result)
 }
+
+/// Explain whether a goal can be proven using stored rule by name
+///
+/// # Arguments
+/// * `name` - Rule name
+/// * `facts_json` - Input facts as JSON string
+/// * `goal` - Goal query
+/// * `version` - Optional specific version (uses default if None)
+///
+/// # Returns
+/// JSON with `goal`, `provable`, ordered `steps` (rule, goal, depth) and `missing_facts`
+///
+/// # Example
+/// ```sql
+/// SELECT rule_can_prove_by_name_explain('eligibility_rules', '{"User": {"Age": 25}}', 'User.CanVote == true');
+/// ```
+#[pg_extern]
+pub fn rule_can_prove_by_name_explain(
+    name: String,
+    facts_json: String,
+    goal: String,
+    version: default!(Option<String>, "NULL"),
+) -> Result<String, RuleEngineError> {
+    // Get the GRL content
+    let grl_content = rule_get(name, version)?;
+
+    let result = crate::api::backward::can_prove_goal_explain(&facts_json, &grl_content, &goal);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn library() -> HashMap<&'static str, &'static str> {
+        HashMap::from([
+            (
+                "shipping",
+                "rule \"Shipping\" { when A.x > 0 then A.s = 1; }",
+            ),
+            (
+                "tax",
+                "@include(\"shipping\")\nrule \"Tax\" { when A.x > 0 then A.t = 1; }",
+            ),
+            ("loop_a", "@include(\"loop_b\")\nrule \"A\" {}"),
+            ("loop_b", "@include(\"loop_a\")\nrule \"B\" {}"),
+        ])
+    }
+
+    fn fetch(name: &str) -> Result<String, RuleEngineError> {
+        library()
+            .get(name)
+            .map(|grl| grl.to_string())
+            .ok_or_else(|| RuleEngineError::RuleNotFound(format!("Rule '{}' not found", name)))
+    }
+
+    #[test]
+    fn test_resolve_includes_nested_and_deduplicated() {
+        let grl = "@include(\"tax\")\n@include( \"shipping\" )\nrule \"Checkout\" {}";
+        let resolved = resolve_includes("checkout", grl, fetch).unwrap();
+
+        assert!(!resolved.contains("@include"));
+        assert_eq!(resolved.matches("rule \"Shipping\"").count(), 1);
+        assert!(resolved.contains("rule \"Tax\""));
+        assert!(resolved.contains("rule \"Checkout\""));
+    }
+
+    #[test]
+    fn test_resolve_includes_detects_cycles() {
+        let err = resolve_includes("loop_a", "@include(\"loop_b\")", fetch).unwrap_err();
+        assert!(err.message().contains("loop_a -> loop_b -> loop_a"));
+
+        let err = resolve_includes("self_ref", "@include(\"self_ref\")", fetch).unwrap_err();
+        assert!(err.message().contains("cycle"));
+    }
+
+    #[test]
+    fn test_diff_facts() {
+        let expected = serde_json::json!({
+            "Order": {"Discount": 15, "Status": "approved", "Coupon": null}
+        });
+        let actual = serde_json::json!({
+            "Order": {"Amount": 150, "Discount": 15.0, "Status": "pending"}
+        });
+
+        let mut diff = serde_json::Map::new();
+        diff_facts("", &expected, &actual, &mut diff);
+
+        // 15 == 15.0, absent Coupon equals expected null, extra Amount ignored
+        assert_eq!(diff.len(), 1);
+        assert_eq!(
+            diff["Order.Status"],
+            serde_json::json!({"expected": "approved", "actual": "pending"})
+        );
+    }
+
+    #[test]
+    fn test_diff_facts_missing_object() {
+        let expected = serde_json::json!({"Customer": {"Tier": "gold"}});
+        let actual = serde_json::json!({"Order": {}});
+
+        let mut diff = serde_json::Map::new();
+        diff_facts("", &expected, &actual, &mut diff);
+
+        assert_eq!(
+            diff["Customer"],
+            serde_json::json!({"expected": {"Tier": "gold"}, "actual": null})
+        );
+    }
+
+    #[test]
+    fn test_resolve_includes_missing_rule() {
+        let err = resolve_includes("checkout", "@include(\"missing\")", fetch).unwrap_err();
+        assert!(matches!(err, RuleEngineError::RuleNotFound(_)));
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&sorted, 50.0), 10.0);
+        assert_eq!(percentile(&sorted, 95.0), 19.0);
+        assert_eq!(percentile(&sorted, 100.0), 20.0);
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&[4.2], 95.0), 4.2);
+    }
+
+    #[test]
+    fn test_collect_fact_paths() {
+        let facts = serde_json::json!({
+            "Order": {"total": 150, "items": {"count": 2}},
+            "Customer.tier": "gold"
+        });
+        let mut paths = std::collections::HashSet::new();
+        collect_fact_paths("", &facts, &mut paths);
+
+        for path in [
+            "Order",
+            "Order.total",
+            "Order.items",
+            "Order.items.count",
+            "Customer.tier",
+        ] {
+            assert!(paths.contains(path), "{}", path);
+        }
+        assert_eq!(paths.len(), 5);
+    }
+}
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pg_schema]
+mod pg_tests {
+    use pgrx::prelude::*;
+
+    fn rule_defined(name: &str) -> bool {
+        Spi::get_one_with_args::<bool>(
+            "SELECT EXISTS(SELECT 1 FROM rule_definitions WHERE name = $1)",
+            &[name.into()],
+        )
+        .unwrap()
+        .unwrap_or(false)
+    }
+
+    #[pg_test]
+    fn test_failed_save_leaves_no_orphan_definition() {
+        Spi::run(include_str!("../../migrations/001_rule_repository.sql")).unwrap();
+
+        // The version is checked after the definition row is written. The
+        // error is inspected in the same transaction, so only rule_save's own
+        // rollback can remove that row.
+        let result = super::rule_save(
+            "test_orphan".to_string(),
+            r#"rule "Orphan" { when X.a > 1 then X.b = 2; }"#.to_string(),
+            Some("not-a-version".to_string()),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(result.is_err());
+        assert!(!rule_defined("test_orphan"));
+    }
+}
@@ -1,6 +1,8 @@
 // Validation functions for rule repository
 use crate::error::RuleEngineError;
 use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
 
 /// Validate rule name format: alphanumeric + underscore/hyphen, must start with letter
 pub fn validate_rule_name(name: &str) -> Result<(), RuleEngineError> {
@@ -54,6 +56,192 @@ pub fn validate_grl_content(grl: &str) -> Result<(), RuleEngineError> {
     Ok(())
 }
 
+/// One problem found by `validate_grl_content_detailed`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Machine-readable kind, e.g. "UNBALANCED_BRACES"
+    pub code: &'static str,
+    pub message: String,
+    pub location: Option<SourceLocation>,
+}
+
+/// 1-based position in the GRL source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ValidationIssue {
+    fn new(code: &'static str, message: String, location: Option<SourceLocation>) -> Self {
+        Self {
+            code,
+            message,
+            location,
+        }
+    }
+}
+
+/// Validate GRL content, reporting every problem found instead of the first
+///
+/// Structural checks only (size, braces, rule headers, `when`/`then`); it
+/// doesn't compile the rules.
+pub fn validate_grl_content_detailed(grl: &str) -> Result<(), Vec<ValidationIssue>> {
+    if grl.trim().is_empty() {
+        return Err(vec![ValidationIssue::new(
+            "EMPTY_GRL",
+            "GRL content cannot be empty".to_string(),
+            None,
+        )]);
+    }
+
+    let mut issues = Vec::new();
+
+    if grl.len() > 1_048_576 {
+        issues.push(ValidationIssue::new(
+            "GRL_TOO_LARGE",
+            "GRL content cannot exceed 1MB".to_string(),
+            None,
+        ));
+    }
+
+    let (braces, matching) = scan_braces(grl);
+    for (offset, message) in braces {
+        issues.push(ValidationIssue::new(
+            "UNBALANCED_BRACES",
+            message,
+            Some(location_of(grl, offset)),
+        ));
+    }
+
+    let header_re = Regex::new(r#"\brule\s+(?:"([^"]*)"|([^\s{]+))[^{]*\{"#).unwrap();
+    let name_re = Regex::new(r"^[A-Za-z_][A-Za-z0-9_ .-]*$").unwrap();
+    let when_re = Regex::new(r"\bwhen\b").unwrap();
+    let then_re = Regex::new(r"\bthen\b").unwrap();
+    let mut seen: HashMap<String, SourceLocation> = HashMap::new();
+
+    for header in header_re.captures_iter(grl) {
+        let whole = header.get(0).unwrap();
+        let name = header.get(1).or(header.get(2)).unwrap().as_str();
+        let location = location_of(grl, whole.start());
+
+        if !name_re.is_match(name) {
+            issues.push(ValidationIssue::new(
+                "INVALID_RULE_NAME",
+                format!(
+                    "Invalid rule name '{}'. Must start with a letter or underscore and contain only letters, digits, spaces, '_', '-' or '.'",
+                    name
+                ),
+                Some(location),
+            ));
+        }
+
+        if let Some(first) = seen.get(name) {
+            issues.push(ValidationIssue::new(
+                "DUPLICATE_RULE_NAME",
+                format!("Rule '{}' is already defined at line {}", name, first.line),
+                Some(location),
+            ));
+        } else {
+            seen.insert(name.to_string(), location);
+        }
+
+        // An unclosed body runs to the end of the input
+        let open = whole.end() - 1;
+        let close = matching.get(&open).copied().unwrap_or(grl.len());
+        let body = &grl[open + 1..close];
+
+        if !when_re.is_match(body) {
+            issues.push(ValidationIssue::new(
+                "MISSING_WHEN",
+                format!("Rule '{}' has no 'when' clause", name),
+                Some(location),
+            ));
+        }
+        if !then_re.is_match(body) {
+            issues.push(ValidationIssue::new(
+                "MISSING_THEN",
+                format!("Rule '{}' has no 'then' clause", name),
+                Some(location),
+            ));
+        }
+    }
+
+    if seen.is_empty() {
+        issues.push(ValidationIssue::new(
+            "NO_RULES",
+            "GRL content must contain at least one rule definition".to_string(),
+            None,
+        ));
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+/// Find unbalanced braces outside string literals and `//` comments
+///
+/// Returns the problems (byte offset, message) and a map from each opening
+/// brace's offset to its closing brace's offset.
+fn scan_braces(grl: &str) -> (Vec<(usize, String)>, HashMap<usize, usize>) {
+    let mut problems = Vec::new();
+    let mut matching = HashMap::new();
+    let mut open = Vec::new();
+    let mut chars = grl.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                let mut escaped = false;
+                for (_, c) in chars.by_ref() {
+                    match c {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '/'))) => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '{' => open.push(i),
+            '}' => match open.pop() {
+                Some(start) => {
+                    matching.insert(start, i);
+                }
+                None => problems.push((i, "Unexpected '}' without a matching '{'".to_string())),
+            },
+            _ => {}
+        }
+    }
+
+    for start in open {
+        problems.push((start, "'{' is never closed".to_string()));
+    }
+    problems.sort_by_key(|(offset, _)| *offset);
+
+    (problems, matching)
+}
+
+fn location_of(grl: &str, offset: usize) -> SourceLocation {
+    let before = &grl[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    SourceLocation {
+        line,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
 /// Validate semantic version format
 pub fn validate_version(version: &str) -> Result<(), RuleEngineError> {
     if version.is_empty() {
@@ -62,10 +250,13 @@ pub fn validate_version(version: &str) -> Result<(), RuleEngineError> {
         ));
     }
 
-    let re = Regex::new(r"^\d+\.\d+\.\d+(-[a-zA-Z0-9]+)?$").unwrap();
+    let re = Regex::new(
+        r"^\d+\.\d+\.\d+(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?(\+[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?$",
+    )
+    .unwrap();
     if !re.is_match(version) {
         return Err(RuleEngineError::InvalidInput(format!(
-            "Invalid version '{}'. Must follow semantic versioning (e.g., 1.0.0, 2.1.0-beta)",
+            "Invalid version '{}'. Must follow semantic versioning (e.g., 1.0.0, 2.1.0-rc.1, 1.0.0+build5)",
             version
         )));
     }
@@ -100,6 +291,34 @@ pub fn validate_tag(tag: &str) -> Result<(), RuleEngineError> {
     Ok(())
 }
 
+/// Validate rule_save idempotency key
+pub fn validate_idempotency_key(key: &str) -> Result<(), RuleEngineError> {
+    if key.trim().is_empty() {
+        return Err(RuleEngineError::InvalidInput(
+            "Idempotency key cannot be empty".to_string(),
+        ));
+    }
+
+    if key.len() > 255 {
+        return Err(RuleEngineError::InvalidInput(
+            "Idempotency key cannot exceed 255 characters".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validate a stored rule's execution algorithm: 'rete' or 'fc'
+pub fn validate_algorithm(algorithm: &str) -> Result<(), RuleEngineError> {
+    match algorithm {
+        "rete" | "fc" => Ok(()),
+        _ => Err(RuleEngineError::InvalidInput(format!(
+            "Invalid algorithm '{}'. Must be 'rete' or 'fc'",
+            algorithm
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,11 +341,58 @@ mod tests {
         assert!(validate_version("2.5.10").is_ok());
         assert!(validate_version("1.0.0-beta").is_ok());
         assert!(validate_version("1.0.0-alpha1").is_ok());
+        assert!(validate_version("1.0.0-beta.1").is_ok());
+        assert!(validate_version("1.0.0-rc.1+build5").is_ok());
+        assert!(validate_version("1.0.0+20240101").is_ok());
 
         assert!(validate_version("").is_err());
         assert!(validate_version("1.0").is_err());
         assert!(validate_version("v1.0.0").is_err());
-        assert!(validate_version("1.0.0-beta.1").is_err());
+        assert!(validate_version("1.0.0-").is_err());
+        assert!(validate_version("1.0.0-beta..1").is_err());
+        assert!(validate_version("1.0.0+").is_err());
+    }
+
+    #[test]
+    fn test_validate_grl_content_detailed() {
+        assert!(validate_grl_content_detailed(
+            r#"rule "Discount" { when Order.total > 100 then Order.discount = 10; }"#
+        )
+        .is_ok());
+        // Braces in strings and comments don't count
+        assert!(validate_grl_content_detailed(
+            "// a { comment\nrule A { when X.s == \"}\" then X.ok = true; }"
+        )
+        .is_ok());
+
+        let grl = "rule \"Discount\" {\n    when Order.total > 100\n}\nrule \"9 lives\" { then X.a = 1; }\nrule Discount {";
+        let issues = validate_grl_content_detailed(grl).unwrap_err();
+        let codes: Vec<&str> = issues.iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "UNBALANCED_BRACES",
+                "MISSING_THEN",
+                "INVALID_RULE_NAME",
+                "MISSING_WHEN",
+                "DUPLICATE_RULE_NAME",
+                "MISSING_WHEN",
+                "MISSING_THEN",
+            ]
+        );
+        assert_eq!(
+            issues[0].location,
+            Some(SourceLocation {
+                line: 5,
+                column: 15
+            })
+        );
+        assert_eq!(issues[2].location.unwrap().line, 4);
+
+        let issues = validate_grl_content_detailed("  ").unwrap_err();
+        assert_eq!(issues[0].code, "EMPTY_GRL");
+        let issues = validate_grl_content_detailed("when x then y").unwrap_err();
+        assert_eq!(issues[0].code, "NO_RULES");
     }
 
     #[test]
@@ -140,4 +406,24 @@ mod tests {
         assert!(validate_tag("123tag").is_err());
         assert!(validate_tag("tag with space").is_err());
     }
+
+    #[test]
+    fn test_validate_idempotency_key() {
+        assert!(validate_idempotency_key("deploy-2024-06-01-abc123").is_ok());
+        assert!(validate_idempotency_key("7f3c9a1e-5b2d-4c8e-9f10-2a6b3d4e5f60").is_ok());
+
+        assert!(validate_idempotency_key("").is_err());
+        assert!(validate_idempotency_key("   ").is_err());
+        assert!(validate_idempotency_key(&"k".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_validate_algorithm() {
+        assert!(validate_algorithm("rete").is_ok());
+        assert!(validate_algorithm("fc").is_ok());
+
+        assert!(validate_algorithm("").is_err());
+        assert!(validate_algorithm("RETE").is_err());
+        assert!(validate_algorithm("backward").is_err());
+    }
 }
@@ -1,32 +1,39 @@
 // Version management utilities
 use crate::error::RuleEngineError;
+use std::cmp::Ordering;
 use std::fmt;
 
 /// Parse semantic version into components
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Follows the semver 2.0.0 spec: `MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]`.
+/// Build metadata is kept for display but ignored for equality and ordering.
+#[derive(Debug, Clone)]
 pub struct SemanticVersion {
     pub major: u32,
     pub minor: u32,
     pub patch: u32,
     pub pre_release: Option<String>,
+    pub build: Option<String>,
 }
 
 impl SemanticVersion {
     pub fn parse(version: &str) -> Result<Self, RuleEngineError> {
-        let parts: Vec<&str> = version.split('-').collect();
-        let version_part = parts[0];
-        let pre_release = if parts.len() > 1 {
-            Some(parts[1].to_string())
-        } else {
-            None
+        let invalid =
+            || RuleEngineError::InvalidInput(format!("Invalid version format: {}", version));
+
+        // Build metadata comes after the first '+', pre-release after the first '-'
+        let (rest, build) = match version.split_once('+') {
+            Some((rest, build)) => (rest, Some(parse_identifiers(build).ok_or_else(invalid)?)),
+            None => (version, None),
+        };
+        let (version_part, pre_release) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(parse_identifiers(pre).ok_or_else(invalid)?)),
+            None => (rest, None),
         };
 
         let numbers: Vec<&str> = version_part.split('.').collect();
         if numbers.len() != 3 {
-            return Err(RuleEngineError::InvalidInput(format!(
-                "Invalid version format: {}",
-                version
-            )));
+            return Err(invalid());
         }
 
         Ok(SemanticVersion {
@@ -40,75 +47,167 @@ impl SemanticVersion {
                 RuleEngineError::InvalidInput(format!("Invalid patch version: {}", numbers[2]))
             })?,
             pre_release,
+            build,
         })
     }
 
     /// Increment patch version
-    #[allow(dead_code)]
     pub fn increment_patch(&self) -> Self {
         SemanticVersion {
             major: self.major,
             minor: self.minor,
             patch: self.patch + 1,
             pre_release: None,
+            build: None,
         }
     }
 
     /// Increment minor version
-    #[allow(dead_code)]
     pub fn increment_minor(&self) -> Self {
         SemanticVersion {
             major: self.major,
             minor: self.minor + 1,
             patch: 0,
             pre_release: None,
+            build: None,
         }
     }
 
     /// Increment major version
-    #[allow(dead_code)]
     pub fn increment_major(&self) -> Self {
         SemanticVersion {
             major: self.major + 1,
             minor: 0,
             patch: 0,
             pre_release: None,
+            build: None,
+        }
+    }
+
+    /// Increment the component selected by `bump`
+    pub fn bump(&self, bump: VersionBump) -> Self {
+        match bump {
+            VersionBump::Major => self.increment_major(),
+            VersionBump::Minor => self.increment_minor(),
+            VersionBump::Patch => self.increment_patch(),
+        }
+    }
+}
+
+/// Version component to increment when a rule is saved without an explicit version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionBump {
+    Major,
+    Minor,
+    Patch,
+}
+
+impl VersionBump {
+    /// Parse a bump mode ("major", "minor" or "patch", case-insensitive)
+    pub fn parse(mode: &str) -> Result<Self, RuleEngineError> {
+        match mode.trim().to_lowercase().as_str() {
+            "major" => Ok(VersionBump::Major),
+            "minor" => Ok(VersionBump::Minor),
+            "patch" => Ok(VersionBump::Patch),
+            _ => Err(RuleEngineError::InvalidInput(format!(
+                "Invalid bump mode: {}. Expected 'major', 'minor' or 'patch'",
+                mode
+            ))),
+        }
+    }
+}
+
+/// Validate dot-separated identifiers (non-empty, `[0-9A-Za-z-]` only)
+fn parse_identifiers(s: &str) -> Option<String> {
+    let valid = s
+        .split('.')
+        .all(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    if valid {
+        Some(s.to_string())
+    } else {
+        None
+    }
+}
+
+/// Compare pre-release strings identifier by identifier (semver 2.0.0 §11.4):
+/// numeric identifiers compare numerically and sort below alphanumeric ones,
+/// and a shorter list sorts first when all preceding identifiers are equal.
+fn compare_pre_release(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(n), Ok(m)) => n.cmp(&m),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
         }
     }
 }
 
 impl fmt::Display for SemanticVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.pre_release {
-            Some(pre) => write!(f, "{}.{}.{}-{}", self.major, self.minor, self.patch, pre),
-            None => write!(f, "{}.{}.{}", self.major, self.minor, self.patch),
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre_release {
+            write!(f, "-{}", pre)?;
         }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for SemanticVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
     }
 }
 
+impl Eq for SemanticVersion {}
+
 impl PartialOrd for SemanticVersion {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for SemanticVersion {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> Ordering {
         self.major
             .cmp(&other.major)
             .then(self.minor.cmp(&other.minor))
             .then(self.patch.cmp(&other.patch))
             .then_with(|| {
                 match (&self.pre_release, &other.pre_release) {
-                    (None, None) => std::cmp::Ordering::Equal,
-                    (Some(_), None) => std::cmp::Ordering::Less, // Pre-release is less than release
-                    (None, Some(_)) => std::cmp::Ordering::Greater,
-                    (Some(a), Some(b)) => a.cmp(b),
+                    (None, None) => Ordering::Equal,
+                    (Some(_), None) => Ordering::Less, // Pre-release is less than release
+                    (None, Some(_)) => Ordering::Greater,
+                    (Some(a), Some(b)) => compare_pre_release(a, b),
                 }
             })
     }
 }
 
+/// Compare two version strings by semantic version precedence
+///
+/// Returns -1, 0 or 1 as `a` is lower than, equal to or higher than `b`.
+/// Build metadata is ignored, so `1.0.0+a` and `1.0.0+b` compare equal.
+pub fn compare_versions(a: &str, b: &str) -> Result<i32, RuleEngineError> {
+    let ordering = SemanticVersion::parse(a)?.cmp(&SemanticVersion::parse(b)?);
+    Ok(ordering as i32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,6 +222,19 @@ mod tests {
 
         let v = SemanticVersion::parse("1.0.0-beta").unwrap();
         assert_eq!(v.pre_release, Some("beta".to_string()));
+
+        let v = SemanticVersion::parse("1.0.0-rc.1+build5").unwrap();
+        assert_eq!(v.pre_release, Some("rc.1".to_string()));
+        assert_eq!(v.build, Some("build5".to_string()));
+        assert_eq!(v.to_string(), "1.0.0-rc.1+build5");
+
+        let v = SemanticVersion::parse("1.0.0-x-y-z.-").unwrap();
+        assert_eq!(v.pre_release, Some("x-y-z.-".to_string()));
+
+        assert!(SemanticVersion::parse("1.0.0-").is_err());
+        assert!(SemanticVersion::parse("1.0.0-rc..1").is_err());
+        assert!(SemanticVersion::parse("1.0.0+").is_err());
+        assert!(SemanticVersion::parse("1.0.0+build+5").is_err());
     }
 
     #[test]
@@ -137,6 +249,27 @@ mod tests {
         assert!(v3 < v4);
     }
 
+    #[test]
+    fn test_pre_release_ordering() {
+        let parse = |v: &str| SemanticVersion::parse(v).unwrap();
+
+        assert!(parse("1.0.0-rc.1") < parse("1.0.0"));
+        assert!(parse("1.0.0-alpha") < parse("1.0.0-alpha.1"));
+        assert!(parse("1.0.0-alpha.1") < parse("1.0.0-alpha.beta"));
+        assert!(parse("1.0.0-alpha.beta") < parse("1.0.0-beta"));
+        assert!(parse("1.0.0-beta.2") < parse("1.0.0-beta.11"));
+        assert!(parse("1.0.0-rc.1") < parse("1.0.0-rc.2"));
+    }
+
+    #[test]
+    fn test_build_metadata_ignored() {
+        let parse = |v: &str| SemanticVersion::parse(v).unwrap();
+
+        assert_eq!(parse("1.0.0+build5"), parse("1.0.0"));
+        assert_eq!(parse("1.0.0-rc.1+001"), parse("1.0.0-rc.1+002"));
+        assert!(parse("1.0.0-rc.1+zzz") < parse("1.0.0+aaa"));
+    }
+
     #[test]
     fn test_increment_version() {
         let v = SemanticVersion::parse("1.2.3").unwrap();
@@ -145,4 +278,36 @@ mod tests {
         assert_eq!(v.increment_minor().to_string(), "1.3.0");
         assert_eq!(v.increment_major().to_string(), "2.0.0");
     }
+
+    #[test]
+    fn test_bump_version() {
+        let v = SemanticVersion::parse("1.2.3").unwrap();
+
+        assert_eq!(v.bump(VersionBump::Patch).to_string(), "1.2.4");
+        assert_eq!(v.bump(VersionBump::Minor).to_string(), "1.3.0");
+        assert_eq!(v.bump(VersionBump::Major).to_string(), "2.0.0");
+    }
+
+    #[test]
+    fn test_parse_bump_mode() {
+        assert_eq!(VersionBump::parse("major").unwrap(), VersionBump::Major);
+        assert_eq!(VersionBump::parse("Minor").unwrap(), VersionBump::Minor);
+        assert_eq!(VersionBump::parse("patch").unwrap(), VersionBump::Patch);
+        assert!(VersionBump::parse("build").is_err());
+        assert!(VersionBump::parse("").is_err());
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0.0", "1.0.1").unwrap(), -1);
+        assert_eq!(compare_versions("1.0.0+build5", "1.0.0").unwrap(), 0);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-rc.1").unwrap(), 1);
+        assert_eq!(compare_versions("1.10.0", "1.9.0").unwrap(), 1);
+
+        assert!(matches!(
+            compare_versions("1.0", "1.0.0"),
+            Err(RuleEngineError::InvalidInput(_))
+        ));
+        assert!(compare_versions("1.0.0", "v1.0.0").is_err());
+    }
 }
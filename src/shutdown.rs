@@ -0,0 +1,95 @@
+//! Backend exit hook
+//!
+//! When a backend exits, flushes its NATS publishers and saves debug sessions
+//! that haven't been persisted yet, so a disconnect or restart doesn't drop
+//! them silently.
+//!
+//! What can run at exit is limited:
+//! - Only a clean exit (client disconnect or `\q`) saves debug sessions.
+//!   `pg_terminate_backend` and other FATAL exits leave with code 1, so
+//!   nothing is written to the database; a crash skips the hook entirely.
+//! - Debug sessions are only saved when persistence is enabled, inside a
+//!   short transaction of their own. A failure is logged and ignored.
+//! - Each NATS flush is capped at `NATS_FLUSH_TIMEOUT` so an unreachable
+//!   server can't hold up shutdown; messages still unsent after that are lost.
+//!   JetStream publishes are already acknowledged, so only core publishes are
+//!   at risk.
+
+use crate::debug::{is_persistence_enabled, save_session_to_db, GLOBAL_EVENT_STORE};
+use pgrx::prelude::*;
+use std::sync::Once;
+use std::time::Duration;
+
+/// Maximum time spent flushing each NATS publisher at exit
+const NATS_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+static REGISTER: Once = Once::new();
+
+/// Register the exit hook for this backend; later calls do nothing
+///
+/// Called when a backend first creates something worth flushing (a NATS
+/// publisher or a debug session) rather than from `_PG_init`: with
+/// shared_preload_libraries `_PG_init` runs in the postmaster, and exit
+/// callbacks registered there are discarded in every forked backend.
+pub fn ensure_registered() {
+    REGISTER.call_once(|| unsafe {
+        pg_sys::before_shmem_exit(Some(on_backend_exit), pg_sys::Datum::from(0));
+    });
+}
+
+#[pg_guard]
+unsafe extern "C-unwind" fn on_backend_exit(code: std::ffi::c_int, _arg: pg_sys::Datum) {
+    let failed = crate::api::nats::flush_all_publishers(NATS_FLUSH_TIMEOUT);
+    if !failed.is_empty() {
+        pgrx::log!(
+            "rule_engine: NATS publishers not flushed at exit: {}",
+            failed.join(", ")
+        );
+    }
+
+    // After a FATAL error the backend's state can't be trusted for writes
+    if code == 0 && is_persistence_enabled() {
+        persist_debug_sessions();
+    }
+}
+
+/// Save running/completed debug sessions that haven't been persisted
+fn persist_debug_sessions() {
+    let sessions = GLOBAL_EVENT_STORE.unpersisted_sessions();
+    if sessions.is_empty() {
+        return;
+    }
+
+    // The client is gone, so normally no transaction is open
+    let own_transaction = unsafe { !pg_sys::IsTransactionState() };
+    if own_transaction {
+        unsafe { pg_sys::StartTransactionCommand() };
+    }
+
+    let saved = PgTryBuilder::new(|| {
+        for session in &sessions {
+            if save_session_to_db(session).is_ok() {
+                let _ = GLOBAL_EVENT_STORE.mark_persisted(&session.session_id);
+            }
+        }
+        true
+    })
+    .catch_others(|error| {
+        pgrx::log!(
+            "rule_engine: failed to save debug sessions at exit: {:?}",
+            error
+        );
+        false
+    })
+    .execute();
+
+    if own_transaction {
+        unsafe {
+            if saved {
+                pg_sys::CommitTransactionCommand();
+            } else {
+                pg_sys::AbortCurrentTransaction();
+            }
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::core::parse_and_validate_rules;
+use crate::core::rules::RULE_HEADER_REGEX;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A style or quality finding in GRL that parses fine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintWarning {
+    /// "error", "warning" or "info"
+    pub severity: &'static str,
+    /// Machine-readable kind, e.g. "CONSTANT_CONDITION"
+    pub code: &'static str,
+    pub message: String,
+    /// 1-based line in the GRL source
+    pub line: Option<usize>,
+}
+
+impl LintWarning {
+    fn new(
+        severity: &'static str,
+        code: &'static str,
+        message: String,
+        line: Option<usize>,
+    ) -> Self {
+        Self {
+            severity,
+            code,
+            message,
+            line,
+        }
+    }
+}
+
+/// One rule as written in the source
+struct RuleSource<'a> {
+    name: &'a str,
+    line: usize,
+    salience: i32,
+    no_loop: bool,
+    /// Condition text and its byte offset in the GRL
+    when: (&'a str, usize),
+    /// Action text and its byte offset in the GRL
+    then: (&'a str, usize),
+}
+
+/// Lint GRL for problems the parser accepts
+///
+/// Checks:
+/// - `CONSTANT_CONDITION`: a comparison of two literals, or of a field with
+///   itself, that is always true or always false
+/// - `AMBIGUOUS_SALIENCE`: rules with the same salience assigning the same
+///   field, so the final value depends on firing order
+/// - `RULE_MAY_REFIRE`: a rule without `no-loop` assigns a field its own
+///   condition reads
+/// - `SELF_ASSIGNMENT`: an action assigns a field to itself
+///
+/// GRL that doesn't parse yields a single `SYNTAX_ERROR`.
+pub fn lint_grl(grl: &str) -> Vec<LintWarning> {
+    let parsed = match parse_and_validate_rules(grl) {
+        Ok(rules) => rules,
+        Err(e) => return vec![LintWarning::new("error", "SYNTAX_ERROR", e, None)],
+    };
+    let attributes: HashMap<&str, (i32, bool)> = parsed
+        .iter()
+        .map(|r| (r.name.as_str(), (r.salience, r.no_loop)))
+        .collect();
+
+    let rules = rule_sources(grl, &attributes);
+    let mut warnings = Vec::new();
+
+    for rule in &rules {
+        check_conditions(grl, rule, &mut warnings);
+        check_actions(grl, rule, &mut warnings);
+    }
+    check_salience(&rules, &mut warnings);
+
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+fn rule_sources<'a>(grl: &'a str, attributes: &HashMap<&str, (i32, bool)>) -> Vec<RuleSource<'a>> {
+    let mut rules = Vec::new();
+    // The GRL already parsed, so the parser's header syntax finds every rule
+    for header in RULE_HEADER_REGEX.captures_iter(grl) {
+        let whole = header.get(0).unwrap();
+        let name = header.get(1).or(header.get(2)).unwrap().as_str();
+        let body_start = whole.end();
+        let body_end = closing_brace(grl, body_start);
+        let body = &grl[body_start..body_end];
+
+        let (Some(when), Some(then)) = (WHEN_RE.find(body), THEN_RE.find(body)) else {
+            continue;
+        };
+        if then.start() < when.end() {
+            continue;
+        }
+        let (salience, no_loop) = attributes.get(name).copied().unwrap_or_default();
+
+        rules.push(RuleSource {
+            name,
+            line: line_of(grl, whole.start()),
+            salience,
+            no_loop,
+            when: (&body[when.end()..then.start()], body_start + when.end()),
+            then: (&body[then.end()..], body_start + then.end()),
+        });
+    }
+
+    rules
+}
+
+/// Offset of the `}` closing a body that starts at `start`, skipping strings
+fn closing_brace(grl: &str, start: usize) -> usize {
+    let mut depth = 1;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in grl[start..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return start + i;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    grl.len()
+}
+
+const LITERAL: &str = r#"-?\d+(?:\.\d+)?|"[^"]*"|true|false"#;
+const FIELD: &str = r"[A-Za-z_]\w*(?:\.\w+)+";
+
+lazy_static::lazy_static! {
+    static ref WHEN_RE: Regex = Regex::new(r"\bwhen\b").unwrap();
+    static ref THEN_RE: Regex = Regex::new(r"\bthen\b").unwrap();
+    /// A comparison between two literals, which is constant
+    static ref LITERAL_RE: Regex = Regex::new(&format!(
+        r"(?:^|[\s(!&|])({LITERAL})\s*(==|!=|>=|<=|>|<)\s*({LITERAL})"
+    ))
+    .unwrap();
+    /// A comparison between two fields
+    static ref FIELD_RE: Regex =
+        Regex::new(&format!(r"({FIELD})\s*(==|!=|>=|<=|>|<)\s*({FIELD})")).unwrap();
+    /// One assignment statement: field, operator, value
+    static ref ASSIGN_RE: Regex =
+        Regex::new(&format!(r"^({FIELD})\s*([-+*/]?=)\s*([^=].*)$")).unwrap();
+}
+
+fn check_conditions(grl: &str, rule: &RuleSource, warnings: &mut Vec<LintWarning>) {
+    let (condition, offset) = rule.when;
+    for caps in LITERAL_RE.captures_iter(condition) {
+        let (left, op, right) = (&caps[1], &caps[2], &caps[3]);
+        let Some(result) = compare_literals(left, op, right) else {
+            continue;
+        };
+        warnings.push(LintWarning::new(
+            "warning",
+            "CONSTANT_CONDITION",
+            format!(
+                "Rule '{}': '{} {} {}' is always {}",
+                rule.name, left, op, right, result
+            ),
+            Some(line_of(grl, offset + caps.get(1).unwrap().start())),
+        ));
+    }
+
+    for caps in FIELD_RE.captures_iter(condition) {
+        let (left, op, right) = (&caps[1], &caps[2], &caps[3]);
+        if left != right {
+            continue;
+        }
+        let result = matches!(op, "==" | ">=" | "<=");
+        warnings.push(LintWarning::new(
+            "warning",
+            "CONSTANT_CONDITION",
+            format!(
+                "Rule '{}': '{} {} {}' compares a field with itself and is always {}",
+                rule.name, left, op, right, result
+            ),
+            Some(line_of(grl, offset + caps.get(0).unwrap().start())),
+        ));
+    }
+}
+
+/// Evaluate a comparison of two literals; None if the types don't compare
+fn compare_literals(left: &str, op: &str, right: &str) -> Option<bool> {
+    if let (Ok(l), Ok(r)) = (left.parse::<f64>(), right.parse::<f64>()) {
+        return Some(match op {
+            "==" => l == r,
+            "!=" => l != r,
+            ">=" => l >= r,
+            "<=" => l <= r,
+            ">" => l > r,
+            _ => l < r,
+        });
+    }
+
+    match op {
+        "==" => Some(left == right),
+        "!=" => Some(left != right),
+        _ => None,
+    }
+}
+
+/// Assignments in a rule's actions: (field, operator, value, offset)
+fn assignments<'a>(rule: &RuleSource<'a>) -> Vec<(&'a str, &'a str, &'a str, usize)> {
+    let (actions, offset) = rule.then;
+
+    let mut found = Vec::new();
+    let mut start = 0;
+    for statement in actions.split(';') {
+        let trimmed = statement.trim();
+        let lead = statement.len() - statement.trim_start().len();
+        if let Some(caps) = ASSIGN_RE.captures(trimmed) {
+            found.push((
+                caps.get(1).unwrap().as_str(),
+                caps.get(2).unwrap().as_str(),
+                caps.get(3).unwrap().as_str().trim(),
+                offset + start + lead,
+            ));
+        }
+        start += statement.len() + 1;
+    }
+
+    found
+}
+
+fn check_actions(grl: &str, rule: &RuleSource, warnings: &mut Vec<LintWarning>) {
+    for (field, op, value, offset) in assignments(rule) {
+        if op == "=" && value == field {
+            warnings.push(LintWarning::new(
+                "info",
+                "SELF_ASSIGNMENT",
+                format!(
+                    "Rule '{}': '{} = {}' has no effect",
+                    rule.name, field, value
+                ),
+                Some(line_of(grl, offset)),
+            ));
+            continue;
+        }
+
+        let read_re = Regex::new(&format!(
+            r"(?:^|[^\w.]){}(?:[^\w.]|$)",
+            regex::escape(field)
+        ))
+        .unwrap();
+        if !rule.no_loop && read_re.is_match(rule.when.0) {
+            warnings.push(LintWarning::new(
+                "warning",
+                "RULE_MAY_REFIRE",
+                format!(
+                    "Rule '{}' sets {} which its own condition reads; add no-loop to stop it re-firing",
+                    rule.name, field
+                ),
+                Some(line_of(grl, offset)),
+            ));
+        }
+    }
+}
+
+fn check_salience(rules: &[RuleSource], warnings: &mut Vec<LintWarning>) {
+    // (field, salience) -> first rule assigning it
+    let mut writers: HashMap<(&str, i32), &str> = HashMap::new();
+
+    for rule in rules {
+        let mut fields: Vec<&str> = assignments(rule).into_iter().map(|a| a.0).collect();
+        fields.sort_unstable();
+        fields.dedup();
+
+        for field in fields {
+            match writers.get(&(field, rule.salience)) {
+                Some(first) if *first != rule.name => warnings.push(LintWarning::new(
+                    "warning",
+                    "AMBIGUOUS_SALIENCE",
+                    format!(
+                        "Rules '{}' and '{}' both set {} with salience {}; the final value depends on firing order",
+                        first, rule.name, field, rule.salience
+                    ),
+                    Some(rule.line),
+                )),
+                Some(_) => {}
+                None => {
+                    writers.insert((field, rule.salience), rule.name);
+                }
+            }
+        }
+    }
+}
+
+fn line_of(grl: &str, offset: usize) -> usize {
+    grl[..offset].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(warnings: &[LintWarning]) -> Vec<&'static str> {
+        warnings.iter().map(|w| w.code).collect()
+    }
+
+    #[test]
+    fn test_clean_rules_have_no_warnings() {
+        let grl = r#"rule "Discount" salience 10 {
+    when Order.total > 100
+    then Order.discount = 10;
+}
+rule "Vip" salience 5 {
+    when Customer.tier == "gold"
+    then Order.discount = 20;
+}"#;
+        assert!(lint_grl(grl).is_empty());
+    }
+
+    #[test]
+    fn test_constant_condition() {
+        let grl = "rule \"A\" {\n    when true == true && Order.total > 100\n    then Order.flag = true;\n}\nrule \"B\" {\n    when Order.total != Order.total\n    then Order.other = 1;\n}";
+        let warnings = lint_grl(grl);
+
+        assert_eq!(codes(&warnings), vec!["CONSTANT_CONDITION"; 2]);
+        assert_eq!(warnings[0].line, Some(2));
+        assert!(warnings[0].message.contains("always true"));
+        assert_eq!(warnings[1].line, Some(6));
+        assert!(warnings[1].message.contains("always false"));
+    }
+
+    #[test]
+    fn test_ambiguous_salience() {
+        let grl = r#"rule "Gold" { when Customer.tier == "gold" then Order.discount = 20; }
+rule "Big" { when Order.total > 100 then Order.discount = 10; }
+rule "Rush" salience 50 { when Order.rush == true then Order.discount = 0; }"#;
+        let warnings = lint_grl(grl);
+
+        assert_eq!(codes(&warnings), vec!["AMBIGUOUS_SALIENCE"]);
+        assert_eq!(warnings[0].line, Some(2));
+        assert!(warnings[0].message.contains("'Gold' and 'Big'"));
+    }
+
+    #[test]
+    fn test_refire_and_self_assignment() {
+        let grl = "rule \"Grow\" {\n    when Counter.value < 10\n    then\n        Counter.value = Counter.value + 1;\n        Counter.name = Counter.name;\n}\nrule \"Safe\" salience 5 no-loop {\n    when Counter.value < 10\n    then Counter.value = Counter.value + 1;\n}";
+        let warnings = lint_grl(grl);
+
+        assert_eq!(codes(&warnings), vec!["RULE_MAY_REFIRE", "SELF_ASSIGNMENT"]);
+        assert_eq!(warnings[0].line, Some(4));
+        assert_eq!(warnings[1].line, Some(5));
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let warnings = lint_grl("rule \"A\" { when");
+        assert_eq!(codes(&warnings), vec!["SYNTAX_ERROR"]);
+        assert_eq!(warnings[0].severity, "error");
+    }
+}
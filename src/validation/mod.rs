@@ -1,4 +1,8 @@
 pub mod input;
 pub mod limits;
+pub mod lint;
+pub mod schema;
 
 pub use input::{validate_facts_input, validate_rules_input};
+pub use lint::lint_grl;
+pub use schema::validate_facts_schema;
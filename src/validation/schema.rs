@@ -0,0 +1,141 @@
+use crate::core::facts::json_type_name;
+use serde_json::Value;
+
+/// Check facts against a schema mapping field paths to types
+///
+/// Schema example: `{"Customer.age": "integer", "Customer.email": "string?"}`.
+/// Supported types are string, number, integer, boolean, object, array, null
+/// and any; a trailing `?` makes the field optional (absent or null allowed).
+/// Paths resolve nested objects, or a flattened dotted key if present.
+///
+/// Returns the list of violations (empty when the facts match), or an error
+/// if the schema itself is malformed.
+pub fn validate_facts_schema(facts: &Value, schema: &Value) -> Result<Vec<String>, String> {
+    let fields = schema
+        .as_object()
+        .ok_or("Schema must be a JSON object mapping field paths to types")?;
+
+    let mut violations = Vec::new();
+
+    for (path, expected) in fields {
+        let expected = expected
+            .as_str()
+            .ok_or_else(|| format!("Schema type for '{}' must be a string", path))?;
+        let (type_name, optional) = match expected.strip_suffix('?') {
+            Some(base) => (base, true),
+            None => (expected, false),
+        };
+
+        if !is_known_type(type_name) {
+            return Err(format!(
+                "Unknown schema type '{}' for '{}' (expected string, number, integer, boolean, object, array, null or any)",
+                type_name, path
+            ));
+        }
+
+        match resolve_path(facts, path) {
+            None => {
+                if !optional {
+                    violations.push(format!("{}: missing (expected {})", path, type_name));
+                }
+            }
+            Some(Value::Null) if optional => {}
+            Some(value) => {
+                if !matches_type(value, type_name) {
+                    violations.push(format!(
+                        "{}: expected {}, got {}",
+                        path,
+                        type_name,
+                        json_type_name(value)
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+fn is_known_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "string" | "number" | "integer" | "boolean" | "object" | "array" | "null" | "any"
+    )
+}
+
+fn matches_type(value: &Value, type_name: &str) -> bool {
+    match type_name {
+        "any" => true,
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        other => json_type_name(value) == other,
+    }
+}
+
+fn resolve_path<'a>(facts: &'a Value, path: &str) -> Option<&'a Value> {
+    if let Some(value) = facts.get(path) {
+        return Some(value);
+    }
+
+    path.split('.')
+        .try_fold(facts, |current, part| current.get(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_match() {
+        let facts = json!({
+            "Customer": { "age": 30, "email": "a@b.co", "tags": [] },
+            "Order.total": 12.5
+        });
+        let schema = json!({
+            "Customer.age": "integer",
+            "Customer.email": "string",
+            "Customer.tags": "array",
+            "Customer.phone": "string?",
+            "Order.total": "number"
+        });
+
+        assert!(validate_facts_schema(&facts, &schema).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_schema_violations() {
+        let facts = json!({ "Customer": { "age": "30", "vip": null } });
+        let schema = json!({
+            "Customer.age": "integer",
+            "Customer.email": "string",
+            "Customer.vip": "boolean"
+        });
+
+        let mut violations = validate_facts_schema(&facts, &schema).unwrap();
+        violations.sort();
+        assert_eq!(
+            violations,
+            vec![
+                "Customer.age: expected integer, got string",
+                "Customer.email: missing (expected string)",
+                "Customer.vip: expected boolean, got null",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schema_integer_rejects_float() {
+        let facts = json!({ "Order": { "qty": 1.5 } });
+        let schema = json!({ "Order.qty": "integer" });
+
+        assert_eq!(validate_facts_schema(&facts, &schema).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_malformed_schema() {
+        let facts = json!({});
+        assert!(validate_facts_schema(&facts, &json!(["Customer.age"])).is_err());
+        assert!(validate_facts_schema(&facts, &json!({ "a": 1 })).is_err());
+        assert!(validate_facts_schema(&facts, &json!({ "a": "date" })).is_err());
+    }
+}
@@ -204,6 +204,34 @@ SELECT
 FROM rule_datasource_rate_limits
 GROUP BY datasource_id;
 
+\echo '\n=== 22.1. Test Queued (Async) Fetch ==='
+-- Queue a request against weather_api; returns request_id without calling the API
+SELECT rule_datasource_fetch_async(2, '/v1/current', '{"city": "London"}'::JSONB) AS queued_id \gset
+
+SELECT request_id, endpoint, status, completed_at IS NULL AS not_completed
+FROM rule_datasource_requests
+WHERE request_id = :queued_id;
+
+-- Disable the data source so the poll fails without making an HTTP call
+UPDATE rule_datasources SET enabled = false WHERE datasource_id = 2;
+
+-- Expected: complete = true, state = 'failed', error mentions the datasource
+SELECT rule_datasource_poll(:queued_id);
+
+-- Polling again returns the stored result
+SELECT rule_datasource_poll(:queued_id)->>'state' AS state_after_second_poll;
+
+-- Nothing left to process
+SELECT rule_datasource_process_pending() AS processed;
+
+UPDATE rule_datasources SET enabled = true WHERE datasource_id = 2;
+
+\echo '\n=== 22.2. Test Connectivity Probe ==='
+-- Expected: reachable/status/latency_ms/error keys; no new request or cache rows
+SELECT COUNT(*) AS requests_before FROM rule_datasource_requests \gset
+SELECT rule_datasource_test(2, '/v1/current') ? 'reachable' AS has_reachable;
+SELECT COUNT(*) = :requests_before AS not_logged FROM rule_datasource_requests;
+
 \echo '\n=== 23. Test Delete Data Source ==='
 -- Try to delete (should work)
 SELECT rule_datasource_delete(3);  -- Delete fraud_detection_api
@@ -5,7 +5,7 @@
 --
 -- Prerequisites:
 -- - PostgreSQL with Rule Engine extension installed and loaded
--- - Migration 007 applied
+-- - Migrations 007 and 009 applied
 -- - NATS server NOT required (tests will check error handling)
 --
 -- Run with:
@@ -90,6 +90,59 @@ BEGIN
     END IF;
 END $$;
 
+-- Test 1.4b: rule_nats_heal exists
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_proc WHERE proname = 'rule_nats_heal') THEN
+        PERFORM nats_func_test.log_test('1.4b - rule_nats_heal exists', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('1.4b - rule_nats_heal exists', 'FAIL', 'Function not found');
+    END IF;
+END $$;
+
+-- Test 1.4c: rule_nats_validate_config accepts valid and rejects invalid configs
+DO $$
+DECLARE
+    v_valid JSONB;
+    v_invalid JSONB;
+BEGIN
+    v_valid := rule_nats_validate_config('{"nats_url": "nats://localhost:4222", "auth_type": "token", "auth_token": "secret"}'::jsonb);
+    v_invalid := rule_nats_validate_config('{"nats_url": "nats://localhost:4222", "auth_type": "token"}'::jsonb);
+
+    IF (v_valid->>'valid')::BOOLEAN
+       AND NOT (v_invalid->>'valid')::BOOLEAN
+       AND v_invalid->>'error' LIKE '%auth_token%' THEN
+        PERFORM nats_func_test.log_test('1.4c - rule_nats_validate_config', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('1.4c - rule_nats_validate_config', 'FAIL',
+            format('valid=%s invalid=%s', v_valid, v_invalid));
+    END IF;
+END $$;
+
+-- Test 1.4d: rule_nats_replay_dead_letter and its table exist
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_proc WHERE proname = 'rule_nats_replay_dead_letter')
+       AND to_regclass('rule_nats_dead_letter') IS NOT NULL THEN
+        PERFORM nats_func_test.log_test('1.4d - rule_nats_replay_dead_letter exists', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('1.4d - rule_nats_replay_dead_letter exists', 'FAIL', 'Function or table not found');
+    END IF;
+END $$;
+
+-- Test 1.4e: rule_nats_metrics requires an initialized publisher
+DO $$
+BEGIN
+    PERFORM rule_nats_metrics('nats_func_test_missing');
+    PERFORM nats_func_test.log_test('1.4e - rule_nats_metrics', 'FAIL', 'Should have raised error');
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE '%not initialized%' THEN
+        PERFORM nats_func_test.log_test('1.4e - rule_nats_metrics', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('1.4e - rule_nats_metrics', 'FAIL', SQLERRM);
+    END IF;
+END $$;
+
 -- Test 1.5: rule_nats_consumer_update_stats exists
 DO $$
 BEGIN
@@ -153,6 +206,50 @@ BEGIN
     END IF;
 END $$;
 
+-- Test 2.3: Init with token auth but no token is rejected before connecting
+DO $$
+DECLARE
+    v_config_id INTEGER;
+    v_error TEXT;
+BEGIN
+    INSERT INTO rule_nats_config (config_name, nats_url, auth_type, enabled)
+    VALUES ('test_token_missing', 'nats://test:4222', 'token', true)
+    RETURNING config_id INTO v_config_id;
+
+    BEGIN
+        PERFORM rule_nats_init('test_token_missing');
+    EXCEPTION WHEN OTHERS THEN
+        v_error := SQLERRM;
+    END;
+
+    DELETE FROM rule_nats_config WHERE config_id = v_config_id;
+
+    IF v_error LIKE '%auth_token%' THEN
+        PERFORM nats_func_test.log_test('2.3 - Token auth requires auth_token', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('2.3 - Token auth requires auth_token', 'FAIL',
+            format('Unexpected result: %s', COALESCE(v_error, 'no error')));
+    END IF;
+END $$;
+
+-- Test 2.4: Replay requires an initialized publisher
+DO $$
+DECLARE
+    v_error_occurred BOOLEAN := false;
+BEGIN
+    BEGIN
+        PERFORM rule_nats_replay_dead_letter('non_existent_config');
+    EXCEPTION WHEN OTHERS THEN
+        v_error_occurred := true;
+    END;
+
+    IF v_error_occurred THEN
+        PERFORM nats_func_test.log_test('2.4 - Replay errors without rule_nats_init', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('2.4 - Replay errors without rule_nats_init', 'FAIL', 'Should have raised error');
+    END IF;
+END $$;
+
 -- =============================================================================
 -- Test 3: rule_webhook_publish_nats Parameter Validation
 -- =============================================================================
@@ -233,6 +330,42 @@ BEGIN
     END IF;
 END $$;
 
+-- Test 3.4: Publish with invalid headers
+DO $$
+DECLARE
+    v_webhook_id INTEGER;
+    v_headers JSONB;
+    v_rejected INTEGER := 0;
+BEGIN
+    INSERT INTO rule_webhooks (webhook_name, webhook_url, nats_enabled, nats_subject)
+    VALUES ('test_bad_headers', 'https://example.com', true, 'webhooks.test')
+    RETURNING webhook_id INTO v_webhook_id;
+
+    FOREACH v_headers IN ARRAY ARRAY[
+        '{"X Tenant": "acme"}',
+        '{"X-Tenant": "acme\r\nX-Evil: 1"}',
+        '{"X-Count": 3}',
+        '["X-Tenant"]'
+    ]::jsonb[] LOOP
+        BEGIN
+            PERFORM rule_webhook_publish_nats(v_webhook_id, '{"test": true}'::jsonb, NULL, v_headers);
+        EXCEPTION WHEN OTHERS THEN
+            IF SQLERRM ILIKE '%header%' THEN
+                v_rejected := v_rejected + 1;
+            END IF;
+        END;
+    END LOOP;
+
+    DELETE FROM rule_webhooks WHERE webhook_id = v_webhook_id;
+
+    IF v_rejected = 4 THEN
+        PERFORM nats_func_test.log_test('3.4 - Invalid headers rejected', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('3.4 - Invalid headers rejected', 'FAIL',
+            format('Only %s of 4 invalid header sets rejected', v_rejected));
+    END IF;
+END $$;
+
 -- =============================================================================
 -- Test 4: rule_webhook_call_unified Logic
 -- =============================================================================
@@ -424,6 +557,51 @@ BEGIN
     );
 END $$;
 
+-- Test 8.2: Payload containing a single quote is bound, not interpolated
+DO $$
+DECLARE
+    v_webhook_id INTEGER;
+    v_result JSONB;
+    v_stored JSONB;
+BEGIN
+    INSERT INTO rule_webhooks (webhook_name, url, publish_mode)
+    VALUES ('test_quote_payload', 'https://example.com', 'queue')
+    RETURNING webhook_id INTO v_webhook_id;
+
+    v_result := rule_webhook_call_unified(
+        v_webhook_id,
+        '{"customer": "O''Brien", "note": "it''s fine"}'::jsonb
+    );
+
+    SELECT payload INTO v_stored
+    FROM rule_webhook_calls
+    WHERE call_id = (v_result->'queue'->>'call_id')::INTEGER;
+
+    IF v_stored->>'customer' = 'O''Brien' THEN
+        PERFORM nats_func_test.log_test('8.2 - Single quote in payload', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('8.2 - Single quote in payload', 'FAIL',
+            'Stored payload: ' || COALESCE(v_stored::TEXT, 'NULL'));
+    END IF;
+
+    DELETE FROM rule_webhooks WHERE webhook_id = v_webhook_id;
+EXCEPTION WHEN OTHERS THEN
+    PERFORM nats_func_test.log_test('8.2 - Single quote in payload', 'FAIL', SQLERRM);
+END $$;
+
+-- Test 8.3: Config name containing a single quote is treated as data
+DO $$
+BEGIN
+    PERFORM rule_nats_init('no''such''config');
+    PERFORM nats_func_test.log_test('8.3 - Quoted config name', 'FAIL', 'Expected not-found error');
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE '%not found%' THEN
+        PERFORM nats_func_test.log_test('8.3 - Quoted config name', 'PASS');
+    ELSE
+        PERFORM nats_func_test.log_test('8.3 - Quoted config name', 'FAIL', SQLERRM);
+    END IF;
+END $$;
+
 -- =============================================================================
 -- Test Summary
 -- =============================================================================
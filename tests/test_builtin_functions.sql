@@ -592,6 +592,189 @@ SELECT run_rule_engine(
 \echo 'Result:'
 SELECT :'result'::jsonb AS performance_result;
 
+-- Test 25: Fact Functions - FactExists
+\echo ''
+\echo 'Test 25: Fact Functions - FactExists'
+\echo 'Expected: hasEmailField=true (present but null), needsPhone=true (absent)'
+SELECT run_rule_engine(
+    '{"Customer": {"email": null, "hasEmailField": false, "needsPhone": false}}',
+    'rule "EmailFieldPresent" salience 10 {
+        when FactExists(Customer.email) == true
+        then Customer.hasEmailField = true;
+    }
+
+    rule "PhoneMissing" salience 5 {
+        when FactExists(Customer.phone) == false
+        then Customer.needsPhone = true;
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS fact_exists_result;
+
+-- Test 26: Math Functions - Between
+\echo ''
+\echo 'Test 26: Math Functions - Between'
+\echo 'Expected: eligible=true (age 18 is on the lower bound)'
+SELECT run_rule_engine(
+    '{"Customer": {"age": 18, "eligible": false}}',
+    'rule "WorkingAge" {
+        when Between(Customer.age, 18, 65) == true
+        then Customer.eligible = true;
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS between_result;
+
+-- Test 27: String Functions - Levenshtein / Similarity
+\echo ''
+\echo 'Test 27: String Functions - Levenshtein / Similarity'
+\echo 'Expected: nameDistance=1, possibleDuplicate=true'
+SELECT run_rule_engine(
+    '{"Customer": {"name": "Jon Smith", "knownName": "John Smith", "nameDistance": 0, "possibleDuplicate": false}}',
+    'rule "FuzzyDuplicate" {
+        when Similarity(Customer.name, Customer.knownName) > 0.85
+        then
+            Customer.possibleDuplicate = true;
+            Customer.nameDistance = Levenshtein(Customer.name, Customer.knownName);
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS fuzzy_match_result;
+
+-- Test 28: JSON Functions - JsonPath
+\echo ''
+\echo 'Test 28: JSON Functions - JsonPath'
+\echo 'Expected: firstSku="A-1"'
+SELECT run_rule_engine(
+    '{"Order": {"response": {"items": [{"sku": "A-1"}, {"sku": "B-2"}]}, "firstSku": ""}}',
+    'rule "FirstItem" {
+        when Order.firstSku == ""
+        then Order.firstSku = JsonPath(Order.response, "$.items[0].sku");
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS json_path_result;
+
+-- Test 29: Template Functions - FormatTemplate
+\echo ''
+\echo 'Test 29: Template Functions - FormatTemplate'
+\echo 'Expected: message="Hi Ana, you owe 120"'
+SELECT run_rule_engine(
+    '{"Customer": {"name": "Ana", "balance": 120, "message": ""}}',
+    'rule "Reminder" {
+        when Customer.balance > 0
+        then Customer.message = FormatTemplate("Hi {name}, you owe {balance}", Customer);
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS format_template_result;
+
+-- Test 30: Logic Functions - InList / NotInList
+\echo ''
+\echo 'Test 30: Logic Functions - InList / NotInList'
+\echo 'Expected: region="NA", export=false'
+SELECT run_rule_engine(
+    '{"Customer": {"country": "CA", "region": "", "export": true}}',
+    'rule "NorthAmerica" {
+        when InList(Customer.country, ["US", "CA", "MX"]) == true
+        then Customer.region = "NA";
+    }
+    rule "Domestic" {
+        when NotInList(Customer.country, ["FR", "DE"]) == true
+        then Customer.export = false;
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS in_list_result;
+
+-- Test 31: String Functions - EqualsIgnoreCase / ContainsIgnoreCase
+\echo ''
+\echo 'Test 31: String Functions - EqualsIgnoreCase / ContainsIgnoreCase'
+\echo 'Expected: domestic=true, priority="refund"'
+SELECT run_rule_engine(
+    '{"Ticket": {"country": "us", "subject": "Please REFUND my order", "domestic": false, "priority": ""}}',
+    'rule "Domestic" {
+        when EqualsIgnoreCase(Ticket.country, "US") == true
+        then Ticket.domestic = true;
+    }
+    rule "Refund" {
+        when ContainsIgnoreCase(Ticket.subject, "refund") == true
+        then Ticket.priority = "refund";
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS ignore_case_result;
+
+-- Test 32: Math Functions - RoundToNearest
+\echo ''
+\echo 'Test 32: Math Functions - RoundToNearest'
+\echo 'Expected: price=2.35'
+SELECT run_rule_engine(
+    '{"Product": {"rawPrice": 2.37, "price": 0}}',
+    'rule "CashRounding" {
+        when Product.price == 0
+        then Product.price = RoundToNearest(Product.rawPrice, 0.05);
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS round_to_nearest_result;
+
+-- Test 33: Preprocessing preview
+\echo ''
+\echo 'Test 33: Preprocessing preview - rule_preprocess_preview'
+\echo 'Expected: transformed_grl uses Order.__func_0_dayssince, one injected field, 2 function calls'
+SELECT rule_preprocess_preview(
+    '{"Order": {"createdAt": "2020-01-01"}}',
+    'rule "Old" {
+        when DaysSince(Order.createdAt) > 90
+        then Order.label = ToUpper("stale");
+    }'
+)::jsonb AS preview \gset
+
+\echo 'Result:'
+SELECT :'preview'::jsonb->'injected_fields' AS injected_fields,
+       jsonb_array_length(:'preview'::jsonb->'function_calls') AS function_calls;
+
+-- Test 34: Fixed clock for Now/Today/DaysSince
+\echo ''
+\echo 'Test 34: Date Functions - rule_engine.fixed_now'
+\echo 'Expected: today="2025-03-01", age=120'
+SET rule_engine.fixed_now = '2025-03-01';
+SELECT run_rule_engine(
+    '{"Order": {"createdAt": "2024-11-01", "today": "", "age": 0}}',
+    'rule "Clock" {
+        when Order.age == 0
+        then Order.today = Today(); Order.age = DaysSince(Order.createdAt);
+    }'
+) AS result \gset
+RESET rule_engine.fixed_now;
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS fixed_now_result;
+
+-- Test 35: String Functions - Length vs ByteLength
+\echo ''
+\echo 'Test 35: String Functions - Length vs ByteLength'
+\echo 'Expected: chars=5, bytes=6'
+SELECT run_rule_engine(
+    '{"User": {"name": "Zoë K", "chars": 0, "bytes": 0}}',
+    'rule "Lengths" {
+        when User.chars == 0
+        then User.chars = Length(User.name); User.bytes = ByteLength(User.name);
+    }'
+) AS result \gset
+
+\echo 'Result:'
+SELECT :'result'::jsonb AS length_result;
+
 \echo ''
 \echo '========================================='
 \echo 'Built-in Functions Tests Complete!'
@@ -376,12 +376,89 @@ WHERE trigger_id = :perf_trigger_id;
 -- Cleanup
 SELECT rule_trigger_delete(:perf_trigger_id);
 
+-- ============================================================================
+-- TEST 10: Simulate Rule Against Existing Rows
+-- ============================================================================
+
+\echo ''
+\echo '11. TEST: rule_simulate (read-only preview)'
+
+SELECT md5(string_agg(row_to_json(t)::text, ',' ORDER BY id)) AS orders_before
+FROM test_orders t \gset
+
+SELECT
+    (s->>'rows_scanned')::int > 0 AS scanned_rows,
+    jsonb_typeof(s->'rows') = 'array' AS has_rows,
+    jsonb_typeof(s->'errors') = 'array' AS has_errors
+FROM rule_simulate('test_order_discount', 'test_orders', 10) s;
+
+-- Nothing was written
+SELECT
+    CASE WHEN md5(string_agg(row_to_json(t)::text, ',' ORDER BY id)) = :'orders_before'
+        THEN '✓ Table unchanged after simulation'
+        ELSE '✗ FAIL: simulation modified rows'
+    END AS status
+FROM test_orders t;
+
+-- Unknown table is rejected
+DO $$
+BEGIN
+    PERFORM rule_simulate('test_order_discount', 'no_such_table');
+    RAISE EXCEPTION 'Expected rule_simulate to fail for a missing table';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE 'Expected%' THEN RAISE; END IF;
+    RAISE NOTICE '✓ Missing table rejected: %', SQLERRM;
+END $$;
+
+-- ============================================================================
+-- TEST 11: Dry-Run Mode
+-- ============================================================================
+
+\echo ''
+\echo '12. TEST: Dry-run trigger records changes without applying them'
+
+SELECT rule_trigger_create(
+    'test_dry_run_trigger',
+    'test_orders',
+    'test_order_discount',
+    'INSERT'
+) AS dry_trigger_id \gset
+
+SELECT rule_trigger_set_dry_run(:dry_trigger_id, TRUE);
+
+INSERT INTO test_orders (customer_id, total_amount)
+VALUES (4242, 500.00)
+RETURNING id AS dry_order_id \gset
+
+SELECT
+    CASE WHEN discount_amount = 0 AND final_amount IS NULL
+        THEN '✓ Dry run left the row unchanged'
+        ELSE '✗ FAIL: dry run modified the row'
+    END AS status
+FROM test_orders
+WHERE id = :dry_order_id;
+
+SELECT
+    dry_run,
+    success,
+    changes IS NOT NULL AS recorded_changes
+FROM rule_trigger_history
+WHERE trigger_id = :dry_trigger_id
+ORDER BY id DESC
+LIMIT 1;
+
+SELECT dry_run, dry_run_executions
+FROM rule_trigger_stats
+WHERE trigger_id = :dry_trigger_id;
+
+SELECT rule_trigger_delete(:dry_trigger_id);
+
 -- ============================================================================
 -- CLEANUP
 -- ============================================================================
 
 \echo ''
-\echo '11. Cleanup: Removing test data...'
+\echo '13. Cleanup: Removing test data...'
 
 DROP TABLE IF EXISTS test_orders CASCADE;
 DELETE FROM rule_definitions WHERE name LIKE 'test_%';
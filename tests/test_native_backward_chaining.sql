@@ -364,6 +364,24 @@ SELECT query_backward_chaining(
 
 \timing off
 
+-- Test 11: Open vs Closed World
+\echo ''
+\echo 'Test 11: Negation as failure (assume_closed_world)'
+\echo 'Expected: open world = false, closed world = true for the same facts'
+
+SELECT query_backward_chaining(
+    '{"User": {"FraudScore": 10}}',
+    'rule "BlockFraud" { when User.FraudScore > 80 then User.Blocked = true; }',
+    'User.Blocked == false'
+)::jsonb->'provable' AS open_world_provable;
+
+SELECT query_backward_chaining(
+    '{"User": {"FraudScore": 10}}',
+    'rule "BlockFraud" { when User.FraudScore > 80 then User.Blocked = true; }',
+    'User.Blocked == false',
+    true
+)::jsonb->'provable' AS closed_world_provable;
+
 \echo ''
 \echo '========================================='
 \echo 'Native Backward Chaining Tests Complete!'
@@ -378,9 +396,10 @@ SELECT query_backward_chaining(
 \echo '6. Complex conditions (AND/OR)'
 \echo '7. Nested dependencies'
 \echo '8. Performance metrics'
+\echo '9. Closed-world negation'
 \echo ''
 \echo 'New PostgreSQL Functions Available:'
-\echo '- query_backward_chaining(facts, rules, goal) → JSON with proof'
+\echo '- query_backward_chaining(facts, rules, goal[, assume_closed_world]) → JSON with proof'
 \echo '- query_backward_chaining_multi(facts, rules, goals[]) → Array of results'
 \echo '- can_prove_goal(facts, rules, goal) → Boolean (fast)'
 \echo ''
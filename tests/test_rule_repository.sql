@@ -326,6 +326,316 @@ SELECT rule_execute_by_name(
     '{"Order": {"Amount": 150}}'
 )::jsonb->'Order'->>'Discount' = '15' AS rollback_execution_correct;
 
+-- =============================================================================
+-- Test 13: Bound Parameters in rule_get / rule_activate
+-- =============================================================================
+
+\echo 'Test 13: Test rule_get and rule_activate with bound parameters'
+
+-- Pre-release versions round-trip through rule_get and rule_activate
+SELECT rule_save(
+    'test_discount',
+    'rule "PreviewDiscount" salience 10 {
+        when Order.Amount > 100
+        then Order.Discount = 25;
+    }',
+    '3.0.0-rc.1',
+    'Release candidate'
+);
+
+SELECT rule_get('test_discount', '3.0.0-rc.1') LIKE '%PreviewDiscount%' AS prerelease_retrieved;
+SELECT rule_activate('test_discount', '3.0.0-rc.1') AS prerelease_activated;
+SELECT rule_get('test_discount') LIKE '%PreviewDiscount%' AS prerelease_is_default;
+
+-- Quoted input is rejected and leaves the default version untouched
+DO $$
+BEGIN
+    PERFORM rule_get('test_discount'' OR ''1''=''1');
+    RAISE EXCEPTION 'Should have rejected quoted rule name';
+EXCEPTION
+    WHEN OTHERS THEN
+        RAISE NOTICE 'Correctly rejected quoted rule name';
+END $$;
+
+DO $$
+BEGIN
+    PERFORM rule_activate('test_discount', '1.0.0'' OR ''1''=''1');
+    RAISE EXCEPTION 'Should have rejected quoted version';
+EXCEPTION
+    WHEN OTHERS THEN
+        RAISE NOTICE 'Correctly rejected quoted version';
+END $$;
+
+SELECT COUNT(*) = 1 AS single_default_after_rejected_input
+FROM rule_versions rv
+JOIN rule_definitions rd ON rv.rule_id = rd.id
+WHERE rd.name = 'test_discount' AND rv.is_default = true;
+
+-- Unknown version reports not found
+DO $$
+BEGIN
+    PERFORM rule_get('test_discount', '9.9.9');
+    RAISE EXCEPTION 'Should have failed with missing version';
+EXCEPTION
+    WHEN OTHERS THEN
+        RAISE NOTICE 'Correctly reported missing version';
+END $$;
+
+-- =============================================================================
+-- Test 14: Rule Includes
+-- =============================================================================
+
+\echo 'Test 14: Test rule_execute_with_includes'
+
+SELECT rule_save(
+    'test_shipping',
+    'rule "FreeShipping" salience 5 {
+        when Order.Amount > 100
+        then Order.FreeShipping = true;
+    }',
+    '1.0.0',
+    NULL
+);
+
+SELECT rule_save(
+    'test_checkout',
+    '@include("test_shipping")
+    rule "CheckoutDiscount" salience 10 {
+        when Order.Amount > 100
+        then Order.Discount = 10;
+    }',
+    '1.0.0',
+    NULL
+);
+
+-- Both the including rule and the included rule fire
+SELECT (r->'Order'->>'Discount') = '10' AND (r->'Order'->>'FreeShipping') = 'true' AS includes_resolved
+FROM (
+    SELECT rule_execute_with_includes(
+        'test_checkout',
+        '{"Order": {"Amount": 150, "Discount": 0, "FreeShipping": false}}'
+    )::jsonb AS r
+) t;
+
+-- Include cycles are rejected
+SELECT rule_save('test_loop_a', '@include("test_loop_b") rule "LoopA" {}', '1.0.0', NULL);
+SELECT rule_save('test_loop_b', '@include("test_loop_a") rule "LoopB" {}', '1.0.0', NULL);
+
+DO $$
+BEGIN
+    PERFORM rule_execute_with_includes('test_loop_a', '{}');
+    RAISE EXCEPTION 'Should have failed with include cycle';
+EXCEPTION
+    WHEN OTHERS THEN
+        RAISE NOTICE 'Correctly rejected include cycle';
+END $$;
+
+-- =============================================================================
+-- Test 15: Rule Assertions
+-- =============================================================================
+
+\echo 'Test 15: Test rule_test assertions'
+
+-- Matching expectation passes (version 2.0.0 sets Discount = 20)
+SELECT (rule_test(
+    'test_discount',
+    '{"Order": {"Amount": 150}}',
+    '{"Order": {"Discount": 20}}',
+    '2.0.0'
+)::jsonb->>'passed')::boolean AS rule_test_passes;
+
+-- Mismatch is reported in diff
+SELECT r->>'passed' = 'false'
+       AND r->'diff'->'Order.Discount'->>'expected' = '99' AS rule_test_reports_diff
+FROM (
+    SELECT rule_test(
+        'test_discount',
+        '{"Order": {"Amount": 150}}',
+        '{"Order": {"Discount": 99}}',
+        '2.0.0'
+    )::jsonb AS r
+) t;
+
+-- =============================================================================
+-- Test 16: Warmup
+-- =============================================================================
+
+\echo 'Test 16: Test rule_engine_warmup'
+
+SELECT (r->>'rule_count')::int = 1
+       AND (r->>'compile_time_ms')::float >= 0 AS warmup_compiles
+FROM (SELECT rule_engine_warmup('test_discount', '2.0.0')::jsonb AS r) t;
+
+-- Unknown rule is an error
+DO $$
+BEGIN
+    PERFORM rule_engine_warmup('test_missing_rule');
+    RAISE EXCEPTION 'rule_engine_warmup should fail for a missing rule';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE '%should fail%' THEN
+        RAISE;
+    END IF;
+END $$;
+
+-- =============================================================================
+-- Test 17: Execution Audit
+-- =============================================================================
+
+\echo 'Test 17: Test execution audit (rule_engine.audit_enabled)'
+
+\i migrations/014_rule_execution_audit.sql
+
+-- Off by default: nothing is recorded
+SELECT rule_execute_by_name('test_discount', '{"Order": {"Amount": 150}}');
+SELECT COUNT(*) = 0 AS audit_off_by_default FROM rule_audit_query('test_discount');
+
+SET LOCAL rule_engine.audit_enabled = on;
+
+SELECT rule_execute_by_name('test_discount', '{"Order": {"Amount": 150}}');
+SELECT rule_execute_by_name('test_discount', '{ "Order" : { "Amount" : 50 } }', '1.0.0');
+
+-- Default version is resolved, the user and a 64-char facts hash are kept
+SELECT COUNT(*) = 2 AS audit_recorded,
+       bool_and(executed_by = current_user) AS user_recorded,
+       bool_and(length(facts_hash) = 64) AS facts_hashed,
+       bool_and(rule_version IS NOT NULL) AS version_recorded,
+       bool_or(fired) AND NOT bool_and(fired) AS fired_tracked
+FROM rule_audit_query('test_discount', now() - interval '1 minute');
+
+-- Same facts with different layout hash the same
+SELECT rule_execute_by_name('test_discount', '{"Order":{"Amount":150}}');
+SELECT COUNT(DISTINCT facts_hash) = 2 AS hash_is_canonical
+FROM rule_audit_query('test_discount');
+
+SET LOCAL rule_engine.audit_enabled = off;
+
+-- =============================================================================
+-- Test 18: Rule Metadata
+-- =============================================================================
+
+\echo 'Test 18: Test rule_get_metadata'
+
+SELECT m->>'name' = 'test_discount' AS name_returned,
+       (m->>'is_default')::boolean AS default_version,
+       m->'tags' ? 'pricing' AS tags_included,
+       m ? 'created_at' AND m ? 'updated_by' AND m ? 'rule_id' AS provenance_included,
+       NOT m ? 'grl_content' AS grl_excluded
+FROM rule_get_metadata('test_discount') m;
+
+SELECT rule_get_metadata('test_discount', '1.0.0')->>'version' = '1.0.0' AS specific_version;
+
+DO $$
+BEGIN
+    PERFORM rule_get_metadata('test_no_such_rule');
+    RAISE EXCEPTION 'Expected rule_get_metadata to fail for a missing rule';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE 'Expected%' THEN RAISE; END IF;
+    RAISE NOTICE 'Missing rule rejected: %', SQLERRM;
+END $$;
+
+-- =============================================================================
+-- Test 19: Version Comparison
+-- =============================================================================
+
+\echo 'Test 19: Test rule_version_compare'
+
+SELECT rule_version_compare('1.2.0', '1.10.0') = -1 AS numeric_ordering,
+       rule_version_compare('1.0.0-rc.1', '1.0.0') = -1 AS pre_release_lower,
+       rule_version_compare('1.0.0+a', '1.0.0+b') = 0 AS build_ignored,
+       rule_version_compare('2.0.0', '1.9.9') = 1 AS higher;
+
+DO $$
+BEGIN
+    PERFORM rule_version_compare('1.0', '1.0.0');
+    RAISE EXCEPTION 'Expected rule_version_compare to reject a malformed version';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE 'Expected%' THEN RAISE; END IF;
+    RAISE NOTICE 'Malformed version rejected: %', SQLERRM;
+END $$;
+
+-- =============================================================================
+-- Test 20: Atomic Save
+-- =============================================================================
+
+\echo 'Test 20: Test rule_save propagates a failed version insert'
+
+-- The exception block below rolls back everything rule_save wrote, so it
+-- can't show whether rule_save cleans up after itself; the pg_test
+-- test_failed_save_leaves_no_orphan_definition checks that.
+
+CREATE FUNCTION test_fail_version_insert() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'forced version insert failure';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER test_fail_version_insert
+    BEFORE INSERT ON rule_versions
+    FOR EACH ROW EXECUTE FUNCTION test_fail_version_insert();
+
+DO $$
+BEGIN
+    PERFORM rule_save('test_orphan', 'rule "Orphan" { when X.a > 1 then X.b = 2; }', '1.0.0', 'Should not persist', NULL);
+    RAISE EXCEPTION 'Expected rule_save to fail';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE 'Expected%' THEN RAISE; END IF;
+    RAISE NOTICE 'Version insert failure propagated: %', SQLERRM;
+END $$;
+
+DROP TRIGGER test_fail_version_insert ON rule_versions;
+DROP FUNCTION test_fail_version_insert();
+
+-- =============================================================================
+-- Test 21: Idempotent Save
+-- =============================================================================
+
+\echo 'Test 21: Test rule_save idempotency_key'
+
+\i migrations/016_rule_save_idempotency.sql
+
+SELECT rule_save('test_idempotent', 'rule "Idem" { when X.a > 1 then X.b = 2; }', NULL, NULL, NULL, NULL, 'deploy-1') AS first_save \gset
+SELECT rule_save('test_idempotent', 'rule "Idem" { when X.a > 1 then X.b = 2; }', NULL, NULL, NULL, NULL, 'deploy-1') = :first_save AS retry_returns_same_id;
+
+SELECT COUNT(*) = 1 AS single_version
+FROM rule_versions rv JOIN rule_definitions rd ON rv.rule_id = rd.id
+WHERE rd.name = 'test_idempotent';
+
+-- A new key saves a new version
+SELECT rule_save('test_idempotent', 'rule "Idem" { when X.a > 1 then X.b = 3; }', NULL, NULL, NULL, NULL, 'deploy-2');
+
+SELECT COUNT(*) = 2 AS new_key_new_version
+FROM rule_versions rv JOIN rule_definitions rd ON rv.rule_id = rd.id
+WHERE rd.name = 'test_idempotent';
+
+-- =============================================================================
+-- Test 22: Apply Rule to Table
+-- =============================================================================
+
+\echo 'Test 22: Test rule_apply_to_table'
+
+SELECT rule_save('test_apply', 'rule "Discount" { when Order.total > 1000 then Order.discount = 100; }', '1.0.0', NULL, NULL);
+
+CREATE TEMP TABLE test_apply_source (id INT PRIMARY KEY, "Order" JSONB);
+CREATE TEMP TABLE test_apply_target (id INT PRIMARY KEY, "Order" JSONB);
+INSERT INTO test_apply_source VALUES (1, '{"total": 500}'), (2, '{"total": 2000}');
+INSERT INTO test_apply_target VALUES (2, '{"total": 0}');
+
+SELECT rule_apply_to_table('test_apply', 'test_apply_source', 'test_apply_target', 'id') = 2 AS all_rows_applied;
+
+SELECT COUNT(*) = 2 AS target_has_both_rows FROM test_apply_target;
+SELECT ("Order"->>'discount')::INT = 100 AS existing_row_upserted FROM test_apply_target WHERE id = 2;
+SELECT "Order"->'discount' IS NULL AS unmatched_row_unchanged FROM test_apply_target WHERE id = 1;
+
+-- Unknown id column is rejected before any row runs
+DO $$
+BEGIN
+    PERFORM rule_apply_to_table('test_apply', 'test_apply_source', 'test_apply_target', 'missing');
+    RAISE EXCEPTION 'Expected rule_apply_to_table to fail';
+EXCEPTION WHEN OTHERS THEN
+    IF SQLERRM LIKE 'Expected%' THEN RAISE; END IF;
+    RAISE NOTICE 'Unknown id column rejected: %', SQLERRM;
+END $$;
+
 -- =============================================================================
 -- Cleanup
 -- =============================================================================